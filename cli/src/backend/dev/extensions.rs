@@ -119,6 +119,7 @@ mod tests {
             repository_url: None,
             license: None,
             readme: None,
+            config_schema: None,
         }
         .into_versioned();
 
@@ -33,6 +33,10 @@ pub enum ExtensionType {
     Resolver,
     /// An extension that provides an authentication provider
     Auth,
+    /// An extension that authorizes fields and types carrying its directives
+    Authorization,
+    /// An extension that hooks into the gateway and subgraph requests
+    Hooks,
 }
 
 #[derive(Debug, Parser)]
@@ -5,7 +5,7 @@ use std::{
 };
 
 use anyhow::Context;
-use extension::{FieldResolver, Kind, Manifest};
+use extension::{Authorizer, FieldResolver, Kind, Manifest};
 use semver::Version;
 use serde_valid::Validate;
 
@@ -72,6 +72,8 @@ struct ExtensionTomlExtension {
     homepage_url: Option<url::Url>,
     repository_url: Option<url::Url>,
     license: Option<String>,
+    /// Path to the JSON Schema of the extension configuration.
+    config_schema: Option<String>,
 }
 
 #[derive(serde::Deserialize)]
@@ -79,12 +81,15 @@ struct ExtensionTomlExtension {
 enum ExtensionKind {
     Resolver,
     Auth,
+    Authorization,
+    Hooks,
 }
 
 #[derive(Default, serde::Deserialize)]
 struct ExtensionTomlDirectives {
     definitions: Option<String>,
     field_resolvers: Option<Vec<String>>,
+    authorization: Option<Vec<String>>,
 }
 
 struct Versions {
@@ -226,6 +231,14 @@ fn parse_manifest(source_dir: &Path, wasm_path: &Path) -> anyhow::Result<Manifes
             Kind::FieldResolver(FieldResolver { resolver_directives })
         }
         ExtensionKind::Auth => Kind::Authenticator(Default::default()),
+        ExtensionKind::Authorization => {
+            let authorization_directives = extension_toml.directives.authorization.unwrap_or_default();
+
+            Kind::Authorizer(Authorizer {
+                authorization_directives,
+            })
+        }
+        ExtensionKind::Hooks => Kind::Hooks(Default::default()),
     };
 
     let sdl = match extension_toml.directives.definitions.map(|path| source_dir.join(&path)) {
@@ -244,6 +257,34 @@ fn parse_manifest(source_dir: &Path, wasm_path: &Path) -> anyhow::Result<Manifes
         None => None,
     };
 
+    let config_schema = match extension_toml
+        .extension
+        .config_schema
+        .map(|path| source_dir.join(&path))
+    {
+        Some(ref path) => {
+            let Ok(schema) = std::fs::read_to_string(path) else {
+                anyhow::bail!("failed to read the configuration schema in {}", path.display())
+            };
+
+            let schema: serde_json::Value = serde_json::from_str(&schema)
+                .with_context(|| format!("failed to parse the configuration schema in {}", path.display()))?;
+
+            if let Err(errors) = extension::check_config_schema(&schema) {
+                let errors = errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n  - ");
+
+                anyhow::bail!("invalid configuration schema in {}:\n  - {errors}", path.display());
+            }
+
+            Some(schema)
+        }
+        None => None,
+    };
+
     let manifest = Manifest {
         id: extension::Id {
             name: extension_toml.extension.name,
@@ -258,6 +299,7 @@ fn parse_manifest(source_dir: &Path, wasm_path: &Path) -> anyhow::Result<Manifes
         homepage_url: extension_toml.extension.homepage_url,
         repository_url: extension_toml.extension.repository_url,
         license: extension_toml.extension.license,
+        config_schema,
     };
 
     Ok(manifest)
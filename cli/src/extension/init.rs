@@ -17,6 +17,18 @@ struct AuthTemplate<'a> {
     name: &'a str,
 }
 
+#[derive(askama::Template)]
+#[template(path = "extension/src/authorization.rs.template", escape = "none")]
+struct AuthorizationTemplate<'a> {
+    name: &'a str,
+}
+
+#[derive(askama::Template)]
+#[template(path = "extension/src/hooks.rs.template", escape = "none")]
+struct HooksTemplate<'a> {
+    name: &'a str,
+}
+
 #[derive(serde::Deserialize)]
 struct SdkCargoToml {
     package: SdkCargoTomlPackage,
@@ -40,6 +52,12 @@ struct GraphQLDefinitionsTemplate<'a> {
     name: &'a str,
 }
 
+#[derive(askama::Template)]
+#[template(path = "extension/authorization.graphql.template", escape = "none")]
+struct AuthorizationDefinitionsTemplate<'a> {
+    name: &'a str,
+}
+
 #[derive(askama::Template)]
 #[template(path = "extension/extension.toml.template", escape = "none")]
 struct ExtensionTomlTemplate<'a> {
@@ -47,6 +65,7 @@ struct ExtensionTomlTemplate<'a> {
     name_camel: &'a str,
     kind: ExtensionType,
     needs_field_resolvers: bool,
+    needs_authorization_directives: bool,
 }
 
 #[derive(askama::Template)]
@@ -63,8 +82,11 @@ pub(super) fn execute(cmd: ExtensionInitCommand) -> anyhow::Result<()> {
     let extension_name = init_cargo_toml(&cmd.path)?;
     init_extension_toml(&cmd.path, cmd.r#type, &extension_name)?;
 
-    if cmd.r#type == ExtensionType::Resolver {
-        init_definitions_graphql(&cmd.path, &extension_name)?;
+    match cmd.r#type {
+        ExtensionType::Resolver | ExtensionType::Authorization => {
+            init_definitions_graphql(&cmd.path, cmd.r#type, &extension_name)?
+        }
+        ExtensionType::Auth | ExtensionType::Hooks => (),
     }
 
     init_rust_files(&cmd.path, cmd.r#type, &extension_name)?;
@@ -98,6 +120,8 @@ fn init_rust_files(path: &Path, extension_type: ExtensionType, extension_name: &
     match extension_type {
         ExtensionType::Resolver => ResolverTemplate { name: &struct_name }.write_into(&mut writer)?,
         ExtensionType::Auth => AuthTemplate { name: &struct_name }.write_into(&mut writer)?,
+        ExtensionType::Authorization => AuthorizationTemplate { name: &struct_name }.write_into(&mut writer)?,
+        ExtensionType::Hooks => HooksTemplate { name: &struct_name }.write_into(&mut writer)?,
     }
 
     let tests_path = path.join("tests");
@@ -109,11 +133,15 @@ fn init_rust_files(path: &Path, extension_type: ExtensionType, extension_name: &
     Ok(())
 }
 
-fn init_definitions_graphql(path: &Path, extension_name: &str) -> anyhow::Result<()> {
+fn init_definitions_graphql(path: &Path, extension_type: ExtensionType, extension_name: &str) -> anyhow::Result<()> {
     let name = extension_name.to_case(Case::Camel);
 
     let mut writer = std::fs::File::create(path.join("definitions.graphql"))?;
-    GraphQLDefinitionsTemplate { name: &name }.write_into(&mut writer)?;
+
+    match extension_type {
+        ExtensionType::Authorization => AuthorizationDefinitionsTemplate { name: &name }.write_into(&mut writer)?,
+        _ => GraphQLDefinitionsTemplate { name: &name }.write_into(&mut writer)?,
+    }
 
     Ok(())
 }
@@ -152,6 +180,7 @@ fn init_extension_toml(project_path: &Path, kind: ExtensionType, extension_name:
         name_camel: &camel_case_extension,
         kind,
         needs_field_resolvers: matches!(kind, ExtensionType::Resolver),
+        needs_authorization_directives: matches!(kind, ExtensionType::Authorization),
     };
 
     template.write_into(&mut writer)?;
@@ -67,6 +67,7 @@ fn init_resolver() {
     # homepage_url = "https://example.com/my-extension"
     # repository_url = "https://github.com/my-username/my-extension"
     # license = "MIT"
+    # config_schema = "config.schema.json"
 
     [directives]
     definitions = "definitions.graphql"
@@ -284,6 +285,7 @@ fn init_auth() {
     # homepage_url = "https://example.com/my-extension"
     # repository_url = "https://github.com/my-username/my-extension"
     # license = "MIT"
+    # config_schema = "config.schema.json"
     "##);
 
     let lib_rs = std::fs::read_to_string(project_path.join("src/lib.rs")).unwrap();
@@ -436,3 +438,125 @@ fn build_auth() {
     "#
     );
 }
+
+#[test]
+fn init_authorization() {
+    let temp_dir = tempdir().unwrap();
+    let project_path = temp_dir.path().join("test_project");
+    let project_path_str = project_path.to_string_lossy();
+
+    let args = vec!["extension", "init", "--type", "authorization", &*project_path_str];
+
+    let command = cmd(cargo_bin("grafbase"), &args).stdout_null().stderr_null();
+
+    command.run().unwrap();
+
+    let extension_toml = std::fs::read_to_string(project_path.join("extension.toml")).unwrap();
+
+    insta::assert_snapshot!(&extension_toml, @r##"
+    [extension]
+    name = "test-project"
+    version = "0.1.0"
+    kind = "authorization"
+    description = "A new extension"
+    # homepage_url = "https://example.com/my-extension"
+    # repository_url = "https://github.com/my-username/my-extension"
+    # license = "MIT"
+    # config_schema = "config.schema.json"
+
+    [directives]
+    definitions = "definitions.graphql"
+    authorization = ["testProjectDirective"]
+    "##);
+
+    let definitions = std::fs::read_to_string(project_path.join("definitions.graphql")).unwrap();
+
+    insta::assert_snapshot!(&definitions, @r#"
+    """
+    Fill in here the authorization directives and types that the extension needs.
+    """
+    directive @testProjectConfiguration(arg1: String) repeatable on SCHEMA
+    directive @testProjectDirective on FIELD_DEFINITION | OBJECT | INTERFACE
+    "#);
+
+    let lib_rs = std::fs::read_to_string(project_path.join("src/lib.rs")).unwrap();
+
+    insta::assert_snapshot!(&lib_rs, @r##"
+    use grafbase_sdk::{
+        types::{Configuration, Directive, QueryElement},
+        AuthorizationExtension, Authorizer, Error, Extension, SharedContext,
+    };
+
+    #[derive(AuthorizationExtension)]
+    struct TestProject;
+
+    impl Extension for TestProject {
+        fn new(schema_directives: Vec<Directive>, config: Configuration) -> Result<Self, Box<dyn std::error::Error>> {
+            Ok(Self)
+        }
+    }
+
+    impl Authorizer for TestProject {
+        fn authorize_query(
+            &mut self,
+            context: SharedContext,
+            elements: Vec<QueryElement>,
+        ) -> Result<Vec<Result<(), Error>>, Error> {
+            todo!()
+        }
+    }
+    "##);
+}
+
+#[test]
+fn init_hooks() {
+    let temp_dir = tempdir().unwrap();
+    let project_path = temp_dir.path().join("test_project");
+    let project_path_str = project_path.to_string_lossy();
+
+    let args = vec!["extension", "init", "--type", "hooks", &*project_path_str];
+
+    let command = cmd(cargo_bin("grafbase"), &args).stdout_null().stderr_null();
+
+    command.run().unwrap();
+
+    let extension_toml = std::fs::read_to_string(project_path.join("extension.toml")).unwrap();
+
+    insta::assert_snapshot!(&extension_toml, @r##"
+    [extension]
+    name = "test-project"
+    version = "0.1.0"
+    kind = "hooks"
+    description = "A new extension"
+    # homepage_url = "https://example.com/my-extension"
+    # repository_url = "https://github.com/my-username/my-extension"
+    # license = "MIT"
+    # config_schema = "config.schema.json"
+    "##);
+
+    assert!(!project_path.join("definitions.graphql").exists());
+
+    let lib_rs = std::fs::read_to_string(project_path.join("src/lib.rs")).unwrap();
+
+    insta::assert_snapshot!(&lib_rs, @r##"
+    use grafbase_sdk::{
+        types::{Configuration, Directive, ErrorResponse},
+        Extension, Headers, Hooks, HooksExtension, SharedContext,
+    };
+
+    #[derive(HooksExtension)]
+    struct TestProject;
+
+    impl Extension for TestProject {
+        fn new(schema_directives: Vec<Directive>, config: Configuration) -> Result<Self, Box<dyn std::error::Error>> {
+            Ok(Self)
+        }
+    }
+
+    impl Hooks for TestProject {
+        fn on_gateway_request(&mut self, context: SharedContext, headers: Headers) -> Result<(), ErrorResponse> {
+            Ok(())
+        }
+    }
+    "##);
+}
@@ -6,7 +6,7 @@ use crate::Authorizer;
 pub struct AnonymousAuthorizer;
 
 impl Authorizer for AnonymousAuthorizer {
-    fn get_access_token(&self, _headers: &http::HeaderMap) -> BoxFuture<'_, Option<AccessToken>> {
+    fn get_access_token(&self, _headers: &http::HeaderMap, _query: Option<&str>) -> BoxFuture<'_, Option<AccessToken>> {
         Box::pin(async { Some(AccessToken::Anonymous) })
     }
 }
@@ -0,0 +1,165 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use futures_util::future::BoxFuture;
+use runtime::{
+    auth::{AccessToken, IntrospectedToken},
+    kv::KvStore,
+};
+use schema::IntrospectionConfig;
+
+use super::Authorizer;
+
+type Claims = HashMap<String, serde_json::Value>;
+
+/// Claims of an active token, `None` for an inactive one.
+type CachedToken = Option<Claims>;
+
+/// Validates opaque tokens with an OAuth2 token introspection endpoint (RFC 7662). Active tokens
+/// are cached in the KV store until they expire, for at most `cache_ttl`. Inactive ones are cached
+/// for `inactive_cache_ttl`, failed introspections aren't cached at all.
+pub struct IntrospectionProvider {
+    config: IntrospectionConfig,
+    kv: KvStore,
+    client: reqwest::Client,
+}
+
+#[derive(serde::Deserialize)]
+struct IntrospectionResponse {
+    active: bool,
+    #[serde(flatten)]
+    claims: Claims,
+}
+
+impl IntrospectionProvider {
+    pub fn new(config: IntrospectionConfig, kv: KvStore) -> Self {
+        IntrospectionProvider {
+            config,
+            kv,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn cache_key(&self, token: &str) -> String {
+        use base64::{engine::general_purpose, Engine as _};
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.config.url.as_str().as_bytes());
+        hasher.update(token.as_bytes());
+
+        let mut key = String::from("token-introspection-");
+        key.push_str(&general_purpose::STANDARD_NO_PAD.encode(hasher.finalize()));
+        key
+    }
+
+    async fn introspect(&self, token: &str, key: &str) -> Option<Claims> {
+        let mut request = self
+            .client
+            .post(self.config.url.clone())
+            .timeout(self.config.timeout)
+            .header(http::header::ACCEPT, "application/json")
+            .form(&[("token", token), ("token_type_hint", "access_token")]);
+
+        if let Some(client_id) = &self.config.client_id {
+            request = request.basic_auth(client_id, self.config.client_secret.as_deref());
+        }
+
+        let response = request
+            .send()
+            .await
+            .inspect_err(|err| tracing::debug!("Could not introspect token: {err}"))
+            .ok()?
+            .error_for_status()
+            .inspect_err(|err| tracing::debug!("Invalid response status: {err}"))
+            .ok()?
+            .json::<IntrospectionResponse>()
+            .await
+            .inspect_err(|err| tracing::debug!("Could not deserialize the introspection response: {err}"))
+            .ok()?;
+
+        if !response.active {
+            if !self.config.inactive_cache_ttl.is_zero() {
+                self.cache(key, &None, self.config.inactive_cache_ttl).await;
+            }
+
+            return None;
+        }
+
+        let ttl = match remaining_lifetime(&response.claims)? {
+            Some(lifetime) => lifetime.min(self.config.cache_ttl),
+            None => self.config.cache_ttl,
+        };
+
+        let token = Some(response.claims);
+        self.cache(key, &token, ttl).await;
+
+        token
+    }
+
+    async fn cache(&self, key: &str, token: &CachedToken, ttl: Duration) {
+        self.kv
+            .put_json(key, token, Some(ttl))
+            .await
+            .inspect_err(|err| {
+                tracing::error!("Could not store introspected token in KV: {err}");
+            })
+            .ok();
+    }
+}
+
+impl Authorizer for IntrospectionProvider {
+    fn get_access_token<'a>(
+        &'a self,
+        headers: &'a http::HeaderMap,
+        _query: Option<&'a str>,
+    ) -> BoxFuture<'a, Option<AccessToken>> {
+        Box::pin(self.get_access_token(headers))
+    }
+}
+
+impl IntrospectionProvider {
+    async fn get_access_token(&self, headers: &http::HeaderMap) -> Option<AccessToken> {
+        let token = headers
+            .get(&self.config.header_name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(&self.config.header_value_prefix))?;
+
+        let key = self.cache_key(token);
+
+        let cached = self
+            .kv
+            .get_json::<CachedToken>(&key, None)
+            .await
+            .inspect_err(|err| {
+                tracing::error!("Could not load introspected token from KV: {err}");
+            })
+            .ok()
+            .flatten()
+            // The KV store may keep entries a bit longer than asked.
+            .filter(|token| token.as_ref().is_none_or(|claims| remaining_lifetime(claims).is_some()));
+
+        let claims = match cached {
+            Some(token) => token?,
+            None => self.introspect(token, &key).await?,
+        };
+
+        Some(AccessToken::Introspected(IntrospectedToken { claims }))
+    }
+}
+
+/// Time left before the token expires, `Some(None)` if it has no expiry and `None` if it has
+/// already expired.
+fn remaining_lifetime(claims: &Claims) -> Option<Option<Duration>> {
+    let Some(exp) = claims.get("exp").and_then(serde_json::Value::as_u64) else {
+        return Some(None);
+    };
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+
+    exp.checked_sub(now)
+        .filter(|remaining| *remaining > 0)
+        .map(|remaining| Some(Duration::from_secs(remaining)))
+}
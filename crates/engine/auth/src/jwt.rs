@@ -1,3 +1,5 @@
+mod jwks;
+
 use std::{borrow::Cow, collections::HashMap};
 
 use futures_util::future::BoxFuture;
@@ -6,13 +8,15 @@ use runtime::{auth::JwtToken, kv::KvStore};
 use schema::JwtConfig;
 use serde::de::DeserializeOwned;
 
+use self::jwks::JwksLoader;
 use super::{AccessToken, Authorizer};
 
 /// Same validation as Apollo's "JWT authentication".
 pub struct JwtProvider {
     config: JwtConfig,
-    kv: KvStore,
-    key: String,
+    jwks: JwksLoader,
+    time_options: TimeOptions,
+    required_claims: Vec<(String, serde_json::Value)>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -50,93 +54,59 @@ impl<'a> std::ops::Deref for Jwk<'a> {
 
 impl JwtProvider {
     pub fn new(config: JwtConfig, kv: KvStore) -> Self {
-        let key: String = {
-            use base64::{engine::general_purpose, Engine as _};
-            use sha2::{Digest, Sha256};
-            let mut key = String::from("jwks-metadata-");
-            let digest = <Sha256 as Digest>::digest(config.jwks.url.to_string().as_bytes());
-            key.push_str(&general_purpose::STANDARD_NO_PAD.encode(digest));
-            key
-        };
-        JwtProvider { config, kv, key }
-    }
-
-    async fn load_metadata(&self) -> Option<Vec<u8>> {
-        let maybe_bytes = self
-            .kv
-            .get(&self.key, Some(self.config.jwks.poll_interval))
-            .await
-            .inspect_err(|err| {
-                tracing::error!("Could not load JWKS metadata from KV: {err}");
+        let jwks = JwksLoader::new(&config.jwks, kv);
+        let leeway = chrono::Duration::from_std(config.leeway).expect("leeway is bounded by the configuration");
+        let time_options = TimeOptions::from_leeway(leeway);
+        let required_claims = config
+            .required_claims
+            .iter()
+            .map(|(name, value)| {
+                let value = serde_json::from_str(value).expect("required claims are serialized as JSON by the schema");
+                (name.clone(), value)
             })
-            .ok()?;
-        match maybe_bytes {
-            Some(bytes) => Some(bytes),
-            None => {
-                tracing::debug!("Loading JWKS from origin");
-
-                let bytes = async move {
-                    reqwest::Client::new()
-                        .get(self.config.jwks.url.clone())
-                        .send()
-                        .await
-                        // TODO: Should be logged through the platform for customers to see those
-                        // messages.
-                        .inspect_err(|err| tracing::debug!("Could not fetch JWKS metadata: {err}"))?
-                        .error_for_status()
-                        .inspect_err(|err| tracing::debug!("Invalid response status: {err}"))?
-                        .bytes()
-                        .await
-                        .inspect_err(|err| tracing::debug!("Could not fetch JWKS metadata: {err}"))
-                }
-                .await
-                .ok()?;
-
-                // No point in caching data we can't deserialize
-                let _: Jwks<'_> = serde_json::from_slice(&bytes)
-                    .inspect_err(|err| {
-                        tracing::debug!("Could not deserialize JWKS: {err}");
-                    })
-                    .ok()?;
-
-                let bytes = Vec::from(bytes);
-                self.kv
-                    .put(
-                        &self.key,
-                        Cow::Borrowed(bytes.as_ref()),
-                        Some(self.config.jwks.poll_interval),
-                    )
-                    .await
-                    .inspect_err(|err| {
-                        tracing::error!("Could not store JWKS metadata in KV: {err}");
-                    })
-                    .ok()?;
-                Some(bytes)
-            }
+            .collect();
+        JwtProvider {
+            config,
+            jwks,
+            time_options,
+            required_claims,
         }
     }
 }
 
 impl Authorizer for JwtProvider {
-    fn get_access_token<'a>(&'a self, headers: &'a http::HeaderMap) -> BoxFuture<'a, Option<AccessToken>> {
-        Box::pin(self.get_access_token(headers))
+    fn get_access_token<'a>(
+        &'a self,
+        headers: &'a http::HeaderMap,
+        query: Option<&'a str>,
+    ) -> BoxFuture<'a, Option<AccessToken>> {
+        Box::pin(self.get_access_token(headers, query))
     }
 }
 
 impl JwtProvider {
-    async fn get_access_token(&self, headers: &http::HeaderMap) -> Option<AccessToken> {
-        let token_str = headers
-            .get(&self.config.header_name)
-            .and_then(|value| value.to_str().ok())
-            .and_then(|value| value.strip_prefix(&self.config.header_value_prefix))?;
+    async fn get_access_token(&self, headers: &http::HeaderMap, query: Option<&str>) -> Option<AccessToken> {
+        let token_str = self.extract_token(headers, query)?;
+        let untrusted_token = UntrustedToken::new(token_str.as_ref()).ok()?;
+
+        if !self.config.algorithms.is_empty()
+            && !self
+                .config
+                .algorithms
+                .iter()
+                .any(|alg| alg.as_str() == untrusted_token.algorithm())
+        {
+            tracing::debug!("Rejecting token signed with algorithm {}", untrusted_token.algorithm());
+            return None;
+        }
 
-        let jwks_bytes = self.load_metadata().await?;
+        let jwks_bytes = self.jwks.load().await?;
         let jwks: Jwks<'_> = serde_json::from_slice(&jwks_bytes)
             .inspect_err(|err| {
                 tracing::debug!("Could not deserialize JWKS: {err}");
             })
             .ok()?;
-        let token = decode_token(jwks.keys, UntrustedToken::new(token_str).ok()?)?;
+        let token = decode_token(jwks.keys, untrusted_token, &self.time_options)?;
 
         if let Some(expected) = self.config.jwks.issuer.as_ref() {
             if token.claims().custom.issuer.as_ref() != Some(expected) {
@@ -165,14 +135,60 @@ impl JwtProvider {
         // but 'iss' is the only one that I can think of that might be useful.
         claims.insert("iss".to_string(), issuer.into());
 
+        if !self.has_required_claims(&claims) {
+            return None;
+        }
+
         Some(AccessToken::Jwt(JwtToken { claims }))
     }
+
+    /// Looks for the token in the header first, then in the cookie and the query parameter.
+    fn extract_token<'a>(&self, headers: &'a http::HeaderMap, query: Option<&'a str>) -> Option<Cow<'a, str>> {
+        let from_header = headers
+            .get(&self.config.header_name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(&self.config.header_value_prefix));
+
+        if let Some(token) = from_header {
+            return Some(Cow::Borrowed(token));
+        }
+
+        let from_cookie = self.config.cookie_name.as_deref().and_then(|name| {
+            headers
+                .get_all(http::header::COOKIE)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|value| value.split(';'))
+                .filter_map(|cookie| cookie.trim().split_once('='))
+                .find_map(|(cookie_name, value)| (cookie_name == name).then_some(value))
+        });
+
+        if let Some(token) = from_cookie {
+            return Some(Cow::Borrowed(token));
+        }
+
+        let name = self.config.query_parameter.as_deref()?;
+
+        url::form_urlencoded::parse(query?.as_bytes()).find_map(|(key, value)| (key == name).then_some(value))
+    }
+
+    fn has_required_claims(&self, claims: &HashMap<String, serde_json::Value>) -> bool {
+        self.required_claims
+            .iter()
+            .all(|(name, expected)| match claims.get(name) {
+                Some(serde_json::Value::Array(values)) if !expected.is_array() => values.contains(expected),
+                Some(value) => value == expected,
+                None => false,
+            })
+    }
 }
 
-fn decode_token(jwks: Vec<Jwk<'_>>, untrusted_token: UntrustedToken<'_>) -> Option<Token<CustomClaims>> {
+fn decode_token(
+    jwks: Vec<Jwk<'_>>,
+    untrusted_token: UntrustedToken<'_>,
+    time_options: &TimeOptions,
+) -> Option<Token<CustomClaims>> {
     use jwt_compact::alg::*;
-
-    let time_options = TimeOptions::default();
     jwks.iter()
         // If 'kid' was provided, we only use the jwk with the correct id.
         .filter(|jwk| match (&untrusted_token.header().key_id, &jwk.key_id) {
@@ -196,10 +212,10 @@ fn decode_token(jwks: Vec<Jwk<'_>>, untrusted_token: UntrustedToken<'_>) -> Opti
         .find(|token| {
             token
                 .claims()
-                .validate_expiration(&time_options)
+                .validate_expiration(time_options)
                 .and_then(|claims| {
                     if claims.not_before.is_some() {
-                        claims.validate_maturity(&time_options)
+                        claims.validate_maturity(time_options)
                     } else {
                         Ok(claims)
                     }
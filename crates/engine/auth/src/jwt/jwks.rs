@@ -0,0 +1,204 @@
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use runtime::kv::KvStore;
+use schema::{JwksConfig, JwksSource};
+use tokio::sync::Mutex;
+
+use super::Jwks;
+
+/// Loads the JWKS from wherever it was configured to come from.
+pub(super) enum JwksLoader {
+    Remote {
+        url: url::Url,
+        poll_interval: Duration,
+        kv: KvStore,
+        key: String,
+    },
+    File(JwksFile),
+    Static(Option<Vec<u8>>),
+}
+
+impl JwksLoader {
+    pub fn new(config: &JwksConfig, kv: KvStore) -> Self {
+        match &config.source {
+            JwksSource::Url(url) => {
+                let key: String = {
+                    use base64::{engine::general_purpose, Engine as _};
+                    use sha2::{Digest, Sha256};
+                    let mut key = String::from("jwks-metadata-");
+                    let digest = <Sha256 as Digest>::digest(url.to_string().as_bytes());
+                    key.push_str(&general_purpose::STANDARD_NO_PAD.encode(digest));
+                    key
+                };
+
+                JwksLoader::Remote {
+                    url: url.clone(),
+                    poll_interval: config.poll_interval,
+                    kv,
+                    key,
+                }
+            }
+            JwksSource::Path(path) => JwksLoader::File(JwksFile {
+                path: path.clone(),
+                poll_interval: config.poll_interval,
+                loaded: Mutex::new(None),
+            }),
+            JwksSource::Keys(keys) => {
+                let bytes = validate(format!(r#"{{"keys":[{}]}}"#, keys.join(",")).into_bytes());
+
+                if bytes.is_none() {
+                    tracing::error!("Invalid inline JSON Web Keys");
+                }
+
+                JwksLoader::Static(bytes)
+            }
+            JwksSource::Secret(secret) => {
+                use base64::{engine::general_purpose, Engine as _};
+
+                let key = serde_json::json!({
+                    "kty": "oct",
+                    "k": general_purpose::URL_SAFE_NO_PAD.encode(secret.as_str()),
+                });
+
+                JwksLoader::Static(serde_json::to_vec(&serde_json::json!({ "keys": [key] })).ok())
+            }
+        }
+    }
+
+    pub async fn load(&self) -> Option<Cow<'_, [u8]>> {
+        match self {
+            JwksLoader::Remote {
+                url,
+                poll_interval,
+                kv,
+                key,
+            } => load_remote(url, *poll_interval, kv, key).await.map(Cow::Owned),
+            JwksLoader::File(file) => file.load().await.map(Cow::Owned),
+            JwksLoader::Static(bytes) => bytes.as_deref().map(Cow::Borrowed),
+        }
+    }
+}
+
+async fn load_remote(url: &url::Url, poll_interval: Duration, kv: &KvStore, key: &str) -> Option<Vec<u8>> {
+    let maybe_bytes = kv
+        .get(key, Some(poll_interval))
+        .await
+        .inspect_err(|err| {
+            tracing::error!("Could not load JWKS metadata from KV: {err}");
+        })
+        .ok()?;
+    match maybe_bytes {
+        Some(bytes) => Some(bytes),
+        None => {
+            tracing::debug!("Loading JWKS from origin");
+
+            let bytes = async move {
+                reqwest::Client::new()
+                    .get(url.clone())
+                    .send()
+                    .await
+                    // TODO: Should be logged through the platform for customers to see those
+                    // messages.
+                    .inspect_err(|err| tracing::debug!("Could not fetch JWKS metadata: {err}"))?
+                    .error_for_status()
+                    .inspect_err(|err| tracing::debug!("Invalid response status: {err}"))?
+                    .bytes()
+                    .await
+                    .inspect_err(|err| tracing::debug!("Could not fetch JWKS metadata: {err}"))
+            }
+            .await
+            .ok()?;
+
+            // No point in caching data we can't deserialize
+            let bytes = validate(Vec::from(bytes))?;
+
+            kv.put(key, Cow::Borrowed(bytes.as_ref()), Some(poll_interval))
+                .await
+                .inspect_err(|err| {
+                    tracing::error!("Could not store JWKS metadata in KV: {err}");
+                })
+                .ok()?;
+            Some(bytes)
+        }
+    }
+}
+
+/// A local JWKS file. Its modification time is checked every `poll_interval` and the file is
+/// read again whenever it changed. Concurrent requests wait for the ongoing check rather than
+/// reading the file themselves.
+pub(super) struct JwksFile {
+    path: PathBuf,
+    poll_interval: Duration,
+    loaded: Mutex<Option<LoadedJwksFile>>,
+}
+
+struct LoadedJwksFile {
+    checked_at: Instant,
+    modified: Option<SystemTime>,
+    bytes: Vec<u8>,
+}
+
+impl JwksFile {
+    async fn load(&self) -> Option<Vec<u8>> {
+        let mut loaded = self.loaded.lock().await;
+        let now = Instant::now();
+
+        if let Some(current) = loaded.as_mut() {
+            if now.duration_since(current.checked_at) < self.poll_interval {
+                return Some(current.bytes.clone());
+            }
+
+            current.checked_at = now;
+
+            if modified(&self.path)
+                .await
+                .is_some_and(|modified| Some(modified) == current.modified)
+            {
+                return Some(current.bytes.clone());
+            }
+        }
+
+        let modified_at = modified(&self.path).await;
+
+        let bytes = tokio::fs::read(&self.path)
+            .await
+            .inspect_err(|err| tracing::error!("Could not read the JWKS file {}: {err}", self.path.display()))
+            .ok()
+            .and_then(validate);
+
+        match bytes {
+            Some(bytes) => {
+                *loaded = Some(LoadedJwksFile {
+                    checked_at: now,
+                    modified: modified_at,
+                    bytes: bytes.clone(),
+                });
+
+                Some(bytes)
+            }
+            // We keep the previous keys if the file is being rewritten or broken.
+            None => loaded.as_ref().map(|current| current.bytes.clone()),
+        }
+    }
+}
+
+async fn modified(path: &Path) -> Option<SystemTime> {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn validate(bytes: Vec<u8>) -> Option<Vec<u8>> {
+    let _: Jwks<'_> = serde_json::from_slice(&bytes)
+        .inspect_err(|err| {
+            tracing::debug!("Could not deserialize JWKS: {err}");
+        })
+        .ok()?;
+
+    Some(bytes)
+}
@@ -1,4 +1,5 @@
 mod anonymous;
+mod introspection;
 mod jwt;
 
 use anonymous::AnonymousAuthorizer;
@@ -8,7 +9,11 @@ use schema::{AuthConfig, AuthProviderConfig};
 use tracing::{info_span, Instrument};
 
 pub trait Authorizer: Send + Sync + 'static {
-    fn get_access_token<'a>(&'a self, headers: &'a http::HeaderMap) -> BoxFuture<'a, Option<AccessToken>>;
+    fn get_access_token<'a>(
+        &'a self,
+        headers: &'a http::HeaderMap,
+        query: Option<&'a str>,
+    ) -> BoxFuture<'a, Option<AccessToken>>;
 }
 
 #[derive(Default)]
@@ -34,6 +39,10 @@ impl AuthService {
                             let authorizer = Box::new(jwt::JwtProvider::new(config, kv.clone()));
                             Some(authorizer)
                         }
+                        AuthProviderConfig::Introspection(config) => {
+                            let authorizer = Box::new(introspection::IntrospectionProvider::new(config, kv.clone()));
+                            Some(authorizer)
+                        }
                         AuthProviderConfig::Anonymous | AuthProviderConfig::Extension(_) => {
                             let authorizer = Box::new(AnonymousAuthorizer);
                             Some(authorizer)
@@ -50,11 +59,13 @@ impl AuthService {
         }
     }
 
-    pub async fn authenticate(&self, headers: &http::HeaderMap) -> Option<AccessToken> {
+    /// The query string of the request URI is given if available, as some providers may read
+    /// the token from it.
+    pub async fn authenticate(&self, headers: &http::HeaderMap, query: Option<&str>) -> Option<AccessToken> {
         let fut = self
             .authorizers
             .iter()
-            .map(|authorizer| authorizer.get_access_token(headers))
+            .map(|authorizer| authorizer.get_access_token(headers, query))
             .collect::<FuturesOrdered<_>>()
             .filter_map(|token| async move { token });
 
@@ -1,6 +1,6 @@
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
-use axum::{response::IntoResponse, Json};
+use axum::{extract::ConnectInfo, response::IntoResponse, Json};
 use engine::{Body, Engine, ErrorCode, Runtime};
 use futures_util::TryFutureExt;
 use runtime::bytes::OwnedOrSharedBytes;
@@ -30,7 +30,13 @@ pub async fn execute<R: Runtime>(
     request: axum::extract::Request,
     body_limit_bytes: usize,
 ) -> axum::response::Response {
-    let (parts, body) = request.into_parts();
+    let (mut parts, body) = request.into_parts();
+
+    // The engine doesn't know about axum, so it gets the client address as a plain extension.
+    if let Some(ConnectInfo(addr)) = parts.extensions.get::<ConnectInfo<SocketAddr>>().copied() {
+        parts.extensions.insert(addr);
+    }
+
     let body = axum::body::to_bytes(body, body_limit_bytes).map_err(|error| {
         if let Some(source) = std::error::Error::source(&error) {
             if source.is::<http_body_util::LengthLimitError>() {
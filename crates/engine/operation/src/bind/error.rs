@@ -58,6 +58,20 @@ pub(crate) enum BindError {
         directive: String,
         span: Span,
     },
+    #[error("Directive '@{directive}' does not have an argument named '{name}'")]
+    UnknownDirectiveArgument {
+        directive: String,
+        name: String,
+        span: Span,
+    },
+    #[error("Directive '@{directive}' can only be used on {expected}")]
+    InvalidDirectiveLocation {
+        directive: String,
+        expected: &'static str,
+        span: Span,
+    },
+    #[error("Directive '@stream' can only be used on list fields")]
+    StreamOnNonListField { span: Span },
 }
 
 impl BindError {
@@ -74,7 +88,10 @@ impl BindError {
             | BindError::InvalidVariableType { span, .. }
             | BindError::LeafMustBeAScalarOrEnum { span, .. }
             | BindError::MissingArgument { span, .. }
-            | BindError::MissingDirectiveArgument { span, .. } => Some(operation.span_to_location(span)),
+            | BindError::MissingDirectiveArgument { span, .. }
+            | BindError::UnknownDirectiveArgument { span, .. }
+            | BindError::InvalidDirectiveLocation { span, .. }
+            | BindError::StreamOnNonListField { span } => Some(operation.span_to_location(span)),
             BindError::DuplicateVariable { location, .. } | BindError::UnusedVariable { location, .. } => {
                 Some(location)
             }
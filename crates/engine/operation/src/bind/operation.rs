@@ -4,13 +4,14 @@ use cynic_parser::{
     Span,
 };
 use id_newtypes::IdRange;
-use schema::{CompositeType, Definition, FieldDefinition, ObjectDefinitionId, TypeRecord, Wrapping};
+use schema::{CompositeType, Definition, FieldDefinition, ObjectDefinitionId, Type, TypeRecord, Wrapping};
 use std::collections::HashSet;
 use walker::Walk;
 
 use crate::{
-    ExecutableDirectiveId, FieldArgumentId, IncludeDirectiveRecord, InlineFragmentId, InlineFragmentRecord,
-    QueryInputValueRecord, SelectionSetRecord, SkipDirectiveRecord, VariableDefinitionRecord,
+    DeferDirectiveRecord, ExecutableDirectiveId, FieldArgumentId, IncludeDirectiveRecord, InlineFragmentId,
+    InlineFragmentRecord, QueryInputValueRecord, SelectionSetRecord, SkipDirectiveRecord, StreamDirectiveRecord,
+    VariableDefinitionRecord,
 };
 
 use super::{
@@ -18,6 +19,15 @@ use super::{
     BindError, BindResult, OperationBinder,
 };
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ExecutableDirectiveLocation {
+    Field {
+        is_list: bool,
+    },
+    /// Inline fragments and fragment spreads
+    Fragment,
+}
+
 impl<'schema, 'p> OperationBinder<'schema, 'p> {
     pub(super) fn bind_root(&mut self) -> BindResult<(ObjectDefinitionId, SelectionSetRecord)> {
         let operation = self.parsed_operation.operation();
@@ -63,7 +73,10 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
     }
 
     fn bind_typename_field(&mut self, field: FieldSelection<'p>) -> BindResult<crate::TypenameFieldId> {
-        let directive_ids = self.bind_executable_directive(field.directives())?;
+        let directive_ids = self.bind_executable_directive(
+            field.directives(),
+            ExecutableDirectiveLocation::Field { is_list: false },
+        )?;
         let response_key = self.response_keys.get_or_intern(field.alias().unwrap_or(field.name()));
         self.typename_fields.push(crate::TypenameFieldRecord {
             response_key,
@@ -121,7 +134,12 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
         };
 
         let argument_ids = self.bind_field_arguments(definition, field.name_span(), field.arguments())?;
-        let directive_ids = self.bind_executable_directive(field.directives())?;
+        let directive_ids = self.bind_executable_directive(
+            field.directives(),
+            ExecutableDirectiveLocation::Field {
+                is_list: definition.ty().wrapping.is_list(),
+            },
+        )?;
         let response_key = self.response_keys.get_or_intern(field.alias().unwrap_or(field.name()));
 
         self.data_fields.push(crate::DataFieldRecord {
@@ -202,7 +220,8 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
             .transpose()?;
         let selection_set_record =
             self.bind_selection_set(type_condition.unwrap_or(parent_output_type), fragment.selection_set())?;
-        let directive_ids = self.bind_executable_directive(fragment.directives())?;
+        let directive_ids =
+            self.bind_executable_directive(fragment.directives(), ExecutableDirectiveLocation::Fragment)?;
 
         self.inline_fragments.push(InlineFragmentRecord {
             type_condition_id: type_condition.map(|ty| ty.id()),
@@ -240,7 +259,8 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
                 id
             }
         };
-        let directive_ids = self.bind_executable_directive(spread.directives())?;
+        let directive_ids =
+            self.bind_executable_directive(spread.directives(), ExecutableDirectiveLocation::Fragment)?;
         self.fragment_spreads.push(crate::FragmentSpreadRecord {
             fragment_id,
             directive_ids,
@@ -304,32 +324,124 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
     fn bind_executable_directive(
         &mut self,
         directives: Iter<'p, Directive<'p>>,
+        location: ExecutableDirectiveLocation,
     ) -> BindResult<Vec<ExecutableDirectiveId>> {
         let mut out = Vec::new();
         for directive in directives {
-            if matches!(directive.name(), "skip" | "include") {
-                let argument = directive
-                    .arguments()
-                    .next()
-                    .ok_or(BindError::MissingDirectiveArgument {
-                        name: "if",
-                        span: directive.name_span(),
-                        directive: directive.name().to_string(),
-                    })?;
-
-                let ty = TypeRecord {
-                    definition_id: self.schema.definition_by_name("Boolean").expect("must exist").id(),
-                    wrapping: schema::Wrapping::required(),
+            match directive.name() {
+                "skip" | "include" => {
+                    let argument = directive
+                        .arguments()
+                        .next()
+                        .ok_or(BindError::MissingDirectiveArgument {
+                            name: "if",
+                            span: directive.name_span(),
+                            directive: directive.name().to_string(),
+                        })?;
+
+                    let ty = self.boolean_type();
+                    let condition = coerce_query_value(self, ty, argument.value())?;
+
+                    if directive.name() == "skip" {
+                        out.push(ExecutableDirectiveId::Skip(SkipDirectiveRecord { condition }));
+                    } else {
+                        out.push(ExecutableDirectiveId::Include(IncludeDirectiveRecord { condition }));
+                    };
                 }
-                .walk(self.schema);
+                "defer" => {
+                    if location != ExecutableDirectiveLocation::Fragment {
+                        return Err(BindError::InvalidDirectiveLocation {
+                            directive: directive.name().to_string(),
+                            expected: "fragment spreads and inline fragments",
+                            span: directive.name_span(),
+                        });
+                    }
 
-                let condition = coerce_query_value(self, ty, argument.value())?;
+                    let mut condition = None;
+                    let mut label = None;
+                    for argument in directive.arguments() {
+                        match argument.name() {
+                            "if" => {
+                                let ty = self.boolean_type();
+                                condition = Some(coerce_query_value(self, ty, argument.value())?);
+                            }
+                            "label" => {
+                                let ty = self.string_type();
+                                label = Some(coerce_query_value(self, ty, argument.value())?);
+                            }
+                            name => {
+                                return Err(BindError::UnknownDirectiveArgument {
+                                    directive: directive.name().to_string(),
+                                    name: name.to_string(),
+                                    span: directive.name_span(),
+                                })
+                            }
+                        }
+                    }
 
-                if directive.name() == "skip" {
-                    out.push(ExecutableDirectiveId::Skip(SkipDirectiveRecord { condition }));
-                } else {
-                    out.push(ExecutableDirectiveId::Include(IncludeDirectiveRecord { condition }));
-                };
+                    let condition = condition
+                        .unwrap_or_else(|| self.query_input_values.push_value(QueryInputValueRecord::Boolean(true)));
+                    out.push(ExecutableDirectiveId::Defer(DeferDirectiveRecord { condition, label }));
+                }
+                "stream" => {
+                    match location {
+                        ExecutableDirectiveLocation::Field { is_list: true } => {}
+                        ExecutableDirectiveLocation::Field { is_list: false } => {
+                            return Err(BindError::StreamOnNonListField {
+                                span: directive.name_span(),
+                            });
+                        }
+                        ExecutableDirectiveLocation::Fragment => {
+                            return Err(BindError::InvalidDirectiveLocation {
+                                directive: directive.name().to_string(),
+                                expected: "fields",
+                                span: directive.name_span(),
+                            });
+                        }
+                    }
+
+                    let mut condition = None;
+                    let mut initial_count = None;
+                    let mut label = None;
+                    for argument in directive.arguments() {
+                        match argument.name() {
+                            "if" => {
+                                let ty = self.boolean_type();
+                                condition = Some(coerce_query_value(self, ty, argument.value())?);
+                            }
+                            "initialCount" => {
+                                let ty = TypeRecord {
+                                    definition_id: self.schema.definition_by_name("Int").expect("must exist").id(),
+                                    wrapping: Wrapping::required(),
+                                }
+                                .walk(self.schema);
+                                initial_count = Some(coerce_query_value(self, ty, argument.value())?);
+                            }
+                            "label" => {
+                                let ty = self.string_type();
+                                label = Some(coerce_query_value(self, ty, argument.value())?);
+                            }
+                            name => {
+                                return Err(BindError::UnknownDirectiveArgument {
+                                    directive: directive.name().to_string(),
+                                    name: name.to_string(),
+                                    span: directive.name_span(),
+                                })
+                            }
+                        }
+                    }
+
+                    let condition = condition
+                        .unwrap_or_else(|| self.query_input_values.push_value(QueryInputValueRecord::Boolean(true)));
+                    let initial_count = initial_count
+                        .unwrap_or_else(|| self.query_input_values.push_value(QueryInputValueRecord::Int(0)));
+                    out.push(ExecutableDirectiveId::Stream(StreamDirectiveRecord {
+                        condition,
+                        initial_count,
+                        label,
+                    }));
+                }
+                _ => {}
             }
         }
         out.sort_unstable();
@@ -337,6 +449,22 @@ impl<'schema, 'p> OperationBinder<'schema, 'p> {
         Ok(out)
     }
 
+    fn string_type(&self) -> Type<'schema> {
+        TypeRecord {
+            definition_id: self.schema.definition_by_name("String").expect("must exist").id(),
+            wrapping: Wrapping::nullable(),
+        }
+        .walk(self.schema)
+    }
+
+    fn boolean_type(&self) -> Type<'schema> {
+        TypeRecord {
+            definition_id: self.schema.definition_by_name("Boolean").expect("must exist").id(),
+            wrapping: Wrapping::required(),
+        }
+        .walk(self.schema)
+    }
+
     fn bind_variable_definitions(
         &mut self,
         variables: cynic_parser::executable::Iter<'_, cynic_parser::executable::VariableDefinition<'_>>,
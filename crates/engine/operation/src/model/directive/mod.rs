@@ -1,5 +1,9 @@
+mod defer;
 mod include;
 mod skip;
+mod stream;
 
+pub use defer::*;
 pub use include::*;
 pub use skip::*;
+pub use stream::*;
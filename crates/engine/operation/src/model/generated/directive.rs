@@ -3,7 +3,10 @@
 //! ===================
 //! Generated with: `cargo run -p engine-codegen`
 //! Source file: <engine-codegen dir>/domain/operation.graphql
-use crate::model::{prelude::*, IncludeDirective, IncludeDirectiveRecord, SkipDirective, SkipDirectiveRecord};
+use crate::model::{
+    prelude::*, DeferDirective, DeferDirectiveRecord, IncludeDirective, IncludeDirectiveRecord, SkipDirective,
+    SkipDirectiveRecord, StreamDirective, StreamDirectiveRecord,
+};
 #[allow(unused_imports)]
 use walker::{Iter, Walk};
 
@@ -16,22 +19,33 @@ use walker::{Iter, Walk};
 /// union ExecutableDirective @id @meta(module: "directive") @variants(remove_suffix: "Directive") =
 ///   | SkipDirective
 ///   | IncludeDirective
+///   | DeferDirective
+///   | StreamDirective
 /// ```
 #[derive(serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ExecutableDirectiveId {
+    Defer(DeferDirectiveRecord),
     Include(IncludeDirectiveRecord),
     Skip(SkipDirectiveRecord),
+    Stream(StreamDirectiveRecord),
 }
 
 impl std::fmt::Debug for ExecutableDirectiveId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            ExecutableDirectiveId::Defer(variant) => variant.fmt(f),
             ExecutableDirectiveId::Include(variant) => variant.fmt(f),
             ExecutableDirectiveId::Skip(variant) => variant.fmt(f),
+            ExecutableDirectiveId::Stream(variant) => variant.fmt(f),
         }
     }
 }
 
+impl From<DeferDirectiveRecord> for ExecutableDirectiveId {
+    fn from(value: DeferDirectiveRecord) -> Self {
+        ExecutableDirectiveId::Defer(value)
+    }
+}
 impl From<IncludeDirectiveRecord> for ExecutableDirectiveId {
     fn from(value: IncludeDirectiveRecord) -> Self {
         ExecutableDirectiveId::Include(value)
@@ -42,8 +56,22 @@ impl From<SkipDirectiveRecord> for ExecutableDirectiveId {
         ExecutableDirectiveId::Skip(value)
     }
 }
+impl From<StreamDirectiveRecord> for ExecutableDirectiveId {
+    fn from(value: StreamDirectiveRecord) -> Self {
+        ExecutableDirectiveId::Stream(value)
+    }
+}
 
 impl ExecutableDirectiveId {
+    pub fn is_defer(&self) -> bool {
+        matches!(self, ExecutableDirectiveId::Defer(_))
+    }
+    pub fn as_defer(&self) -> Option<&DeferDirectiveRecord> {
+        match self {
+            ExecutableDirectiveId::Defer(item) => Some(item),
+            _ => None,
+        }
+    }
     pub fn is_include(&self) -> bool {
         matches!(self, ExecutableDirectiveId::Include(_))
     }
@@ -62,20 +90,33 @@ impl ExecutableDirectiveId {
             _ => None,
         }
     }
+    pub fn is_stream(&self) -> bool {
+        matches!(self, ExecutableDirectiveId::Stream(_))
+    }
+    pub fn as_stream(&self) -> Option<&StreamDirectiveRecord> {
+        match self {
+            ExecutableDirectiveId::Stream(item) => Some(item),
+            _ => None,
+        }
+    }
 }
 
 /// Deduplicated
 #[derive(Clone, Copy)]
 pub enum ExecutableDirective<'a> {
+    Defer(DeferDirective<'a>),
     Include(IncludeDirective<'a>),
     Skip(SkipDirective<'a>),
+    Stream(StreamDirective<'a>),
 }
 
 impl std::fmt::Debug for ExecutableDirective<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            ExecutableDirective::Defer(variant) => variant.fmt(f),
             ExecutableDirective::Include(variant) => variant.fmt(f),
             ExecutableDirective::Skip(variant) => variant.fmt(f),
+            ExecutableDirective::Stream(variant) => variant.fmt(f),
         }
     }
 }
@@ -92,13 +133,24 @@ impl<'a> Walk<OperationContext<'a>> for ExecutableDirectiveId {
     {
         let ctx: OperationContext<'a> = ctx.into();
         match self {
+            ExecutableDirectiveId::Defer(item) => ExecutableDirective::Defer(item.walk(ctx)),
             ExecutableDirectiveId::Include(item) => ExecutableDirective::Include(item.walk(ctx)),
             ExecutableDirectiveId::Skip(item) => ExecutableDirective::Skip(item.walk(ctx)),
+            ExecutableDirectiveId::Stream(item) => ExecutableDirective::Stream(item.walk(ctx)),
         }
     }
 }
 
 impl<'a> ExecutableDirective<'a> {
+    pub fn is_defer(&self) -> bool {
+        matches!(self, ExecutableDirective::Defer(_))
+    }
+    pub fn as_defer(&self) -> Option<DeferDirective<'a>> {
+        match self {
+            ExecutableDirective::Defer(item) => Some(*item),
+            _ => None,
+        }
+    }
     pub fn is_include(&self) -> bool {
         matches!(self, ExecutableDirective::Include(_))
     }
@@ -117,4 +169,13 @@ impl<'a> ExecutableDirective<'a> {
             _ => None,
         }
     }
+    pub fn is_stream(&self) -> bool {
+        matches!(self, ExecutableDirective::Stream(_))
+    }
+    pub fn as_stream(&self) -> Option<StreamDirective<'a>> {
+        match self {
+            ExecutableDirective::Stream(item) => Some(*item),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,102 @@
+use operation::ExecutableDirectiveId;
+use petgraph::{graph::NodeIndex, visit::EdgeRef, Direction};
+
+use crate::{
+    query::{Edge, Node},
+    solve::CrudeSolvedQuery,
+};
+
+/// Moves the fields of a query partition that are within different @defer fragments, or that
+/// are streamed, into their own partitions so that deferred fields don't hold back the others
+/// and vice versa. Only the root fields of a partition can be moved, nested ones stay with their
+/// parent field.
+///
+/// The @defer and @stream `if` arguments are only known at execution, so fields are grouped by
+/// the directives themselves. Partitions whose fields all end up not being deferred are simply
+/// executed along the others.
+pub(super) fn split_deferred_query_partitions(query: &mut CrudeSolvedQuery) {
+    let mut incremental_directives = vec![Vec::new(); query.deduplicated_flat_sorted_executable_directives.len()];
+    for (directives, id) in &query.deduplicated_flat_sorted_executable_directives {
+        incremental_directives[usize::from(*id)] = directives
+            .iter()
+            .filter(|directive| directive.is_defer() || directive.is_stream())
+            .copied()
+            .collect::<Vec<_>>();
+    }
+
+    let partitions = query
+        .graph
+        .node_indices()
+        .filter(|ix| matches!(query.graph[*ix], Node::QueryPartition { .. }))
+        .collect::<Vec<_>>();
+
+    let mut groups: Vec<(&[ExecutableDirectiveId], Vec<NodeIndex>)> = Vec::new();
+    for partition_ix in partitions {
+        debug_assert!(groups.is_empty());
+        for edge in query.graph.edges(partition_ix) {
+            if !matches!(edge.weight(), Edge::Field) {
+                continue;
+            }
+            let Node::Field { id, .. } = query.graph[edge.target()] else {
+                continue;
+            };
+            // Extra fields stay in the original partition.
+            if query[id].query_position.is_none() {
+                continue;
+            }
+            let directives = query[id]
+                .flat_directive_id
+                .map(|id| incremental_directives[usize::from(id)].as_slice())
+                .unwrap_or_default();
+            match groups.iter_mut().find(|(group, _)| *group == directives) {
+                Some((_, fields)) => fields.push(edge.target()),
+                None => groups.push((directives, vec![edge.target()])),
+            }
+        }
+
+        if groups.len() < 2 {
+            groups.clear();
+            continue;
+        }
+
+        let Some(parent_ix) = query
+            .graph
+            .edges_directed(partition_ix, Direction::Incoming)
+            .find(|edge| matches!(edge.weight(), Edge::QueryPartition))
+            .map(|edge| edge.source())
+        else {
+            groups.clear();
+            continue;
+        };
+
+        // Fields delivered in the initial payload stay in the original partition.
+        let kept = groups
+            .iter()
+            .position(|(directives, _)| directives.is_empty())
+            .unwrap_or_default();
+
+        for (ix, (_, fields)) in groups.drain(..).enumerate() {
+            if ix == kept {
+                continue;
+            }
+
+            let new_partition_ix = query.graph.add_node(query.graph[partition_ix]);
+            query.graph.add_edge(parent_ix, new_partition_ix, Edge::QueryPartition);
+
+            let mut neighbors = query.graph.neighbors(partition_ix).detach();
+            while let Some((edge_ix, node_ix)) = neighbors.next(&query.graph) {
+                let weight = query.graph[edge_ix];
+                if matches!(weight, Edge::RequiredBySubgraph | Edge::MutationExecutedAfter) {
+                    query.graph.add_edge(new_partition_ix, node_ix, weight);
+                }
+            }
+
+            for field_ix in fields {
+                if let Some(edge_ix) = query.graph.find_edge(partition_ix, field_ix) {
+                    query.graph.remove_edge(edge_ix);
+                }
+                query.graph.add_edge(new_partition_ix, field_ix, Edge::Field);
+            }
+        }
+    }
+}
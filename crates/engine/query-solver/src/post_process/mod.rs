@@ -1,3 +1,4 @@
+mod deferred_partitions;
 mod mutation_order;
 mod partition_cycles;
 mod response_key;
@@ -22,6 +23,9 @@ pub(crate) fn post_process(schema: &Schema, operation: &mut Operation, mut query
     } else {
         let starting_nodes = vec![query.root_node_ix];
         partition_cycles::split_query_partition_dependency_cycles(&mut query, starting_nodes);
+        if operation.root_object_id == schema.graph.root_operation_types_record.query_id {
+            deferred_partitions::split_deferred_query_partitions(&mut query);
+        }
     }
 
     root_typename::assign_root_typename_fields(schema, operation, &mut query);
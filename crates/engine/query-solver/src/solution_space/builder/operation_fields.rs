@@ -17,6 +17,9 @@ struct IngestSelectionSet<'op> {
     parent_query_field_node_ix: NodeIndex,
     parent_output_type: CompositeTypeId,
     selection_set: operation::SelectionSet<'op>,
+    /// @defer applies to the whole sub-tree of the fragment, so it's propagated to nested
+    /// selection sets contrary to @skip/@include.
+    inherited_directive_ids: Vec<ExecutableDirectiveId>,
 }
 
 impl<'schema, 'op> QuerySolutionSpaceBuilder<'schema, 'op>
@@ -32,6 +35,7 @@ where
                 operation: self.operation,
             }
             .root_selection_set(),
+            inherited_directive_ids: Vec::new(),
         }]
         .into();
         OperationFieldsIngestor {
@@ -71,10 +75,12 @@ where
             parent_query_field_node_ix,
             parent_output_type,
             selection_set,
+            inherited_directive_ids,
         }) = self.queue.pop_front()
         {
             self.parent_type_conditions.clear();
             self.parent_directive_ids.clear();
+            self.parent_directive_ids.extend(inherited_directive_ids);
             let bloom_filter = selection_set_to_response_key_bloom_filter
                 .entry(parent_query_field_node_ix)
                 .or_default();
@@ -330,6 +336,13 @@ where
                 parent_query_field_node_ix: query_field_node_ix,
                 parent_output_type: ty,
                 selection_set: field.selection_set(),
+                inherited_directive_ids: self
+                    .parent_directive_ids
+                    .iter()
+                    .chain(field.directive_ids())
+                    .filter(|id| id.is_defer())
+                    .copied()
+                    .collect(),
             })
         }
 
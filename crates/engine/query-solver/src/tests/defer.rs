@@ -0,0 +1,112 @@
+use crate::assert_solving_snapshots;
+
+const SCHEMA: &str = r###"
+enum join__Graph {
+  ACCOUNTS @join__graph(name: "accounts", url: "http://accounts:4001/graphql")
+  INVENTORY @join__graph(name: "inventory", url: "http://inventory:4002/graphql")
+  PRODUCTS @join__graph(name: "products", url: "http://products:4003/graphql")
+  REVIEWS @join__graph(name: "reviews", url: "http://reviews:4004/graphql")
+}
+
+type Product
+  @join__type(graph: INVENTORY, key: "upc")
+  @join__type(graph: PRODUCTS, key: "upc")
+  @join__type(graph: REVIEWS, key: "upc")
+{
+  upc: String!
+  weight: Int @join__field(graph: INVENTORY, external: true) @join__field(graph: PRODUCTS)
+  price: Int @join__field(graph: INVENTORY, external: true) @join__field(graph: PRODUCTS)
+  inStock: Boolean @join__field(graph: INVENTORY)
+  shippingEstimate: Int @join__field(graph: INVENTORY, requires: "price weight")
+  name: String @join__field(graph: PRODUCTS)
+  reviews: [Review] @join__field(graph: REVIEWS)
+}
+
+type Query
+  @join__type(graph: ACCOUNTS)
+  @join__type(graph: INVENTORY)
+  @join__type(graph: PRODUCTS)
+  @join__type(graph: REVIEWS)
+{
+  me: User @join__field(graph: ACCOUNTS)
+  user(id: ID!): User @join__field(graph: ACCOUNTS)
+  users: [User] @join__field(graph: ACCOUNTS)
+  topProducts(first: Int = 5): [Product] @join__field(graph: PRODUCTS)
+}
+
+type Review
+  @join__type(graph: REVIEWS, key: "id")
+{
+  id: ID!
+  body: String
+  product: Product
+  author: User @join__field(graph: REVIEWS, provides: "username")
+}
+
+type User
+  @join__type(graph: ACCOUNTS, key: "id")
+  @join__type(graph: REVIEWS, key: "id")
+{
+  id: ID!
+  name: String @join__field(graph: ACCOUNTS)
+  username: String @join__field(graph: ACCOUNTS) @join__field(graph: REVIEWS, external: true)
+  birthday: Int @join__field(graph: ACCOUNTS)
+  reviews: [Review] @join__field(graph: REVIEWS)
+}
+"###;
+
+#[tokio::test]
+async fn deferred_root_fields_have_their_own_partition() {
+    assert_solving_snapshots!(
+        "deferred_root_fields_have_their_own_partition",
+        SCHEMA,
+        r#"
+        query {
+          users {
+            name
+          }
+          ... @defer {
+            me {
+              name
+            }
+          }
+        }
+        "#
+    );
+}
+
+#[tokio::test]
+async fn deferred_entity_fields_have_their_own_partition() {
+    assert_solving_snapshots!(
+        "deferred_entity_fields_have_their_own_partition",
+        SCHEMA,
+        r#"
+        query {
+          topProducts {
+            inStock
+            ... @defer {
+              shippingEstimate
+            }
+          }
+        }
+        "#
+    );
+}
+
+#[tokio::test]
+async fn streamed_root_fields_have_their_own_partition() {
+    assert_solving_snapshots!(
+        "streamed_root_fields_have_their_own_partition",
+        SCHEMA,
+        r#"
+        query {
+          users @stream(initialCount: 1) {
+            name
+          }
+          me {
+            name
+          }
+        }
+        "#
+    );
+}
@@ -1,6 +1,7 @@
 mod abstract_types;
 mod basic;
 mod cycle;
+mod defer;
 mod entities;
 mod flatten;
 mod inaccessible;
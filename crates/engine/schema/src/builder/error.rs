@@ -134,6 +134,10 @@ pub enum BuildError {
     ExtensionCouldNotReadLink { id: extension_catalog::Id, err: String },
     #[error("Extension {id} imports an unknown Grafbase definition: '{name}'")]
     ExtensionLinksToUnknownGrafbaseDefinition { id: extension_catalog::Id, name: String },
+    #[error("Invalid value for the required JWT claim '{name}': {err}")]
+    InvalidRequiredClaim { name: String, err: String },
+    #[error("Invalid JSON Web Key at index {index}: {err}")]
+    InvalidJsonWebKey { index: usize, err: String },
 }
 
 #[derive(Debug)]
@@ -218,10 +218,15 @@ impl GraphContext<'_> {
 
         self[extension_id].sdl = Some(sdl);
 
+        let catalog_id = self[extension_id].catalog_id;
         let record = ExtensionDirectiveRecord {
             subgraph_id: self.subgraphs[subgraph_id],
-            extension_id: self[extension_id].catalog_id,
+            extension_id: catalog_id,
             name_id: directive_name_id,
+            kind: self
+                .ctx
+                .extension_catalog
+                .get_directive_kind(catalog_id, directive_name),
             argument_ids,
         };
         self.graph.extension_directives.push(record);
@@ -409,6 +409,10 @@ fn ingest_field_directive(
         let &TypeSystemDirectiveId::Extension(directive_id) = directive_id else {
             continue;
         };
+        // Authorization directives are applied by the engine, they don't resolve anything.
+        if ctx.graph[directive_id].kind.is_authorization() {
+            continue;
+        }
         if !exists_in_subgraph_ids.contains(&ctx.graph[directive_id].subgraph_id) {
             exists_in_subgraph_ids.push(ctx.graph[directive_id].subgraph_id);
         }
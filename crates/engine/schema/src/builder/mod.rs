@@ -63,7 +63,8 @@ impl Context<'_> {
         let auth_config = config
             .authentication
             .as_ref()
-            .map(|auth| AuthConfig::new(auth, extension_catalog));
+            .map(|auth| AuthConfig::new(auth, extension_catalog))
+            .transpose()?;
 
         let response_extension = config
             .telemetry
@@ -86,9 +87,17 @@ impl Context<'_> {
             operation_limits: config.operation_limits.unwrap_or_default(),
             disable_introspection: !config.graph.introspection.unwrap_or_default(),
             retry: config.gateway.retry.enabled.then_some(config.gateway.retry.into()),
+            circuit_breaker: config
+                .gateway
+                .circuit_breaker
+                .enabled
+                .then_some(config.gateway.circuit_breaker.into()),
             batching: config.gateway.batching.clone(),
+            query_deduplication: config.gateway.query_deduplication.enabled,
             complexity_control: (&config.complexity_control).into(),
             response_extension,
+            response_caching: (&config.response_caching).into(),
+            entity_caching_invalidate_on_mutation: config.entity_caching.invalidate_on_mutation,
             apq_enabled: config.apq.enabled,
             executable_document_limit_bytes,
             trusted_documents: config.trusted_documents.clone().into(),
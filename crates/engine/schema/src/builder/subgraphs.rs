@@ -47,6 +47,8 @@ impl Context<'_> {
                 websocket_url,
                 timeout,
                 retry,
+                circuit_breaker,
+                hedging,
                 entity_caching,
                 subscription_protocol,
                 ..
@@ -84,6 +86,8 @@ impl Context<'_> {
                     config: super::SubgraphConfig {
                         timeout,
                         retry: retry.map(Into::into),
+                        circuit_breaker: circuit_breaker.map(Into::into),
+                        hedging: hedging.filter(|config| config.enabled).map(Into::into),
                         cache_ttl: entity_caching
                             .as_ref()
                             .and_then(|cfg| {
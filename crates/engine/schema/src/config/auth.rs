@@ -1,28 +1,66 @@
+use std::collections::BTreeMap;
+
 use extension_catalog::{ExtensionCatalog, ExtensionId};
+use gateway_config::JwtAlgorithm;
 use serde::{Deserialize, Serialize};
 
+use crate::BuildError;
+
 #[derive(Default, PartialEq, Clone, Serialize, Deserialize, Debug)]
 pub struct AuthConfig {
     pub providers: Vec<AuthProviderConfig>,
 }
 
 impl AuthConfig {
-    pub fn new(auth: &gateway_config::AuthenticationConfig, extension_catalog: &ExtensionCatalog) -> Self {
+    pub fn new(
+        auth: &gateway_config::AuthenticationConfig,
+        extension_catalog: &ExtensionCatalog,
+    ) -> Result<Self, BuildError> {
         let mut providers = Vec::new();
 
         for provider in &auth.providers {
             match provider {
                 gateway_config::AuthenticationProvider::Jwt(provider) => {
+                    let required_claims = provider
+                        .required_claims
+                        .iter()
+                        .map(|(name, value)| match serde_json::to_string(value) {
+                            Ok(value) => Ok((name.clone(), value)),
+                            Err(err) => Err(BuildError::InvalidRequiredClaim {
+                                name: name.clone(),
+                                err: err.to_string(),
+                            }),
+                        })
+                        .collect::<Result<_, _>>()?;
+
                     providers.push(AuthProviderConfig::Jwt(JwtConfig {
                         name: provider.name.clone(),
                         jwks: JwksConfig {
                             issuer: provider.jwks.issuer.clone(),
                             audience: provider.jwks.audience.clone(),
-                            url: provider.jwks.url.clone(),
+                            source: JwksSource::new(&provider.jwks)?,
                             poll_interval: provider.jwks.poll_interval,
                         },
                         header_name: provider.header.name.to_string(),
                         header_value_prefix: provider.header.value_prefix.to_string(),
+                        cookie_name: provider.cookie.clone(),
+                        query_parameter: provider.query_parameter.clone(),
+                        algorithms: provider.algorithms.clone(),
+                        leeway: provider.leeway,
+                        required_claims,
+                    }));
+                }
+                gateway_config::AuthenticationProvider::Introspection(provider) => {
+                    providers.push(AuthProviderConfig::Introspection(IntrospectionConfig {
+                        name: provider.name.clone(),
+                        url: provider.url.clone(),
+                        client_id: provider.client_id.clone(),
+                        client_secret: provider.client_secret.as_ref().map(|secret| secret.to_string()),
+                        header_name: provider.header.name.to_string(),
+                        header_value_prefix: provider.header.value_prefix.to_string(),
+                        cache_ttl: provider.cache_ttl,
+                        inactive_cache_ttl: provider.inactive_cache_ttl,
+                        timeout: provider.timeout,
                     }));
                 }
                 gateway_config::AuthenticationProvider::Anonymous => {
@@ -41,7 +79,7 @@ impl AuthConfig {
             }
         }
 
-        AuthConfig { providers }
+        Ok(AuthConfig { providers })
     }
 }
 
@@ -49,6 +87,7 @@ impl AuthConfig {
 #[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub enum AuthProviderConfig {
     Jwt(JwtConfig),
+    Introspection(IntrospectionConfig),
     Extension(ExtensionId),
     Anonymous,
 }
@@ -61,6 +100,14 @@ pub struct JwtConfig {
     pub jwks: JwksConfig,
     pub header_name: String,
     pub header_value_prefix: String,
+    pub cookie_name: Option<String>,
+    pub query_parameter: Option<String>,
+    /// All supported algorithms are accepted if empty.
+    pub algorithms: Vec<JwtAlgorithm>,
+    pub leeway: std::time::Duration,
+    /// Expected claim values serialized as JSON, keeping the schema serializable with formats
+    /// that aren't self-describing.
+    pub required_claims: BTreeMap<String, String>,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
@@ -68,6 +115,82 @@ pub struct JwksConfig {
     pub issuer: Option<String>,
     #[serde(default)]
     pub audience: Vec<String>,
-    pub url: url::Url,
+    pub source: JwksSource,
     pub poll_interval: std::time::Duration,
 }
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub enum JwksSource {
+    Url(url::Url),
+    /// A local JWKS file, reloaded when it changes.
+    Path(std::path::PathBuf),
+    /// Inline JSON Web Keys, serialized as JSON.
+    Keys(Vec<String>),
+    /// HMAC shared secret.
+    Secret(HmacSecret),
+}
+
+impl JwksSource {
+    fn new(config: &gateway_config::JwksConfig) -> Result<Self, BuildError> {
+        let source = if let Some(url) = &config.url {
+            JwksSource::Url(url.clone())
+        } else if let Some(path) = &config.path {
+            JwksSource::Path(path.clone())
+        } else if let Some(secret) = &config.secret {
+            JwksSource::Secret(HmacSecret(secret.to_string()))
+        } else {
+            let keys = config
+                .keys
+                .iter()
+                .enumerate()
+                .map(|(index, key)| {
+                    let invalid = |err: String| BuildError::InvalidJsonWebKey { index, err };
+                    let key = serde_json::to_value(key).map_err(|err| invalid(err.to_string()))?;
+
+                    if let Err(err) = jwt_compact::jwk::JsonWebKey::deserialize(&key) {
+                        return Err(invalid(err.to_string()));
+                    }
+
+                    Ok(key.to_string())
+                })
+                .collect::<Result<_, _>>()?;
+
+            JwksSource::Keys(keys)
+        };
+
+        Ok(source)
+    }
+}
+
+/// HMAC shared secret, kept out of the `Debug` output.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct HmacSecret(String);
+
+impl HmacSecret {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for HmacSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HmacSecret(<redacted>)")
+    }
+}
+
+/// OAuth2 token introspection (RFC 7662) of opaque tokens.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct IntrospectionConfig {
+    /// Used for logging/error messages.
+    pub name: Option<String>,
+    pub url: url::Url,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub header_name: String,
+    pub header_value_prefix: String,
+    /// Maximum duration an active token is cached.
+    pub cache_ttl: std::time::Duration,
+    /// Duration an inactive token is cached.
+    pub inactive_cache_ttl: std::time::Duration,
+    pub timeout: std::time::Duration,
+}
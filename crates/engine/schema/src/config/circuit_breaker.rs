@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// A subgraph can disable the circuit breaker enabled globally.
+    pub enabled: bool,
+    /// Fraction of failed requests within the window above which the circuit opens.
+    pub failure_rate: Option<f32>,
+    /// Minimum number of requests within the window before the failure rate is considered.
+    pub min_requests: Option<u32>,
+    /// Duration of the window over which requests are counted.
+    pub window: Option<Duration>,
+    /// Requests taking longer than this are counted as failures.
+    pub slow_request_threshold: Option<Duration>,
+    /// How long the circuit stays open before letting trial requests through.
+    pub open_duration: Option<Duration>,
+    /// Number of successful trial requests needed to close the circuit again.
+    pub half_open_requests: Option<u32>,
+}
+
+impl From<gateway_config::CircuitBreakerConfig> for CircuitBreakerConfig {
+    fn from(config: gateway_config::CircuitBreakerConfig) -> Self {
+        CircuitBreakerConfig {
+            enabled: config.enabled,
+            failure_rate: config.failure_rate,
+            min_requests: config.min_requests,
+            window: config.window,
+            slow_request_threshold: config.slow_request_threshold,
+            open_duration: config.open_duration,
+            half_open_requests: config.half_open_requests,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct HedgingConfig {
+    /// Latency percentile of the recent requests after which the hedged request is sent.
+    pub percentile: Option<f32>,
+    /// Minimum delay before sending the hedged request.
+    pub min_delay: Option<Duration>,
+}
+
+impl From<gateway_config::HedgingConfig> for HedgingConfig {
+    fn from(config: gateway_config::HedgingConfig) -> Self {
+        HedgingConfig {
+            percentile: config.percentile,
+            min_delay: config.min_delay,
+        }
+    }
+}
@@ -1,5 +1,8 @@
 mod auth;
+mod circuit_breaker;
 mod complexity_control;
+mod hedging;
+mod response_caching;
 mod response_extensions;
 mod retry;
 mod trusted_documents;
@@ -8,7 +11,10 @@ use id_newtypes::IdRange;
 
 use crate::HeaderRuleId;
 pub use auth::*;
+pub use circuit_breaker::*;
 pub use complexity_control::*;
+pub use hedging::*;
+pub use response_caching::*;
 pub use response_extensions::*;
 pub use retry::*;
 pub use trusted_documents::*;
@@ -22,9 +28,13 @@ pub struct PartialConfig {
     pub operation_limits: gateway_config::OperationLimitsConfig,
     pub disable_introspection: bool,
     pub retry: Option<RetryConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub batching: gateway_config::BatchingConfig,
+    pub query_deduplication: bool,
     pub complexity_control: ComplexityControl,
     pub response_extension: ResponseExtensionConfig,
+    pub response_caching: ResponseCachingConfig,
+    pub entity_caching_invalidate_on_mutation: bool,
     pub apq_enabled: bool,
     pub executable_document_limit_bytes: usize,
     pub trusted_documents: TrustedDocumentsConfig,
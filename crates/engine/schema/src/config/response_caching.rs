@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Headers carrying the credentials of the caller, key headers are lowercased.
+const IDENTITY_HEADERS: &[&str] = &["authorization", "cookie"];
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ResponseCachingConfig {
+    pub enabled: bool,
+    pub ttl: Duration,
+    pub key_headers: Vec<String>,
+    pub key_claims: Vec<String>,
+}
+
+impl ResponseCachingConfig {
+    /// Whether the cache key is scoped to the caller with headers or claims.
+    pub fn has_scoped_key(&self) -> bool {
+        !self.key_headers.is_empty() || !self.key_claims.is_empty()
+    }
+
+    /// Whether the cache key identifies the caller with claims or credential headers, making it
+    /// safe to cache private responses. Other headers, like a tenant id, may be shared by many
+    /// users.
+    pub fn has_identity_key(&self) -> bool {
+        !self.key_claims.is_empty()
+            || self
+                .key_headers
+                .iter()
+                .any(|name| IDENTITY_HEADERS.contains(&name.as_str()))
+    }
+}
+
+impl From<&gateway_config::ResponseCachingConfig> for ResponseCachingConfig {
+    fn from(config: &gateway_config::ResponseCachingConfig) -> Self {
+        ResponseCachingConfig {
+            enabled: config.enabled,
+            ttl: config.ttl,
+            key_headers: config
+                .key_headers
+                .iter()
+                .map(|name| name.to_ascii_lowercase())
+                .collect(),
+            key_claims: config.key_claims.clone(),
+        }
+    }
+}
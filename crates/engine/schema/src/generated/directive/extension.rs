@@ -18,6 +18,7 @@ use walker::{Iter, Walk};
 ///   subgraph: Subgraph!
 ///   extension_id: ExtensionId!
 ///   name: String!
+///   kind: ExtensionDirectiveKind!
 ///   argument_ids: [ExtensionDirectiveArgumentId!]!
 /// }
 /// ```
@@ -26,6 +27,7 @@ pub struct ExtensionDirectiveRecord {
     pub subgraph_id: SubgraphId,
     pub extension_id: ExtensionId,
     pub name_id: StringId,
+    pub kind: ExtensionDirectiveKind,
     pub argument_ids: IdRange<ExtensionDirectiveArgumentId>,
 }
 
@@ -82,6 +84,7 @@ impl std::fmt::Debug for ExtensionDirective<'_> {
             .field("subgraph", &self.subgraph())
             .field("extension_id", &self.extension_id)
             .field("name", &self.name())
+            .field("kind", &self.kind)
             .field("argument_ids", &self.argument_ids)
             .finish()
     }
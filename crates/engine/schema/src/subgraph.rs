@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use walker::Walk;
 
-use crate::{ExtensionDirective, RetryConfig, Subgraph, TypeSystemDirectiveId};
+use crate::{CircuitBreakerConfig, ExtensionDirective, HedgingConfig, RetryConfig, Subgraph, TypeSystemDirectiveId};
 
 impl<'a> Subgraph<'a> {
     pub fn name(&self) -> &'a str {
@@ -30,6 +30,8 @@ impl<'a> Subgraph<'a> {
 pub struct SubgraphConfig {
     pub timeout: Duration,
     pub retry: Option<RetryConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub hedging: Option<HedgingConfig>,
     // The ttl to use for caching for this subgraph.
     // If None then caching is disabled for this subgraph
     pub cache_ttl: Option<Duration>,
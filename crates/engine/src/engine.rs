@@ -2,9 +2,11 @@ use ::runtime::{hooks::Hooks as _, operation_cache::OperationCache};
 use auth_extension::AuthExtensionService;
 use bytes::Bytes;
 use cache::CacheKey;
+use circuit_breaker::CircuitBreakers;
 use engine_auth::AuthService;
 use futures::{StreamExt, TryFutureExt};
 use futures_util::Stream;
+use hedging::HedgingPolicies;
 use http::HeaderMap;
 use retry_budget::RetryBudgets;
 use schema::Schema;
@@ -17,13 +19,19 @@ use crate::{
     websocket::{self, InitPayload},
     Body, HooksExtension,
 };
+pub(crate) use circuit_breaker::CircuitBreaker;
 pub(crate) use execute::*;
+pub(crate) use hedging::HedgingPolicy;
+pub(crate) use inflight_fetches::{InflightFetchKey, InflightFetches};
 pub(crate) use runtime::*;
 
 mod auth_extension;
 pub(crate) mod cache;
+mod circuit_breaker;
 mod errors;
 mod execute;
+mod hedging;
+mod inflight_fetches;
 mod retry_budget;
 mod runtime;
 
@@ -37,6 +45,9 @@ pub struct Engine<R: Runtime> {
     auth: AuthService,
     auth_extension: Option<AuthExtensionService>,
     retry_budgets: RetryBudgets,
+    circuit_breakers: CircuitBreakers,
+    hedging_policies: HedgingPolicies,
+    inflight_fetches: InflightFetches,
     default_response_format: ResponseFormat,
 }
 
@@ -54,6 +65,9 @@ impl<R: Runtime> Engine<R> {
         Self {
             auth,
             retry_budgets: RetryBudgets::build(&schema),
+            circuit_breakers: CircuitBreakers::build(&schema),
+            hedging_policies: HedgingPolicies::build(&schema),
+            inflight_fetches: InflightFetches::default(),
             schema,
             runtime,
             // Could be coming from configuration one day
@@ -99,11 +113,11 @@ impl<R: Runtime> Engine<R> {
         };
 
         let request_context_fut = self
-            .create_request_context(&ctx, headers, None)
+            .create_request_context(&ctx, headers, uri.query(), None)
             .map_err(|(response, context)| (response, Some(context)));
 
         let graphql_request_fut = self
-            .extract_well_formed_graphql_over_http_request(&ctx, uri, body)
+            .extract_well_formed_graphql_over_http_request(&ctx, uri.clone(), body)
             .map_err(|response| (response, None));
 
         // Retrieve the request body while processing the headers
@@ -142,19 +156,22 @@ impl<R: Runtime> Engine<R> {
             method: http::Method::POST,
             response_format,
             include_grafbase_response_extension: false,
+            client_ip: None,
         };
 
-        let (request_context, hooks_context) =
-            match self.create_request_context(&ctx, headers, Some(init_payload)).await {
-                Ok(context) => context,
-                Err((response, _)) => {
-                    return Err(response
-                        .errors()
-                        .first()
-                        .map(|error| error.message.clone())
-                        .unwrap_or("Internal server error".into()))
-                }
-            };
+        let (request_context, hooks_context) = match self
+            .create_request_context(&ctx, headers, None, Some(init_payload))
+            .await
+        {
+            Ok(context) => context,
+            Err((response, _)) => {
+                return Err(response
+                    .errors()
+                    .first()
+                    .map(|error| error.message.clone())
+                    .unwrap_or("Internal server error".into()))
+            }
+        };
 
         Ok(WebsocketSession {
             engine: Arc::clone(self),
@@ -2,10 +2,12 @@ use std::borrow::Cow;
 
 use base64::{display::Base64Display, engine::general_purpose::URL_SAFE_NO_PAD};
 use operation::extensions::PersistedQueryRequestExtension;
+use runtime::auth::AccessToken;
 use schema::Schema;
 
 mod namespaces {
     pub const OPERATION: &str = "op";
+    pub const RESPONSE: &str = "resp";
 }
 
 /// Unique cache key that generates a URL-safe string.
@@ -14,6 +16,17 @@ pub(crate) enum CacheKey<'a> {
         schema: &'a Schema,
         document: &'a DocumentKey<'a>,
     },
+    Response {
+        schema: &'a Schema,
+        normalized_document: &'a str,
+        /// Serialized literal values of the operation, which are stripped from the normalized document.
+        query_input_values: &'a [u8],
+        /// Serialized request variables.
+        variables: &'a [u8],
+        access_token: &'a AccessToken,
+        headers: Vec<(&'a str, Option<&'a [u8]>)>,
+        claims: Vec<(&'a str, &'a serde_json::Value)>,
+    },
 }
 
 impl CacheKey<'_> {
@@ -72,11 +85,7 @@ impl std::fmt::Display for CacheKey<'_> {
             // Schema version + Commit SHA ensures we don't need to care about
             // backwards-compatibility
             CacheKey::Operation { schema, document } => {
-                let mut hasher = blake3::Hasher::new();
-                hasher.update(&Schema::build_identifier().len().to_ne_bytes());
-                hasher.update(Schema::build_identifier());
-                hasher.update(&schema.version.len().to_ne_bytes());
-                hasher.update(&schema.version);
+                let mut hasher = schema_hasher(schema);
 
                 match document {
                     DocumentKey::AutomaticPersistedQuery { operation_name, ext } => {
@@ -128,6 +137,63 @@ impl std::fmt::Display for CacheKey<'_> {
                     Base64Display::new(hash.as_bytes(), &URL_SAFE_NO_PAD)
                 ))
             }
+            CacheKey::Response {
+                schema,
+                normalized_document,
+                query_input_values,
+                variables,
+                access_token,
+                headers,
+                claims,
+            } => {
+                let mut hasher = schema_hasher(schema);
+                hasher.update(&normalized_document.len().to_ne_bytes());
+                hasher.update(normalized_document.as_bytes());
+                hasher.update(&query_input_values.len().to_ne_bytes());
+                hasher.update(query_input_values);
+                hasher.update(&variables.len().to_ne_bytes());
+                hasher.update(variables);
+                hasher.update(&[access_token.stable_id()]);
+                hasher.update(&headers.len().to_ne_bytes());
+                for (name, value) in headers {
+                    hasher.update(&name.len().to_ne_bytes());
+                    hasher.update(name.as_bytes());
+                    match value {
+                        Some(value) => {
+                            hasher.update(&[0x01]);
+                            hasher.update(&value.len().to_ne_bytes());
+                            hasher.update(value);
+                        }
+                        None => {
+                            hasher.update(&[0x00]);
+                        }
+                    }
+                }
+                hasher.update(&claims.len().to_ne_bytes());
+                for (name, value) in claims {
+                    hasher.update(&name.len().to_ne_bytes());
+                    hasher.update(name.as_bytes());
+                    let value = value.to_string();
+                    hasher.update(&value.len().to_ne_bytes());
+                    hasher.update(value.as_bytes());
+                }
+                let hash = hasher.finalize();
+
+                f.write_fmt(format_args!(
+                    "{}.blake3.{}",
+                    namespaces::RESPONSE,
+                    Base64Display::new(hash.as_bytes(), &URL_SAFE_NO_PAD)
+                ))
+            }
         }
     }
 }
+
+fn schema_hasher(schema: &Schema) -> blake3::Hasher {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&Schema::build_identifier().len().to_ne_bytes());
+    hasher.update(Schema::build_identifier());
+    hasher.update(&schema.version.len().to_ne_bytes());
+    hasher.update(&schema.version);
+    hasher
+}
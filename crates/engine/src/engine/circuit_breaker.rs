@@ -0,0 +1,209 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use schema::{CircuitBreakerConfig, GraphqlEndpointId, Schema};
+
+use super::Runtime;
+
+const DEFAULT_FAILURE_RATE: f32 = 0.5;
+const DEFAULT_MIN_REQUESTS: u32 = 20;
+const DEFAULT_WINDOW: Duration = Duration::from_secs(10);
+const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(30);
+const DEFAULT_HALF_OPEN_REQUESTS: u32 = 1;
+
+#[derive(id_derives::IndexedFields)]
+pub(super) struct CircuitBreakers {
+    #[indexed_by(GraphqlEndpointId)]
+    by_graphql_endpoints: Vec<Option<CircuitBreaker>>,
+}
+
+impl CircuitBreakers {
+    pub fn build(schema: &Schema) -> Self {
+        Self {
+            by_graphql_endpoints: schema
+                .graphql_endpoints()
+                .map(|endpoint| {
+                    let config = endpoint
+                        .config
+                        .circuit_breaker
+                        .as_ref()
+                        .or(schema.settings.circuit_breaker.as_ref())
+                        .filter(|config| config.enabled)?;
+
+                    Some(CircuitBreaker::new(endpoint.subgraph_name(), config))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<R: Runtime> super::Engine<R> {
+    pub(crate) fn get_circuit_breaker(&self, endpoint_id: GraphqlEndpointId) -> Option<&CircuitBreaker> {
+        self.circuit_breakers[endpoint_id].as_ref()
+    }
+}
+
+/// Tracks the outcome of the requests sent to a subgraph endpoint. Once the failure rate within
+/// the window exceeds the threshold, the circuit opens and requests are rejected without being
+/// sent. After `open_duration`, a few trial requests are let through (half-open) and decide
+/// whether the circuit closes again or re-opens.
+pub(crate) struct CircuitBreaker {
+    subgraph_name: String,
+    failure_rate: f32,
+    min_requests: u32,
+    window: Duration,
+    slow_request_threshold: Option<Duration>,
+    open_duration: Duration,
+    half_open_requests: u32,
+    state: Mutex<State>,
+}
+
+enum State {
+    Closed {
+        window_start: Instant,
+        requests: u32,
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    HalfOpen {
+        since: Instant,
+        started: u32,
+        succeeded: u32,
+    },
+}
+
+impl State {
+    fn closed() -> Self {
+        State::Closed {
+            window_start: Instant::now(),
+            requests: 0,
+            failures: 0,
+        }
+    }
+}
+
+impl CircuitBreaker {
+    fn new(subgraph_name: &str, config: &CircuitBreakerConfig) -> Self {
+        Self {
+            subgraph_name: subgraph_name.to_string(),
+            failure_rate: config.failure_rate.unwrap_or(DEFAULT_FAILURE_RATE),
+            min_requests: config.min_requests.unwrap_or(DEFAULT_MIN_REQUESTS).max(1),
+            window: config.window.unwrap_or(DEFAULT_WINDOW),
+            slow_request_threshold: config.slow_request_threshold,
+            open_duration: config.open_duration.unwrap_or(DEFAULT_OPEN_DURATION),
+            half_open_requests: config.half_open_requests.unwrap_or(DEFAULT_HALF_OPEN_REQUESTS).max(1),
+            state: Mutex::new(State::closed()),
+        }
+    }
+
+    /// Whether a request may be sent to the subgraph right now.
+    pub fn try_acquire(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        match &mut *state {
+            State::Closed { .. } => true,
+            State::Open { until } => {
+                if now < *until {
+                    return false;
+                }
+
+                *state = State::HalfOpen {
+                    since: now,
+                    started: 1,
+                    succeeded: 0,
+                };
+
+                true
+            }
+            State::HalfOpen {
+                since,
+                started,
+                succeeded,
+            } => {
+                // Trial requests may have been cancelled without ever reporting back, so we
+                // don't wait on them forever.
+                if now.duration_since(*since) >= self.open_duration {
+                    *since = now;
+                    *started = *succeeded;
+                }
+
+                if *started < self.half_open_requests {
+                    *started += 1;
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Records the outcome of a request that was let through by `try_acquire`.
+    pub fn record(&self, success: bool, duration: Duration) {
+        let success = success
+            && self
+                .slow_request_threshold
+                .is_none_or(|threshold| duration <= threshold);
+
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        match &mut *state {
+            State::Closed {
+                window_start,
+                requests,
+                failures,
+            } => {
+                if now.duration_since(*window_start) >= self.window {
+                    *window_start = now;
+                    *requests = 0;
+                    *failures = 0;
+                }
+
+                *requests += 1;
+
+                if !success {
+                    *failures += 1;
+                }
+
+                if *requests >= self.min_requests && (*failures as f32 / *requests as f32) >= self.failure_rate {
+                    tracing::warn!(
+                        "Opening the circuit breaker of subgraph '{}' after {failures} failures out of {requests} requests",
+                        self.subgraph_name
+                    );
+
+                    *state = State::Open {
+                        until: now + self.open_duration,
+                    };
+                }
+            }
+            // Response to a request sent before the circuit opened.
+            State::Open { .. } => {}
+            State::HalfOpen { succeeded, .. } => {
+                if !success {
+                    tracing::warn!(
+                        "Re-opening the circuit breaker of subgraph '{}' after a failed trial request",
+                        self.subgraph_name
+                    );
+
+                    *state = State::Open {
+                        until: now + self.open_duration,
+                    };
+
+                    return;
+                }
+
+                *succeeded += 1;
+
+                if *succeeded >= self.half_open_requests {
+                    tracing::info!("Closing the circuit breaker of subgraph '{}'", self.subgraph_name);
+                    *state = State::closed();
+                }
+            }
+        }
+    }
+}
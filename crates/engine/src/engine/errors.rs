@@ -89,16 +89,46 @@ impl<R: Runtime> Engine<R> {
 }
 
 pub(crate) mod response {
+    use std::time::Duration;
+
+    use headers::HeaderMapExt;
+    use runtime::rate_limiting;
+
     use crate::{
         response::{GraphqlError, Response},
         ErrorCode,
     };
 
-    pub(crate) fn gateway_rate_limited<OnOperationResponseHookOutput>() -> Response<OnOperationResponseHookOutput> {
-        Response::refuse_request_with(
-            http::StatusCode::TOO_MANY_REQUESTS,
-            vec![GraphqlError::new("Rate limited", ErrorCode::RateLimited)],
-        )
+    const RATE_LIMIT_LIMIT: http::HeaderName = http::HeaderName::from_static("ratelimit-limit");
+    const RATE_LIMIT_REMAINING: http::HeaderName = http::HeaderName::from_static("ratelimit-remaining");
+    const RATE_LIMIT_RESET: http::HeaderName = http::HeaderName::from_static("ratelimit-reset");
+
+    pub(crate) fn gateway_rate_limited<OnOperationResponseHookOutput>(
+        error: &rate_limiting::Error,
+    ) -> Response<OnOperationResponseHookOutput> {
+        let mut headers = http::HeaderMap::new();
+        let mut graphql_error = GraphqlError::new("Rate limited", ErrorCode::RateLimited);
+
+        if let rate_limiting::Error::ExceededCapacity(exceeded) = error {
+            headers.insert(RATE_LIMIT_LIMIT, exceeded.limit.into());
+            headers.insert(RATE_LIMIT_REMAINING, exceeded.remaining.into());
+            headers.insert(RATE_LIMIT_RESET, ceil_seconds(exceeded.reset).into());
+
+            if let Some(retry_after) = exceeded.retry_after {
+                headers.typed_insert(headers::RetryAfter::delay(Duration::from_secs(ceil_seconds(
+                    retry_after,
+                ))));
+            }
+
+            graphql_error = graphql_error.with_extension("rate_limit_key", exceeded.key.clone());
+        }
+
+        Response::refuse_request_with_headers(http::StatusCode::TOO_MANY_REQUESTS, headers, vec![graphql_error])
+    }
+
+    /// Rate limit headers only have a precision of seconds, rounding up so clients don't come back too early.
+    fn ceil_seconds(duration: Duration) -> u64 {
+        duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
     }
 
     pub(crate) fn unauthenticated<OnOperationResponseHookOutput>() -> Response<OnOperationResponseHookOutput> {
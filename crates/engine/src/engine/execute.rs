@@ -1,3 +1,5 @@
+mod rate_limit;
+mod response_cache;
 mod response_extension;
 mod single;
 mod stream;
@@ -6,9 +8,14 @@ use ::runtime::{hooks::Hooks, rate_limiting::RateLimitKey};
 use bytes::Bytes;
 use futures::StreamExt;
 use grafbase_telemetry::grafbase_client::Client;
+use headers::HeaderMapExt;
 use operation::{BatchRequest, QueryParamsRequest, Request};
 use runtime::{auth::AccessToken, error::ErrorResponse};
-use std::{future::Future, sync::Arc};
+use std::{
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
 
 use crate::{
     execution::create_subgraph_headers_with_rules,
@@ -31,6 +38,7 @@ pub(crate) struct RequestContext {
     pub access_token: AccessToken,
     pub subgraph_default_headers: http::HeaderMap,
     pub include_grafbase_response_extension: bool,
+    pub client_ip: Option<IpAddr>,
 }
 
 /// Context only used early in the request processing before generating the RequestContext used
@@ -39,6 +47,8 @@ pub(super) struct EarlyHttpContext {
     pub method: http::method::Method,
     pub response_format: ResponseFormat,
     pub include_grafbase_response_extension: bool,
+    /// Address of the client connection, if provided by the server as a `SocketAddr` request extension.
+    pub client_ip: Option<IpAddr>,
 }
 
 impl<R: Runtime> Engine<R> {
@@ -74,6 +84,7 @@ impl<R: Runtime> Engine<R> {
             method: parts.method,
             response_format,
             include_grafbase_response_extension,
+            client_ip: parts.extensions.get::<SocketAddr>().map(SocketAddr::ip),
         };
 
         Ok((ctx, parts.uri, parts.headers, body))
@@ -83,6 +94,7 @@ impl<R: Runtime> Engine<R> {
         &self,
         ctx: &EarlyHttpContext,
         headers: http::HeaderMap,
+        query: Option<&str>,
         websocket_init_payload: Option<InitPayload>,
     ) -> Result<
         (RequestContext, HooksContext<R>),
@@ -96,7 +108,7 @@ impl<R: Runtime> Engine<R> {
             |(context, ErrorResponse { status, errors })| (Response::refuse_request_with(status, errors), context),
         )?;
 
-        let Some(access_token) = self.auth.authenticate(&headers).await else {
+        let Some(access_token) = self.auth.authenticate(&headers, query).await else {
             return Err((errors::response::unauthenticated(), hooks_context));
         };
 
@@ -112,8 +124,8 @@ impl<R: Runtime> Engine<R> {
         };
 
         // Currently it doesn't rely on authentication, but likely will at some point.
-        if self.runtime.rate_limiter().limit(&RateLimitKey::Global).await.is_err() {
-            return Err((errors::response::gateway_rate_limited(), hooks_context));
+        if let Err(err) = self.runtime.rate_limiter().limit(&RateLimitKey::Global).await {
+            return Err((errors::response::gateway_rate_limited(&err), hooks_context));
         }
 
         let mut request_context = RequestContext {
@@ -125,6 +137,7 @@ impl<R: Runtime> Engine<R> {
             access_token,
             subgraph_default_headers: http::HeaderMap::new(),
             include_grafbase_response_extension: ctx.include_grafbase_response_extension,
+            client_ip: ctx.client_ip,
         };
         request_context.subgraph_default_headers =
             create_subgraph_headers_with_rules(&request_context, self.schema.default_header_rules());
@@ -189,7 +202,16 @@ impl<R: Runtime> Engine<R> {
                         return self.gateway_timeout_error(&request_context);
                     };
 
-                    Http::single(format, hooks_context, response)
+                    // Only queries can be sent with GET, allowing CDNs to cache the response.
+                    let cache_control = (!request_context.mutations_allowed)
+                        .then(|| response.http_cache_control(!request_context.access_token.is_anonymous()));
+
+                    let mut http_response = Http::single(format, hooks_context, response);
+                    if let Some(cache_control) = cache_control {
+                        http_response.headers_mut().typed_insert(cache_control);
+                    }
+
+                    http_response
                 }
             },
             BatchRequest::Batch(requests) => {
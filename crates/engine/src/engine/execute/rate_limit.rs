@@ -0,0 +1,66 @@
+use std::net::IpAddr;
+
+use runtime::{auth::AccessToken, hooks::Hooks, rate_limiting::RateLimiterContext};
+
+use crate::{
+    prepare::{PrepareContext, PreparedOperation},
+    response::Response,
+    Runtime,
+};
+
+use super::errors;
+
+/// Everything rate limit rules may key requests on.
+struct RequestRateLimiterContext<'a> {
+    headers: &'a http::HeaderMap,
+    access_token: &'a AccessToken,
+    client_ip: Option<IpAddr>,
+    operation_name: Option<&'a str>,
+    complexity_cost: Option<usize>,
+}
+
+impl RateLimiterContext for RequestRateLimiterContext<'_> {
+    fn header(&self, name: http::HeaderName) -> Option<&http::HeaderValue> {
+        self.headers.get(name)
+    }
+
+    fn graphql_operation_name(&self) -> Option<&str> {
+        self.operation_name
+    }
+
+    fn ip(&self) -> Option<IpAddr> {
+        self.client_ip
+    }
+
+    fn jwt_claim(&self, key: &str) -> Option<&serde_json::Value> {
+        Some(self.access_token.get_claim(key)).filter(|value| !value.is_null())
+    }
+
+    fn complexity_cost(&self) -> Option<usize> {
+        self.complexity_cost
+    }
+}
+
+impl<R: Runtime> PrepareContext<'_, R> {
+    /// Applies the configured rate limit rules, keyed on the request headers, JWT claims,
+    /// client IP or operation name. Complexity rules consume the operation's complexity cost.
+    pub(crate) async fn rate_limit_operation(
+        &self,
+        operation: &PreparedOperation,
+    ) -> Result<(), Response<<R::Hooks as Hooks>::OnOperationResponseOutput>> {
+        let context = RequestRateLimiterContext {
+            headers: self.headers(),
+            access_token: self.access_token(),
+            client_ip: self.request_context.client_ip,
+            operation_name: operation.cached.operation.attributes.name.original(),
+            complexity_cost: operation.complexity_cost.map(|cost| cost.0),
+        };
+
+        self.engine
+            .runtime
+            .rate_limiter()
+            .limit(&context)
+            .await
+            .map_err(|err| errors::response::gateway_rate_limited(&err))
+    }
+}
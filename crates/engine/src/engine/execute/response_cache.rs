@@ -0,0 +1,207 @@
+use std::{
+    borrow::Cow,
+    time::{Duration, SystemTime},
+};
+
+use grafbase_telemetry::graphql::{GraphqlResponseStatus, OperationType};
+use operation::Request;
+use runtime::hooks::{ExecutedOperation, Hooks};
+use serde_json::value::RawValue;
+
+use crate::{
+    engine::cache::CacheKey,
+    prepare::{PrepareContext, PreparedOperation},
+    response::{CacheControl, Response},
+    Engine, Runtime,
+};
+
+/// Response cache entry. Cache hints are kept alongside the data so that a hit is served with
+/// the same Cache-Control as the original response.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CachedResponse {
+    private: bool,
+    /// Seconds since the Unix epoch after which the entry is stale.
+    expires_at: u64,
+    data: Box<RawValue>,
+}
+
+impl<R: Runtime> PrepareContext<'_, R> {
+    /// Request variables are consumed while preparing the operation, so they're serialized
+    /// beforehand if the response cache is enabled.
+    pub(super) fn response_cache_variables(&self, request: &Request) -> Option<Vec<u8>> {
+        if !self.schema().settings.response_caching.enabled {
+            return None;
+        }
+
+        serde_json::to_vec(&request.variables)
+            .inspect_err(|err| tracing::warn!("Failed to serialize variables for the response cache: {err}"))
+            .ok()
+    }
+
+    pub(super) fn response_cache_key(&self, operation: &PreparedOperation, variables: &[u8]) -> Option<String> {
+        let config = &self.schema().settings.response_caching;
+
+        if !matches!(operation.cached.ty(), OperationType::Query) {
+            return None;
+        }
+
+        // The cache key doesn't include authorization decisions, two users sharing the same key
+        // headers and claims may not be allowed to see the same data.
+        if operation.cached.has_authorization_modifiers() {
+            return None;
+        }
+
+        // Without any header or claim in the key, we can't tell users apart.
+        if !self.access_token().is_anonymous() && !config.has_scoped_key() {
+            return None;
+        }
+
+        let normalized_document = operation.cached.normalized_document()?;
+        let query_input_values = minicbor_serde::to_vec(&operation.cached.operation.query_input_values)
+            .inspect_err(|err| tracing::warn!("Failed to serialize query input values for the response cache: {err}"))
+            .ok()?;
+
+        let key = CacheKey::Response {
+            schema: self.schema(),
+            normalized_document,
+            query_input_values: &query_input_values,
+            variables,
+            access_token: self.access_token(),
+            headers: config
+                .key_headers
+                .iter()
+                .map(|name| (name.as_str(), self.headers().get(name).map(|value| value.as_bytes())))
+                .collect(),
+            claims: config
+                .key_claims
+                .iter()
+                .map(|name| (name.as_str(), self.access_token().get_claim(name)))
+                .collect(),
+        };
+
+        Some(key.to_string())
+    }
+
+    /// Cached responses never reach the subgraphs, but the operation response hooks are still
+    /// called for them.
+    pub(super) async fn fetch_cached_response(
+        &mut self,
+        key: &str,
+        operation: &PreparedOperation,
+    ) -> Option<Response<<R::Hooks as Hooks>::OnOperationResponseOutput>> {
+        let bytes = self
+            .engine
+            .runtime
+            .response_cache()
+            .get(key)
+            .await
+            .inspect_err(|err| tracing::warn!("Failed to read the cache key {key}: {err}"))
+            .ok()
+            .flatten()?;
+
+        let CachedResponse {
+            private,
+            expires_at,
+            data,
+        } = serde_json::from_slice(&bytes)
+            .inspect_err(|err| tracing::warn!("Failed to deserialize the cached response {key}: {err}"))
+            .ok()?;
+
+        let remaining_ttl = expires_at.saturating_sub(unix_timestamp());
+        if remaining_ttl == 0 {
+            return None;
+        }
+
+        let cache_control = CacheControl {
+            max_age: Some(Duration::from_secs(remaining_ttl)),
+            private,
+            no_store: false,
+        };
+
+        let executed_operation = std::mem::replace(&mut self.executed_operation_builder, ExecutedOperation::builder())
+            .build(
+                operation.cached.operation.attributes.name.original(),
+                &operation.cached.operation.attributes.sanitized_query,
+                GraphqlResponseStatus::Success,
+            );
+
+        let response = match self.hooks().on_operation_response(executed_operation).await {
+            Ok(output) => Response::cached(&self.engine.schema, operation, data, cache_control, Some(output)),
+            Err(err) => Response::execution_error(&self.engine.schema, operation, None, [err]),
+        };
+
+        Some(response)
+    }
+}
+
+/// A serialized response ready to be stored in the response cache.
+pub(super) struct ResponseCacheEntry {
+    bytes: Vec<u8>,
+    ttl: Duration,
+    tags: Vec<String>,
+}
+
+impl<R: Runtime> Engine<R> {
+    /// Returns the cache entry for the response, if it can be cached at all.
+    pub(super) fn response_cache_entry<OnOperationResponseHookOutput>(
+        &self,
+        key: &str,
+        response: &Response<OnOperationResponseHookOutput>,
+    ) -> Option<ResponseCacheEntry> {
+        let config = &self.schema.settings.response_caching;
+
+        let cache_control = response.cache_control()?;
+
+        if !response.errors().is_empty() || !cache_control.is_storable() {
+            return None;
+        }
+
+        if cache_control.private && !config.has_identity_key() {
+            return None;
+        }
+
+        // Responses without any max-age from their subgraphs are never cached, and the configured
+        // ttl is an upper bound.
+        let max_age = cache_control.max_age?;
+        let ttl = max_age.min(config.ttl);
+        if ttl.as_secs() == 0 {
+            return None;
+        }
+
+        let data = response.serialize_data()?.ok()?;
+        let data = RawValue::from_string(String::from_utf8(data).ok()?).ok()?;
+
+        let entry = CachedResponse {
+            private: cache_control.private,
+            expires_at: unix_timestamp() + ttl.as_secs(),
+            data,
+        };
+        let bytes = serde_json::to_vec(&entry)
+            .inspect_err(|err| tracing::warn!("Failed to serialize the response for the cache key {key}: {err}"))
+            .ok()?;
+
+        Some(ResponseCacheEntry {
+            bytes,
+            ttl,
+            tags: response.response_cache_tags(),
+        })
+    }
+
+    pub(super) async fn store_response_in_cache(&self, key: String, entry: ResponseCacheEntry) {
+        let ResponseCacheEntry { bytes, ttl, tags } = entry;
+
+        self.runtime
+            .response_cache()
+            .put(&key, Cow::Owned(bytes), ttl, &tags)
+            .await
+            .inspect_err(|err| tracing::warn!("Failed to write the cache key {key}: {err}"))
+            .ok();
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
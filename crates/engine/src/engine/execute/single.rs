@@ -67,6 +67,7 @@ impl<R: Runtime> Engine<R> {
 
 impl<R: Runtime> PrepareContext<'_, R> {
     async fn execute_single(mut self, request: Request) -> Response<<R::Hooks as Hooks>::OnOperationResponseOutput> {
+        let response_cache_variables = self.response_cache_variables(&request);
         let operation = match self.prepare_operation(request).await {
             Ok(operation) => operation,
             Err(response) => return response.with_grafbase_extension(self.grafbase_response_extension(None)),
@@ -81,8 +82,23 @@ impl<R: Runtime> PrepareContext<'_, R> {
         }
 
         let response_ext = self.grafbase_response_extension(Some(&operation));
-        self.execute_query_or_mutation(operation)
-            .await
-            .with_grafbase_extension(response_ext)
+
+        let response_cache_key =
+            response_cache_variables.and_then(|variables| self.response_cache_key(&operation, &variables));
+        if let Some(key) = &response_cache_key {
+            if let Some(response) = self.fetch_cached_response(key, &operation).await {
+                return response.with_grafbase_extension(response_ext);
+            }
+        }
+
+        let engine = self.engine;
+        let response = self.execute_query_or_mutation(operation).await;
+        if let Some(key) = response_cache_key {
+            if let Some(entry) = engine.response_cache_entry(&key, &response) {
+                engine.store_response_in_cache(key, entry).await;
+            }
+        }
+
+        response.with_grafbase_extension(response_ext)
     }
 }
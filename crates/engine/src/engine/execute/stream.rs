@@ -18,7 +18,8 @@ use tracing::Instrument;
 use crate::{
     engine::{errors, HooksContext, RequestContext},
     execution::ResponseSender,
-    prepare::PrepareContext,
+    graphql_over_http::{ResponseFormat, StreamingResponseFormat},
+    prepare::{PrepareContext, PreparedOperation},
     response::{ErrorCode, ErrorCodeCounter, GrafbaseResponseExtension, Response},
     utils::StreamJoinExt,
     Engine, Runtime,
@@ -125,6 +126,17 @@ impl<R: Runtime> Engine<R> {
 }
 
 impl<R: Runtime> PrepareContext<'_, R> {
+    /// @defer and @stream are only supported with streaming formats that can deliver multiple payloads for
+    /// a single query.
+    fn supports_incremental_delivery(&self, operation: &PreparedOperation) -> bool {
+        matches!(
+            self.request_context.response_format,
+            ResponseFormat::Streaming(
+                StreamingResponseFormat::IncrementalDelivery | StreamingResponseFormat::GraphQLOverSSE
+            )
+        ) && operation.plan.requires_incremental_delivery()
+    }
+
     async fn execute_stream<S>(mut self, request: Request, mut sender: S) -> Option<GraphqlOperationAttributes>
     where
         S: ResponseSender<<R::Hooks as Hooks>::OnOperationResponseOutput, Error = mpsc::SendError>,
@@ -146,7 +158,22 @@ impl<R: Runtime> PrepareContext<'_, R> {
                     }
                 };
 
-                if matches!(operation.cached.ty(), OperationType::Query | OperationType::Mutation) {
+                if matches!(operation.cached.ty(), OperationType::Query)
+                    && self.supports_incremental_delivery(&operation)
+                {
+                    let attributes = operation.attributes();
+                    let response_ext = self.grafbase_response_extension(Some(&operation));
+                    self.execute_query_incrementally(
+                        operation,
+                        AddExtToFirstResponse {
+                            sender: &mut sender,
+                            response_ext,
+                        },
+                    )
+                    .await;
+
+                    Err(Some(attributes))
+                } else if matches!(operation.cached.ty(), OperationType::Query | OperationType::Mutation) {
                     let attributes = operation.attributes();
                     let response_ext = self.grafbase_response_extension(Some(&operation));
                     let response = self.execute_query_or_mutation(operation).await;
@@ -176,20 +203,6 @@ impl<R: Runtime> PrepareContext<'_, R> {
 
         let attributes = operation.attributes();
 
-        struct AddExtToFirstResponse<Sender> {
-            sender: Sender,
-            response_ext: Option<GrafbaseResponseExtension>,
-        }
-
-        impl<O: 'static + Send, S: ResponseSender<O>> ResponseSender<O> for AddExtToFirstResponse<S> {
-            type Error = S::Error;
-            async fn send(&mut self, response: Response<O>) -> Result<(), Self::Error> {
-                self.sender
-                    .send(response.with_grafbase_extension(self.response_ext.take()))
-                    .await
-            }
-        }
-
         let response_ext = ctx.grafbase_response_extension(Some(&operation));
         ctx.execute_subscription(operation, AddExtToFirstResponse { sender, response_ext })
             .await;
@@ -197,3 +210,17 @@ impl<R: Runtime> PrepareContext<'_, R> {
         Some(attributes)
     }
 }
+
+struct AddExtToFirstResponse<Sender> {
+    sender: Sender,
+    response_ext: Option<GrafbaseResponseExtension>,
+}
+
+impl<O: 'static + Send, S: ResponseSender<O>> ResponseSender<O> for AddExtToFirstResponse<S> {
+    type Error = S::Error;
+    async fn send(&mut self, response: Response<O>) -> Result<(), Self::Error> {
+        self.sender
+            .send(response.with_grafbase_extension(self.response_ext.take()))
+            .await
+    }
+}
@@ -0,0 +1,88 @@
+use std::{collections::VecDeque, sync::Mutex, time::Duration};
+
+use schema::{GraphqlEndpointId, HedgingConfig, Schema};
+
+use super::Runtime;
+
+const DEFAULT_PERCENTILE: f32 = 0.95;
+/// Number of recent latencies the percentile is computed from.
+const MAX_SAMPLES: usize = 512;
+/// We don't hedge until we have a reasonable idea of the usual latency.
+const MIN_SAMPLES: usize = 20;
+/// The percentile is only recomputed every few requests, sorting the samples isn't free.
+const RECOMPUTE_EVERY: usize = 16;
+
+#[derive(id_derives::IndexedFields)]
+pub(super) struct HedgingPolicies {
+    #[indexed_by(GraphqlEndpointId)]
+    by_graphql_endpoints: Vec<Option<HedgingPolicy>>,
+}
+
+impl HedgingPolicies {
+    pub fn build(schema: &Schema) -> Self {
+        Self {
+            by_graphql_endpoints: schema
+                .graphql_endpoints()
+                .map(|endpoint| endpoint.config.hedging.as_ref().map(HedgingPolicy::new))
+                .collect(),
+        }
+    }
+}
+
+impl<R: Runtime> super::Engine<R> {
+    pub(crate) fn get_hedging_policy(&self, endpoint_id: GraphqlEndpointId) -> Option<&HedgingPolicy> {
+        self.hedging_policies[endpoint_id].as_ref()
+    }
+}
+
+/// Keeps track of the recent latencies of a subgraph endpoint to determine after how long a
+/// request is slow enough to be worth sending a second time.
+pub(crate) struct HedgingPolicy {
+    percentile: f32,
+    min_delay: Duration,
+    latencies: Mutex<Latencies>,
+}
+
+#[derive(Default)]
+struct Latencies {
+    samples: VecDeque<Duration>,
+    recorded: usize,
+    delay: Option<Duration>,
+}
+
+impl HedgingPolicy {
+    fn new(config: &HedgingConfig) -> Self {
+        Self {
+            percentile: config.percentile.unwrap_or(DEFAULT_PERCENTILE).clamp(0.0, 1.0),
+            min_delay: config.min_delay.unwrap_or_default(),
+            latencies: Default::default(),
+        }
+    }
+
+    /// How long to wait for the first request before sending the hedged one, if we know enough
+    /// about the subgraph latency.
+    pub fn delay(&self) -> Option<Duration> {
+        self.latencies.lock().unwrap().delay
+    }
+
+    pub fn record(&self, latency: Duration) {
+        let mut latencies = self.latencies.lock().unwrap();
+
+        if latencies.samples.len() == MAX_SAMPLES {
+            latencies.samples.pop_front();
+        }
+
+        latencies.samples.push_back(latency);
+        latencies.recorded += 1;
+
+        if latencies.samples.len() < MIN_SAMPLES || latencies.recorded % RECOMPUTE_EVERY != 0 {
+            return;
+        }
+
+        let mut sorted = latencies.samples.iter().copied().collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        let index = ((sorted.len() - 1) as f32 * self.percentile).round() as usize;
+        latencies.delay = Some(sorted[index].max(self.min_delay));
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    future::Future,
+    sync::Mutex,
+};
+
+use bytes::Bytes;
+use futures::channel::oneshot;
+use runtime::{bytes::OwnedOrSharedBytes, fetch::FetchResult, hooks::ResponseInfo};
+use schema::GraphqlEndpointId;
+
+use super::Runtime;
+
+type FetchOutput = (FetchResult<http::Response<OwnedOrSharedBytes>>, Option<ResponseInfo>);
+type SharedFetchOutput = (FetchResult<http::Response<Bytes>>, Option<ResponseInfo>);
+
+pub(crate) type InflightFetchKey = [u8; 32];
+
+/// Subgraph queries currently in flight, so that identical ones share a single request. The
+/// first caller sends the request and every caller arriving while it is in flight waits for its
+/// response.
+#[derive(Default)]
+pub(crate) struct InflightFetches {
+    waiters: Mutex<HashMap<InflightFetchKey, Vec<oneshot::Sender<SharedFetchOutput>>>>,
+}
+
+impl InflightFetches {
+    pub fn key(endpoint_id: GraphqlEndpointId, headers: &http::HeaderMap, body: &[u8]) -> InflightFetchKey {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&usize::from(endpoint_id).to_le_bytes());
+        hasher.update(&headers.len().to_le_bytes());
+        for (name, value) in headers {
+            hasher.update(&name.as_str().len().to_le_bytes());
+            hasher.update(name.as_str().as_bytes());
+            hasher.update(&value.len().to_le_bytes());
+            hasher.update(value.as_bytes());
+        }
+        hasher.update(body);
+        hasher.finalize().into()
+    }
+
+    async fn fetch(&self, key: InflightFetchKey, fetch: impl Future<Output = FetchOutput> + Send) -> FetchOutput {
+        let receiver = match self.waiters.lock().unwrap().entry(key) {
+            Entry::Occupied(mut entry) => {
+                let (sender, receiver) = oneshot::channel();
+                entry.get_mut().push(sender);
+                Some(receiver)
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(Vec::new());
+                None
+            }
+        };
+
+        if let Some(receiver) = receiver {
+            // If the leading request was cancelled, we send our own.
+            return match receiver.await {
+                Ok((result, info)) => (result.map(|response| response.map(OwnedOrSharedBytes::Shared)), info),
+                Err(_) => fetch.await,
+            };
+        }
+
+        let guard = LeaderGuard {
+            fetches: self,
+            key: Some(key),
+        };
+
+        let (result, info) = fetch.await;
+        let shared = result.map(|response| response.map(Bytes::from));
+
+        for sender in guard.complete() {
+            let result = shared.as_ref().map(clone_response).map_err(Clone::clone);
+            sender.send((result, info)).ok();
+        }
+
+        (shared.map(|response| response.map(OwnedOrSharedBytes::Shared)), info)
+    }
+}
+
+impl<R: Runtime> super::Engine<R> {
+    pub(crate) async fn deduplicated_fetch(
+        &self,
+        key: InflightFetchKey,
+        fetch: impl Future<Output = FetchOutput> + Send,
+    ) -> FetchOutput {
+        self.inflight_fetches.fetch(key, fetch).await
+    }
+}
+
+/// Removes the in-flight entry if the leading request is cancelled, letting the waiters send
+/// their own.
+struct LeaderGuard<'a> {
+    fetches: &'a InflightFetches,
+    key: Option<InflightFetchKey>,
+}
+
+impl LeaderGuard<'_> {
+    fn complete(mut self) -> Vec<oneshot::Sender<SharedFetchOutput>> {
+        self.key
+            .take()
+            .and_then(|key| self.fetches.waiters.lock().unwrap().remove(&key))
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for LeaderGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.fetches.waiters.lock().unwrap().remove(&key);
+        }
+    }
+}
+
+fn clone_response(response: &http::Response<Bytes>) -> http::Response<Bytes> {
+    let mut clone = http::Response::new(response.body().clone());
+    *clone.status_mut() = response.status();
+    *clone.version_mut() = response.version();
+    *clone.headers_mut() = response.headers().clone();
+    clone
+}
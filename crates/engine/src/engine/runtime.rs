@@ -24,6 +24,7 @@ pub trait Runtime: Send + Sync + 'static {
     fn rate_limiter(&self) -> &RateLimiter;
     fn sleep(&self, duration: std::time::Duration) -> impl Future<Output = ()> + Send;
     fn entity_cache(&self) -> &dyn EntityCache;
+    fn response_cache(&self) -> &dyn EntityCache;
     fn extensions(&self) -> &Self::Extensions;
 }
 
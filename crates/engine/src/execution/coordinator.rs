@@ -17,8 +17,8 @@ use crate::{
     prepare::{PrepareContext, PreparedOperation},
     resolver::ResolverResult,
     response::{
-        GraphqlError, InputObjectId, InputResponseObjectSet, Response, ResponseBuilder, SubgraphResponse,
-        SubgraphResponseRefMut,
+        GraphqlError, IncrementalResult, InputObjectId, InputResponseObjectSet, Response, ResponseBuilder,
+        SubgraphResponse, SubgraphResponseRefMut,
     },
     Runtime,
 };
@@ -30,6 +30,13 @@ pub(crate) trait ResponseSender<O>: Send {
     fn send(&mut self, response: Response<O>) -> impl Future<Output = Result<(), Self::Error>> + Send;
 }
 
+impl<O: Send + 'static, S: ResponseSender<O>> ResponseSender<O> for &mut S {
+    type Error = S::Error;
+    async fn send(&mut self, response: Response<O>) -> Result<(), Self::Error> {
+        (**self).send(response).await
+    }
+}
+
 impl<R: Runtime> PrepareContext<'_, R> {
     pub async fn execute_query_or_mutation(
         mut self,
@@ -58,6 +65,36 @@ impl<R: Runtime> PrepareContext<'_, R> {
         }
     }
 
+    /// Executes a query with @defer fragments, sending the initial payload as soon as all
+    /// non-deferred plans are finished and the deferred ones as they complete.
+    pub async fn execute_query_incrementally(
+        mut self,
+        operation: PreparedOperation,
+        mut responses: impl ResponseSender<<R::Hooks as Hooks>::OnOperationResponseOutput>,
+    ) {
+        let background_futures: FuturesUnordered<_> =
+            std::mem::take(&mut self.background_futures).into_iter().collect();
+        let background_fut = background_futures.collect::<Vec<_>>();
+        let operation = Arc::new(operation);
+        let ctx = ExecutionContext {
+            engine: self.engine,
+            operation: &operation,
+            request_context: self.request_context,
+            hooks_context: &self.hooks_context,
+        };
+
+        tracing::trace!("Starting incremental execution...");
+        if operation.plan.query_modifications.root_error_ids.is_empty() {
+            let response_fut = ctx.execute_incrementally(self.executed_operation_builder, &mut responses);
+            let (response, _) = futures_util::join!(response_fut, background_fut);
+            responses.send(response).await.ok();
+        } else {
+            let response_fut = ctx.response_for_root_errors(self.executed_operation_builder);
+            let (response, _) = futures_util::join!(response_fut, background_fut);
+            responses.send(response).await.ok();
+        }
+    }
+
     pub async fn execute_subscription(
         mut self,
         operation: PreparedOperation,
@@ -156,12 +193,35 @@ impl<'ctx, R: Runtime> ExecutionContext<'ctx, R> {
                 self.operation.cached.clone(),
                 self.operation.cached.operation.root_object_id,
             ),
+            incremental: None,
             ctx: self,
         }
         .run(VecDeque::new())
         .await
     }
 
+    async fn execute_incrementally(
+        self,
+        executed_operation_builder: ExecutedOperationBuilder<<R::Hooks as Hooks>::OnSubgraphResponseOutput>,
+        responses: &mut impl ResponseSender<<R::Hooks as Hooks>::OnOperationResponseOutput>,
+    ) -> Response<<R::Hooks as Hooks>::OnOperationResponseOutput> {
+        assert!(matches!(self.operation.cached.ty(), OperationType::Query));
+
+        OperationExecution {
+            state: self.new_execution_state(),
+            executed_operation_builder,
+            response: ResponseBuilder::new(
+                self.engine.schema.clone(),
+                self.operation.cached.clone(),
+                self.operation.cached.operation.root_object_id,
+            ),
+            incremental: Some(IncrementalDelivery::default()),
+            ctx: self,
+        }
+        .run_with_incremental_delivery(VecDeque::new(), responses)
+        .await
+    }
+
     async fn execute_subscription(
         self,
         executed_operation_builder: ExecutedOperationBuilder<<R::Hooks as Hooks>::OnSubgraphResponseOutput>,
@@ -310,6 +370,7 @@ where
                                 executed_operation_builder,
                                 state: self.initial_state.clone(),
                                 response,
+                                incremental: None,
                             };
 
                             response_futures.push_back(operation_execution.run(results));
@@ -368,6 +429,50 @@ struct OperationExecution<'ctx, R: Runtime> {
     executed_operation_builder: ExecutedOperationBuilder<<R::Hooks as Hooks>::OnSubgraphResponseOutput>,
     state: OperationExecutionState<'ctx, R>,
     response: ResponseBuilder,
+    incremental: Option<IncrementalDelivery>,
+}
+
+/// State of the incremental delivery (@defer & @stream) of a query.
+#[derive(Default)]
+struct IncrementalDelivery {
+    /// Number of non-deferred plans currently executing. The initial payload is sent once there
+    /// are none left.
+    pending_initial_plans: usize,
+    initial_payload_sent: bool,
+    /// Results not sent yet, in delivery order. The batches of a streamed list are queued before
+    /// the results of the deferred plans depending on it, so items are never patched before
+    /// being sent.
+    results: VecDeque<IncrementalResult>,
+}
+
+impl IncrementalDelivery {
+    fn has_payload_ready(&self) -> bool {
+        if self.initial_payload_sent {
+            !self.results.is_empty()
+        } else {
+            self.pending_initial_plans == 0
+        }
+    }
+
+    /// Results of the next subsequent payload, batches of streamed list items are sent one by one.
+    fn take_next_results(&mut self) -> Vec<IncrementalResult> {
+        let end = self
+            .results
+            .iter()
+            .position(IncrementalResult::is_stream_batch)
+            .map_or(self.results.len(), |ix| ix + 1);
+        self.results.drain(..end).collect()
+    }
+}
+
+/// Used for executions without incremental delivery, no payload is ever sent through it.
+struct NoIncrementalDelivery;
+
+impl<O: Send + 'static> ResponseSender<O> for NoIncrementalDelivery {
+    type Error = std::convert::Infallible;
+    async fn send(&mut self, _: Response<O>) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
 
 impl<'ctx, R: Runtime> std::ops::Deref for OperationExecution<'ctx, R> {
@@ -390,8 +495,20 @@ enum TaskResult<U, V> {
 impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
     /// Runs a single execution to completion, returning its response
     async fn run(
+        self,
+        results: VecDeque<PlanExecutionResult<<R::Hooks as Hooks>::OnSubgraphResponseOutput>>,
+    ) -> Response<<R::Hooks as Hooks>::OnOperationResponseOutput> {
+        self.run_with_incremental_delivery(results, &mut NoIncrementalDelivery)
+            .await
+    }
+
+    /// Runs a single execution to completion. If the incremental delivery is enabled, all
+    /// intermediate payloads are sent through `responses` and the returned response is the last
+    /// one.
+    async fn run_with_incremental_delivery(
         mut self,
         mut results: VecDeque<PlanExecutionResult<<R::Hooks as Hooks>::OnSubgraphResponseOutput>>,
+        responses: &mut impl ResponseSender<<R::Hooks as Hooks>::OnOperationResponseOutput>,
     ) -> Response<<R::Hooks as Hooks>::OnOperationResponseOutput> {
         let futures = FuturesUnordered::new();
         let initial_plans = self.state.get_executable_plans().collect::<Vec<_>>();
@@ -404,7 +521,7 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
         let mut state = State::Execution(self);
 
         futures_util::pin_mut!(futures);
-        let this = loop {
+        let mut this = loop {
             state = match state {
                 State::Ingestion(mut ingestion_fut) => {
                     let task_result = futures_util::select_biased! {
@@ -431,9 +548,18 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
                         }
                     }
                 }
-                State::Execution(this) => {
+                State::Execution(mut this) => {
                     if let Some(result) = results.pop_front() {
                         State::Ingestion(Box::pin(this.ingest_execution_result(result).fuse()))
+                    } else if !futures.is_empty()
+                        && this
+                            .incremental
+                            .as_ref()
+                            .is_some_and(IncrementalDelivery::has_payload_ready)
+                    {
+                        let payload = this.next_incremental_payload();
+                        responses.send(payload).await.ok();
+                        State::Execution(this)
                     } else if let Some(result) = futures.next().await {
                         results.push_back(result);
                         State::Execution(this)
@@ -445,15 +571,112 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
         };
 
         let operation = this.ctx.operation;
+        if matches!(operation.cached.ty(), OperationType::Mutation)
+            && this.ctx.engine.schema.settings.entity_caching_invalidate_on_mutation
+        {
+            this.purge_mutated_entities().await;
+        }
+
+        // Everything may have finished before the initial payload could be sent, streamed lists
+        // are still delivered incrementally.
+        if this
+            .incremental
+            .as_ref()
+            .is_some_and(|incremental| !incremental.initial_payload_sent)
+            && !operation.plan.query_modifications.streamed_fields.is_empty()
+        {
+            let payload = this.next_incremental_payload();
+            responses.send(payload).await.ok();
+        }
+        if let Some(incremental) = this
+            .incremental
+            .as_mut()
+            .filter(|incremental| incremental.initial_payload_sent)
+        {
+            // The last batch is sent with the final payload.
+            while incremental
+                .results
+                .iter()
+                .rev()
+                .skip(1)
+                .any(IncrementalResult::is_stream_batch)
+            {
+                let results = incremental.take_next_results();
+                let payload = Response::subsequent_incremental_payload(operation, results, Vec::new(), true, None);
+                responses.send(payload).await.ok();
+            }
+        }
+
         let executed_operation = this.executed_operation_builder.build(
             operation.cached.operation.attributes.name.original(),
             &operation.cached.operation.attributes.sanitized_query,
             this.response.graphql_status(),
         );
 
-        match this.ctx.hooks().on_operation_response(executed_operation).await {
-            Ok(output) => this.response.build(operation.attributes(), output),
-            Err(err) => Response::execution_error(&this.ctx.engine.schema, operation, None, [err]),
+        let result = this.ctx.hooks().on_operation_response(executed_operation).await;
+        match this.incremental {
+            Some(IncrementalDelivery {
+                initial_payload_sent: true,
+                results,
+                ..
+            }) => match result {
+                Ok(output) => {
+                    Response::subsequent_incremental_payload(operation, results.into(), Vec::new(), false, Some(output))
+                }
+                Err(err) => Response::subsequent_incremental_payload(operation, results.into(), vec![err], false, None),
+            },
+            // Everything finished before the initial payload could be sent.
+            _ => match result {
+                Ok(output) => this.response.build(operation.attributes(), output),
+                Err(err) => Response::execution_error(&this.ctx.engine.schema, operation, None, [err]),
+            },
+        }
+    }
+
+    /// Entities returned by a mutation were most likely modified by it, so their cached
+    /// representations and the cached responses containing them are purged.
+    async fn purge_mutated_entities(&self) {
+        let runtime = &self.ctx.engine.runtime;
+
+        let tags = self.response.entity_cache_tags();
+        if !tags.is_empty() {
+            runtime
+                .entity_cache()
+                .purge(&tags)
+                .await
+                .inspect_err(|err| tracing::warn!("Failed to purge mutated entities from the entity cache: {err}"))
+                .ok();
+        }
+
+        if !self.ctx.engine.schema.settings.response_caching.enabled {
+            return;
+        }
+
+        let tags = self.response.response_cache_entity_tags();
+        if !tags.is_empty() {
+            runtime
+                .response_cache()
+                .purge(&tags)
+                .await
+                .inspect_err(|err| tracing::warn!("Failed to purge mutated entities from the response cache: {err}"))
+                .ok();
+        }
+    }
+
+    fn next_incremental_payload(&mut self) -> Response<<R::Hooks as Hooks>::OnOperationResponseOutput> {
+        let incremental = self
+            .incremental
+            .as_mut()
+            .expect("Only called with incremental delivery");
+        if incremental.initial_payload_sent {
+            let results = incremental.take_next_results();
+            Response::subsequent_incremental_payload(self.ctx.operation, results, Vec::new(), true, None)
+        } else {
+            let streams = &self.ctx.operation.plan.query_modifications.streamed_fields;
+            incremental.initial_payload_sent = true;
+            incremental.results.extend(self.response.build_stream_results(streams));
+            self.response
+                .build_initial_incremental_payload(self.ctx.operation.attributes(), streams)
         }
     }
 
@@ -474,6 +697,19 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
         let mut next_futures = Vec::new();
         let plan = plan_id.walk(&self.ctx);
 
+        let errors_count = self.response.errors().len();
+        let mut incremental_root_response_object_set = None;
+        if let Some(incremental) = &mut self.incremental {
+            if !plan.is_deferred {
+                incremental.pending_initial_plans -= 1;
+            } else if incremental.initial_payload_sent {
+                incremental_root_response_object_set = Some(match &result {
+                    Ok(subgraph_response) => Arc::clone(subgraph_response.input_response_object_set()),
+                    Err((root_response_object_set, _)) => Arc::clone(root_response_object_set),
+                });
+            }
+        }
+
         // Retrieving the first edge (response key) appearing in the query to provide a better
         // error path if necessary.
         match result {
@@ -515,6 +751,21 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
             self.executed_operation_builder.push_on_subgraph_response_output(output);
         }
 
+        // The initial payload was already sent, so the data of this deferred plan needs to be
+        // delivered separately.
+        if let Some(root_response_object_set) = incremental_root_response_object_set {
+            let errors = self.response.errors()[errors_count..].to_vec();
+            let results = self.response.build_incremental_results(
+                plan,
+                root_response_object_set.iter(),
+                errors,
+                &self.ctx.operation.plan.query_modifications.streamed_fields,
+            );
+            if let Some(incremental) = &mut self.incremental {
+                incremental.results.extend(results);
+            }
+        }
+
         (self, next_futures)
     }
 
@@ -537,6 +788,12 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
             return None;
         }
 
+        if let Some(incremental) = &mut self.incremental {
+            if !plan.is_deferred {
+                incremental.pending_initial_plans += 1;
+            }
+        }
+
         let span = tracing::debug_span!("resolver", "plan_id" = usize::from(plan.id)).entered();
 
         let subgraph_response = self
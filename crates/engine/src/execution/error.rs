@@ -10,6 +10,8 @@ pub enum ExecutionError {
     Internal(Cow<'static, str>),
     #[error("Request to subgraph '{subgraph_name}' failed with: {error}")]
     Fetch { subgraph_name: String, error: FetchError },
+    #[error("Subgraph '{subgraph_name}' is unavailable, its circuit breaker is open")]
+    CircuitOpen { subgraph_name: String },
     #[error(transparent)]
     RateLimit(#[from] runtime::rate_limiting::Error),
     #[error("{0}")]
@@ -39,6 +41,7 @@ impl From<ExecutionError> for GraphqlError {
         let code = match &err {
             ExecutionError::Internal(_) => ErrorCode::InternalServerError,
             ExecutionError::Fetch { .. } => ErrorCode::SubgraphRequestError,
+            ExecutionError::CircuitOpen { .. } => ErrorCode::SubgraphUnavailable,
             ExecutionError::RateLimit(_) => ErrorCode::RateLimited,
             ExecutionError::Graphql(err) => err.code,
         };
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
+use futures::FutureExt;
 use itertools::Itertools;
+use runtime::extension::{AuthorizedDefinition, ExtensionAuthorizationDirective, ExtensionRuntime};
 use walker::Walk;
 
 use crate::{
@@ -99,6 +101,80 @@ impl<'ctx, R: Runtime> ExecutionContext<'ctx, R> {
                         }
                     }
                 }
+                ResponseModifierRule::ExtensionField {
+                    directive_id,
+                    definition_id,
+                } => {
+                    let definition = definition_id.walk(self);
+                    let directive = directive_id.walk(self);
+                    let input = Arc::new(input);
+                    let result = {
+                        let values = response.read_field_values(input.clone(), target_field.response_key);
+                        self.engine
+                            .runtime
+                            .extensions()
+                            .authorize_response(
+                                self.hooks_context,
+                                ExtensionAuthorizationDirective {
+                                    extension_id: directive.extension_id,
+                                    subgraph: directive.subgraph(),
+                                    definition: AuthorizedDefinition::Field(definition),
+                                    name: directive.name(),
+                                    arguments: target_field
+                                        .arguments()
+                                        .into_extension_directive_query_view(directive, self.variables()),
+                                },
+                                values.iter(),
+                            )
+                            // FIXME: Unfortunately, boxing seems to be the only solution for the bug explained here:
+                            //        https://github.com/rust-lang/rust/issues/110338#issuecomment-1513761297
+                            //        Otherwise is not correctly evaluated to be Send due to the impl IntoIterator
+                            .boxed()
+                            .await
+                    };
+                    let result = match result {
+                        Ok(result) => {
+                            if result.len() == input.len() {
+                                result
+                                    .into_iter()
+                                    .map(|res| res.map_err(GraphqlError::from))
+                                    .collect::<Vec<_>>()
+                            } else if result.len() == 1 {
+                                let res = result.into_iter().next().unwrap().map_err(GraphqlError::from);
+                                (0..input.len()).map(|_| res.clone()).collect()
+                            } else {
+                                tracing::error!("Incorrect number of authorization replies");
+                                (0..input.len())
+                                    .map(|_| {
+                                        Err(GraphqlError::new(
+                                            "Authorization failure",
+                                            ErrorCode::InternalServerError,
+                                        ))
+                                    })
+                                    .collect()
+                            }
+                        }
+                        Err(err) => {
+                            let err = GraphqlError::from(err);
+                            (0..input.len()).map(|_| Err(err.clone())).collect()
+                        }
+                    };
+
+                    for (obj_ref, result) in input.iter().zip_eq(result) {
+                        if let Err(err) = result {
+                            if definition.ty().wrapping.is_required() {
+                                response.propagate_null(&obj_ref.path);
+                            } else {
+                                response.make_inacessible(ResponseValueId::Field {
+                                    object_id: obj_ref.id,
+                                    key: target_field.response_key,
+                                    nullable: true,
+                                });
+                            }
+                            response.push_error(err.with_path((&obj_ref.path, target_field.response_key)));
+                        }
+                    }
+                }
                 ResponseModifierRule::AuthorizedEdgeChild {
                     directive_id,
                     definition_id,
@@ -153,12 +153,15 @@ impl Http {
         rest: impl Stream<Item = Response<O>> + 'static + Send,
     ) -> http::Response<Body> {
         let status = compute_status_code(ResponseFormat::Streaming(format), &response);
+        let additional_headers = additional_headers(&response);
 
-        let (headers, stream) = stream::encode_response(
+        let (mut headers, stream) = stream::encode_response(
             futures_util::stream::iter(std::iter::once(response)).chain(rest),
             format,
         );
 
+        headers.extend(additional_headers);
+
         let body = Body::Stream(stream.map_ok(|bytes| bytes.into()).boxed());
         let mut http_response = http::Response::new(body);
         *http_response.status_mut() = status;
@@ -185,6 +188,7 @@ impl Http {
 
         headers.insert(http::header::CONTENT_TYPE, format.to_content_type());
         headers.typed_insert(headers::ContentLength(bytes.len() as u64));
+        headers.extend(additional_headers(response));
 
         let mut http_response = http::Response::new(Body::Bytes(bytes));
         *http_response.status_mut() = status_code;
@@ -194,6 +198,13 @@ impl Http {
     }
 }
 
+fn additional_headers<O>(response: &Response<O>) -> http::HeaderMap {
+    match response {
+        Response::RefusedRequest(resp) => resp.headers().clone(),
+        _ => http::HeaderMap::new(),
+    }
+}
+
 fn compute_status_code<O>(format: ResponseFormat, response: &Response<O>) -> http::StatusCode {
     match response {
         // GraphQL-over-HTTP spec:
@@ -241,7 +252,7 @@ fn compute_status_code<O>(format: ResponseFormat, response: &Response<O>) -> htt
                 }
             }
         }
-        Response::Executed(_) => {
+        Response::Executed(_) | Response::Incremental(_) => {
             // GraphQL-over-HTTP spec:
             //   If the GraphQL response contains the {data} entry and it is {null}, then the server SHOULD
             //   reply with a 2xx status code and it is RECOMMENDED it replies with 200 status code.
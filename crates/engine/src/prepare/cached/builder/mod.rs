@@ -71,6 +71,7 @@ impl<'a> Solver<'a> {
                 },
                 operation,
                 shapes: Shapes::default(),
+                normalized_document: Default::default(),
             },
             node_to_field: vec![None; solution.graph.node_count()],
             solution,
@@ -312,6 +313,7 @@ impl<'a> Solver<'a> {
         enum Rule {
             Query(QueryModifierRule),
             Resp(ResponseModifierRule),
+            QueryAndResp(QueryModifierRule, ResponseModifierRule),
         }
         let node_to_field = std::mem::take(&mut self.node_to_field);
         for (node_ix, field_id) in node_to_field.iter().enumerate() {
@@ -341,28 +343,9 @@ impl<'a> Solver<'a> {
                                     definition_id: definition.id,
                                 })
                             } else if dir.fields_record.is_some() {
-                                if self.output.query_plan[field_id].parent_field_output_id.is_none() {
-                                    let parent_ix = self
-                                        .solution
-                                        .graph
-                                        .edges_directed(node_ix, Direction::Incoming)
-                                        .find(|edge| matches!(edge.weight(), Edge::Field))
-                                        .expect("Must have a parent field node or root")
-                                        .source();
-                                    let Some(PartitionFieldId::Data(parent_field_id)) =
-                                        node_to_field[parent_ix.index()]
-                                    else {
-                                        tracing::error!("@authorized with fields on root field isn't supported yet");
-                                        return Err(SolveError::InternalError);
-                                    };
-                                    let output_id = Some(self.create_new_response_object_set_definition(parent_ix));
-                                    self.output.query_plan[parent_field_id].output_id = output_id;
-                                    for id in self.output.query_plan[parent_field_id]
-                                        .selection_set_record
-                                        .data_field_ids_ordered_by_type_conditions_then_position
-                                    {
-                                        self.output.query_plan[id].parent_field_output_id = output_id;
-                                    }
+                                if !self.create_parent_field_output(&node_to_field, node_ix, field_id) {
+                                    tracing::error!("@authorized with fields on root field isn't supported yet");
+                                    return Err(SolveError::InternalError);
                                 }
                                 Rule::Resp(ResponseModifierRule::AuthorizedParentEdge {
                                     directive_id: dir.id,
@@ -384,35 +367,57 @@ impl<'a> Solver<'a> {
                         TypeSystemDirective::RequiresScopes(dir) => {
                             Rule::Query(QueryModifierRule::RequiresScopes(dir.id))
                         }
+                        TypeSystemDirective::Extension(dir) if dir.kind.is_authorization() => {
+                            let query_rule = QueryModifierRule::ExtensionField {
+                                directive_id: dir.id,
+                                definition_id: definition.id,
+                                argument_ids: self.output.query_plan[field_id].argument_ids,
+                            };
+                            // Root fields have no parent objects to authorize the response data with.
+                            if self.create_parent_field_output(&node_to_field, node_ix, field_id) {
+                                Rule::QueryAndResp(
+                                    query_rule,
+                                    ResponseModifierRule::ExtensionField {
+                                        directive_id: dir.id,
+                                        definition_id: definition.id,
+                                    },
+                                )
+                            } else {
+                                Rule::Query(query_rule)
+                            }
+                        }
                         TypeSystemDirective::Cost(_)
                         | TypeSystemDirective::Deprecated(_)
                         | TypeSystemDirective::ListSize(_)
                         | TypeSystemDirective::Extension(_) => continue,
                     };
-                    match rule {
-                        Rule::Query(rule) => {
-                            let ix = deduplicated_query_modifier_rules
-                                .entry(rule.clone())
-                                .or_insert_with(|| {
-                                    query_modifiers.push(QueryModifierRecord {
-                                        rule,
-                                        impacts_root_object: false,
-                                        impacted_field_ids: Vec::new(),
-                                    });
-                                    query_modifiers.len() - 1
-                                });
-                            query_modifiers[*ix].impacted_field_ids.push(field_id.into());
-                        }
-                        Rule::Resp(rule) => {
-                            let ix = deduplicated_response_modifier_rules.entry(rule).or_insert_with(|| {
-                                response_modifier_definitions.push(ResponseModifierDefinitionRecord {
+                    let (query_rule, response_rule) = match rule {
+                        Rule::Query(rule) => (Some(rule), None),
+                        Rule::Resp(rule) => (None, Some(rule)),
+                        Rule::QueryAndResp(query_rule, response_rule) => (Some(query_rule), Some(response_rule)),
+                    };
+                    if let Some(rule) = query_rule {
+                        let ix = deduplicated_query_modifier_rules
+                            .entry(rule.clone())
+                            .or_insert_with(|| {
+                                query_modifiers.push(QueryModifierRecord {
                                     rule,
+                                    impacts_root_object: false,
                                     impacted_field_ids: Vec::new(),
                                 });
-                                response_modifier_definitions.len() - 1
+                                query_modifiers.len() - 1
                             });
-                            response_modifier_definitions[*ix].impacted_field_ids.push(field_id);
-                        }
+                        query_modifiers[*ix].impacted_field_ids.push(field_id.into());
+                    }
+                    if let Some(rule) = response_rule {
+                        let ix = deduplicated_response_modifier_rules.entry(rule).or_insert_with(|| {
+                            response_modifier_definitions.push(ResponseModifierDefinitionRecord {
+                                rule,
+                                impacted_field_ids: Vec::new(),
+                            });
+                            response_modifier_definitions.len() - 1
+                        });
+                        response_modifier_definitions[*ix].impacted_field_ids.push(field_id);
                     }
                 }
 
@@ -436,35 +441,44 @@ impl<'a> Solver<'a> {
                         TypeSystemDirective::RequiresScopes(dir) => {
                             Rule::Query(QueryModifierRule::RequiresScopes(dir.id))
                         }
+                        TypeSystemDirective::Extension(dir) if dir.kind.is_authorization() => {
+                            Rule::Query(QueryModifierRule::ExtensionDefinition {
+                                directive_id: dir.id,
+                                definition_id: output_definition.id(),
+                            })
+                        }
                         TypeSystemDirective::Cost(_)
                         | TypeSystemDirective::Deprecated(_)
                         | TypeSystemDirective::ListSize(_)
                         | TypeSystemDirective::Extension(_) => continue,
                     };
-                    match rule {
-                        Rule::Query(rule) => {
-                            let ix = deduplicated_query_modifier_rules
-                                .entry(rule.clone())
-                                .or_insert_with(|| {
-                                    query_modifiers.push(QueryModifierRecord {
-                                        rule,
-                                        impacts_root_object: false,
-                                        impacted_field_ids: Vec::new(),
-                                    });
-                                    query_modifiers.len() - 1
-                                });
-                            query_modifiers[*ix].impacted_field_ids.push(field_id.into());
-                        }
-                        Rule::Resp(rule) => {
-                            let ix = deduplicated_response_modifier_rules.entry(rule).or_insert_with(|| {
-                                response_modifier_definitions.push(ResponseModifierDefinitionRecord {
+                    let (query_rule, response_rule) = match rule {
+                        Rule::Query(rule) => (Some(rule), None),
+                        Rule::Resp(rule) => (None, Some(rule)),
+                        Rule::QueryAndResp(query_rule, response_rule) => (Some(query_rule), Some(response_rule)),
+                    };
+                    if let Some(rule) = query_rule {
+                        let ix = deduplicated_query_modifier_rules
+                            .entry(rule.clone())
+                            .or_insert_with(|| {
+                                query_modifiers.push(QueryModifierRecord {
                                     rule,
+                                    impacts_root_object: false,
                                     impacted_field_ids: Vec::new(),
                                 });
-                                response_modifier_definitions.len() - 1
+                                query_modifiers.len() - 1
                             });
-                            response_modifier_definitions[*ix].impacted_field_ids.push(field_id);
-                        }
+                        query_modifiers[*ix].impacted_field_ids.push(field_id.into());
+                    }
+                    if let Some(rule) = response_rule {
+                        let ix = deduplicated_response_modifier_rules.entry(rule).or_insert_with(|| {
+                            response_modifier_definitions.push(ResponseModifierDefinitionRecord {
+                                rule,
+                                impacted_field_ids: Vec::new(),
+                            });
+                            response_modifier_definitions.len() - 1
+                        });
+                        response_modifier_definitions[*ix].impacted_field_ids.push(field_id);
                     }
                 }
             }
@@ -484,6 +498,12 @@ impl<'a> Solver<'a> {
                     definition_id: self.output.operation.root_object_id.into(),
                 },
                 TypeSystemDirective::RequiresScopes(dir) => QueryModifierRule::RequiresScopes(dir.id),
+                TypeSystemDirective::Extension(dir) if dir.kind.is_authorization() => {
+                    QueryModifierRule::ExtensionDefinition {
+                        directive_id: dir.id,
+                        definition_id: self.output.operation.root_object_id.into(),
+                    }
+                }
                 TypeSystemDirective::Cost(_)
                 | TypeSystemDirective::Deprecated(_)
                 | TypeSystemDirective::ListSize(_)
@@ -509,6 +529,38 @@ impl<'a> Solver<'a> {
         Ok(())
     }
 
+    /// Creates the response object set of the parent field, if not already present, to access
+    /// the parent objects of the field after its execution. Returns false for root fields.
+    fn create_parent_field_output(
+        &mut self,
+        node_to_field: &[Option<PartitionFieldId>],
+        node_ix: NodeIndex,
+        field_id: PartitionDataFieldId,
+    ) -> bool {
+        if self.output.query_plan[field_id].parent_field_output_id.is_some() {
+            return true;
+        }
+        let parent_ix = self
+            .solution
+            .graph
+            .edges_directed(node_ix, Direction::Incoming)
+            .find(|edge| matches!(edge.weight(), Edge::Field))
+            .expect("Must have a parent field node or root")
+            .source();
+        let Some(PartitionFieldId::Data(parent_field_id)) = node_to_field[parent_ix.index()] else {
+            return false;
+        };
+        let output_id = Some(self.create_new_response_object_set_definition(parent_ix));
+        self.output.query_plan[parent_field_id].output_id = output_id;
+        for id in self.output.query_plan[parent_field_id]
+            .selection_set_record
+            .data_field_ids_ordered_by_type_conditions_then_position
+        {
+            self.output.query_plan[id].parent_field_output_id = output_id;
+        }
+        true
+    }
+
     fn generate_mutation_partition_order_after_partition_generation(&mut self) -> SolveResult<()> {
         if !self.output.operation.attributes.ty.is_mutation() {
             return Ok(());
@@ -4,6 +4,8 @@ mod error;
 mod query_plan;
 mod shape;
 
+use std::sync::OnceLock;
+
 use grafbase_telemetry::graphql::OperationType;
 use id_newtypes::IdRange;
 use operation::{Operation, OperationContext};
@@ -21,6 +23,9 @@ pub struct CachedOperation {
     pub(crate) operation: Operation,
     pub(crate) query_plan: QueryPlan,
     pub(crate) shapes: Shapes,
+    /// Lazily computed as it's only needed by the response cache.
+    #[serde(skip)]
+    normalized_document: OnceLock<Option<String>>,
 }
 
 /// Solving is divided in roughly three steps:
@@ -91,4 +96,27 @@ impl CachedOperation {
     pub(crate) fn ty(&self) -> OperationType {
         self.operation.attributes.ty
     }
+
+    /// Document normalized following the operation signature rules, so that formatting,
+    /// field ordering and literal values don't matter.
+    pub(crate) fn normalized_document(&self) -> Option<&str> {
+        self.normalized_document
+            .get_or_init(|| {
+                operation_normalizer::normalize(&self.document.content, self.document.operation_name())
+                    .inspect_err(|err| tracing::debug!("Failed to normalize the operation: {err}"))
+                    .ok()
+            })
+            .as_deref()
+    }
+
+    /// Whether the response depends on authorization decisions, from `@authenticated`,
+    /// `@requiresScopes`, `@authorized` or authorization extensions.
+    pub(crate) fn has_authorization_modifiers(&self) -> bool {
+        !self.query_plan.response_modifier_definitions.is_empty()
+            || self
+                .query_plan
+                .query_modifiers
+                .iter()
+                .any(|modifier| !matches!(modifier.rule, QueryModifierRule::Executable { .. }))
+    }
 }
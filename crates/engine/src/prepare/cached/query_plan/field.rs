@@ -9,6 +9,7 @@ use crate::prepare::FieldShapeId;
 
 pub(crate) use argument::*;
 pub(crate) use data::*;
+pub(crate) use extension::ExtensionDirectiveArgumentsQueryView;
 pub(crate) use typename::*;
 
 impl<'a> PartitionDataField<'a> {
@@ -1,4 +1,4 @@
-use schema::{AuthorizedDirectiveId, DefinitionId, FieldDefinitionId, RequiresScopesDirectiveId};
+use schema::{AuthorizedDirectiveId, DefinitionId, ExtensionDirectiveId, FieldDefinitionId, RequiresScopesDirectiveId};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
 pub(crate) enum QueryModifierRule {
@@ -17,6 +17,15 @@ pub(crate) enum QueryModifierRule {
         directive_id: AuthorizedDirectiveId,
         definition_id: DefinitionId,
     },
+    ExtensionField {
+        directive_id: ExtensionDirectiveId,
+        definition_id: FieldDefinitionId,
+        argument_ids: query_solver::QueryOrSchemaFieldArgumentIds,
+    },
+    ExtensionDefinition {
+        directive_id: ExtensionDirectiveId,
+        definition_id: DefinitionId,
+    },
     Executable {
         // sorted
         directives: Vec<operation::ExecutableDirectiveId>,
@@ -33,4 +42,8 @@ pub(crate) enum ResponseModifierRule {
         directive_id: AuthorizedDirectiveId,
         definition_id: FieldDefinitionId,
     },
+    ExtensionField {
+        directive_id: ExtensionDirectiveId,
+        definition_id: FieldDefinitionId,
+    },
 }
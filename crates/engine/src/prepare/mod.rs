@@ -46,6 +46,8 @@ impl<R: Runtime> PrepareContext<'_, R> {
                 self.metrics()
                     .record_successful_preparation_duration(operation.attributes(), duration);
 
+                self.rate_limit_operation(&operation).await?;
+
                 Ok(operation)
             }
             Err(response) => {
@@ -1,3 +1,4 @@
+use grafbase_telemetry::graphql::OperationType;
 use id_newtypes::IdToMany;
 use itertools::Itertools;
 use schema::CompositeTypeId;
@@ -119,9 +120,50 @@ impl<'op, R: Runtime> Builder<'op, '_, R> {
             self.operation_plan[next_id].parent_count += 1;
         }
 
+        self.propagate_deferral();
+
         Ok(self.operation_plan)
     }
 
+    /// Plans depending on a deferred plan can't be part of the initial response either.
+    fn propagate_deferral(&mut self) {
+        let mut stack = self
+            .operation_plan
+            .plans
+            .iter()
+            .enumerate()
+            .filter(|(_, plan)| plan.is_deferred)
+            .map(|(ix, _)| ExecutableId::from(PlanId::from(ix)))
+            .collect::<Vec<_>>();
+
+        while let Some(id) = stack.pop() {
+            let (children_ids, defer_label) = match id {
+                ExecutableId::Plan(plan_id) => {
+                    let plan = &self.operation_plan[plan_id];
+                    (plan.children_ids.clone(), plan.defer_label.clone())
+                }
+                ExecutableId::ResponseModifier(modifier_id) => {
+                    (self.operation_plan[modifier_id].children_ids.clone(), None)
+                }
+            };
+            for child_id in children_ids {
+                match child_id {
+                    ExecutableId::Plan(plan_id) => {
+                        let plan = &mut self.operation_plan[plan_id];
+                        if !plan.is_deferred {
+                            plan.is_deferred = true;
+                            if plan.defer_label.is_none() {
+                                plan.defer_label = defer_label.clone();
+                            }
+                            stack.push(child_id);
+                        }
+                    }
+                    ExecutableId::ResponseModifier(_) => stack.push(child_id),
+                }
+            }
+        }
+    }
+
     fn generate_response_modifier(&mut self, definition: ResponseModifierDefinition<'op>) -> PlanResult<()> {
         let mut impacted_fields = Vec::new();
         for field in definition.impacted_fields() {
@@ -129,7 +171,7 @@ impl<'op, R: Runtime> Builder<'op, '_, R> {
                 continue;
             }
             let (set_id, composite_type_id) = match definition.rule {
-                ResponseModifierRule::AuthorizedParentEdge { .. } => (
+                ResponseModifierRule::AuthorizedParentEdge { .. } | ResponseModifierRule::ExtensionField { .. } => (
                     field.parent_field_output_id.ok_or_else(|| {
                         tracing::error!("Missing response object set id.");
                         PlanError::InternalError
@@ -189,6 +231,7 @@ impl<'op, R: Runtime> Builder<'op, '_, R> {
         let required_fields_record = self.create_required_field_set_for_query_partition(query_partition);
 
         self.register_dependencies(plan_id.into(), required_fields_record.walk(self.cached_ctx));
+        let (is_deferred, defer_label) = self.is_query_partition_deferred(query_partition);
         let plan_resolver = PlanRecord {
             query_partition_id: query_partition.id,
             required_fields_record,
@@ -196,11 +239,71 @@ impl<'op, R: Runtime> Builder<'op, '_, R> {
             // Set later
             parent_count: 0,
             children_ids: Vec::new(),
+            is_deferred,
+            defer_label,
         };
         self.operation_plan.plans.push(plan_resolver);
         Ok(())
     }
 
+    /// A query partition is deferred if all of the fields it resolves for the client are within
+    /// an active @defer fragment or are streamed lists. A streamed plan is thus never waited on
+    /// for the initial payload, its first items are delivered in a subsequent one instead. The
+    /// query solver already puts these root fields into their own partitions, so a partition only
+    /// mixes deferred and non-deferred fields if the @defer `if` argument turned out to be false,
+    /// or through nested fields which are part of the initial response with their parent.
+    fn is_query_partition_deferred(&self, query_partition: QueryPartition<'_>) -> (bool, Option<String>) {
+        if !matches!(self.operation.ty(), OperationType::Query) {
+            return (false, None);
+        }
+        let modifications = &self.operation_plan.query_modifications;
+        let selection_set = query_partition.as_ref().selection_set_record;
+
+        let mut has_client_field = false;
+        let mut label = None;
+        for field in selection_set
+            .data_field_ids_ordered_by_type_conditions_then_position
+            .walk(self.cached_ctx)
+        {
+            if field.query_position.is_none() || !modifications.response_data_fields[field.id] {
+                continue;
+            }
+            let stream = field.query_position.and_then(|query_position| {
+                modifications
+                    .streamed_fields
+                    .binary_search_by_key(&query_position, |stream| stream.query_position)
+                    .ok()
+                    .map(|ix| &modifications.streamed_fields[ix])
+            });
+            if !modifications.deferred_data_fields[field.id] && stream.is_none() {
+                return (false, None);
+            }
+            has_client_field = true;
+            if label.is_none() {
+                label = modifications
+                    .data_field_id_to_defer_label
+                    .find_all(field.id)
+                    .next()
+                    .cloned()
+                    .or_else(|| stream.and_then(|stream| stream.label.clone()));
+            }
+        }
+        for field in selection_set
+            .typename_field_ids_ordered_by_type_conditions_then_position
+            .walk(self.cached_ctx)
+        {
+            if field.query_position.is_none() || !modifications.response_typename_fields[field.id] {
+                continue;
+            }
+            if !modifications.deferred_typename_fields[field.id] {
+                return (false, None);
+            }
+            has_client_field = true;
+        }
+
+        (has_client_field, label)
+    }
+
     fn create_required_field_set_for_query_partition(
         &mut self,
         query_partition: QueryPartition<'_>,
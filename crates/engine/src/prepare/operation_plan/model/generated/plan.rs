@@ -19,6 +19,9 @@ use walker::{Iter, Walk};
 ///   resolver: Resolver!
 ///   parent_count: usize!
 ///   children: [Executable!]!
+///   "Only resolves fields within @defer fragments, so it can be delivered after the initial response"
+///   is_deferred: Boolean!
+///   defer_label: String
 /// }
 /// ```
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -28,6 +31,9 @@ pub(crate) struct PlanRecord {
     pub resolver: Resolver,
     pub parent_count: usize,
     pub children_ids: Vec<ExecutableId>,
+    /// Only resolves fields within @defer fragments, so it can be delivered after the initial response
+    pub is_deferred: bool,
+    pub defer_label: Option<String>,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, serde::Serialize, serde::Deserialize, id_derives::Id)]
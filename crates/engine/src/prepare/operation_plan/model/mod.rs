@@ -61,3 +61,10 @@ pub(crate) struct OperationPlan {
     #[indexed_by(ResponseModifierId)]
     pub response_modifiers: Vec<ResponseModifierRecord>,
 }
+
+impl OperationPlan {
+    /// Whether any plan is deferred or any list field is streamed.
+    pub(crate) fn requires_incremental_delivery(&self) -> bool {
+        !self.query_modifications.streamed_fields.is_empty() || self.plans.iter().any(|plan| plan.is_deferred)
+    }
+}
@@ -18,7 +18,6 @@ impl<'a> Plan<'a> {
     pub(crate) fn entity_definition(&self) -> EntityDefinition<'a> {
         self.query_partition().entity_definition()
     }
-    #[allow(unused)]
     pub(crate) fn resolver_definition(&self) -> ResolverDefinition<'a> {
         self.query_partition().resolver_definition()
     }
@@ -1,16 +1,19 @@
 use std::{num::NonZero, ops::Deref};
 
+use futures::FutureExt;
+use grafbase_telemetry::graphql::OperationType;
 use id_newtypes::{BitSet, IdToMany};
-use operation::{InputValueContext, Variables};
+use operation::{InputValueContext, QueryPosition, Variables};
 use query_solver::QueryOrSchemaFieldArgumentIds;
+use runtime::extension::{AuthorizedDefinition, ExtensionAuthorizationDirective, ExtensionRuntime};
 use serde::Deserialize;
 use walker::Walk;
 
 use crate::{
     prepare::{
-        CachedOperation, CachedOperationContext, ConcreteShapeId, ErrorCode, FieldShapeId, GraphqlError,
-        PartitionDataFieldId, PartitionField, PartitionTypenameFieldId, PrepareContext, QueryModifier,
-        QueryModifierRule, RequiredFieldSetRecord,
+        CachedOperation, CachedOperationContext, ConcreteShapeId, ErrorCode, ExtensionDirectiveArgumentsQueryView,
+        FieldShapeId, GraphqlError, PartitionDataFieldId, PartitionField, PartitionTypenameFieldId, PrepareContext,
+        QueryModifier, QueryModifierRule, RequiredFieldSetRecord,
     },
     Runtime,
 };
@@ -29,6 +32,18 @@ pub(crate) struct QueryModifications {
     pub field_shape_id_to_error_ids: IdToMany<FieldShapeId, ErrorId>,
     pub skipped_field_shapes: BitSet<FieldShapeId>,
     pub root_error_ids: Vec<ErrorId>,
+    /// Fields within an active @defer fragment.
+    pub deferred_data_fields: BitSet<PartitionDataFieldId>,
+    pub deferred_typename_fields: BitSet<PartitionTypenameFieldId>,
+    pub data_field_id_to_defer_label: IdToMany<PartitionDataFieldId, String>,
+    /// List fields with an active @stream directive, sorted by query position.
+    pub streamed_fields: Vec<StreamedField>,
+}
+
+pub(crate) struct StreamedField {
+    pub query_position: QueryPosition,
+    pub initial_count: usize,
+    pub label: Option<String>,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, serde::Serialize, serde::Deserialize, id_derives::Id)]
@@ -52,6 +67,7 @@ impl QueryModifications {
                 variables,
             },
             field_shape_id_to_error_ids: Default::default(),
+            data_field_id_to_defer_label: Default::default(),
             modifications: QueryModifications {
                 is_any_field_skipped: false,
                 response_data_fields: cached.query_plan.response_data_fields.clone(),
@@ -62,6 +78,10 @@ impl QueryModifications {
                 field_shape_id_to_error_ids: Default::default(),
                 root_error_ids: Vec::new(),
                 skipped_field_shapes: BitSet::with_capacity(cached.shapes.fields.len()),
+                deferred_data_fields: BitSet::with_capacity(cached.query_plan.data_fields.len()),
+                deferred_typename_fields: BitSet::with_capacity(cached.query_plan.typename_fields.len()),
+                data_field_id_to_defer_label: Default::default(),
+                streamed_fields: Vec::new(),
             },
         }
         .build()
@@ -74,6 +94,7 @@ struct Builder<'ctx, 'op, R: Runtime> {
     operation_ctx: CachedOperationContext<'op>,
     input_value_ctx: InputValueContext<'op>,
     field_shape_id_to_error_ids: Vec<(FieldShapeId, ErrorId)>,
+    data_field_id_to_defer_label: Vec<(PartitionDataFieldId, String)>,
     modifications: QueryModifications,
 }
 
@@ -83,9 +104,10 @@ where
 {
     pub(super) async fn build(mut self) -> PlanResult<QueryModifications> {
         let mut scope_jwt_claim = None;
+        let mut extension_elements = Vec::new();
 
         for modifier in self.operation_ctx.query_modifiers() {
-            match &modifier.rule {
+            match &modifier.as_ref().rule {
                 QueryModifierRule::Authenticated => {
                     if self.ctx.access_token().is_anonymous() {
                         self.handle_authorization_modifier(
@@ -175,10 +197,50 @@ where
                         self.handle_authorization_modifier(modifier, AuthorizationModifierResult::Denied(Some(error)));
                     }
                 }
+                QueryModifierRule::ExtensionField {
+                    directive_id,
+                    definition_id,
+                    argument_ids,
+                } => {
+                    let directive = directive_id.walk(self.ctx.schema());
+                    extension_elements.push((
+                        modifier,
+                        ExtensionAuthorizationDirective {
+                            extension_id: directive.extension_id,
+                            subgraph: directive.subgraph(),
+                            definition: AuthorizedDefinition::Field(definition_id.walk(self.ctx.schema())),
+                            name: directive.name(),
+                            arguments: argument_ids
+                                .walk(self.operation_ctx)
+                                .into_extension_directive_query_view(directive, self.input_value_ctx.variables),
+                        },
+                    ));
+                }
+                QueryModifierRule::ExtensionDefinition {
+                    directive_id,
+                    definition_id,
+                } => {
+                    let directive = directive_id.walk(self.ctx.schema());
+                    extension_elements.push((
+                        modifier,
+                        ExtensionAuthorizationDirective {
+                            extension_id: directive.extension_id,
+                            subgraph: directive.subgraph(),
+                            definition: AuthorizedDefinition::Type(definition_id.walk(self.ctx.schema())),
+                            name: directive.name(),
+                            arguments: QueryOrSchemaFieldArgumentIds::default()
+                                .walk(self.operation_ctx)
+                                .into_extension_directive_query_view(directive, self.input_value_ctx.variables),
+                        },
+                    ));
+                }
                 QueryModifierRule::Executable { directives } => {
                     // GraphQL spec:
                     //   Stated conversely, the field or fragment must not be queried if either the @skip condition is true or the @include condition is false.
                     let is_skipped = directives.iter().any(|directive| match directive {
+                        operation::ExecutableDirectiveId::Defer(_) | operation::ExecutableDirectiveId::Stream(_) => {
+                            false
+                        }
                         operation::ExecutableDirectiveId::Include(directive) => {
                             !bool::deserialize(directive.condition.walk(self.input_value_ctx))
                                 .expect("at this point we've already checked the argument type")
@@ -190,15 +252,127 @@ where
                     });
 
                     if is_skipped {
-                        self.handle_skipped_field(modifier)
+                        self.handle_skipped_field(modifier);
+                        continue;
+                    }
+
+                    let mut active_defer_directives = directives
+                        .iter()
+                        .filter_map(|directive| directive.as_defer())
+                        .filter(|directive| {
+                            bool::deserialize(directive.condition.walk(self.input_value_ctx))
+                                .expect("at this point we've already checked the argument type")
+                        })
+                        .peekable();
+
+                    if active_defer_directives.peek().is_some() {
+                        let label = active_defer_directives
+                            .filter_map(|directive| directive.label)
+                            .find_map(|label| {
+                                Option::<String>::deserialize(label.walk(self.input_value_ctx))
+                                    .expect("at this point we've already checked the argument type")
+                            });
+                        self.handle_deferred_field(modifier, label);
+                    }
+
+                    // Incremental delivery is only supported for queries.
+                    if self.operation_ctx.cached.ty() != OperationType::Query {
+                        continue;
+                    }
+                    for directive in directives.iter().filter_map(|directive| directive.as_stream()) {
+                        if !bool::deserialize(directive.condition.walk(self.input_value_ctx))
+                            .expect("at this point we've already checked the argument type")
+                        {
+                            continue;
+                        }
+                        let initial_count = i32::deserialize(directive.initial_count.walk(self.input_value_ctx))
+                            .expect("at this point we've already checked the argument type");
+                        let label = directive.label.and_then(|label| {
+                            Option::<String>::deserialize(label.walk(self.input_value_ctx))
+                                .expect("at this point we've already checked the argument type")
+                        });
+                        self.handle_streamed_field(modifier, initial_count.max(0) as usize, label);
                     }
                 }
             }
         }
 
+        if !extension_elements.is_empty() {
+            self.authorize_with_extensions(extension_elements).await;
+        }
+
         Ok(self.finalize())
     }
 
+    /// Authorizes all the elements with a single call per extension.
+    async fn authorize_with_extensions(
+        &mut self,
+        mut elements: Vec<(
+            QueryModifier<'op>,
+            ExtensionAuthorizationDirective<'op, ExtensionDirectiveArgumentsQueryView<'op>>,
+        )>,
+    ) {
+        elements.sort_by_key(|(_, element)| element.extension_id);
+        let mut elements = elements.into_iter().peekable();
+
+        while let Some((modifier, element)) = elements.next() {
+            let extension_id = element.extension_id;
+            let mut modifiers = vec![modifier];
+            let mut directives = vec![element];
+            while let Some((modifier, element)) = elements.next_if(|(_, element)| element.extension_id == extension_id)
+            {
+                modifiers.push(modifier);
+                directives.push(element);
+            }
+
+            let result = self
+                .ctx
+                .engine
+                .runtime
+                .extensions()
+                .authorize_query(&self.ctx.hooks_context, extension_id, directives)
+                // FIXME: Unfortunately, boxing seems to be the only solution for the bug explained here:
+                //        https://github.com/rust-lang/rust/issues/110338#issuecomment-1513761297
+                //        Otherwise is not correctly evaluated to be Send due to the impl Anything
+                .boxed()
+                .await;
+
+            match result {
+                Ok(verdicts) if verdicts.len() == modifiers.len() => {
+                    for (modifier, verdict) in modifiers.into_iter().zip(verdicts) {
+                        if let Err(error) = verdict {
+                            self.handle_authorization_modifier(
+                                modifier,
+                                AuthorizationModifierResult::Denied(Some(error.into())),
+                            );
+                        }
+                    }
+                }
+                Ok(_) => {
+                    tracing::error!("Incorrect number of authorization replies");
+                    for modifier in modifiers {
+                        self.handle_authorization_modifier(
+                            modifier,
+                            AuthorizationModifierResult::Denied(Some(GraphqlError::new(
+                                "Authorization failure",
+                                ErrorCode::InternalServerError,
+                            ))),
+                        );
+                    }
+                }
+                Err(error) => {
+                    let error = GraphqlError::from(error);
+                    for modifier in modifiers {
+                        self.handle_authorization_modifier(
+                            modifier,
+                            AuthorizationModifierResult::Denied(Some(error.clone())),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     fn finalize(mut self) -> QueryModifications {
         self.modifications.subgraph_request_data_fields = self.modifications.response_data_fields.clone();
         let mut requires_stack: Vec<&'op RequiredFieldSetRecord> =
@@ -241,6 +415,11 @@ where
             }
         }
 
+        self.modifications.data_field_id_to_defer_label = self.data_field_id_to_defer_label.into();
+        self.modifications
+            .streamed_fields
+            .sort_unstable_by_key(|field| field.query_position);
+
         // Identify all concrete shapes with errors.
         self.modifications.field_shape_id_to_error_ids = self.field_shape_id_to_error_ids.into();
         let mut field_shape_ids_with_errors = self.modifications.field_shape_id_to_error_ids.ids();
@@ -317,6 +496,36 @@ where
         }
     }
 
+    fn handle_deferred_field(&mut self, modifier: QueryModifier<'op>, label: Option<String>) {
+        for field in modifier.impacted_fields() {
+            match field {
+                PartitionField::Typename(field) => {
+                    self.modifications.deferred_typename_fields.set(field.id, true);
+                }
+                PartitionField::Data(field) => {
+                    self.modifications.deferred_data_fields.set(field.id, true);
+                    if let Some(label) = &label {
+                        self.data_field_id_to_defer_label.push((field.id, label.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_streamed_field(&mut self, modifier: QueryModifier<'op>, initial_count: usize, label: Option<String>) {
+        for field in modifier.impacted_fields() {
+            if let PartitionField::Data(field) = field {
+                if let Some(query_position) = field.query_position {
+                    self.modifications.streamed_fields.push(StreamedField {
+                        query_position,
+                        initial_count,
+                        label: label.clone(),
+                    });
+                }
+            }
+        }
+    }
+
     fn push_error(&mut self, error: GraphqlError) -> ErrorId {
         let id = ErrorId::from(self.modifications.errors.len());
         self.modifications.errors.push(error);
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use futures::future::BoxFuture;
 use futures_lite::FutureExt;
+use futures_util::{stream::BoxStream, StreamExt};
 use runtime::{
     error::PartialGraphqlError,
     extension::{Data, ExtensionFieldDirective, ExtensionRuntime},
@@ -10,10 +11,13 @@ use schema::{FieldResolverExtensionDefinition, FieldResolverExtensionDefinitionR
 use walker::Walk;
 
 use crate::{
-    execution::{ExecutionContext, ExecutionResult},
+    execution::{ExecutionContext, ExecutionError, ExecutionResult, SubscriptionResponse},
     prepare::{Plan, SubgraphField},
     resolver::Resolver,
-    response::{GraphqlError, InputResponseObjectSet, ResponseObjectsView, SubgraphResponse},
+    response::{
+        GraphqlError, InputObjectId, InputResponseObjectSet, ResponseObjectsView, SubgraphResponse,
+        SubgraphResponseRefMut,
+    },
     Runtime,
 };
 
@@ -43,11 +47,10 @@ impl FieldResolverExtension {
             .next()
             .expect("At least one field must be present");
 
-        let field_definition = field.definition();
         let extension_directive = ExtensionFieldDirective {
             extension_id: directive.extension_id,
             subgraph: directive.subgraph(),
-            field: field_definition,
+            field: field.definition(),
             name: directive.name(),
             arguments: field
                 .arguments()
@@ -69,6 +72,58 @@ impl FieldResolverExtension {
             future,
         }
     }
+
+    pub(in crate::resolver) async fn execute_subscription<'ctx, R: Runtime>(
+        &'ctx self,
+        ctx: ExecutionContext<'ctx, R>,
+        plan: Plan<'ctx>,
+        new_response: impl Fn() -> SubscriptionResponse + Send + 'ctx,
+    ) -> ExecutionResult<BoxStream<'ctx, ExecutionResult<SubscriptionResponse>>> {
+        let directive = self.definition.walk(ctx.schema()).directive();
+        let field = plan
+            .selection_set()
+            .fields()
+            .next()
+            .expect("At least one field must be present");
+
+        let extension_directive = ExtensionFieldDirective {
+            extension_id: directive.extension_id,
+            subgraph: directive.subgraph(),
+            field: field.definition(),
+            name: directive.name(),
+            arguments: field
+                .arguments()
+                .into_extension_directive_query_view(directive, ctx.variables()),
+        };
+
+        let stream = ctx
+            .engine
+            .runtime
+            .extensions()
+            .resolve_subscription(ctx.hooks_context, extension_directive)
+            .boxed()
+            .await
+            .map_err(|err| ExecutionError::Graphql(err.into()))?;
+
+        let stream = stream.map(move |item| {
+            let mut subscription_response = new_response();
+
+            let input_id = subscription_response.input_id();
+
+            {
+                let response = subscription_response.as_mut();
+
+                match item {
+                    Ok(data) => ingest_data(&ctx, &response, input_id, field, data)?,
+                    Err(err) => response.borrow_mut().insert_errors(err, [input_id]),
+                }
+            }
+
+            Ok(subscription_response)
+        });
+
+        Ok(Box::pin(stream))
+    }
 }
 
 pub(in crate::resolver) struct FieldResolverExtensionRequest<'ctx> {
@@ -92,42 +147,7 @@ impl<'ctx> FieldResolverExtensionRequest<'ctx> {
                 let response = subgraph_response.as_shared_mut();
                 for (id, result) in input_object_refs.ids().zip(result) {
                     match result {
-                        Ok(data) => match data {
-                            Data::JsonBytes(bytes) => {
-                                tracing::debug!("Received:\n{}", String::from_utf8_lossy(&bytes));
-
-                                response
-                                    .seed(&ctx, id)
-                                    .deserialize_field_as_entity(
-                                        field.subgraph_response_key_str(),
-                                        &mut serde_json::Deserializer::from_slice(&bytes),
-                                    )
-                                    .map_err(|err| {
-                                        tracing::error!("Failed to deserialize subgraph response: {}", err);
-                                        GraphqlError::invalid_subgraph_response()
-                                    })?;
-                            }
-                            Data::CborBytes(bytes) => {
-                                tracing::debug!(
-                                    "Received:\n{}",
-                                    minicbor_serde::from_slice(&bytes)
-                                        .ok()
-                                        .and_then(|v: serde_json::Value| serde_json::to_string_pretty(&v).ok())
-                                        .unwrap_or_else(|| "<error>".to_string())
-                                );
-
-                                response
-                                    .seed(&ctx, id)
-                                    .deserialize_field_as_entity(
-                                        field.subgraph_response_key_str(),
-                                        &mut minicbor_serde::Deserializer::new(&bytes),
-                                    )
-                                    .map_err(|err| {
-                                        tracing::error!("Failed to deserialize subgraph response: {}", err);
-                                        GraphqlError::invalid_subgraph_response()
-                                    })?;
-                            }
-                        },
+                        Ok(data) => ingest_data(&ctx, &response, id, field, data)?,
                         Err(err) => response.borrow_mut().insert_errors(err, [id]),
                     }
                 }
@@ -138,3 +158,53 @@ impl<'ctx> FieldResolverExtensionRequest<'ctx> {
         Ok(subgraph_response)
     }
 }
+
+fn ingest_data<'ctx, 'resp, R: Runtime>(
+    ctx: &ExecutionContext<'ctx, R>,
+    response: &SubgraphResponseRefMut<'resp>,
+    id: InputObjectId,
+    field: SubgraphField<'ctx>,
+    data: Data,
+) -> Result<(), GraphqlError>
+where
+    'ctx: 'resp,
+{
+    match data {
+        Data::JsonBytes(bytes) => {
+            tracing::debug!("Received:\n{}", String::from_utf8_lossy(&bytes));
+
+            response
+                .seed(ctx, id)
+                .deserialize_field_as_entity(
+                    field.subgraph_response_key_str(),
+                    &mut serde_json::Deserializer::from_slice(&bytes),
+                )
+                .map_err(|err| {
+                    tracing::error!("Failed to deserialize subgraph response: {}", err);
+                    GraphqlError::invalid_subgraph_response()
+                })?;
+        }
+        Data::CborBytes(bytes) => {
+            tracing::debug!(
+                "Received:\n{}",
+                minicbor_serde::from_slice(&bytes)
+                    .ok()
+                    .and_then(|v: serde_json::Value| serde_json::to_string_pretty(&v).ok())
+                    .unwrap_or_else(|| "<error>".to_string())
+            );
+
+            response
+                .seed(ctx, id)
+                .deserialize_field_as_entity(
+                    field.subgraph_response_key_str(),
+                    &mut minicbor_serde::Deserializer::new(&bytes),
+                )
+                .map_err(|err| {
+                    tracing::error!("Failed to deserialize subgraph response: {}", err);
+                    GraphqlError::invalid_subgraph_response()
+                })?;
+        }
+    }
+
+    Ok(())
+}
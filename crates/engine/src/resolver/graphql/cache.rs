@@ -3,7 +3,8 @@ use grafbase_telemetry::graphql::GraphqlResponseStatus;
 use headers::HeaderMapExt;
 use http::HeaderMap;
 use itertools::Itertools;
-use runtime::entity_cache::EntityCache;
+use runtime::entity_cache::{EntityCache, EntityCacheTag};
+use schema::FieldSet;
 use serde_json::value::RawValue;
 use std::time::Duration;
 
@@ -60,17 +61,19 @@ pub(super) async fn fetch_response<R: Runtime>(
     ctx.engine()
         .runtime
         .entity_cache()
-        .get(&key)
+        .get_with_ttl(&key)
         .await
         .inspect_err(|err| tracing::warn!("Failed to read the cache key {key}: {err}"))
         .ok()
         .flatten()
-        .map(|data| Ok(ResponseCacheHit { data }))
+        .map(|(data, ttl)| Ok(ResponseCacheHit { data, ttl }))
         .unwrap_or(Err(ResponseCacheMiss { key }))
 }
 
 pub(super) struct ResponseCacheHit {
     pub data: Vec<u8>,
+    /// Remaining time to live of the entry.
+    pub ttl: Option<Duration>,
 }
 
 pub(super) struct ResponseCacheMiss {
@@ -80,6 +83,7 @@ pub(super) struct ResponseCacheMiss {
 pub(super) async fn fetch_entities<R: Runtime>(
     ctx: &mut SubgraphContext<'_, R>,
     subgraph_headers: &http::HeaderMap,
+    key_fields: FieldSet<'_>,
     entities_to_fetch: Vec<EntityToFetch>,
 ) -> CacheFetchEntitiesOutcome {
     let entity_cache = ctx.engine.runtime.entity_cache();
@@ -103,7 +107,8 @@ pub(super) async fn fetch_entities<R: Runtime>(
     // FIXME: handle cache scopes
     let additional_scopes = Vec::new();
 
-    let hasher = prepare_key_hasher(ctx.endpoint().subgraph_name(), subgraph_headers, &additional_scopes);
+    let subgraph_name = ctx.endpoint().subgraph_name();
+    let hasher = prepare_key_hasher(subgraph_name, subgraph_headers, &additional_scopes);
     let fetches = entities_to_fetch
         .into_iter()
         .map(|EntityToFetch { id, representation }| {
@@ -112,7 +117,7 @@ pub(super) async fn fetch_entities<R: Runtime>(
                 .update(representation.get().as_bytes())
                 .finalize()
                 .to_string();
-            fetch_entity(entity_cache, id, key, representation)
+            fetch_entity(entity_cache, subgraph_name, key_fields, id, key, representation)
         });
 
     let (hits, misses) = join_all(fetches).await.into_iter().partition_result();
@@ -127,37 +132,95 @@ pub(super) struct CacheFetchEntitiesOutcome {
 pub(super) struct EntityCacheHit {
     pub id: InputObjectId,
     pub data: Vec<u8>,
+    /// Remaining time to live of the entry.
+    pub ttl: Option<Duration>,
 }
 
 pub(super) struct EntityCacheMiss {
     pub id: InputObjectId,
     pub key: String,
+    pub tags: Vec<String>,
     pub representation: Box<RawValue>,
 }
 
 async fn fetch_entity(
     entity_cache: &dyn EntityCache,
+    subgraph_name: &str,
+    key_fields: FieldSet<'_>,
     id: InputObjectId,
     key: String,
     representation: Box<RawValue>,
 ) -> Result<EntityCacheHit, EntityCacheMiss> {
-    let data = entity_cache
-        .get(&key)
+    let entry = entity_cache
+        .get_with_ttl(&key)
         .await
         .inspect_err(|err| tracing::warn!("Failed to read the cache key {key}: {err}"))
         .ok()
         .flatten();
 
-    match data {
-        Some(data) => Ok(EntityCacheHit { id, data }),
+    match entry {
+        Some((data, ttl)) => Ok(EntityCacheHit { id, data, ttl }),
         None => Err(EntityCacheMiss {
             id,
             key,
+            tags: entity_tags(subgraph_name, key_fields, &representation),
             representation,
         }),
     }
 }
 
+/// Tags an entity with its subgraph, its type and its key.
+fn entity_tags(subgraph_name: &str, key_fields: FieldSet<'_>, representation: &RawValue) -> Vec<String> {
+    let mut tags = vec![EntityCacheTag::Subgraph(subgraph_name).to_string()];
+
+    let Ok(representation) = serde_json::from_str::<serde_json::Value>(representation.get()) else {
+        return tags;
+    };
+
+    let Some(typename) = representation.get("__typename").and_then(|typename| typename.as_str()) else {
+        return tags;
+    };
+
+    tags.push(EntityCacheTag::Type(typename).to_string());
+
+    // The representation also holds the `@requires` fields, which aren't part of the key.
+    if let Some(key) = key_value(&representation, key_fields) {
+        tags.push(EntityCacheTag::Entity { typename, key: &key }.to_string());
+    }
+
+    tags
+}
+
+fn key_value(value: &serde_json::Value, key_fields: FieldSet<'_>) -> Option<serde_json::Value> {
+    if key_fields.is_empty() {
+        return Some(value.clone());
+    }
+
+    match value {
+        serde_json::Value::Object(fields) => {
+            let mut key = serde_json::Map::new();
+
+            for item in key_fields.items() {
+                let name = item.field().definition().name();
+                key.insert(name.to_string(), key_value(fields.get(name)?, item.subselection())?);
+            }
+
+            Some(key.into())
+        }
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| key_value(item, key_fields))
+            .collect::<Option<Vec<_>>>()
+            .map(Into::into),
+        _ => Some(value.clone()),
+    }
+}
+
+/// Root field responses can only be purged with their subgraph.
+pub(super) fn root_fields_tags(subgraph_name: &str) -> Vec<String> {
+    vec![EntityCacheTag::Subgraph(subgraph_name).to_string()]
+}
+
 fn prepare_key_hasher(subgraph_name: &str, headers: &HeaderMap, additional_scopes: &[String]) -> blake3::Hasher {
     let mut hasher = blake3::Hasher::new();
     hasher.update(b"v1");
@@ -18,13 +18,14 @@ use tracing::{Instrument, Span};
 use grafbase_telemetry::{
     graphql::SubgraphResponseStatus,
     metrics::{
-        SubgraphCacheHitAttributes, SubgraphCacheMissAttributes, SubgraphInFlightRequestAttributes,
-        SubgraphRequestBodySizeAttributes, SubgraphRequestDurationAttributes, SubgraphRequestRetryAttributes,
-        SubgraphResponseBodySizeAttributes,
+        SubgraphCacheHitAttributes, SubgraphCacheMissAttributes, SubgraphCircuitBreakerAttributes,
+        SubgraphInFlightRequestAttributes, SubgraphRequestBodySizeAttributes, SubgraphRequestDurationAttributes,
+        SubgraphRequestRetryAttributes, SubgraphResponseBodySizeAttributes,
     },
 };
 
 use crate::{
+    engine::{CircuitBreaker, HedgingPolicy},
     execution::{ExecutionContext, ExecutionError, ExecutionResult, RequestHooks},
     resolver::ResolverResult,
     response::SubgraphResponse,
@@ -36,6 +37,9 @@ pub(crate) struct SubgraphContext<'ctx, R: Runtime> {
     pub(super) ctx: ExecutionContext<'ctx, R>,
     pub(super) endpoint: GraphqlEndpoint<'ctx>,
     pub(super) retry_budget: Option<&'ctx TpsBudget>,
+    pub(super) circuit_breaker: Option<&'ctx CircuitBreaker>,
+    pub(super) hedging_policy: Option<&'ctx HedgingPolicy>,
+    deduplicate_requests: bool,
     span: SubgraphGraphqlRequestSpan,
     start: Instant,
     executed_request_builder: ExecutedSubgraphRequestBuilder<'ctx>,
@@ -65,6 +69,11 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
             "mutation" => ctx.engine.get_retry_budget_for_mutation(endpoint.id),
             _ => ctx.engine.get_retry_budget_for_non_mutation(endpoint.id),
         };
+        let circuit_breaker = ctx.engine.get_circuit_breaker(endpoint.id);
+        // Mutations have side effects, so each one must reach the subgraph exactly once.
+        let is_query = span.operation_type == "query";
+        let deduplicate_requests = is_query && ctx.schema().settings.query_deduplication;
+        let hedging_policy = ctx.engine.get_hedging_policy(endpoint.id).filter(|_| is_query);
         let span = span.build();
 
         Self {
@@ -74,6 +83,9 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
             span,
             start: Instant::now(),
             retry_budget,
+            circuit_breaker,
+            hedging_policy,
+            deduplicate_requests,
             status: None,
             http_status_code: None,
             send_count: 0,
@@ -88,6 +100,10 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
         self.span.span.clone()
     }
 
+    pub fn graphql_span(&self) -> SubgraphGraphqlRequestSpan {
+        self.span.clone()
+    }
+
     pub fn engine(&self) -> &Engine<R> {
         self.execution_context().engine
     }
@@ -104,6 +120,14 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
         self.retry_budget
     }
 
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreaker> {
+        self.circuit_breaker
+    }
+
+    pub fn deduplicate_requests(&self) -> bool {
+        self.deduplicate_requests
+    }
+
     pub async fn finalize(
         self,
         subgraph_result: ExecutionResult<SubgraphResponse>,
@@ -200,6 +224,13 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
         });
     }
 
+    pub(super) fn record_circuit_breaker_rejection(&self) {
+        self.metrics()
+            .record_subgraph_circuit_breaker_rejection(SubgraphCircuitBreakerAttributes {
+                name: self.endpoint.subgraph_name().to_string(),
+            });
+    }
+
     pub(super) fn record_request_retry(&self) {
         self.metrics().record_subgraph_retry(SubgraphRequestRetryAttributes {
             name: self.endpoint.subgraph_name().to_string(),
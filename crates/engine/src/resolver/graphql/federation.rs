@@ -2,7 +2,7 @@ mod with_cache;
 mod without_cache;
 
 use grafbase_telemetry::{graphql::OperationType, span::subgraph::SubgraphRequestSpanBuilder};
-use schema::{GraphqlEndpointId, GraphqlFederationEntityResolverDefinition};
+use schema::{FieldSet, GraphqlEndpointId, GraphqlFederationEntityResolverDefinition, ResolverDefinitionVariant};
 use serde_json::value::RawValue;
 use tracing::Instrument;
 use walker::Walk;
@@ -85,8 +85,14 @@ impl FederationEntityResolver {
                 }
             }
 
+            let ResolverDefinitionVariant::GraphqlFederationEntity(definition) = plan.resolver_definition().variant()
+            else {
+                return Err("Expected a federation entity resolver".into());
+            };
+
             Ok(FederationEntityRequest {
                 resolver: self,
+                key_fields: definition.key_fields(),
                 subgraph_response,
                 entities_to_fetch,
                 entities_without_expected_requirements,
@@ -107,6 +113,7 @@ struct EntityWithoutExpectedRequirements {
 
 pub(crate) struct FederationEntityRequest<'ctx> {
     resolver: &'ctx FederationEntityResolver,
+    key_fields: FieldSet<'ctx>,
     subgraph_response: SubgraphResponse,
     entities_to_fetch: Vec<EntityToFetch>,
     entities_without_expected_requirements: Vec<EntityWithoutExpectedRequirements>,
@@ -116,6 +123,7 @@ impl<'ctx> FederationEntityRequest<'ctx> {
     pub async fn execute<R: Runtime>(self, ctx: &mut SubgraphContext<'ctx, R>) -> ExecutionResult<SubgraphResponse> {
         let Self {
             resolver: FederationEntityResolver { subgraph_operation, .. },
+            key_fields,
             mut subgraph_response,
             entities_to_fetch,
             entities_without_expected_requirements,
@@ -145,6 +153,7 @@ impl<'ctx> FederationEntityRequest<'ctx> {
                     ctx,
                     subgraph_headers,
                     subgraph_operation,
+                    key_fields,
                     entities_to_fetch,
                     subgraph_response,
                 )
@@ -221,10 +230,11 @@ pub(super) async fn fetch_entities_with_cache<R: Runtime>(
     ctx: &mut SubgraphContext<'_, R>,
     subgraph_headers: http::HeaderMap,
     subgraph_operation: &PreparedFederationEntityOperation,
+    key_fields: FieldSet<'_>,
     entities_to_fetch: Vec<EntityToFetch>,
     subgraph_response: SubgraphResponse,
 ) -> ExecutionResult<SubgraphResponse> {
-    let cache_fetch_outcome = super::cache::fetch_entities(ctx, &subgraph_headers, entities_to_fetch).await;
+    let cache_fetch_outcome = super::cache::fetch_entities(ctx, &subgraph_headers, key_fields, entities_to_fetch).await;
     if cache_fetch_outcome.misses.is_empty() {
         ctx.record_cache_hit();
         return with_cache::ingest_hits(ctx.execution_context(), cache_fetch_outcome.hits, subgraph_response);
@@ -15,7 +15,7 @@ use crate::{
         deserialize::{EntitiesDataSeed, EntityErrorPathConverter, GraphqlErrorsSeed, GraphqlResponseSeed},
         request::ResponseIngester,
     },
-    response::{GraphqlError, SubgraphResponse, SubgraphResponseRefMut},
+    response::{CacheControl, GraphqlError, SubgraphResponse, SubgraphResponseRefMut},
     Runtime,
 };
 
@@ -24,6 +24,10 @@ pub(super) fn ingest_hits(
     hits: Vec<EntityCacheHit>,
     mut subgraph_response: SubgraphResponse,
 ) -> ExecutionResult<SubgraphResponse> {
+    for hit in &hits {
+        subgraph_response.merge_cache_control(CacheControl::from_cache_ttl(hit.ttl));
+    }
+
     {
         let subgraph_response = subgraph_response.as_shared_mut();
         for hit in hits {
@@ -61,6 +65,10 @@ where
             subgraph_default_cache_ttl,
         } = self;
 
+        for hit in &hits {
+            subgraph_response.merge_cache_control(CacheControl::from_cache_ttl(hit.ttl));
+        }
+
         // New cache values we should update the cache with if everything went fine. Populated
         // while deserializing.
         let mut cache_updates = Vec::with_capacity(misses.len());
@@ -105,9 +113,9 @@ where
         if status.is_success() {
             if let Some(cache_ttl) = calculate_cache_ttl(status, http_response.headers(), subgraph_default_cache_ttl) {
                 let cache = ctx.engine.runtime.entity_cache();
-                join_all(cache_updates.into_iter().map(|(key, value)| async move {
+                join_all(cache_updates.into_iter().map(|(key, tags, value)| async move {
                     cache
-                        .put(&key, Cow::Borrowed(value.get().as_bytes()), cache_ttl)
+                        .put(&key, Cow::Borrowed(value.get().as_bytes()), cache_ttl, &tags)
                         .await
                         .inspect_err(|err| tracing::warn!("Failed to write the cache key {key}: {err}"))
                         .ok();
@@ -124,7 +132,7 @@ struct PartiallyCachedEntitiesSeed<'ctx, 'resp, 'de, 'updates, R: Runtime> {
     ctx: ExecutionContext<'ctx, R>,
     misses: Vec<EntityCacheMiss>,
     subgraph_response: SubgraphResponseRefMut<'resp>,
-    cache_updates: &'updates mut Vec<(String, &'de RawValue)>,
+    cache_updates: &'updates mut Vec<(String, Vec<String>, &'de RawValue)>,
 }
 
 impl<'ctx, 'resp, 'de, R: Runtime> DeserializeSeed<'de> for PartiallyCachedEntitiesSeed<'ctx, 'resp, 'de, '_, R>
@@ -166,7 +174,7 @@ where
 
         let mut cache_misses = misses.into_iter();
 
-        for EntityCacheMiss { id, key, .. } in cache_misses.by_ref() {
+        for EntityCacheMiss { id, key, tags, .. } in cache_misses.by_ref() {
             let raw_value = match seq.next_element::<&RawValue>() {
                 Ok(Some(value)) => value,
                 Ok(None) => {
@@ -196,7 +204,7 @@ where
                 .seed(&ctx, id)
                 .deserialize(raw_value)
                 .map_err(|err| A::Error::custom(err.to_string()))?;
-            cache_updates.push((key, raw_value));
+            cache_updates.push((key, tags, raw_value));
         }
 
         if seq.next_element::<IgnoredAny>().unwrap_or_default().is_some() {
@@ -1,10 +1,18 @@
-use std::{borrow::Cow, time::Duration};
+use std::{
+    borrow::Cow,
+    pin::pin,
+    time::{Duration, Instant},
+};
 
 use bytes::Bytes;
-use futures::Future;
+use futures::{
+    future::{select, Either},
+    Future,
+};
 use grafbase_telemetry::{
-    graphql::GraphqlResponseStatus, otel::tracing_opentelemetry::OpenTelemetrySpanExt as _,
-    span::subgraph::SubgraphHttpRequestSpan,
+    graphql::GraphqlResponseStatus,
+    otel::tracing_opentelemetry::OpenTelemetrySpanExt as _,
+    span::subgraph::{SubgraphGraphqlRequestSpan, SubgraphHttpRequestSpan},
 };
 use headers::HeaderMapExt;
 use runtime::{
@@ -13,14 +21,15 @@ use runtime::{
     hooks::{ResponseInfo, SubgraphRequestExecutionKind},
     rate_limiting::RateLimitKey,
 };
-use tower::retry::budget::Budget;
+use tower::retry::budget::{Budget, TpsBudget};
 use tracing::{Instrument, Span};
 
 use crate::{
+    engine::{HedgingPolicy, InflightFetchKey, InflightFetches},
     execution::{ExecutionError, ExecutionResult},
     resolver::graphql::SubgraphContext,
-    response::{ErrorCode, GraphqlError, SubgraphResponse},
-    Runtime,
+    response::{CacheControl, ErrorCode, GraphqlError, SubgraphResponse},
+    Engine, Runtime,
 };
 
 pub trait ResponseIngester: Send {
@@ -81,8 +90,13 @@ pub(crate) async fn execute_subgraph_request<R: Runtime>(
 
     ctx.record_request_size(&request);
 
-    let fetcher = ctx.engine.runtime.fetcher();
-    let fetch_result = retrying_fetch(ctx, || async {
+    let engine = ctx.engine;
+    let fetcher = engine.runtime.fetcher();
+    let deduplication_key = ctx
+        .deduplicate_requests()
+        .then(|| InflightFetches::key(endpoint.id, &request.headers, &request.body));
+
+    let send = |deduplication_key: Option<InflightFetchKey>| {
         let http_span = SubgraphHttpRequestSpan::new(endpoint.url(), &http::Method::POST);
         let mut request = request.clone();
 
@@ -94,30 +108,47 @@ pub(crate) async fn execute_subgraph_request<R: Runtime>(
             );
         });
 
-        let (fetch_result, info) = fetcher.fetch(request).instrument(http_span.span()).await;
+        async move {
+            let fetch = fetcher.fetch(request).instrument(http_span.span());
+            let (fetch_result, info) = match deduplication_key {
+                Some(key) => engine.deduplicated_fetch(key, fetch).await,
+                None => fetch.await,
+            };
 
-        let fetch_result = fetch_result.and_then(|response| {
-            tracing::debug!("Received response:\n{}", String::from_utf8_lossy(response.body()));
-            // For those status codes we want to retry the request, so marking the request as
-            // failed.
-            let status = response.status();
-            if status.is_server_error() || status == http::StatusCode::TOO_MANY_REQUESTS {
-                Err(FetchError::InvalidStatusCode(status))
-            } else {
-                Ok(response)
-            }
-        });
+            let fetch_result = fetch_result.and_then(|response| {
+                tracing::debug!("Received response:\n{}", String::from_utf8_lossy(response.body()));
+                // For those status codes we want to retry the request, so marking the request as
+                // failed.
+                let status = response.status();
+                if status.is_server_error() || status == http::StatusCode::TOO_MANY_REQUESTS {
+                    Err(FetchError::InvalidStatusCode(status))
+                } else {
+                    Ok(response)
+                }
+            });
 
-        match fetch_result {
-            Ok(ref response) => {
-                http_span.record_http_status_code(response.status());
-            }
-            Err(ref err) => {
-                http_span.set_as_http_error(err.as_invalid_status_code());
-            }
-        };
+            match fetch_result {
+                Ok(ref response) => {
+                    http_span.record_http_status_code(response.status());
+                }
+                Err(ref err) => {
+                    http_span.set_as_http_error(err.as_invalid_status_code());
+                }
+            };
+
+            (fetch_result, info)
+        }
+    };
 
-        (fetch_result, info)
+    let hedging_policy = ctx.hedging_policy;
+    let retry_budget = ctx.retry_budget;
+    let graphql_span = ctx.graphql_span();
+
+    let fetch_result = retrying_fetch(ctx, || async {
+        match hedging_policy {
+            Some(policy) => hedged_fetch(engine, policy, retry_budget, &graphql_span, &send, deduplication_key).await,
+            None => send(deduplication_key).await,
+        }
     })
     .await;
 
@@ -147,9 +178,11 @@ pub(crate) async fn execute_subgraph_request<R: Runtime>(
         .into());
     }
 
+    let cache_control = CacheControl::from_headers(response.headers());
     match ingester.ingest(response).await {
-        Ok((status, response)) => {
+        Ok((status, mut response)) => {
             ctx.set_graphql_response_status(status);
+            response.merge_cache_control(cache_control);
             Ok(response)
         }
         Err(err) => {
@@ -159,6 +192,65 @@ pub(crate) async fn execute_subgraph_request<R: Runtime>(
     }
 }
 
+/// Sends the request a second time if the first one is slower than usual and uses the first
+/// successful response, falling back to the other request if one fails. The hedged request is
+/// never deduplicated, it would just wait on the first one otherwise.
+///
+/// Hedged requests are withdrawn from the retry budget when there is one. Without a retry budget
+/// nothing bounds them besides the latency percentile.
+async fn hedged_fetch<R: Runtime, F, T>(
+    engine: &Engine<R>,
+    policy: &HedgingPolicy,
+    retry_budget: Option<&TpsBudget>,
+    span: &SubgraphGraphqlRequestSpan,
+    send: impl Fn(Option<InflightFetchKey>) -> F + Send + Sync,
+    deduplication_key: Option<InflightFetchKey>,
+) -> (FetchResult<T>, Option<ResponseInfo>)
+where
+    F: Future<Output = (FetchResult<T>, Option<ResponseInfo>)> + Send,
+    T: Send,
+{
+    let start = Instant::now();
+
+    let output = match policy.delay() {
+        None => send(deduplication_key).await,
+        Some(delay) => {
+            let mut primary = pin!(send(deduplication_key));
+
+            let early_output = match select(primary.as_mut(), pin!(engine.runtime.sleep(delay))).await {
+                Either::Left((output, _)) => Some(output),
+                Either::Right(_) => None,
+            };
+
+            if let Some(output) = early_output {
+                output
+            } else if retry_budget.is_some_and(|budget| !budget.withdraw()) {
+                // Hedged requests share the retry budget, they're just as much of an additional load.
+                primary.await
+            } else {
+                let (output, from_hedge) = match select(primary, pin!(send(None))).await {
+                    Either::Left((output, _)) if output.0.is_ok() => (output, false),
+                    Either::Left((_, hedged)) => (hedged.await, true),
+                    Either::Right((output, _)) if output.0.is_ok() => (output, true),
+                    Either::Right((_, primary)) => (primary.await, false),
+                };
+
+                // Recorded once the final result is known, the hedge did not win if both requests failed.
+                span.record_hedged_request(from_hedge && output.0.is_ok());
+
+                output
+            }
+        }
+    };
+
+    // If the hedged request won, the first one took at least this long.
+    if output.0.is_ok() {
+        policy.record(start.elapsed());
+    }
+
+    output
+}
+
 pub(crate) async fn retrying_fetch<R: Runtime, F, T>(
     ctx: &mut SubgraphContext<'_, R>,
     fetch: impl Fn() -> F + Send + Sync,
@@ -183,6 +275,8 @@ where
                 }
                 return Ok(response);
             }
+            // Retrying won't help until the circuit closes again.
+            Err(err @ ExecutionError::CircuitOpen { .. }) => return Err(err),
             Err(err) => {
                 let withdraw = ctx.retry_budget().map(|b| b.withdraw()).unwrap_or_default();
 
@@ -224,10 +318,24 @@ where
             ctx.push_request_execution(SubgraphRequestExecutionKind::RateLimited);
         })?;
 
+    if ctx.circuit_breaker().is_some_and(|breaker| !breaker.try_acquire()) {
+        ctx.record_circuit_breaker_rejection();
+        ctx.push_request_execution(SubgraphRequestExecutionKind::RequestError);
+
+        return Err(ExecutionError::CircuitOpen {
+            subgraph_name: ctx.endpoint().subgraph_name().to_string(),
+        });
+    }
+
     ctx.increment_inflight_requests();
+    let start = Instant::now();
     let (result, response_info) = fetch().await;
     ctx.decrement_inflight_requests();
 
+    if let Some(breaker) = ctx.circuit_breaker() {
+        breaker.record(result.is_ok(), start.elapsed());
+    }
+
     match response_info {
         Some(response_info) => ctx.push_request_execution(SubgraphRequestExecutionKind::Responsed(response_info)),
         None if result.is_err() => ctx.push_request_execution(SubgraphRequestExecutionKind::RequestError),
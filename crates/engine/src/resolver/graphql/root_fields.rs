@@ -20,7 +20,10 @@ use crate::{
     execution::{ExecutionContext, ExecutionError},
     prepare::{PlanError, PlanQueryPartition, PlanResult},
     resolver::{graphql::request::SubgraphGraphqlRequest, ExecutionResult, Resolver},
-    response::{ErrorPath, ErrorPathSegment, GraphqlError, InputObjectId, InputResponseObjectSet, SubgraphResponse},
+    response::{
+        CacheControl, ErrorPath, ErrorPathSegment, GraphqlError, InputObjectId, InputResponseObjectSet,
+        SubgraphResponse,
+    },
     Runtime,
 };
 
@@ -143,8 +146,9 @@ async fn fetch_response_with_cache<R: Runtime>(
     mut subgraph_response: SubgraphResponse,
 ) -> ExecutionResult<SubgraphResponse> {
     match super::cache::fetch_response(ctx, &subgraph_headers, &body).await {
-        Ok(ResponseCacheHit { data }) => {
+        Ok(ResponseCacheHit { data, ttl }) => {
             ctx.record_cache_hit();
+            subgraph_response.merge_cache_control(CacheControl::from_cache_ttl(ttl));
 
             let response = subgraph_response.as_shared_mut();
             GraphqlResponseSeed::new(
@@ -166,6 +170,7 @@ async fn fetch_response_with_cache<R: Runtime>(
                 input_object_id,
                 subgraph_default_cache_ttl: ctx.endpoint().config.cache_ttl,
                 cache_key: key,
+                cache_tags: super::cache::root_fields_tags(ctx.endpoint().subgraph_name()),
                 subgraph_response,
             };
 
@@ -180,6 +185,7 @@ struct GraphqlWithCachePutIngester<'ctx, R: Runtime> {
     subgraph_response: SubgraphResponse,
     subgraph_default_cache_ttl: Option<Duration>,
     cache_key: String,
+    cache_tags: Vec<String>,
 }
 
 impl<R> ResponseIngester for GraphqlWithCachePutIngester<'_, R>
@@ -196,6 +202,7 @@ where
             input_object_id,
             subgraph_default_cache_ttl,
             cache_key,
+            cache_tags,
         } = self;
 
         let status = {
@@ -220,7 +227,12 @@ where
                 ctx.engine
                     .runtime
                     .entity_cache()
-                    .put(&cache_key, Cow::Borrowed(http_response.body().as_ref()), cache_ttl)
+                    .put(
+                        &cache_key,
+                        Cow::Borrowed(http_response.body().as_ref()),
+                        cache_ttl,
+                        &cache_tags,
+                    )
                     .await
                     .inspect_err(|err| tracing::warn!("Failed to write the cache key {cache_key}: {err}"))
                     .ok();
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use headers::HeaderMapExt;
+
+/// Cache hints of a response, aggregated from all the subgraph responses it was built from.
+/// The most restrictive hint always wins.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CacheControl {
+    /// Smallest max-age, `None` if no subgraph response nor cache entry was involved. Subgraph
+    /// responses without any max-age count as zero.
+    pub max_age: Option<Duration>,
+    /// Whether any part of the response is specific to the user.
+    pub private: bool,
+    /// Whether any part of the response must not be cached at all.
+    pub no_store: bool,
+}
+
+impl CacheControl {
+    pub fn from_headers(headers: &http::HeaderMap) -> Self {
+        let Some(cache_control) = headers.typed_get::<headers::CacheControl>() else {
+            return Self {
+                max_age: Some(Duration::ZERO),
+                ..Default::default()
+            };
+        };
+
+        let age = headers
+            .typed_get::<headers::Age>()
+            .map(|age| age.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            max_age: Some(
+                cache_control
+                    .max_age()
+                    .map(|max_age| max_age.saturating_sub(Duration::from_secs(age)))
+                    .unwrap_or_default(),
+            ),
+            private: cache_control.private(),
+            no_store: cache_control.no_store() || cache_control.no_cache(),
+        }
+    }
+
+    /// Hints of an entity cache hit, valid for as long as the entry lives. The remaining time to
+    /// live is truncated to whole seconds, the only precision a max-age can have.
+    pub fn from_cache_ttl(ttl: Option<Duration>) -> Self {
+        Self {
+            max_age: ttl.map(|ttl| Duration::from_secs(ttl.as_secs())),
+            ..Default::default()
+        }
+    }
+
+    pub fn merge(&mut self, other: CacheControl) {
+        self.max_age = match (self.max_age, other.max_age) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.private |= other.private;
+        self.no_store |= other.no_store;
+    }
+
+    /// Whether the response can be stored at all and for how long. A max-age of zero is
+    /// equivalent to not storing it.
+    pub fn is_storable(&self) -> bool {
+        !self.no_store && self.max_age.is_none_or(|max_age| !max_age.is_zero())
+    }
+
+    /// Cache-Control header sent back to the client. Responses to authenticated requests are
+    /// always private, and without any max-age we require clients to revalidate.
+    pub fn to_header(self, authenticated: bool) -> headers::CacheControl {
+        if self.no_store {
+            return headers::CacheControl::new().with_no_store();
+        }
+
+        let header = if self.private || authenticated {
+            headers::CacheControl::new().with_private()
+        } else {
+            headers::CacheControl::new().with_public()
+        };
+
+        match self.max_age {
+            Some(max_age) if !max_age.is_zero() => header.with_max_age(max_age),
+            _ => header.with_no_cache(),
+        }
+    }
+}
@@ -30,6 +30,7 @@ pub enum ErrorCode {
     SubgraphError,
     SubgraphInvalidResponseError,
     SubgraphRequestError,
+    SubgraphUnavailable,
     // Auth
     Unauthenticated,
     Unauthorized,
@@ -63,6 +64,7 @@ impl ErrorCode {
             ErrorCode::SubgraphError | ErrorCode::SubgraphInvalidResponseError | ErrorCode::SubgraphRequestError => {
                 (http::StatusCode::BAD_GATEWAY, 300)
             }
+            ErrorCode::SubgraphUnavailable => (http::StatusCode::SERVICE_UNAVAILABLE, 300),
             ErrorCode::GatewayTimeout => (http::StatusCode::GATEWAY_TIMEOUT, 200),
             // least helpful error codes
             ErrorCode::HookError | ErrorCode::InternalServerError => (http::StatusCode::INTERNAL_SERVER_ERROR, 0),
@@ -14,7 +14,7 @@ impl std::ops::Deref for ErrorPath {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum ErrorPathSegment {
     Field(ResponseKey),
     Index(usize),
@@ -1,3 +1,4 @@
+mod cache_control;
 mod data;
 mod extensions;
 mod object_set;
@@ -8,6 +9,7 @@ mod write;
 
 use std::sync::Arc;
 
+pub(crate) use cache_control::*;
 pub(crate) use data::*;
 pub(crate) use error::*;
 use extensions::ResponseExtensions;
@@ -38,17 +40,27 @@ pub(crate) enum Response<OnOperationResponseHookOutput> {
     /// So `data` is present, even if null. That's considered to be a "partial response" and
     /// HTTP status code SHOULD be 2xx according to the GraphQL-over-HTTP spec for application/graphql-response+json
     Executed(ExecutedResponse<OnOperationResponseHookOutput>),
+    /// A query with @defer fragments is delivered in multiple payloads with a streaming response
+    /// format. The initial payload contains the `data` and subsequent ones the `incremental` results.
+    Incremental(IncrementalResponse<OnOperationResponseHookOutput>),
 }
 
 pub(crate) struct ExecutedResponse<OnOperationResponseHookOutput> {
     schema: Arc<Schema>,
     operation: Arc<CachedOperation>,
     operation_attributes: GraphqlOperationAttributes,
-    data: Option<ResponseData>,
+    data: Option<ExecutedResponseData>,
     errors: Vec<GraphqlError>,
     error_code_counter: ErrorCodeCounter,
     on_operation_response_output: Option<OnOperationResponseHookOutput>,
     extensions: Option<ResponseExtensions>,
+    cache_control: CacheControl,
+}
+
+pub(crate) enum ExecutedResponseData {
+    Built(ResponseData),
+    /// Already serialized data retrieved from the response cache.
+    Cached(Box<serde_json::value::RawValue>),
 }
 
 impl<OnOperationResponseHookOutput> ExecutedResponse<OnOperationResponseHookOutput> {
@@ -68,6 +80,54 @@ impl<OnOperationResponseHookOutput> ExecutedResponse<OnOperationResponseHookOutp
     }
 }
 
+pub(crate) struct IncrementalResponse<OnOperationResponseHookOutput> {
+    operation: Arc<CachedOperation>,
+    operation_attributes: GraphqlOperationAttributes,
+    /// Only present in the initial payload, already serialized as the response data keeps on
+    /// changing after it is sent.
+    data: Option<serde_json::Value>,
+    incremental: Vec<IncrementalResult>,
+    errors: Vec<GraphqlError>,
+    error_code_counter: ErrorCodeCounter,
+    has_next: bool,
+    on_operation_response_output: Option<OnOperationResponseHookOutput>,
+    extensions: Option<ResponseExtensions>,
+}
+
+pub(crate) struct IncrementalResult {
+    data: IncrementalData,
+    path: Vec<ErrorPathSegment>,
+    label: Option<String>,
+    errors: Vec<GraphqlError>,
+}
+
+impl IncrementalResult {
+    pub(crate) fn is_stream_batch(&self) -> bool {
+        matches!(self.data, IncrementalData::Items(_))
+    }
+}
+
+pub(crate) enum IncrementalData {
+    /// Fields of a deferred fragment.
+    Object(serde_json::Value),
+    /// Remaining items of a streamed list.
+    Items(Vec<serde_json::Value>),
+}
+
+impl<OnOperationResponseHookOutput> IncrementalResponse<OnOperationResponseHookOutput> {
+    pub(crate) fn graphql_status(&self) -> GraphqlResponseStatus {
+        let count = self.errors.len() + self.incremental.iter().map(|result| result.errors.len()).sum::<usize>();
+        if count == 0 {
+            GraphqlResponseStatus::Success
+        } else {
+            GraphqlResponseStatus::FieldError {
+                count: count as u64,
+                data_is_null: self.data.as_ref().is_some_and(|data| data.is_null()),
+            }
+        }
+    }
+}
+
 pub(crate) struct RequestErrorResponse {
     operation_attributes: Option<GraphqlOperationAttributes>,
     errors: Vec<GraphqlError>,
@@ -77,6 +137,8 @@ pub(crate) struct RequestErrorResponse {
 
 pub(crate) struct RefusedRequestResponse {
     status: http::StatusCode,
+    /// Additional HTTP headers sent with the response.
+    headers: http::HeaderMap,
     errors: Vec<GraphqlError>,
     error_code_counter: ErrorCodeCounter,
     extensions: Option<ResponseExtensions>,
@@ -86,12 +148,24 @@ impl RefusedRequestResponse {
     pub(crate) fn status(&self) -> http::StatusCode {
         self.status
     }
+
+    pub(crate) fn headers(&self) -> &http::HeaderMap {
+        &self.headers
+    }
 }
 
 impl<OnOperationResponseHookOutput> Response<OnOperationResponseHookOutput> {
     pub(crate) fn refuse_request_with(
         status: http::StatusCode,
         errors: impl IntoIterator<Item = impl Into<GraphqlError>>,
+    ) -> Self {
+        Self::refuse_request_with_headers(status, http::HeaderMap::new(), errors)
+    }
+
+    pub(crate) fn refuse_request_with_headers(
+        status: http::StatusCode,
+        headers: http::HeaderMap,
+        errors: impl IntoIterator<Item = impl Into<GraphqlError>>,
     ) -> Self {
         let mut error_code_counter = ErrorCodeCounter::default();
 
@@ -106,6 +180,7 @@ impl<OnOperationResponseHookOutput> Response<OnOperationResponseHookOutput> {
 
         Self::RefusedRequest(RefusedRequestResponse {
             status,
+            headers,
             errors,
             error_code_counter,
             extensions: None,
@@ -148,6 +223,52 @@ impl<OnOperationResponseHookOutput> Response<OnOperationResponseHookOutput> {
             errors,
             error_code_counter,
             extensions: None,
+            cache_control: CacheControl::default(),
+        })
+    }
+
+    pub(crate) fn cached(
+        schema: &Arc<Schema>,
+        operation: &PreparedOperation,
+        data: Box<serde_json::value::RawValue>,
+        cache_control: CacheControl,
+        on_operation_response_output: Option<OnOperationResponseHookOutput>,
+    ) -> Self {
+        Self::Executed(ExecutedResponse {
+            schema: schema.clone(),
+            operation: operation.cached.clone(),
+            operation_attributes: operation.attributes(),
+            data: Some(ExecutedResponseData::Cached(data)),
+            on_operation_response_output,
+            errors: Vec::new(),
+            error_code_counter: ErrorCodeCounter::default(),
+            extensions: None,
+            cache_control,
+        })
+    }
+
+    pub(crate) fn subsequent_incremental_payload(
+        operation: &PreparedOperation,
+        incremental: Vec<IncrementalResult>,
+        errors: Vec<GraphqlError>,
+        has_next: bool,
+        on_operation_response_output: Option<OnOperationResponseHookOutput>,
+    ) -> Self {
+        let mut error_code_counter = ErrorCodeCounter::from_errors(&errors);
+        for result in &incremental {
+            error_code_counter.add(&ErrorCodeCounter::from_errors(&result.errors));
+        }
+
+        Self::Incremental(IncrementalResponse {
+            operation: operation.cached.clone(),
+            operation_attributes: operation.attributes(),
+            data: None,
+            incremental,
+            errors,
+            error_code_counter,
+            has_next,
+            on_operation_response_output,
+            extensions: None,
         })
     }
 
@@ -161,6 +282,7 @@ impl<OnOperationResponseHookOutput> Response<OnOperationResponseHookOutput> {
             Self::RefusedRequest(resp) => &mut resp.extensions,
             Self::RequestError(resp) => &mut resp.extensions,
             Self::Executed(resp) => &mut resp.extensions,
+            Self::Incremental(resp) => &mut resp.extensions,
         }
         .get_or_insert_with(Default::default)
     }
@@ -168,6 +290,7 @@ impl<OnOperationResponseHookOutput> Response<OnOperationResponseHookOutput> {
     pub(crate) fn take_on_operation_response_output(&mut self) -> Option<OnOperationResponseHookOutput> {
         match self {
             Self::Executed(resp) => std::mem::take(&mut resp.on_operation_response_output),
+            Self::Incremental(resp) => std::mem::take(&mut resp.on_operation_response_output),
             _ => None,
         }
     }
@@ -188,12 +311,14 @@ impl<OnOperationResponseHookOutput> Response<OnOperationResponseHookOutput> {
             Self::RefusedRequest(_) => None,
             Self::RequestError(resp) => resp.operation_attributes.as_ref(),
             Self::Executed(resp) => Some(&resp.operation_attributes),
+            Self::Incremental(resp) => Some(&resp.operation_attributes),
         }
     }
 
     pub(crate) fn graphql_status(&self) -> GraphqlResponseStatus {
         match self {
             Self::Executed(resp) => resp.graphql_status(),
+            Self::Incremental(resp) => resp.graphql_status(),
             Self::RequestError(resp) => GraphqlResponseStatus::RequestError {
                 count: resp.errors.len() as u64,
             },
@@ -206,6 +331,26 @@ impl<OnOperationResponseHookOutput> Response<OnOperationResponseHookOutput> {
             Response::RefusedRequest(resp) => &resp.errors,
             Response::RequestError(resp) => &resp.errors,
             Response::Executed(resp) => &resp.errors,
+            Response::Incremental(resp) => &resp.errors,
+        }
+    }
+
+    /// Cache hints of the response data. Only executed responses built from subgraph responses
+    /// have any.
+    pub(crate) fn cache_control(&self) -> Option<&CacheControl> {
+        match self {
+            Response::Executed(resp) => Some(&resp.cache_control),
+            _ => None,
+        }
+    }
+
+    /// Cache-Control header for the client. Only successful queries may be cached.
+    pub(crate) fn http_cache_control(&self, authenticated: bool) -> headers::CacheControl {
+        match self {
+            Response::Executed(resp) if resp.errors.is_empty() && !resp.operation_attributes.ty.is_mutation() => {
+                resp.cache_control.to_header(authenticated)
+            }
+            _ => headers::CacheControl::new().with_no_store(),
         }
     }
 
@@ -214,6 +359,7 @@ impl<OnOperationResponseHookOutput> Response<OnOperationResponseHookOutput> {
             Response::RefusedRequest(resp) => &resp.error_code_counter,
             Response::RequestError(resp) => &resp.error_code_counter,
             Response::Executed(resp) => &resp.error_code_counter,
+            Response::Incremental(resp) => &resp.error_code_counter,
         }
     }
 }
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use operation::ResponseKey;
+use runtime::entity_cache::EntityCacheTag;
+use schema::{FieldDefinitionId, FieldSet, ObjectDefinitionId, ResolverDefinitionVariant, Schema};
+use walker::Walk;
+
+use crate::{
+    prepare::CachedOperation,
+    response::{
+        DataParts, ExecutedResponse, ExecutedResponseData, Response, ResponseBuilder, ResponseObject, ResponseObjectId,
+        ResponseValue,
+    },
+};
+
+impl ResponseBuilder {
+    /// Entity cache tags of all the entities in the response whose key fields are present,
+    /// for every key of the subgraphs with entity caching enabled.
+    pub fn entity_cache_tags(&self) -> Vec<String> {
+        let Some((root_id, _)) = self.root else {
+            return Vec::new();
+        };
+
+        EntityTagsCollector::new(
+            &self.schema,
+            &self.operation,
+            &self.data_parts,
+            TagsScope::CachedEntities,
+        )
+        .collect(root_id)
+    }
+
+    /// Entity tags of all the entities in the response whose key fields are present, for every
+    /// key of every subgraph. Used to purge the cached responses containing them.
+    pub fn response_cache_entity_tags(&self) -> Vec<String> {
+        let Some((root_id, _)) = self.root else {
+            return Vec::new();
+        };
+
+        EntityTagsCollector::new(&self.schema, &self.operation, &self.data_parts, TagsScope::Entities).collect(root_id)
+    }
+}
+
+impl<OnOperationResponseHookOutput> Response<OnOperationResponseHookOutput> {
+    /// Tags of the response cache entry, so that purging a subgraph, an entity type or a single
+    /// entity also purges the cached responses containing them. Every subgraph of the query plan
+    /// is included and the entities of every subgraph, whether they're entity cached or not.
+    pub(crate) fn response_cache_tags(&self) -> Vec<String> {
+        let Response::Executed(ExecutedResponse {
+            schema,
+            operation,
+            data: Some(ExecutedResponseData::Built(data)),
+            ..
+        }) = self
+        else {
+            return Vec::new();
+        };
+
+        let mut tags =
+            EntityTagsCollector::new(schema, operation, &data.parts, TagsScope::EntitiesAndTypes).collect(data.root);
+        tags.extend(subgraph_tags(schema, operation));
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+}
+
+fn subgraph_tags<'a>(schema: &'a Schema, operation: &'a CachedOperation) -> impl Iterator<Item = String> + 'a {
+    operation.query_plan.partitions.iter().map(move |partition| {
+        let subgraph = partition.resolver_definition_id.walk(schema).subgraph();
+        EntityCacheTag::Subgraph(subgraph.name()).to_string()
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TagsScope {
+    /// Entities with a key in a subgraph with entity caching.
+    CachedEntities,
+    /// Entities with a key in any subgraph.
+    Entities,
+    /// Entities with a key in any subgraph, along with their types.
+    EntitiesAndTypes,
+}
+
+struct EntityTagsCollector<'a> {
+    schema: &'a Schema,
+    data_parts: &'a DataParts,
+    scope: TagsScope,
+    keys: HashMap<ObjectDefinitionId, Vec<FieldSet<'a>>>,
+    /// Response keys of the fields of each definition, as fields may be aliased.
+    response_keys: HashMap<FieldDefinitionId, Vec<ResponseKey>>,
+    tags: Vec<String>,
+}
+
+impl<'a> EntityTagsCollector<'a> {
+    fn new(schema: &'a Schema, operation: &'a CachedOperation, data_parts: &'a DataParts, scope: TagsScope) -> Self {
+        let mut response_keys = HashMap::<_, Vec<_>>::new();
+
+        for field in &operation.query_plan.data_fields {
+            response_keys
+                .entry(field.definition_id)
+                .or_default()
+                .push(field.response_key);
+        }
+
+        EntityTagsCollector {
+            schema,
+            data_parts,
+            scope,
+            keys: HashMap::new(),
+            response_keys,
+            tags: Vec::new(),
+        }
+    }
+
+    fn collect(mut self, root_id: ResponseObjectId) -> Vec<String> {
+        self.visit_object(root_id);
+
+        let mut tags = self.tags;
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    fn visit_value(&mut self, value: &ResponseValue) {
+        let data_parts = self.data_parts;
+
+        match value {
+            ResponseValue::Object { id } => self.visit_object(*id),
+            ResponseValue::List { id } => {
+                for value in &data_parts[*id] {
+                    self.visit_value(value);
+                }
+            }
+            ResponseValue::Inaccessible { id } => self.visit_value(&data_parts[*id]),
+            _ => {}
+        }
+    }
+
+    fn visit_object(&mut self, id: ResponseObjectId) {
+        let object = &self.data_parts[id];
+
+        if let Some(definition_id) = object.definition_id {
+            let definition = definition_id.walk(self.schema);
+            let keys = self.keys(definition_id);
+
+            // Cached responses are tagged with the types of all the entities they contain.
+            if self.scope == TagsScope::EntitiesAndTypes && !keys.is_empty() {
+                self.tags.push(EntityCacheTag::Type(definition.name()).to_string());
+            }
+
+            for key_fields in keys {
+                if let Some(key) = self.key_value(object, key_fields) {
+                    self.tags.push(
+                        EntityCacheTag::Entity {
+                            typename: definition.name(),
+                            key: &key,
+                        }
+                        .to_string(),
+                    );
+                }
+            }
+        }
+
+        for field in object.fields() {
+            self.visit_value(&field.value);
+        }
+    }
+
+    /// Key fields of the entity resolvers of this object, only in subgraphs with entity caching
+    /// unless the keys of all subgraphs are used.
+    fn keys(&mut self, definition_id: ObjectDefinitionId) -> Vec<FieldSet<'a>> {
+        let schema = self.schema;
+        let all_subgraphs = self.scope != TagsScope::CachedEntities;
+
+        self.keys
+            .entry(definition_id)
+            .or_insert_with(|| {
+                let mut resolver_ids = definition_id
+                    .walk(schema)
+                    .fields()
+                    .flat_map(|field| field.resolvers())
+                    .map(|resolver| resolver.id)
+                    .collect::<Vec<_>>();
+                resolver_ids.sort_unstable();
+                resolver_ids.dedup();
+
+                resolver_ids
+                    .into_iter()
+                    .filter_map(|id| match id.walk(schema).variant() {
+                        ResolverDefinitionVariant::GraphqlFederationEntity(resolver)
+                            if all_subgraphs || resolver.endpoint().config.cache_ttl.is_some() =>
+                        {
+                            Some(resolver.key_fields())
+                        }
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .clone()
+    }
+
+    fn key_value(&self, object: &ResponseObject, key_fields: FieldSet<'_>) -> Option<serde_json::Value> {
+        let mut map = serde_json::Map::new();
+
+        for item in key_fields.items() {
+            let definition = item.field().definition();
+            let response_keys = self.response_keys.get(&definition.id)?;
+            let field = object
+                .fields()
+                .find(|field| response_keys.contains(&field.key.response_key))?;
+            let value = self.json_value(&field.value, item.subselection())?;
+            map.insert(definition.name().to_string(), value);
+        }
+
+        Some(serde_json::Value::Object(map))
+    }
+
+    fn json_value(&self, value: &ResponseValue, subselection: FieldSet<'_>) -> Option<serde_json::Value> {
+        let data = self.data_parts;
+
+        Some(match value {
+            ResponseValue::Null => serde_json::Value::Null,
+            ResponseValue::Boolean { value } => (*value).into(),
+            ResponseValue::Int { value } => (*value).into(),
+            ResponseValue::BigInt { value } => (*value).into(),
+            ResponseValue::Float { value } => (*value).into(),
+            ResponseValue::U64 { value } => (*value).into(),
+            ResponseValue::String { value } => value.as_ref().into(),
+            ResponseValue::StringId { id } => self.schema[*id].as_str().into(),
+            ResponseValue::Inaccessible { id } => self.json_value(&data[*id], subselection)?,
+            ResponseValue::List { id } => data[*id]
+                .iter()
+                .map(|value| self.json_value(value, subselection))
+                .collect::<Option<Vec<_>>>()?
+                .into(),
+            ResponseValue::Object { id } => self.key_value(&data[*id], subselection)?,
+            ResponseValue::Map { .. } | ResponseValue::Unexpected => return None,
+        })
+    }
+}
@@ -1,8 +1,11 @@
 use std::sync::Arc;
 
+use operation::ResponseKey;
+
 use crate::prepare::RequiredFieldSet;
 
 use super::{InputResponseObjectSet, ResponseBuilder};
+mod entities;
 mod ser;
 mod view;
 
@@ -20,4 +23,18 @@ impl ResponseBuilder {
             selection_set,
         }
     }
+
+    /// Reads the value of a single field of each response object, as it would appear in the
+    /// response.
+    pub fn read_field_values(
+        &self,
+        response_object_set: Arc<InputResponseObjectSet>,
+        response_key: ResponseKey,
+    ) -> ResponseFieldValuesView<'_> {
+        ResponseFieldValuesView {
+            ctx: ViewContext { response: self },
+            response_object_set,
+            response_key,
+        }
+    }
 }
@@ -1,18 +1,34 @@
-use operation::ResponseKeys;
+use operation::{PositionedResponseKey, ResponseKeys};
 use schema::Schema;
 use serde::ser::{SerializeMap, SerializeSeq};
 
-use crate::response::{value::ResponseObjectField, ResponseData, ResponseObject, ResponseValue};
+use crate::{
+    prepare::StreamedField,
+    response::{value::ResponseObjectField, DataParts, ResponseData, ResponseObject, ResponseValue},
+};
 
 #[derive(Clone, Copy)]
 pub(super) struct Context<'a> {
     pub keys: &'a ResponseKeys,
-    pub data: &'a ResponseData,
+    pub data: &'a DataParts,
     pub schema: &'a Schema,
+    /// Streamed list fields, only their first items are serialized. Sorted by query position.
+    pub streams: &'a [StreamedField],
+}
+
+impl Context<'_> {
+    pub fn stream_initial_count(&self, key: PositionedResponseKey) -> Option<usize> {
+        let query_position = key.query_position?;
+        self.streams
+            .binary_search_by_key(&query_position, |stream| stream.query_position)
+            .ok()
+            .map(|ix| self.streams[ix].initial_count)
+    }
 }
 
 pub(super) struct SerializableResponseData<'a> {
     pub ctx: Context<'a>,
+    pub data: &'a ResponseData,
 }
 
 impl serde::Serialize for SerializableResponseData<'_> {
@@ -22,15 +38,15 @@ impl serde::Serialize for SerializableResponseData<'_> {
     {
         SerializableResponseObject {
             ctx: self.ctx,
-            object: self.ctx.data.root_object(),
+            object: self.data.root_object(),
         }
         .serialize(serializer)
     }
 }
 
-struct SerializableResponseObject<'a> {
-    ctx: Context<'a>,
-    object: &'a ResponseObject,
+pub(super) struct SerializableResponseObject<'a> {
+    pub ctx: Context<'a>,
+    pub object: &'a ResponseObject,
 }
 
 impl serde::Serialize for SerializableResponseObject<'_> {
@@ -44,7 +60,11 @@ impl serde::Serialize for SerializableResponseObject<'_> {
         for ResponseObjectField { key, value, .. } in fields.by_ref() {
             if key.query_position.is_some() {
                 map.serialize_key(&self.ctx.keys[key.response_key])?;
-                map.serialize_value(&SerializableResponseValue { ctx: self.ctx, value })?;
+                map.serialize_value(&SerializableResponseFieldValue {
+                    ctx: self.ctx,
+                    key: *key,
+                    value,
+                })?;
             };
         }
         for ResponseObjectField { key, value, .. } in fields.by_ref() {
@@ -55,6 +75,64 @@ impl serde::Serialize for SerializableResponseObject<'_> {
     }
 }
 
+/// Only serializes the fields with the given keys, used for incremental delivery where subsequent
+/// payloads only contain the fields that were deferred.
+pub(super) struct SerializableResponseObjectSubset<'a> {
+    pub ctx: Context<'a>,
+    pub object: &'a ResponseObject,
+    pub keys: &'a [PositionedResponseKey],
+}
+
+impl serde::Serialize for SerializableResponseObjectSubset<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        for ResponseObjectField { key, value, .. } in self.object.fields() {
+            if key.query_position.is_some() && self.keys.contains(key) {
+                map.serialize_key(&self.ctx.keys[key.response_key])?;
+                map.serialize_value(&SerializableResponseFieldValue {
+                    ctx: self.ctx,
+                    key: *key,
+                    value,
+                })?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// Field value, truncated to the initial count if it's a streamed list.
+struct SerializableResponseFieldValue<'a> {
+    ctx: Context<'a>,
+    key: PositionedResponseKey,
+    value: &'a ResponseValue,
+}
+
+impl serde::Serialize for SerializableResponseFieldValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match (self.value, self.ctx.stream_initial_count(self.key)) {
+            (ResponseValue::List { id, .. }, Some(initial_count)) => {
+                let list = &self.ctx.data[*id];
+                SerializableResponseList {
+                    ctx: self.ctx,
+                    value: &list[..initial_count.min(list.len())],
+                }
+                .serialize(serializer)
+            }
+            _ => SerializableResponseValue {
+                ctx: self.ctx,
+                value: self.value,
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
 struct SerializableResponseList<'a> {
     ctx: Context<'a>,
     value: &'a [ResponseValue],
@@ -73,9 +151,9 @@ impl serde::Serialize for SerializableResponseList<'_> {
     }
 }
 
-struct SerializableResponseValue<'a> {
-    ctx: Context<'a>,
-    value: &'a ResponseValue,
+pub(super) struct SerializableResponseValue<'a> {
+    pub ctx: Context<'a>,
+    pub value: &'a ResponseValue,
 }
 
 impl serde::Serialize for SerializableResponseValue<'_> {
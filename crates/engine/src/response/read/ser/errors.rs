@@ -64,9 +64,9 @@ impl serde::Serialize for SerializableError<'_> {
     }
 }
 
-struct SerializableResponsePath<'a> {
-    keys: &'a ResponseKeys,
-    path: &'a [ErrorPathSegment],
+pub(super) struct SerializableResponsePath<'a> {
+    pub keys: &'a ResponseKeys,
+    pub path: &'a [ErrorPathSegment],
 }
 
 impl serde::Serialize for SerializableResponsePath<'_> {
@@ -0,0 +1,241 @@
+use std::collections::VecDeque;
+
+use grafbase_telemetry::graphql::GraphqlOperationAttributes;
+use operation::PositionedResponseKey;
+
+use crate::{
+    prepare::{Plan, StreamedField},
+    response::{
+        value::ResponseObjectField, ErrorCodeCounter, ErrorPathSegment, GraphqlError, IncrementalData,
+        IncrementalResponse, IncrementalResult, Response, ResponseBuilder, ResponseObject, ResponseObjectRef,
+        ResponseValue,
+    },
+};
+
+use super::data::{Context, SerializableResponseObject, SerializableResponseObjectSubset, SerializableResponseValue};
+
+/// Maximum number of streamed list items sent within a single incremental result.
+const STREAM_BATCH_SIZE: usize = 100;
+
+impl ResponseBuilder {
+    /// Initial payload of an incremental delivery with all the data and errors available so far.
+    /// Deferred plans that finished early are simply part of it.
+    /// Streamed lists only contain their initial items.
+    pub fn build_initial_incremental_payload<O>(
+        &self,
+        operation_attributes: GraphqlOperationAttributes,
+        streams: &[StreamedField],
+    ) -> Response<O> {
+        let data = match self.root {
+            Some((root, _)) => to_json_value(&SerializableResponseObject {
+                ctx: self.serialization_context(streams),
+                object: &self.data_parts[root],
+            }),
+            None => serde_json::Value::Null,
+        };
+        let errors = self.errors.clone();
+        let error_code_counter = ErrorCodeCounter::from_errors(&errors);
+
+        Response::Incremental(IncrementalResponse {
+            operation: self.operation.clone(),
+            operation_attributes,
+            data: Some(data),
+            incremental: Vec::new(),
+            errors,
+            error_code_counter,
+            has_next: true,
+            on_operation_response_output: None,
+            extensions: None,
+        })
+    }
+
+    /// Incremental results for a deferred plan, one per response object it resolved fields for,
+    /// each followed by the batches of the lists it streamed. Each error is delivered with the
+    /// result whose path is the closest ancestor of its own, errors without any matching path go
+    /// with the first one.
+    pub fn build_incremental_results<'a>(
+        &self,
+        plan: Plan<'_>,
+        obj_refs: impl IntoIterator<Item = &'a ResponseObjectRef>,
+        errors: Vec<GraphqlError>,
+        streams: &[StreamedField],
+    ) -> Vec<IncrementalResult> {
+        let shape = plan.shape();
+        let keys = shape
+            .fields()
+            .map(|field| field.key)
+            .chain(shape.typename_response_keys.iter().copied())
+            .collect::<Vec<PositionedResponseKey>>();
+        let ctx = self.serialization_context(streams);
+
+        let mut results = obj_refs
+            .into_iter()
+            .map(|obj_ref| {
+                let result = IncrementalResult {
+                    data: IncrementalData::Object(to_json_value(&SerializableResponseObjectSubset {
+                        ctx,
+                        object: &self.data_parts[obj_ref.id],
+                        keys: &keys,
+                    })),
+                    path: obj_ref.path.iter().map(Into::into).collect(),
+                    label: plan.defer_label.clone(),
+                    errors: Vec::new(),
+                };
+                (result, obj_ref)
+            })
+            .collect::<Vec<_>>();
+
+        if results.is_empty() {
+            return Vec::new();
+        }
+
+        for error in errors {
+            let ix = error
+                .path
+                .as_ref()
+                .and_then(|error_path| {
+                    results
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, (result, _))| error_path.starts_with(&result.path))
+                        .max_by_key(|(_, (result, _))| result.path.len())
+                        .map(|(ix, _)| ix)
+                })
+                .unwrap_or_default();
+            results[ix].0.errors.push(error);
+        }
+
+        let mut stream_results = VecDeque::new();
+        let mut incremental_results = Vec::with_capacity(results.len());
+        for (result, obj_ref) in results {
+            if !streams.is_empty() {
+                let mut collector = StreamResultsCollector {
+                    ctx,
+                    path: result.path.clone(),
+                    results: &mut stream_results,
+                };
+                collector.collect_fields(
+                    self.data_parts[obj_ref.id]
+                        .fields()
+                        .filter(|field| keys.contains(&field.key)),
+                );
+            }
+            incremental_results.push(result);
+            incremental_results.extend(stream_results.drain(..));
+        }
+
+        incremental_results
+    }
+
+    /// Incremental results for all the streamed list items that were not part of the initial payload,
+    /// in batches of at most `STREAM_BATCH_SIZE` items.
+    ///
+    /// Subgraph responses are ingested as a whole, so streamed lists are only split once they are
+    /// complete. Plans resolving a streamed list don't hold back the initial payload though, if
+    /// they finish later their first items are delivered with `build_incremental_results`.
+    pub fn build_stream_results(&self, streams: &[StreamedField]) -> VecDeque<IncrementalResult> {
+        let mut results = VecDeque::new();
+        if let Some((root, _)) = self.root {
+            let mut collector = StreamResultsCollector {
+                ctx: self.serialization_context(streams),
+                path: Vec::new(),
+                results: &mut results,
+            };
+            collector.collect_object(&self.data_parts[root]);
+        }
+        results
+    }
+
+    fn serialization_context<'a>(&'a self, streams: &'a [StreamedField]) -> Context<'a> {
+        Context {
+            keys: &self.operation.operation.response_keys,
+            data: &self.data_parts,
+            schema: &self.schema,
+            streams,
+        }
+    }
+}
+
+struct StreamResultsCollector<'a, 'r> {
+    ctx: Context<'a>,
+    path: Vec<ErrorPathSegment>,
+    results: &'r mut VecDeque<IncrementalResult>,
+}
+
+impl StreamResultsCollector<'_, '_> {
+    fn collect_object(&mut self, object: &ResponseObject) {
+        self.collect_fields(object.fields())
+    }
+
+    fn collect_fields<'o>(&mut self, fields: impl Iterator<Item = &'o ResponseObjectField>) {
+        let data = self.ctx.data;
+        for ResponseObjectField { key, value, .. } in fields {
+            if key.query_position.is_none() {
+                continue;
+            }
+            self.path.push(ErrorPathSegment::Field(key.response_key));
+            match (value, self.ctx.stream_initial_count(*key)) {
+                (ResponseValue::List { id, .. }, Some(initial_count)) => {
+                    let label = self
+                        .ctx
+                        .streams
+                        .iter()
+                        .find(|stream| Some(stream.query_position) == key.query_position)
+                        .and_then(|stream| stream.label.clone());
+                    let items = &data[*id];
+                    let initial_count = initial_count.min(items.len());
+                    for (index, item) in items[..initial_count].iter().enumerate() {
+                        self.path.push(ErrorPathSegment::Index(index));
+                        self.collect_value(item);
+                        self.path.pop();
+                    }
+                    // Nested streams within a streamed item are delivered as a whole.
+                    let ctx = Context {
+                        streams: &[],
+                        ..self.ctx
+                    };
+                    for (batch_index, batch) in items[initial_count..].chunks(STREAM_BATCH_SIZE).enumerate() {
+                        // The path of a batch points to its first item.
+                        let mut path = self.path.clone();
+                        path.push(ErrorPathSegment::Index(initial_count + batch_index * STREAM_BATCH_SIZE));
+                        self.results.push_back(IncrementalResult {
+                            data: IncrementalData::Items(
+                                batch
+                                    .iter()
+                                    .map(|item| to_json_value(&SerializableResponseValue { ctx, value: item }))
+                                    .collect(),
+                            ),
+                            path,
+                            label: label.clone(),
+                            errors: Vec::new(),
+                        });
+                    }
+                }
+                _ => self.collect_value(value),
+            }
+            self.path.pop();
+        }
+    }
+
+    fn collect_value(&mut self, value: &ResponseValue) {
+        let data = self.ctx.data;
+        match value {
+            ResponseValue::Object { id, .. } => self.collect_object(&data[*id]),
+            ResponseValue::List { id, .. } => {
+                for (index, item) in data[*id].iter().enumerate() {
+                    self.path.push(ErrorPathSegment::Index(index));
+                    self.collect_value(item);
+                    self.path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn to_json_value(value: &impl serde::Serialize) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or_else(|err| {
+        tracing::error!("Failed to serialize incremental payload: {err}");
+        serde_json::Value::Null
+    })
+}
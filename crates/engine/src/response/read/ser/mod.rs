@@ -1,10 +1,14 @@
 mod data;
 mod errors;
+mod incremental;
 
 use operation::ResponseKeys;
 use serde::ser::SerializeMap;
 
-use crate::response::{ExecutedResponse, RefusedRequestResponse, RequestErrorResponse, Response};
+use crate::response::{
+    ExecutedResponse, ExecutedResponseData, IncrementalData, IncrementalResponse, IncrementalResult,
+    RefusedRequestResponse, RequestErrorResponse, Response,
+};
 
 impl<OnOperationResponseHookOutput> serde::Serialize for Response<OnOperationResponseHookOutput> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -22,22 +26,72 @@ impl<OnOperationResponseHookOutput> serde::Serialize for Response<OnOperationRes
             }) => {
                 let mut map = serializer.serialize_map(None)?;
 
+                let keys = &operation.operation.response_keys;
+                match data {
+                    Some(ExecutedResponseData::Built(data)) => {
+                        map.serialize_entry(
+                            "data",
+                            &data::SerializableResponseData {
+                                ctx: data::Context {
+                                    keys,
+                                    data,
+                                    schema,
+                                    streams: &[],
+                                },
+                                data,
+                            },
+                        )?;
+                    }
+                    Some(ExecutedResponseData::Cached(data)) => {
+                        map.serialize_entry("data", data)?;
+                    }
+                    None => {
+                        map.serialize_entry("data", &())?;
+                    }
+                }
+
+                if !errors.is_empty() {
+                    map.serialize_entry("errors", &errors::SerializableErrors { keys, errors })?;
+                }
+
+                if let Some(ext) = extensions.as_ref().filter(|ext| !ext.is_emtpy()) {
+                    map.serialize_entry("extensions", ext)?;
+                }
+
+                map.end()
+            }
+            Response::Incremental(IncrementalResponse {
+                operation,
+                data,
+                incremental,
+                errors,
+                has_next,
+                extensions,
+                ..
+            }) => {
+                let mut map = serializer.serialize_map(None)?;
+
                 let keys = &operation.operation.response_keys;
                 if let Some(data) = data {
+                    map.serialize_entry("data", data)?;
+                }
+
+                if !incremental.is_empty() {
                     map.serialize_entry(
-                        "data",
-                        &data::SerializableResponseData {
-                            ctx: data::Context { keys, data, schema },
+                        "incremental",
+                        &SerializableIncrementalResults {
+                            keys,
+                            results: incremental,
                         },
                     )?;
-                } else {
-                    map.serialize_entry("data", &())?;
                 }
 
                 if !errors.is_empty() {
                     map.serialize_entry("errors", &errors::SerializableErrors { keys, errors })?;
                 }
 
+                map.serialize_entry("hasNext", has_next)?;
+
                 if let Some(ext) = extensions.as_ref().filter(|ext| !ext.is_emtpy()) {
                     map.serialize_entry("extensions", ext)?;
                 }
@@ -85,3 +139,79 @@ impl<OnOperationResponseHookOutput> serde::Serialize for Response<OnOperationRes
         }
     }
 }
+
+impl<OnOperationResponseHookOutput> Response<OnOperationResponseHookOutput> {
+    /// Serializes the `data` of an executed response on its own, as stored in the response cache.
+    pub(crate) fn serialize_data(&self) -> Option<serde_json::Result<Vec<u8>>> {
+        let Response::Executed(resp) = self else {
+            return None;
+        };
+        match resp.data.as_ref()? {
+            ExecutedResponseData::Built(data) => Some(serde_json::to_vec(&data::SerializableResponseData {
+                ctx: data::Context {
+                    keys: &resp.operation.operation.response_keys,
+                    data,
+                    schema: &resp.schema,
+                    streams: &[],
+                },
+                data,
+            })),
+            ExecutedResponseData::Cached(data) => Some(Ok(data.get().as_bytes().to_vec())),
+        }
+    }
+}
+
+struct SerializableIncrementalResults<'a> {
+    keys: &'a ResponseKeys,
+    results: &'a [IncrementalResult],
+}
+
+impl serde::Serialize for SerializableIncrementalResults<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.results.iter().map(|result| SerializableIncrementalResult {
+            keys: self.keys,
+            result,
+        }))
+    }
+}
+
+struct SerializableIncrementalResult<'a> {
+    keys: &'a ResponseKeys,
+    result: &'a IncrementalResult,
+}
+
+impl serde::Serialize for SerializableIncrementalResult<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        match &self.result.data {
+            IncrementalData::Object(data) => map.serialize_entry("data", data)?,
+            IncrementalData::Items(items) => map.serialize_entry("items", items)?,
+        }
+        map.serialize_entry(
+            "path",
+            &errors::SerializableResponsePath {
+                keys: self.keys,
+                path: &self.result.path,
+            },
+        )?;
+        if let Some(label) = &self.result.label {
+            map.serialize_entry("label", label)?;
+        }
+        if !self.result.errors.is_empty() {
+            map.serialize_entry(
+                "errors",
+                &errors::SerializableErrors {
+                    keys: self.keys,
+                    errors: &self.result.errors,
+                },
+            )?;
+        }
+        map.end()
+    }
+}
@@ -3,6 +3,8 @@ mod ser;
 
 use std::{borrow::Cow, sync::Arc};
 
+use operation::ResponseKey;
+
 use crate::{
     prepare::RequiredFieldSet,
     response::{InputObjectId, InputResponseObjectSet, ResponseBuilder, ResponseObject, ResponseValue},
@@ -85,6 +87,28 @@ impl ResponseObjectsViewWithExtraFields<'_> {
     }
 }
 
+#[derive(Clone)]
+pub(crate) struct ResponseFieldValuesView<'a> {
+    pub(super) ctx: ViewContext<'a>,
+    pub(super) response_object_set: Arc<InputResponseObjectSet>,
+    pub(super) response_key: ResponseKey,
+}
+
+impl<'a> ResponseFieldValuesView<'a> {
+    pub fn iter(&self) -> impl Iterator<Item = ResponseFieldValueView<'a>> + '_ {
+        self.response_object_set.iter().map(|obj_ref| ResponseFieldValueView {
+            ctx: self.ctx,
+            value: self.ctx.response.data_parts[obj_ref.id].find_by_response_key(self.response_key),
+        })
+    }
+}
+
+/// A field value with all of its client visible sub-fields.
+pub(crate) struct ResponseFieldValueView<'a> {
+    ctx: ViewContext<'a>,
+    value: Option<&'a ResponseValue>,
+}
+
 pub(crate) struct ResponseObjectView<'a> {
     ctx: ViewContext<'a>,
     response_object: &'a ResponseObject,
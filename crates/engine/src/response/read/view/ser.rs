@@ -1,8 +1,10 @@
 use schema::EntityDefinition;
 use serde::ser::{Error, SerializeMap};
 
-use super::{ResponseObjectView, ResponseObjectViewWithExtraFields, ResponseValueView};
-use crate::response::ResponseValue;
+use super::{
+    ResponseFieldValueView, ResponseObjectView, ResponseObjectViewWithExtraFields, ResponseValueView, ViewContext,
+};
+use crate::response::{ResponseObject, ResponseValue};
 
 impl serde::Serialize for ResponseObjectViewWithExtraFields<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -130,3 +132,89 @@ impl serde::Serialize for ResponseValueView<'_> {
         }
     }
 }
+
+impl serde::Serialize for ResponseFieldValueView<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.value {
+            Some(value) => FullResponseValueView { ctx: self.ctx, value }.serialize(serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+struct FullResponseValueView<'a> {
+    ctx: ViewContext<'a>,
+    value: &'a ResponseValue,
+}
+
+impl serde::Serialize for FullResponseValueView<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let ctx = self.ctx;
+        match self.value {
+            ResponseValue::Null => serializer.serialize_none(),
+            ResponseValue::Inaccessible { id } => FullResponseValueView {
+                ctx,
+                value: &ctx.response.data_parts[*id],
+            }
+            .serialize(serializer),
+            &ResponseValue::List { id, .. } => serializer.collect_seq(
+                ctx.response.data_parts[id]
+                    .iter()
+                    .map(|value| FullResponseValueView { ctx, value }),
+            ),
+            &ResponseValue::Object { id, .. } => FullResponseObjectView {
+                ctx,
+                response_object: &ctx.response.data_parts[id],
+            }
+            .serialize(serializer),
+            ResponseValue::Map { id } => serializer.collect_map(
+                ctx.response.data_parts[*id]
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), FullResponseValueView { ctx, value })),
+            ),
+            ResponseValue::Boolean { value, .. } => value.serialize(serializer),
+            ResponseValue::Int { value, .. } => value.serialize(serializer),
+            ResponseValue::Float { value, .. } => value.serialize(serializer),
+            ResponseValue::String { value, .. } => value.serialize(serializer),
+            ResponseValue::StringId { id, .. } => ctx.response.schema[*id].serialize(serializer),
+            ResponseValue::BigInt { value, .. } => value.serialize(serializer),
+            ResponseValue::U64 { value } => value.serialize(serializer),
+            ResponseValue::Unexpected => Err(S::Error::custom("Unexpected value")),
+        }
+    }
+}
+
+struct FullResponseObjectView<'a> {
+    ctx: ViewContext<'a>,
+    response_object: &'a ResponseObject,
+}
+
+impl serde::Serialize for FullResponseObjectView<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let ctx = self.ctx;
+        let keys = &ctx.response.operation.operation.response_keys;
+        serializer.collect_map(
+            self.response_object
+                .fields()
+                .filter(|field| field.key.query_position.is_some())
+                .map(|field| {
+                    (
+                        &keys[field.key.response_key],
+                        FullResponseValueView {
+                            ctx,
+                            value: &field.value,
+                        },
+                    )
+                }),
+        )
+    }
+}
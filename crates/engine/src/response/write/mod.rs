@@ -10,9 +10,9 @@ use schema::{ObjectDefinitionId, Schema};
 use walker::Walk;
 
 use super::{
-    DataParts, ErrorCodeCounter, ErrorPathSegment, ExecutedResponse, GraphqlError, InputResponseObjectSet,
-    OutputResponseObjectSets, Response, ResponseData, ResponseObject, ResponseObjectField, ResponseObjectId,
-    ResponseObjectRef, ResponseValue, ResponseValueId,
+    CacheControl, DataParts, ErrorCodeCounter, ErrorPathSegment, ExecutedResponse, ExecutedResponseData, GraphqlError,
+    InputResponseObjectSet, OutputResponseObjectSets, Response, ResponseData, ResponseObject, ResponseObjectField,
+    ResponseObjectId, ResponseObjectRef, ResponseValue, ResponseValueId,
 };
 use crate::{
     execution::ExecutionError,
@@ -23,10 +23,11 @@ pub(crate) use subgraph_response::*;
 pub(crate) struct ResponseBuilder {
     // will be None if an error propagated up to the root.
     pub(in crate::response) schema: Arc<Schema>,
-    operation: Arc<CachedOperation>,
+    pub(super) operation: Arc<CachedOperation>,
     pub(super) root: Option<(ResponseObjectId, ObjectDefinitionId)>,
     pub(super) data_parts: DataParts,
-    errors: Vec<GraphqlError>,
+    pub(super) errors: Vec<GraphqlError>,
+    pub(super) cache_control: CacheControl,
 }
 
 impl ResponseBuilder {
@@ -49,9 +50,14 @@ impl ResponseBuilder {
             root: Some((root_id, root_object_definition_id)),
             data_parts: parts,
             errors: Vec::new(),
+            cache_control: CacheControl::default(),
         }
     }
 
+    pub fn errors(&self) -> &[GraphqlError] {
+        &self.errors
+    }
+
     pub fn push_error(&mut self, error: impl Into<GraphqlError>) {
         self.errors.push(error.into());
     }
@@ -112,6 +118,7 @@ impl ResponseBuilder {
 
     pub fn ingest(&mut self, plan: Plan<'_>, mut subgraph_response: SubgraphResponse) -> OutputResponseObjectSets {
         self.data_parts.insert(subgraph_response.data);
+        self.cache_control.merge(subgraph_response.cache_control);
 
         let (any_response_key, default_fields_sorted_by_key) =
             self.extract_any_response_key_and_default_fields_sorted_by_key(plan);
@@ -275,14 +282,17 @@ impl ResponseBuilder {
             schema: self.schema,
             operation: self.operation,
             operation_attributes,
-            data: self.root.map(|(root, _)| ResponseData {
-                root,
-                parts: self.data_parts,
+            data: self.root.map(|(root, _)| {
+                ExecutedResponseData::Built(ResponseData {
+                    root,
+                    parts: self.data_parts,
+                })
             }),
             errors: self.errors,
             error_code_counter,
             on_operation_response_output: Some(on_operation_response_output),
             extensions: None,
+            cache_control: self.cache_control,
         })
     }
 }
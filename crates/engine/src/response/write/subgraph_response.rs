@@ -4,8 +4,8 @@ use crate::{
     execution::ExecutionContext,
     prepare::{ConcreteShapeId, ResponseObjectSetDefinitionId},
     response::{
-        DataPart, GraphqlError, InputObjectId, InputResponseObjectSet, ResponseObjectField, ResponseObjectRef,
-        ResponseObjectSet, ResponseValueId,
+        CacheControl, DataPart, GraphqlError, InputObjectId, InputResponseObjectSet, ResponseObjectField,
+        ResponseObjectRef, ResponseObjectSet, ResponseValueId,
     },
     Runtime,
 };
@@ -22,6 +22,7 @@ pub(crate) struct SubgraphResponse {
     pub(super) subgraph_errors: Vec<GraphqlError>,
     pub(super) updates: Vec<ObjectUpdate>,
     pub(super) response_object_sets: Vec<(ResponseObjectSetDefinitionId, ResponseObjectSet)>,
+    pub(super) cache_control: CacheControl,
 }
 
 impl SubgraphResponse {
@@ -40,9 +41,14 @@ impl SubgraphResponse {
             errors: Vec::new(),
             subgraph_errors: Vec::new(),
             response_object_sets: Vec::new(),
+            cache_control: CacheControl::default(),
         }
     }
 
+    pub fn input_response_object_set(&self) -> &Arc<InputResponseObjectSet> {
+        &self.input_response_object_set
+    }
+
     /// Executors manipulate the response within a Send future, so we can't use a Rc/RefCell
     /// directly. Only once the executor is ready to write should it use this method.
     pub fn as_shared_mut(&mut self) -> SubgraphResponseRefMut<'_> {
@@ -90,6 +96,11 @@ impl SubgraphResponse {
     pub fn set_subgraph_errors(&mut self, errors: Vec<GraphqlError>) {
         self.subgraph_errors = errors;
     }
+
+    /// Cache hints of the subgraph response and of every entity cache hit it includes.
+    pub fn merge_cache_control(&mut self, cache_control: CacheControl) {
+        self.cache_control.merge(cache_control);
+    }
 }
 
 /// We end up writing objects or lists at various step of the de-serialization / query
@@ -17,17 +17,22 @@ pub struct Extension {
     pub wasm_path: PathBuf,
 }
 
-#[derive(Default)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ExtensionDirectiveKind {
     #[default]
     Unknown,
     FieldResolver,
+    Authorization,
 }
 
 impl ExtensionDirectiveKind {
     pub fn is_field_resolver(&self) -> bool {
         matches!(self, Self::FieldResolver)
     }
+
+    pub fn is_authorization(&self) -> bool {
+        matches!(self, Self::Authorization)
+    }
 }
 
 impl ExtensionCatalog {
@@ -55,6 +60,9 @@ impl ExtensionCatalog {
             {
                 ExtensionDirectiveKind::FieldResolver
             }
+            extension::Kind::Authorizer(Authorizer {
+                authorization_directives,
+            }) if authorization_directives.iter().any(|dir| dir == name) => ExtensionDirectiveKind::Authorization,
             _ => ExtensionDirectiveKind::Unknown,
         }
     }
@@ -0,0 +1,741 @@
+//! Validation of the extension configuration against the JSON Schema declared in its manifest.
+//!
+//! Only a subset of JSON Schema is supported: `type`, `enum`, `const`, `properties`, `required`,
+//! `additionalProperties`, `items`, `minItems`, `maxItems`, `minLength`, `maxLength`, `minimum`,
+//! `maximum`, `exclusiveMinimum`, `exclusiveMaximum`, `allOf`, `anyOf`, `oneOf` and `not`.
+//! Annotations such as `title`, `description` or `format` are accepted and ignored. Any other
+//! keyword is rejected when the extension is built, so that a schema never validates less than
+//! its author expects.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+const ANNOTATIONS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "format",
+];
+
+const TYPES: &[&str] = &["null", "boolean", "integer", "number", "string", "array", "object"];
+
+/// An error in the configuration or in the configuration schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    /// JSON pointer to the invalid value, empty for the root.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "at the root: {}", self.message)
+        } else {
+            write!(f, "at `{}`: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Checks that the schema only uses supported keywords with well-formed values.
+pub fn check_config_schema(schema: &Value) -> Result<(), Vec<ConfigError>> {
+    let mut errors = Vec::new();
+    check_schema(schema, "", &mut errors);
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Validates the configuration against the schema, returning every error found.
+pub fn validate_config(schema: &Value, config: &Value) -> Result<(), Vec<ConfigError>> {
+    let mut errors = Vec::new();
+    validate(schema, config, "", &mut errors);
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn check_schema(schema: &Value, path: &str, errors: &mut Vec<ConfigError>) {
+    let schema = match schema {
+        Value::Bool(_) => return,
+        Value::Object(schema) => schema,
+        _ => {
+            errors.push(error(path, "a schema must be an object or a boolean"));
+            return;
+        }
+    };
+
+    for (keyword, value) in schema {
+        let path = &pointer(path, keyword);
+
+        match keyword.as_str() {
+            keyword if ANNOTATIONS.contains(&keyword) => (),
+            "type" => {
+                let valid = match value {
+                    Value::String(name) => TYPES.contains(&name.as_str()),
+                    Value::Array(names) => names
+                        .iter()
+                        .all(|name| name.as_str().is_some_and(|name| TYPES.contains(&name))),
+                    _ => false,
+                };
+
+                if !valid {
+                    errors.push(error(path, format!("expected one of {}", TYPES.join(", "))));
+                }
+            }
+            "enum" => {
+                if !value.is_array() {
+                    errors.push(error(path, "expected an array"));
+                }
+            }
+            "const" => (),
+            "properties" => match value.as_object() {
+                Some(properties) => {
+                    for (name, schema) in properties {
+                        check_schema(schema, &pointer(path, name), errors);
+                    }
+                }
+                None => errors.push(error(path, "expected an object")),
+            },
+            "required" => {
+                let valid = value.as_array().is_some_and(|names| names.iter().all(Value::is_string));
+
+                if !valid {
+                    errors.push(error(path, "expected an array of strings"));
+                }
+            }
+            "additionalProperties" | "items" | "not" => check_schema(value, path, errors),
+            "allOf" | "anyOf" | "oneOf" => match value.as_array() {
+                Some(schemas) if !schemas.is_empty() => {
+                    for (i, schema) in schemas.iter().enumerate() {
+                        check_schema(schema, &pointer(path, &i.to_string()), errors);
+                    }
+                }
+                _ => errors.push(error(path, "expected a non-empty array of schemas")),
+            },
+            "minItems" | "maxItems" | "minLength" | "maxLength" => {
+                if !value.is_u64() {
+                    errors.push(error(path, "expected a non-negative integer"));
+                }
+            }
+            "minimum" | "maximum" | "exclusiveMinimum" | "exclusiveMaximum" => {
+                if !value.is_number() {
+                    errors.push(error(path, "expected a number"));
+                }
+            }
+            keyword => errors.push(error(path, format!("unsupported keyword `{keyword}`"))),
+        }
+    }
+}
+
+fn validate(schema: &Value, value: &Value, path: &str, errors: &mut Vec<ConfigError>) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            errors.push(error(path, "no value is allowed here"));
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let matches = match expected {
+            Value::String(name) => has_type(value, name),
+            Value::Array(names) => names.iter().filter_map(Value::as_str).any(|name| has_type(value, name)),
+            _ => true,
+        };
+
+        if !matches {
+            let expected = match expected {
+                Value::Array(names) => names.iter().filter_map(Value::as_str).collect::<Vec<_>>().join(" or "),
+                expected => expected.as_str().unwrap_or_default().to_string(),
+            };
+
+            errors.push(error(path, format!("expected {expected}, found {}", type_name(value))));
+
+            // Other keywords would only report the same mismatch again.
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            let allowed = allowed.iter().map(Value::to_string).collect::<Vec<_>>().join(", ");
+            errors.push(error(path, format!("expected one of {allowed}, found {value}")));
+        }
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(error(path, format!("expected {expected}, found {value}")));
+        }
+    }
+
+    match value {
+        Value::Object(object) => validate_object(schema, object, path, errors),
+        Value::Array(items) => validate_array(schema, items, path, errors),
+        Value::String(string) => validate_string(schema, string, path, errors),
+        Value::Number(number) => {
+            if let Some(number) = number.as_f64() {
+                validate_number(schema, number, path, errors)
+            }
+        }
+        Value::Null | Value::Bool(_) => (),
+    }
+
+    if let Some(schemas) = schema.get("allOf").and_then(Value::as_array) {
+        for schema in schemas {
+            validate(schema, value, path, errors);
+        }
+    }
+
+    if let Some(schemas) = schema.get("anyOf").and_then(Value::as_array) {
+        if !schemas.iter().any(|schema| is_valid(schema, value)) {
+            errors.push(error(path, "does not match any of the allowed schemas"));
+        }
+    }
+
+    if let Some(schemas) = schema.get("oneOf").and_then(Value::as_array) {
+        let matching = schemas.iter().filter(|schema| is_valid(schema, value)).count();
+
+        if matching != 1 {
+            errors.push(error(
+                path,
+                format!("must match exactly one of the allowed schemas, matches {matching}"),
+            ));
+        }
+    }
+
+    if let Some(schema) = schema.get("not") {
+        if is_valid(schema, value) {
+            errors.push(error(path, "matches a schema it must not match"));
+        }
+    }
+}
+
+fn validate_object(
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<ConfigError>,
+) {
+    for name in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+        let Some(name) = name.as_str() else { continue };
+
+        if !object.contains_key(name) {
+            errors.push(error(path, format!("missing required field `{name}`")));
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+
+    for (name, value) in object {
+        let path = &pointer(path, name);
+
+        match properties.and_then(|properties| properties.get(name)) {
+            Some(schema) => validate(schema, value, path, errors),
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => errors.push(error(path, "unknown field")),
+                Some(schema) => validate(schema, value, path, errors),
+                None => (),
+            },
+        }
+    }
+}
+
+fn validate_array(schema: &Map<String, Value>, items: &[Value], path: &str, errors: &mut Vec<ConfigError>) {
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if (items.len() as u64) < min {
+            errors.push(error(
+                path,
+                format!("expected at least {min} items, found {}", items.len()),
+            ));
+        }
+    }
+
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if (items.len() as u64) > max {
+            errors.push(error(
+                path,
+                format!("expected at most {max} items, found {}", items.len()),
+            ));
+        }
+    }
+
+    if let Some(item_schema) = schema.get("items") {
+        for (i, item) in items.iter().enumerate() {
+            validate(item_schema, item, &pointer(path, &i.to_string()), errors);
+        }
+    }
+}
+
+fn validate_string(schema: &Map<String, Value>, string: &str, path: &str, errors: &mut Vec<ConfigError>) {
+    let length = string.chars().count() as u64;
+
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if length < min {
+            errors.push(error(
+                path,
+                format!("expected at least {min} characters, found {length}"),
+            ));
+        }
+    }
+
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if length > max {
+            errors.push(error(
+                path,
+                format!("expected at most {max} characters, found {length}"),
+            ));
+        }
+    }
+}
+
+fn validate_number(schema: &Map<String, Value>, number: f64, path: &str, errors: &mut Vec<ConfigError>) {
+    let bound = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+
+    if let Some(minimum) = bound("minimum").filter(|minimum| number < *minimum) {
+        errors.push(error(
+            path,
+            format!("expected a value of at least {minimum}, found {number}"),
+        ));
+    }
+
+    if let Some(maximum) = bound("maximum").filter(|maximum| number > *maximum) {
+        errors.push(error(
+            path,
+            format!("expected a value of at most {maximum}, found {number}"),
+        ));
+    }
+
+    if let Some(minimum) = bound("exclusiveMinimum").filter(|minimum| number <= *minimum) {
+        errors.push(error(
+            path,
+            format!("expected a value greater than {minimum}, found {number}"),
+        ));
+    }
+
+    if let Some(maximum) = bound("exclusiveMaximum").filter(|maximum| number >= *maximum) {
+        errors.push(error(
+            path,
+            format!("expected a value less than {maximum}, found {number}"),
+        ));
+    }
+}
+
+fn is_valid(schema: &Value, value: &Value) -> bool {
+    let mut errors = Vec::new();
+    validate(schema, value, "", &mut errors);
+    errors.is_empty()
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn pointer(path: &str, segment: &str) -> String {
+    format!("{path}/{}", segment.replace('~', "~0").replace('/', "~1"))
+}
+
+fn error(path: &str, message: impl Into<String>) -> ConfigError {
+    ConfigError {
+        path: path.to_string(),
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn jwt_schema() -> Value {
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "required": ["url"],
+            "additionalProperties": false,
+            "properties": {
+                "url": { "type": "string", "format": "uri" },
+                "poll_interval": { "type": "integer", "minimum": 1 },
+                "header_name": { "type": "string", "minLength": 1 },
+                "audience": {
+                    "anyOf": [
+                        { "type": "string" },
+                        { "type": "array", "items": { "type": "string" }, "minItems": 1 }
+                    ]
+                }
+            }
+        })
+    }
+
+    fn messages(result: Result<(), Vec<ConfigError>>) -> Vec<String> {
+        result.unwrap_err().iter().map(ToString::to_string).collect()
+    }
+
+    fn errors(schema: &Value, config: Value) -> Vec<String> {
+        check_config_schema(schema).unwrap();
+
+        match validate_config(schema, &config) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn valid_config() {
+        let config = json!({
+            "url": "https://example.com/.well-known/jwks.json",
+            "poll_interval": 60,
+            "audience": ["my-project"]
+        });
+
+        check_config_schema(&jwt_schema()).unwrap();
+        validate_config(&jwt_schema(), &config).unwrap();
+    }
+
+    #[test]
+    fn invalid_config() {
+        let config = json!({
+            "audience": [],
+            "header_name": "",
+            "issuer": "example.com",
+            "poll_interval": "60"
+        });
+
+        assert_eq!(
+            messages(validate_config(&jwt_schema(), &config)),
+            vec![
+                "at the root: missing required field `url`",
+                "at `/audience`: does not match any of the allowed schemas",
+                "at `/header_name`: expected at least 1 characters, found 0",
+                "at `/issuer`: unknown field",
+                "at `/poll_interval`: expected integer, found string",
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_paths() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "rules": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": { "level": { "enum": ["read", "write"] } }
+                    }
+                }
+            }
+        });
+
+        let config = json!({ "rules": [{ "level": "read" }, { "level": "admin" }] });
+
+        assert_eq!(
+            messages(validate_config(&schema, &config)),
+            vec![r#"at `/rules/1/level`: expected one of "read", "write", found "admin""#]
+        );
+    }
+
+    #[test]
+    fn unsupported_keywords() {
+        let schema = json!({
+            "anyOf": [],
+            "properties": {
+                "name": { "pattern": "^[a-z]+$", "type": "text" }
+            },
+            "type": "object"
+        });
+
+        assert_eq!(
+            messages(check_config_schema(&schema)),
+            vec![
+                "at `/anyOf`: expected a non-empty array of schemas",
+                "at `/properties/name/pattern`: unsupported keyword `pattern`",
+                "at `/properties/name/type`: expected one of null, boolean, integer, number, string, array, object",
+            ]
+        );
+    }
+
+    #[test]
+    fn type_keyword() {
+        let cases = [
+            ("null", json!(null), json!(false)),
+            ("boolean", json!(true), json!(0)),
+            ("integer", json!(1.0), json!(1.5)),
+            ("number", json!(1.5), json!("1.5")),
+            ("string", json!(""), json!(null)),
+            ("array", json!([]), json!({})),
+            ("object", json!({}), json!([])),
+        ];
+
+        for (name, valid, invalid) in cases {
+            let schema = json!({ "type": name });
+
+            assert_eq!(errors(&schema, valid), Vec::<String>::new(), "{name}");
+            assert_eq!(errors(&schema, invalid).len(), 1, "{name}");
+        }
+
+        let schema = json!({ "type": ["string", "null"] });
+
+        assert_eq!(errors(&schema, json!(null)), Vec::<String>::new());
+        assert_eq!(
+            errors(&schema, json!(1)),
+            vec!["at the root: expected string or null, found integer"]
+        );
+    }
+
+    #[test]
+    fn enum_and_const_keywords() {
+        let schema = json!({ "enum": [1, "one", null] });
+
+        assert_eq!(errors(&schema, json!("one")), Vec::<String>::new());
+        assert_eq!(
+            errors(&schema, json!(2)),
+            vec![r#"at the root: expected one of 1, "one", null, found 2"#]
+        );
+
+        let schema = json!({ "const": { "a": [1] } });
+
+        assert_eq!(errors(&schema, json!({ "a": [1] })), Vec::<String>::new());
+        assert_eq!(
+            errors(&schema, json!({ "a": [2] })),
+            vec![r#"at the root: expected {"a":[1]}, found {"a":[2]}"#]
+        );
+    }
+
+    #[test]
+    fn object_keywords() {
+        let schema = json!({
+            "additionalProperties": { "type": "integer" },
+            "properties": { "name": { "type": "string" } },
+            "required": ["id", "name"]
+        });
+
+        assert_eq!(errors(&schema, json!({ "id": 1, "name": "a" })), Vec::<String>::new());
+        assert_eq!(
+            errors(&schema, json!({ "id": "1", "name": 1 })),
+            vec![
+                "at `/id`: expected integer, found string",
+                "at `/name`: expected string, found integer",
+            ]
+        );
+        assert_eq!(
+            errors(&schema, json!({})),
+            vec![
+                "at the root: missing required field `id`",
+                "at the root: missing required field `name`",
+            ]
+        );
+
+        // Without `additionalProperties`, unknown fields are allowed.
+        let schema = json!({ "properties": { "a/b~c": false } });
+
+        assert_eq!(errors(&schema, json!({ "other": 1 })), Vec::<String>::new());
+        assert_eq!(
+            errors(&schema, json!({ "a/b~c": 1 })),
+            vec!["at `/a~1b~0c`: no value is allowed here"]
+        );
+    }
+
+    #[test]
+    fn array_keywords() {
+        let schema = json!({ "items": { "type": "boolean" }, "maxItems": 2, "minItems": 1 });
+
+        assert_eq!(errors(&schema, json!([true, false])), Vec::<String>::new());
+        assert_eq!(
+            errors(&schema, json!([])),
+            vec!["at the root: expected at least 1 items, found 0"]
+        );
+        assert_eq!(
+            errors(&schema, json!([true, false, 1])),
+            vec![
+                "at the root: expected at most 2 items, found 3",
+                "at `/2`: expected boolean, found integer",
+            ]
+        );
+    }
+
+    #[test]
+    fn string_keywords() {
+        let schema = json!({ "maxLength": 3, "minLength": 2 });
+
+        // Lengths are counted in characters, not bytes.
+        assert_eq!(errors(&schema, json!("été")), Vec::<String>::new());
+        assert_eq!(
+            errors(&schema, json!("é")),
+            vec!["at the root: expected at least 2 characters, found 1"]
+        );
+        assert_eq!(
+            errors(&schema, json!("abcd")),
+            vec!["at the root: expected at most 3 characters, found 4"]
+        );
+    }
+
+    #[test]
+    fn number_keywords() {
+        let inclusive = json!({ "maximum": 10, "minimum": 1 });
+
+        assert_eq!(errors(&inclusive, json!(1)), Vec::<String>::new());
+        assert_eq!(errors(&inclusive, json!(10)), Vec::<String>::new());
+        assert_eq!(
+            errors(&inclusive, json!(0.5)),
+            vec!["at the root: expected a value of at least 1, found 0.5"]
+        );
+        assert_eq!(
+            errors(&inclusive, json!(11)),
+            vec!["at the root: expected a value of at most 10, found 11"]
+        );
+
+        let exclusive = json!({ "exclusiveMaximum": 10, "exclusiveMinimum": 1 });
+
+        assert_eq!(errors(&exclusive, json!(1.5)), Vec::<String>::new());
+        assert_eq!(
+            errors(&exclusive, json!(1)),
+            vec!["at the root: expected a value greater than 1, found 1"]
+        );
+        assert_eq!(
+            errors(&exclusive, json!(10)),
+            vec!["at the root: expected a value less than 10, found 10"]
+        );
+    }
+
+    #[test]
+    fn combinators() {
+        let all_of = json!({ "allOf": [{ "minimum": 1 }, { "maximum": 2 }] });
+
+        assert_eq!(errors(&all_of, json!(2)), Vec::<String>::new());
+        assert_eq!(
+            errors(&all_of, json!(3)),
+            vec!["at the root: expected a value of at most 2, found 3"]
+        );
+
+        let any_of = json!({ "anyOf": [{ "type": "string" }, { "type": "integer" }] });
+
+        assert_eq!(errors(&any_of, json!(1)), Vec::<String>::new());
+        assert_eq!(
+            errors(&any_of, json!(true)),
+            vec!["at the root: does not match any of the allowed schemas"]
+        );
+
+        let one_of = json!({ "oneOf": [{ "type": "integer" }, { "minimum": 2 }] });
+
+        assert_eq!(errors(&one_of, json!(1)), Vec::<String>::new());
+        assert_eq!(errors(&one_of, json!(2.5)), Vec::<String>::new());
+        assert_eq!(
+            errors(&one_of, json!(2)),
+            vec!["at the root: must match exactly one of the allowed schemas, matches 2"]
+        );
+        assert_eq!(
+            errors(&one_of, json!(1.5)),
+            vec!["at the root: must match exactly one of the allowed schemas, matches 0"]
+        );
+
+        let not = json!({ "not": { "const": "admin" } });
+
+        assert_eq!(errors(&not, json!("user")), Vec::<String>::new());
+        assert_eq!(
+            errors(&not, json!("admin")),
+            vec!["at the root: matches a schema it must not match"]
+        );
+    }
+
+    #[test]
+    fn boolean_schemas() {
+        assert_eq!(errors(&json!(true), json!({ "any": "thing" })), Vec::<String>::new());
+        assert_eq!(
+            errors(&json!(false), json!(null)),
+            vec!["at the root: no value is allowed here"]
+        );
+    }
+
+    #[test]
+    fn annotations_are_ignored() {
+        let schema = json!({
+            "$comment": "",
+            "$id": "https://example.com/config.json",
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "default": 1,
+            "deprecated": true,
+            "description": "",
+            "examples": [1],
+            "format": "uri",
+            "readOnly": true,
+            "title": "",
+            "writeOnly": false
+        });
+
+        assert_eq!(errors(&schema, json!("not a uri")), Vec::<String>::new());
+    }
+
+    #[test]
+    fn malformed_keywords() {
+        let schema = json!({
+            "additionalProperties": 1,
+            "allOf": [{ "type": "text" }],
+            "enum": "a",
+            "items": "a",
+            "maxLength": 1.5,
+            "minItems": -1,
+            "minimum": "1",
+            "not": [],
+            "oneOf": {},
+            "properties": [],
+            "required": [1]
+        });
+
+        assert_eq!(
+            messages(check_config_schema(&schema)),
+            vec![
+                "at `/additionalProperties`: a schema must be an object or a boolean",
+                "at `/allOf/0/type`: expected one of null, boolean, integer, number, string, array, object",
+                "at `/enum`: expected an array",
+                "at `/items`: a schema must be an object or a boolean",
+                "at `/maxLength`: expected a non-negative integer",
+                "at `/minItems`: expected a non-negative integer",
+                "at `/minimum`: expected a number",
+                "at `/not`: a schema must be an object or a boolean",
+                "at `/oneOf`: expected a non-empty array of schemas",
+                "at `/properties`: expected an object",
+                "at `/required`: expected an array of strings",
+            ]
+        );
+    }
+}
@@ -1,5 +1,7 @@
+mod config_schema;
 mod id;
 mod manifest;
 
+pub use config_schema::*;
 pub use id::*;
 pub use manifest::*;
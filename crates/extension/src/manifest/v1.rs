@@ -1,4 +1,4 @@
-use crate::Id;
+use crate::{ConfigError, Id};
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Manifest {
@@ -17,6 +17,16 @@ pub struct Manifest {
     pub repository_url: Option<url::Url>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<String>,
+    /// JSON Schema of the extension configuration.
+    ///
+    /// Only a subset of JSON Schema is supported, listed in the SDK documentation. The gateway
+    /// validates the configuration at startup without fetching anything, so `$ref` and `$defs` are
+    /// not resolved, and `pattern` or `patternProperties` are rejected because their ECMA-262
+    /// regular expressions cannot be evaluated faithfully by the Rust regex engine. `minProperties`
+    /// and `maxProperties` aren't implemented yet. Schemas using any of those keywords fail the
+    /// extension build rather than silently validating less than their author expects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_schema: Option<serde_json::Value>,
 }
 
 impl Manifest {
@@ -39,12 +49,30 @@ impl Manifest {
     pub fn is_authenticator(&self) -> bool {
         matches!(self.kind, Kind::Authenticator(_))
     }
+
+    pub fn is_authorizer(&self) -> bool {
+        matches!(self.kind, Kind::Authorizer(_))
+    }
+
+    pub fn is_hooks(&self) -> bool {
+        matches!(self.kind, Kind::Hooks(_))
+    }
+
+    /// Validates the configuration against the schema of the extension, if it declares one.
+    pub fn validate_config(&self, config: &serde_json::Value) -> Result<(), Vec<ConfigError>> {
+        match self.config_schema {
+            Some(ref schema) => crate::validate_config(schema, config),
+            None => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum Kind {
     FieldResolver(FieldResolver),
     Authenticator(Empty),
+    Authorizer(Authorizer),
+    Hooks(Empty),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -52,6 +80,11 @@ pub struct FieldResolver {
     pub resolver_directives: Vec<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Authorizer {
+    pub authorization_directives: Vec<String>,
+}
+
 // Allows us to add fields later, as adding a value to an enum that doesn't have one would be
 // breaking change if not handled carefully in serde.
 #[derive(Default, Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -97,7 +130,8 @@ mod tests {
                 readme: None,
                 homepage_url: Some("http://example.com/my-extension".parse().unwrap()),
                 repository_url: None,
-                license: None
+                license: None,
+                config_schema: None,
             }
         );
     }
@@ -132,11 +166,83 @@ mod tests {
                 readme: None,
                 homepage_url: Some("http://example.com/my-extension".parse().unwrap()),
                 repository_url: None,
-                license: None
+                license: None,
+                config_schema: None,
             }
         )
     }
 
+    #[test]
+    fn v1_authorizer() {
+        let json = json!({
+            "id": {"name": "policy", "version": "1.0.0"},
+            "kind": {
+                "Authorizer": {
+                    "authorization_directives": ["policy"]
+                }
+            },
+            "sdl": "directive @policy(name: String!) on FIELD_DEFINITION | OBJECT",
+            "sdk_version": "0.1.0",
+            "minimum_gateway_version": "0.1.0",
+            "description": "An extension in a test",
+        });
+
+        let manifest: Manifest = serde_json::from_value(json).unwrap();
+        assert!(manifest.is_authorizer());
+        assert_eq!(
+            manifest.kind,
+            Kind::Authorizer(Authorizer {
+                authorization_directives: vec!["policy".to_string()]
+            })
+        );
+    }
+
+    #[test]
+    fn v1_hooks() {
+        let json = json!({
+            "id": {"name": "headers", "version": "1.0.0"},
+            "kind": {
+                "Hooks": {}
+            },
+            "sdk_version": "0.1.0",
+            "minimum_gateway_version": "0.1.0",
+            "description": "An extension in a test",
+        });
+
+        let manifest: Manifest = serde_json::from_value(json).unwrap();
+        assert!(manifest.is_hooks());
+        assert_eq!(manifest.kind, Kind::Hooks(Empty {}));
+    }
+
+    #[test]
+    fn v1_config_schema() {
+        let json = json!({
+            "id": {"name": "jwt", "version": "1.0.0"},
+            "kind": {
+                "Authenticator": {}
+            },
+            "sdk_version": "0.1.0",
+            "minimum_gateway_version": "0.1.0",
+            "description": "An extension in a test",
+            "config_schema": {
+                "type": "object",
+                "required": ["url"],
+                "properties": {
+                    "url": { "type": "string" }
+                }
+            }
+        });
+
+        let manifest: Manifest = serde_json::from_value(json).unwrap();
+
+        manifest
+            .validate_config(&json!({ "url": "https://example.com" }))
+            .unwrap();
+
+        let errors = manifest.validate_config(&json!({ "url": 1 })).unwrap_err();
+        assert_eq!(errors[0].to_string(), "at `/url`: expected string, found integer");
+    }
+
     #[test]
     fn v1_missing_optional_fields() {
         // Test older versions that might not have had the sdl field
@@ -17,6 +17,12 @@ pub enum Error {
     Server(#[source] std::io::Error),
     #[error("fetcher configuration error: {0}")]
     FetcherConfigError(String),
+    /// The configuration of an extension does not match its schema
+    #[error("extension configuration error: {0}")]
+    ExtensionConfigError(String),
+    /// The gateway configuration is invalid
+    #[error("configuration error: {0}")]
+    ConfigError(String),
 }
 
 impl<T> From<watch::error::SendError<T>> for Error {
@@ -1,11 +1,14 @@
 mod access_logs;
+mod compression;
 mod cors;
 mod csrf;
 mod engine_reloader;
+mod entity_cache_purge;
 mod gateway;
 mod graph_fetch_method;
 mod graph_updater;
 mod health;
+mod metrics;
 mod state;
 mod trusted_documents_client;
 
@@ -94,6 +97,13 @@ pub async fn serve(
     let path = config.graph.path.as_deref().unwrap_or("/graphql");
     let websocket_path = config.graph.websocket_path.as_deref().unwrap_or("/ws");
 
+    // The purge endpoint is not authenticated, so it's never exposed next to the GraphQL endpoint.
+    if config.entity_caching.purge.enabled && config.entity_caching.purge.listen.is_none() {
+        return Err(crate::Error::ConfigError(
+            "entity_caching.purge.listen must be set when the purge endpoint is enabled".to_string(),
+        ));
+    }
+
     let meter = grafbase_telemetry::metrics::meter_from_global_provider();
     let pending_logs_counter = meter.i64_up_down_counter("grafbase.gateway.access_log.pending").build();
 
@@ -156,6 +166,10 @@ pub async fn serve(
         ))
         .layer(cors);
 
+    if config.compression.enabled {
+        router = compression::inject_layers(router, &config.compression);
+    }
+
     if config.health.enabled {
         if let Some(listen) = config.health.listen {
             tokio::spawn(health::bind_health_endpoint(
@@ -169,6 +183,32 @@ pub async fn serve(
         }
     }
 
+    if let Some(prometheus_config) = config.telemetry.metrics_prometheus_config() {
+        if let Some(listen) = prometheus_config.listen {
+            tokio::spawn(metrics::bind_metrics_endpoint(
+                listen,
+                config.tls.clone(),
+                prometheus_config.clone(),
+            ));
+        } else {
+            router = router.route(&prometheus_config.path, get(metrics::metrics));
+        }
+    }
+
+    if let Some(listen) = config
+        .entity_caching
+        .purge
+        .listen
+        .filter(|_| config.entity_caching.purge.enabled)
+    {
+        tokio::spawn(entity_cache_purge::bind_entity_cache_purge_endpoint(
+            listen,
+            config.tls.clone(),
+            config.entity_caching.purge.clone(),
+            state.clone(),
+        ));
+    }
+
     let mut router = router.with_state(state);
 
     if config.csrf.enabled {
@@ -207,7 +247,7 @@ async fn bind(
     tls: Option<&TlsConfig>,
     server_runtime: impl ServerRuntime,
 ) -> crate::Result<()> {
-    let app = router.into_make_service();
+    let app = router.into_make_service_with_connect_info::<SocketAddr>();
 
     let handle = axum_server::Handle::new();
 
@@ -0,0 +1,284 @@
+use std::{
+    io::Write,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Request, State},
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use gateway_config::CompressionConfig;
+use http::{header, HeaderMap, HeaderValue};
+use http_body::Frame;
+use tower_http::compression::{
+    predicate::{NotForContentType, Predicate, SizeAbove},
+    CompressionLayer,
+};
+
+/// The default quality of 11 is far too slow for responses compressed on the fly.
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_WINDOW: u32 = 22;
+
+/// Compressed data is sent once it reaches this size, even if the inner body has more data ready.
+const MAX_OUTPUT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Injects the layers compressing the responses with the best algorithm accepted by the client.
+///
+/// Streamed responses such as SSE and multipart are compressed by their own layer, which flushes
+/// the encoder whenever the next event or part isn't ready yet. Otherwise the encoder would hold
+/// them back until its buffer fills up.
+pub(super) fn inject_layers<S>(router: Router<S>, config: &CompressionConfig) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let min_size = u16::try_from(config.min_size.bytes()).expect("validated by the configuration");
+
+    router
+        .layer(middleware::from_fn_with_state(config.clone(), compress_stream))
+        .layer(
+            CompressionLayer::new()
+                .gzip(config.gzip)
+                .br(config.brotli)
+                .zstd(config.zstd)
+                .compress_when(SizeAbove::new(min_size).and(NotForContentType::IMAGES)),
+        )
+}
+
+async fn compress_stream(State(config): State<CompressionConfig>, request: Request, next: Next) -> Response {
+    let encoding = preferred_encoding(request.headers(), &config);
+    let response = next.run(request).await;
+
+    let Some(encoding) = encoding else {
+        return response;
+    };
+
+    if !is_stream(response.headers()) || response.headers().contains_key(header::CONTENT_ENCODING) {
+        return response;
+    }
+
+    let encoder = match Encoder::new(encoding) {
+        Ok(encoder) => encoder,
+        Err(err) => {
+            tracing::error!("Failed to create the {} encoder: {err}", encoding.as_str());
+            return response;
+        }
+    };
+
+    let (mut parts, body) = response.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts
+        .headers
+        .insert(header::CONTENT_ENCODING, HeaderValue::from_static(encoding.as_str()));
+    parts
+        .headers
+        .append(header::VARY, HeaderValue::from_static("accept-encoding"));
+
+    let body = StreamEncoder {
+        inner: body,
+        encoder: Some(encoder),
+        unflushed: false,
+    };
+
+    Response::from_parts(parts, Body::new(body))
+}
+
+fn is_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| {
+            content_type.starts_with("text/event-stream") || content_type.starts_with("multipart/")
+        })
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Encoding {
+    Gzip,
+    Brotli,
+    Zstd,
+}
+
+impl Encoding {
+    fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Brotli => "br",
+            Encoding::Zstd => "zstd",
+        }
+    }
+}
+
+/// Picks the enabled encoding with the highest quality value in `Accept-Encoding`, the first
+/// one listed on ties.
+fn preferred_encoding(headers: &HeaderMap, config: &CompressionConfig) -> Option<Encoding> {
+    let mut preferred: Option<(Encoding, f32)> = None;
+
+    let accepted = headers
+        .get_all(header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','));
+
+    for item in accepted {
+        let mut params = item.split(';');
+
+        let encoding = match params.next().unwrap_or_default().trim() {
+            "gzip" if config.gzip => Encoding::Gzip,
+            "br" if config.brotli => Encoding::Brotli,
+            "zstd" if config.zstd => Encoding::Zstd,
+            _ => continue,
+        };
+
+        let quality = params
+            .find_map(|param| param.trim().strip_prefix("q="))
+            .map(|quality| quality.trim().parse::<f32>().unwrap_or(0.0))
+            .unwrap_or(1.0);
+
+        if quality > 0.0 && preferred.is_none_or(|(_, preferred)| quality > preferred) {
+            preferred = Some((encoding, quality));
+        }
+    }
+
+    preferred.map(|(encoding, _)| encoding)
+}
+
+enum Encoder {
+    Gzip(flate2::write::GzEncoder<Vec<u8>>),
+    Brotli(Box<brotli::CompressorWriter<Vec<u8>>>),
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+}
+
+impl Encoder {
+    fn new(encoding: Encoding) -> std::io::Result<Self> {
+        Ok(match encoding {
+            Encoding::Gzip => Encoder::Gzip(flate2::write::GzEncoder::new(
+                Vec::new(),
+                flate2::Compression::default(),
+            )),
+            Encoding::Brotli => Encoder::Brotli(Box::new(brotli::CompressorWriter::new(
+                Vec::new(),
+                4096,
+                BROTLI_QUALITY,
+                BROTLI_WINDOW,
+            ))),
+            Encoding::Zstd => Encoder::Zstd(zstd::stream::write::Encoder::new(
+                Vec::new(),
+                zstd::DEFAULT_COMPRESSION_LEVEL,
+            )?),
+        })
+    }
+
+    fn write(&mut self, data: &[u8]) -> std::io::Result<()> {
+        match self {
+            Encoder::Gzip(encoder) => encoder.write_all(data),
+            Encoder::Brotli(encoder) => encoder.write_all(data),
+            Encoder::Zstd(encoder) => encoder.write_all(data),
+        }
+    }
+
+    /// Flushes the encoder, returning everything it produced since the last flush.
+    fn flush(&mut self) -> std::io::Result<Bytes> {
+        match self {
+            Encoder::Gzip(encoder) => encoder.flush()?,
+            Encoder::Brotli(encoder) => encoder.flush()?,
+            Encoder::Zstd(encoder) => encoder.flush()?,
+        }
+
+        Ok(self.take_output())
+    }
+
+    fn output_len(&self) -> usize {
+        match self {
+            Encoder::Gzip(encoder) => encoder.get_ref().len(),
+            Encoder::Brotli(encoder) => encoder.get_ref().len(),
+            Encoder::Zstd(encoder) => encoder.get_ref().len(),
+        }
+    }
+
+    fn take_output(&mut self) -> Bytes {
+        let output = match self {
+            Encoder::Gzip(encoder) => encoder.get_mut(),
+            Encoder::Brotli(encoder) => encoder.get_mut(),
+            Encoder::Zstd(encoder) => encoder.get_mut(),
+        };
+
+        Bytes::from(std::mem::take(output))
+    }
+
+    fn finish(self) -> std::io::Result<Bytes> {
+        let output = match self {
+            Encoder::Gzip(encoder) => encoder.finish()?,
+            Encoder::Brotli(encoder) => encoder.into_inner(),
+            Encoder::Zstd(encoder) => encoder.finish()?,
+        };
+
+        Ok(Bytes::from(output))
+    }
+}
+
+/// Compresses a streamed body, flushing the encoder as soon as the inner body has nothing more
+/// to send for now. Events and parts sent back to back end up in the same compressed chunk.
+struct StreamEncoder {
+    inner: Body,
+    /// Taken once the inner body is done.
+    encoder: Option<Encoder>,
+    /// Whether data was written to the encoder since the last flush.
+    unflushed: bool,
+}
+
+impl http_body::Body for StreamEncoder {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        let this = &mut *self;
+
+        loop {
+            let Some(encoder) = this.encoder.as_mut() else {
+                return Poll::Ready(None);
+            };
+
+            match Pin::new(&mut this.inner).poll_frame(cx) {
+                Poll::Ready(Some(Ok(frame))) => {
+                    // Streamed GraphQL responses never have trailers.
+                    let Ok(data) = frame.into_data() else {
+                        continue;
+                    };
+
+                    if let Err(err) = encoder.write(&data) {
+                        this.encoder = None;
+                        return Poll::Ready(Some(Err(axum::Error::new(err))));
+                    }
+
+                    this.unflushed = true;
+
+                    // Bounds the memory used by bodies that are never pending.
+                    if encoder.output_len() >= MAX_OUTPUT_CHUNK_SIZE {
+                        return Poll::Ready(Some(Ok(Frame::data(encoder.take_output()))));
+                    }
+                }
+                Poll::Ready(Some(Err(err))) => {
+                    this.encoder = None;
+                    return Poll::Ready(Some(Err(err)));
+                }
+                Poll::Ready(None) => {
+                    let encoder = this.encoder.take().expect("checked above");
+                    return Poll::Ready(Some(encoder.finish().map(Frame::data).map_err(axum::Error::new)));
+                }
+                Poll::Pending if this.unflushed => {
+                    this.unflushed = false;
+                    return Poll::Ready(Some(encoder.flush().map(Frame::data).map_err(axum::Error::new)));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.encoder.is_none()
+    }
+}
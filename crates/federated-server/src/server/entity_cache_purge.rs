@@ -0,0 +1,123 @@
+use std::net::SocketAddr;
+
+use engine::Runtime;
+use gateway_config::{EntityCachePurgeConfig, TlsConfig};
+use runtime::entity_cache::EntityCacheTag;
+
+use super::{state::ServerState, ServerRuntime};
+use axum::{extract::State, routing::post, Json, Router};
+use http::StatusCode;
+
+/// Selects the entity cache entries to purge. Entries matching any of the provided
+/// selectors are removed.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PurgeRequest {
+    /// Every entry fetched from this subgraph.
+    subgraph: Option<String>,
+    /// Every entry of this entity type, or a single one if `key` is provided.
+    #[serde(rename = "type")]
+    typename: Option<String>,
+    /// Key fields of the entity to purge, such as `{"upc": "1"}`.
+    key: Option<serde_json::Value>,
+}
+
+impl PurgeRequest {
+    fn tags(&self) -> Result<Vec<String>, &'static str> {
+        let mut tags = Vec::new();
+
+        if let Some(subgraph) = &self.subgraph {
+            tags.push(EntityCacheTag::Subgraph(subgraph).to_string());
+        }
+
+        match (&self.typename, &self.key) {
+            (Some(typename), Some(key)) => tags.push(EntityCacheTag::Entity { typename, key }.to_string()),
+            (Some(typename), None) => tags.push(EntityCacheTag::Type(typename).to_string()),
+            (None, Some(_)) => return Err("a key requires a type"),
+            (None, None) => (),
+        }
+
+        if tags.is_empty() {
+            return Err("expected a subgraph, a type or a type with a key");
+        }
+
+        Ok(tags)
+    }
+}
+
+/// Purges entity cache entries of the current engine, along with the cached responses
+/// containing them.
+///
+/// # Arguments
+///
+/// - `State(state)`: The server state containing the gateway information.
+/// - `Json(request)`: The selectors of the entries to purge.
+///
+/// # Returns
+///
+/// `204 No Content` once the entries are purged.
+async fn purge<SR>(State(state): State<ServerState<SR>>, Json(request): Json<PurgeRequest>) -> (StatusCode, String) {
+    let tags = match request.tags() {
+        Ok(tags) => tags,
+        Err(message) => return (StatusCode::BAD_REQUEST, message.to_string()),
+    };
+
+    let engine = state.gateway.borrow().clone();
+
+    let result = match engine.runtime.entity_cache().purge(&tags).await {
+        Ok(()) => engine.runtime.response_cache().purge(&tags).await,
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(()) => (StatusCode::NO_CONTENT, String::new()),
+        Err(err) => {
+            tracing::error!("Failed to purge the entity cache: {err}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+/// Binds the entity cache purge endpoint to a separate address.
+///
+/// # Arguments
+///
+/// - `addr`: The socket address to bind the server to.
+/// - `tls_config`: Optional TLS configuration for secure connections.
+/// - `purge_config`: Configuration of the purge endpoint.
+/// - `state`: The current state of the server.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure of binding the endpoint.
+pub(super) async fn bind_entity_cache_purge_endpoint<SR: ServerRuntime>(
+    addr: SocketAddr,
+    tls_config: Option<TlsConfig>,
+    purge_config: EntityCachePurgeConfig,
+    state: ServerState<SR>,
+) -> crate::Result<()> {
+    let scheme = if tls_config.is_some() { "https" } else { "http" };
+    let path = &purge_config.path;
+    let app = Router::new()
+        .route(path, post(purge))
+        .with_state(state)
+        .into_make_service();
+
+    tracing::info!("Entity cache purge endpoint exposed at {scheme}://{addr}{path}");
+
+    match tls_config {
+        Some(tls) => {
+            let rustls_config = axum_server::tls_rustls::RustlsConfig::from_pem_file(&tls.certificate, &tls.key)
+                .await
+                .map_err(crate::Error::CertificateError)?;
+
+            axum_server::bind_rustls(addr, rustls_config)
+                .serve(app)
+                .await
+                .map_err(crate::Error::Server)?;
+        }
+        None => axum_server::bind(addr).serve(app).await.map_err(crate::Error::Server)?,
+    }
+
+    Ok(())
+}
@@ -94,10 +94,12 @@ pub(super) async fn generate(
         runtime.trusted_documents = trusted_documents;
     }
 
-    if let Some(extensions) = create_wasi_extension_configs(&extension_catalog, gateway_config, &schema) {
-        runtime.extensions = WasiExtensions::new(access_log, extensions)
+    if let Some(extensions) = create_wasi_extension_configs(&extension_catalog, gateway_config, &schema)? {
+        runtime.extensions = WasiExtensions::new(access_log, runtime.purgeable_caches(), extensions)
             .await
             .map_err(|e| Error::InternalError(e.to_string()))?;
+
+        runtime.hooks = runtime.hooks.clone().with_extensions(runtime.extensions.clone());
     }
 
     Ok(Engine::new(Arc::new(schema), runtime).await)
@@ -107,10 +109,12 @@ fn create_wasi_extension_configs(
     extension_catalog: &ExtensionCatalog,
     gateway_config: &Config,
     schema: &engine::Schema,
-) -> Option<Vec<ExtensionConfig>> {
+) -> crate::Result<Option<Vec<ExtensionConfig>>> {
     let mut wasi_extensions: Vec<ExtensionConfig> = Vec::with_capacity(extension_catalog.len());
 
-    let extension_configs = gateway_config.extensions.as_ref()?;
+    let Some(extension_configs) = gateway_config.extensions.as_ref() else {
+        return Ok(None);
+    };
 
     for (id, extension) in extension_catalog.iter().enumerate() {
         let extension_config = extension_configs
@@ -120,6 +124,8 @@ fn create_wasi_extension_configs(
         let extension_type = match &extension.manifest.kind {
             extension_catalog::Kind::FieldResolver(_) => ExtensionType::Resolver,
             extension_catalog::Kind::Authenticator(_) => ExtensionType::Authentication,
+            extension_catalog::Kind::Authorizer(_) => ExtensionType::Authorization,
+            extension_catalog::Kind::Hooks(_) => ExtensionType::Hooks,
         };
 
         let wasi_config = WasiExtensionsConfig {
@@ -128,11 +134,13 @@ fn create_wasi_extension_configs(
             stdout: extension_config.stdout(),
             stderr: extension_config.stderr(),
             environment_variables: extension_config.environment_variables(),
+            limits: extension_config.limits(),
         };
 
         let name = extension.manifest.name().to_owned();
         let version = extension.manifest.version().to_owned();
         let max_pool_size = extension_config.max_pool_size();
+        let order = extension_config.order();
         let id = ExtensionId::from(id);
 
         match extension_type {
@@ -148,6 +156,37 @@ fn create_wasi_extension_configs(
                     max_pool_size,
                     wasi_config,
                     extension_config: Vec::new(),
+                    order,
+                });
+            }
+            ExtensionType::Hooks => {
+                let id = ExtensionPoolId::Hooks(id);
+
+                wasi_extensions.push(ExtensionConfig {
+                    id,
+                    name,
+                    version,
+                    extension_type,
+                    schema_directives: Vec::new(),
+                    max_pool_size,
+                    wasi_config,
+                    extension_config: Vec::new(),
+                    order,
+                });
+            }
+            ExtensionType::Authorization => {
+                let id = ExtensionPoolId::Authorization(id);
+
+                wasi_extensions.push(ExtensionConfig {
+                    id,
+                    name,
+                    version,
+                    extension_type,
+                    schema_directives: Vec::new(),
+                    max_pool_size,
+                    wasi_config,
+                    extension_config: Vec::new(),
+                    order,
                 });
             }
             ExtensionType::Authentication => {
@@ -164,6 +203,12 @@ fn create_wasi_extension_configs(
                         continue;
                     }
 
+                    validate_extension_config(
+                        &extension.manifest,
+                        extension_provider.config.as_ref(),
+                        &format!("authentication.providers[{auth_id}].extension.config"),
+                    )?;
+
                     let extension_config = match extension_provider.config {
                         Some(ref config) => minicbor_serde::to_vec(config).unwrap(),
                         None => Vec::new(),
@@ -180,6 +225,7 @@ fn create_wasi_extension_configs(
                         max_pool_size,
                         wasi_config: wasi_config.clone(),
                         extension_config,
+                        order,
                     });
                 }
             }
@@ -200,7 +246,31 @@ fn create_wasi_extension_configs(
         }
     }
 
-    wasi_extensions.is_empty().not().then_some(wasi_extensions)
+    Ok(wasi_extensions.is_empty().not().then_some(wasi_extensions))
+}
+
+/// Validates the configuration against the schema declared by the extension, reporting every
+/// error with its location.
+fn validate_extension_config(manifest: &Manifest, config: Option<&toml::Value>, location: &str) -> crate::Result<()> {
+    let config = match config {
+        Some(config) => serde_json::to_value(config).map_err(|e| Error::InternalError(e.to_string()))?,
+        None => serde_json::Value::Object(Default::default()),
+    };
+
+    let Err(errors) = manifest.validate_config(&config) else {
+        return Ok(());
+    };
+
+    let errors = errors
+        .iter()
+        .map(|error| error.to_string())
+        .collect::<Vec<_>>()
+        .join("\n  - ");
+
+    Err(Error::ExtensionConfigError(format!(
+        "invalid configuration of the extension {} in {location}:\n  - {errors}",
+        manifest.name()
+    )))
 }
 
 // TODO: with lock file this will be smarter...
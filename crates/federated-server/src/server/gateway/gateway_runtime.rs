@@ -1,7 +1,8 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{borrow::Cow, path::PathBuf, sync::Arc, time::Duration};
 
 use engine::CachedOperation;
-use gateway_config::{Config, EntityCachingRedisConfig};
+use futures_util::{future::BoxFuture, FutureExt};
+use gateway_config::{Config, EntityCachingRedisConfig, EntityCachingStorage};
 use grafbase_telemetry::metrics::EngineMetrics;
 use runtime::entity_cache::EntityCache;
 use runtime_local::{
@@ -22,10 +23,11 @@ pub struct GatewayRuntime {
     pub(super) trusted_documents: runtime::trusted_documents_client::Client,
     kv: runtime::kv::KvStore,
     metrics: EngineMetrics,
-    hooks: HooksWasi,
+    pub(super) hooks: HooksWasi,
     pub(crate) extensions: WasiExtensions,
     rate_limiter: runtime::rate_limiting::RateLimiter,
-    entity_cache: Box<dyn EntityCache>,
+    entity_cache: Arc<dyn EntityCache>,
+    response_cache: Arc<dyn EntityCache>,
     pub(crate) operation_cache: TieredOperationCache<Arc<CachedOperation>>,
 }
 
@@ -64,24 +66,17 @@ impl GatewayRuntime {
             _ => InMemoryRateLimiter::runtime_with_watcher(watcher),
         };
 
-        let entity_cache: Box<dyn EntityCache> = match gateway_config.entity_caching.storage {
-            gateway_config::EntityCachingStorage::Memory => Box::new(InMemoryEntityCache::default()),
-            gateway_config::EntityCachingStorage::Redis => {
-                let EntityCachingRedisConfig { url, key_prefix, tls } = &gateway_config.entity_caching.redis;
+        let response_cache = entity_cache(
+            &gateway_config.response_caching.storage,
+            &gateway_config.response_caching.redis,
+            &mut redis_factory,
+        )?;
 
-                let tls = tls.as_ref().map(|tls| RedisTlsConfig {
-                    cert: tls.cert.as_deref(),
-                    key: tls.key.as_deref(),
-                    ca: tls.ca.as_deref(),
-                });
-
-                let pool = redis_factory
-                    .pool(url.as_str(), tls)
-                    .map_err(|e| crate::Error::InternalError(e.to_string()))?;
-
-                Box::new(RedisEntityCache::new(pool, key_prefix))
-            }
-        };
+        let entity_cache = entity_cache(
+            &gateway_config.entity_caching.storage,
+            &gateway_config.entity_caching.redis,
+            &mut redis_factory,
+        )?;
 
         let operation_cache = operation_cache(gateway_config, &mut redis_factory)?;
 
@@ -93,7 +88,8 @@ impl GatewayRuntime {
             extensions,
             metrics: EngineMetrics::build(&meter, version_id.map(|id| id.to_string())),
             rate_limiter,
-            entity_cache,
+            entity_cache: entity_cache.into(),
+            response_cache: response_cache.into(),
             operation_cache,
         };
 
@@ -101,6 +97,47 @@ impl GatewayRuntime {
     }
 }
 
+impl GatewayRuntime {
+    /// The caches purged by extensions. Purging entities also purges the cached responses
+    /// containing them.
+    pub(super) fn purgeable_caches(&self) -> Arc<dyn EntityCache> {
+        Arc::new(PurgeableCaches {
+            entity_cache: self.entity_cache.clone(),
+            response_cache: self.response_cache.clone(),
+        })
+    }
+}
+
+/// The entity cache, whose purges also apply to the response cache.
+struct PurgeableCaches {
+    entity_cache: Arc<dyn EntityCache>,
+    response_cache: Arc<dyn EntityCache>,
+}
+
+impl EntityCache for PurgeableCaches {
+    fn get<'a>(&'a self, name: &'a str) -> BoxFuture<'a, anyhow::Result<Option<Vec<u8>>>> {
+        self.entity_cache.get(name)
+    }
+
+    fn put<'a>(
+        &'a self,
+        name: &'a str,
+        bytes: Cow<'a, [u8]>,
+        expiration_ttl: Duration,
+        tags: &'a [String],
+    ) -> BoxFuture<'a, anyhow::Result<()>> {
+        self.entity_cache.put(name, bytes, expiration_ttl, tags)
+    }
+
+    fn purge<'a>(&'a self, tags: &'a [String]) -> BoxFuture<'a, anyhow::Result<()>> {
+        async move {
+            self.entity_cache.purge(tags).await?;
+            self.response_cache.purge(tags).await
+        }
+        .boxed()
+    }
+}
+
 impl engine::Runtime for GatewayRuntime {
     type Hooks = HooksWasi;
     type Fetcher = NativeFetcher;
@@ -139,6 +176,10 @@ impl engine::Runtime for GatewayRuntime {
         self.entity_cache.as_ref()
     }
 
+    fn response_cache(&self) -> &dyn EntityCache {
+        self.response_cache.as_ref()
+    }
+
     fn metrics(&self) -> &grafbase_telemetry::metrics::EngineMetrics {
         &self.metrics
     }
@@ -148,6 +189,31 @@ impl engine::Runtime for GatewayRuntime {
     }
 }
 
+fn entity_cache(
+    storage: &EntityCachingStorage,
+    redis_config: &EntityCachingRedisConfig,
+    redis_factory: &mut RedisPoolFactory,
+) -> Result<Box<dyn EntityCache>, crate::Error> {
+    Ok(match storage {
+        EntityCachingStorage::Memory => Box::new(InMemoryEntityCache::default()),
+        EntityCachingStorage::Redis => {
+            let EntityCachingRedisConfig { url, key_prefix, tls } = redis_config;
+
+            let tls = tls.as_ref().map(|tls| RedisTlsConfig {
+                cert: tls.cert.as_deref(),
+                key: tls.key.as_deref(),
+                ca: tls.ca.as_deref(),
+            });
+
+            let pool = redis_factory
+                .pool(url.as_str(), tls)
+                .map_err(|e| crate::Error::InternalError(e.to_string()))?;
+
+            Box::new(RedisEntityCache::new(pool, key_prefix))
+        }
+    })
+}
+
 fn operation_cache(
     gateway_config: &Config,
     redis_factory: &mut RedisPoolFactory,
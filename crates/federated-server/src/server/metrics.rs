@@ -0,0 +1,65 @@
+use std::net::SocketAddr;
+
+use gateway_config::{PrometheusExporterConfig, TlsConfig};
+
+use axum::{response::IntoResponse, routing::get, Router};
+use http::{header, StatusCode};
+
+/// Serves the gateway metrics in the Prometheus text format.
+pub(crate) async fn metrics() -> impl IntoResponse {
+    match grafbase_telemetry::metrics::encode_global_prometheus_registry() {
+        Some(Ok(body)) => (
+            StatusCode::OK,
+            [(
+                header::CONTENT_TYPE,
+                grafbase_telemetry::metrics::PROMETHEUS_CONTENT_TYPE,
+            )],
+            body,
+        )
+            .into_response(),
+        Some(Err(err)) => {
+            tracing::error!("Failed to encode Prometheus metrics: {err}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Binds the Prometheus metrics endpoint to a separate address.
+///
+/// # Arguments
+///
+/// - `addr`: The socket address to bind the server to.
+/// - `tls_config`: Optional TLS configuration for secure connections.
+/// - `prometheus_config`: Configuration of the Prometheus exporter.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure of binding the endpoint.
+pub(super) async fn bind_metrics_endpoint(
+    addr: SocketAddr,
+    tls_config: Option<TlsConfig>,
+    prometheus_config: PrometheusExporterConfig,
+) -> crate::Result<()> {
+    let scheme = if tls_config.is_some() { "https" } else { "http" };
+    let path = &prometheus_config.path;
+    let app = Router::new().route(path, get(metrics)).into_make_service();
+
+    tracing::info!("Prometheus metrics endpoint exposed at {scheme}://{addr}{path}");
+
+    match tls_config {
+        Some(tls) => {
+            let rustls_config = axum_server::tls_rustls::RustlsConfig::from_pem_file(&tls.certificate, &tls.key)
+                .await
+                .map_err(crate::Error::CertificateError)?;
+
+            axum_server::bind_rustls(addr, rustls_config)
+                .serve(app)
+                .await
+                .map_err(crate::Error::Server)?;
+        }
+        None => axum_server::bind(addr).serve(app).await.map_err(crate::Error::Server)?,
+    }
+
+    Ok(())
+}
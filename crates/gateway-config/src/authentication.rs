@@ -1,7 +1,8 @@
 use ascii::AsciiString;
 use duration_str::deserialize_duration;
 use serde::Deserialize;
-use std::time::Duration;
+use serde_dynamic_string::DynamicString;
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
 use url::Url;
 
 /// Configures the GraphQL server JWT authentication
@@ -15,6 +16,7 @@ pub struct AuthenticationConfig {
 #[serde(rename_all = "snake_case")]
 pub enum AuthenticationProvider {
     Jwt(Box<JwtProvider>),
+    Introspection(Box<IntrospectionProvider>),
     Extension(Box<ExtensionProvider>),
     Anonymous,
 }
@@ -37,26 +39,187 @@ pub struct JwtProvider {
     /// The header from which to look for the token
     #[serde(default)]
     pub header: AuthenticationHeader,
+    /// The cookie from which to look for the token if it isn't in the header
+    pub cookie: Option<String>,
+    /// The query parameter from which to look for the token if it isn't in the header or the cookie
+    pub query_parameter: Option<String>,
+    /// The accepted signing algorithms. All supported algorithms are accepted if empty.
+    #[serde(default)]
+    pub algorithms: Vec<JwtAlgorithm>,
+    /// Tolerated clock skew when validating the `exp` and `nbf` claims, at most one hour. Default: 60 seconds
+    #[serde(default = "default_leeway", deserialize_with = "deserialize_leeway")]
+    pub leeway: Duration,
+    /// Claims the token must have, with their expected value. For an array claim, the token is
+    /// accepted if the array contains the expected value.
+    #[serde(default)]
+    pub required_claims: BTreeMap<String, toml::Value>,
 }
 
+fn default_leeway() -> Duration {
+    Duration::from_secs(60)
+}
+
+const MAX_LEEWAY: Duration = Duration::from_secs(60 * 60);
+
+fn deserialize_leeway<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let leeway = deserialize_duration(deserializer)?;
+
+    if leeway > MAX_LEEWAY {
+        return Err(serde::de::Error::custom("JWT leeway must be at most one hour"));
+    }
+
+    Ok(leeway)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub enum JwtAlgorithm {
+    HS256,
+    HS384,
+    HS512,
+    ES256,
+    RS256,
+    RS384,
+    RS512,
+    PS256,
+    PS384,
+    PS512,
+    EdDSA,
+}
+
+impl JwtAlgorithm {
+    /// The name of the algorithm in the `alg` header of a token.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JwtAlgorithm::HS256 => "HS256",
+            JwtAlgorithm::HS384 => "HS384",
+            JwtAlgorithm::HS512 => "HS512",
+            JwtAlgorithm::ES256 => "ES256",
+            JwtAlgorithm::RS256 => "RS256",
+            JwtAlgorithm::RS384 => "RS384",
+            JwtAlgorithm::RS512 => "RS512",
+            JwtAlgorithm::PS256 => "PS256",
+            JwtAlgorithm::PS384 => "PS384",
+            JwtAlgorithm::PS512 => "PS512",
+            JwtAlgorithm::EdDSA => "EdDSA",
+        }
+    }
+}
+
+/// Where the keys verifying the token signatures come from. Exactly one of `url`, `path`, `keys`
+/// or `secret` must be defined.
 #[derive(Debug, PartialEq, serde::Deserialize, Clone)]
+#[serde(try_from = "RawJwksConfig")]
 pub struct JwksConfig {
     /// The well-known URL of the JWKS
-    pub url: Url,
+    pub url: Option<Url>,
+    /// A local JWKS file, reloaded when it changes
+    pub path: Option<PathBuf>,
+    /// Inline JSON Web Keys
+    pub keys: Vec<toml::Value>,
+    /// A shared secret for tokens signed with HMAC
+    pub secret: Option<DynamicString<String>>,
     /// The issuer URL
     pub issuer: Option<String>,
     /// The name of the audience, e.g. the project
-    #[serde(deserialize_with = "deserialize_string_or_vec", default)]
     pub audience: Vec<String>,
-    /// How often to poll changes to the configuration
-    #[serde(default = "default_poll_interval", deserialize_with = "deserialize_duration")]
+    /// How often to poll changes to the remote JWKS or the local file
     pub poll_interval: Duration,
 }
 
+#[derive(serde::Deserialize)]
+struct RawJwksConfig {
+    url: Option<Url>,
+    path: Option<PathBuf>,
+    #[serde(default)]
+    keys: Vec<toml::Value>,
+    secret: Option<DynamicString<String>>,
+    issuer: Option<String>,
+    #[serde(deserialize_with = "deserialize_string_or_vec", default)]
+    audience: Vec<String>,
+    #[serde(default = "default_poll_interval", deserialize_with = "deserialize_duration")]
+    poll_interval: Duration,
+}
+
+impl TryFrom<RawJwksConfig> for JwksConfig {
+    type Error = &'static str;
+
+    fn try_from(raw: RawJwksConfig) -> Result<Self, Self::Error> {
+        let sources = [
+            raw.url.is_some(),
+            raw.path.is_some(),
+            !raw.keys.is_empty(),
+            raw.secret.is_some(),
+        ];
+
+        if sources.into_iter().filter(|defined| *defined).count() != 1 {
+            return Err("exactly one of `url`, `path`, `keys` or `secret` must be defined");
+        }
+
+        Ok(Self {
+            url: raw.url,
+            path: raw.path,
+            keys: raw.keys,
+            secret: raw.secret,
+            issuer: raw.issuer,
+            audience: raw.audience,
+            poll_interval: raw.poll_interval,
+        })
+    }
+}
+
 fn default_poll_interval() -> Duration {
     Duration::from_secs(60)
 }
 
+/// Validates opaque tokens with an OAuth2 token introspection endpoint (RFC 7662).
+#[derive(Debug, PartialEq, serde::Deserialize, Clone)]
+pub struct IntrospectionProvider {
+    /// A name of the provider, used for log/error messages
+    pub name: Option<String>,
+    /// The URL of the introspection endpoint
+    pub url: Url,
+    /// The client ID to authenticate with the introspection endpoint
+    pub client_id: Option<String>,
+    /// The client secret to authenticate with the introspection endpoint
+    pub client_secret: Option<DynamicString<String>>,
+    /// The header from which to look for the token
+    #[serde(default)]
+    pub header: AuthenticationHeader,
+    /// How long an active token is cached at most. It is never cached past its expiry.
+    /// Default: 5 minutes
+    #[serde(
+        default = "default_introspection_cache_ttl",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub cache_ttl: Duration,
+    /// How long an inactive token is cached, sparing the endpoint from the same invalid tokens.
+    /// Default: 10 seconds
+    #[serde(
+        default = "default_introspection_inactive_cache_ttl",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub inactive_cache_ttl: Duration,
+    /// How long to wait for the introspection endpoint before rejecting the token.
+    /// Default: 5 seconds
+    #[serde(default = "default_introspection_timeout", deserialize_with = "deserialize_duration")]
+    pub timeout: Duration,
+}
+
+fn default_introspection_cache_ttl() -> Duration {
+    Duration::from_secs(300)
+}
+
+fn default_introspection_inactive_cache_ttl() -> Duration {
+    Duration::from_secs(10)
+}
+
+fn default_introspection_timeout() -> Duration {
+    Duration::from_secs(5)
+}
+
 #[derive(Debug, PartialEq, serde::Deserialize, Clone)]
 pub struct AuthenticationHeader {
     /// The name of the header the token is sent from
@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+/// Stops sending requests to a subgraph for a while once too many of them fail or are too slow.
+#[derive(Debug, serde::Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CircuitBreakerConfig {
+    /// Whether the circuit breaker is enabled.
+    pub enabled: bool,
+    /// Fraction of failed requests within the window above which the circuit opens. Defaults to 0.5.
+    pub failure_rate: Option<f32>,
+    /// Minimum number of requests within the window before the failure rate is considered. Defaults to 20.
+    pub min_requests: Option<u32>,
+    /// Duration of the window over which requests are counted. Defaults to 10s.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub window: Option<Duration>,
+    /// Requests taking longer than this are counted as failures.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub slow_request_threshold: Option<Duration>,
+    /// How long the circuit stays open before letting trial requests through. Defaults to 30s.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub open_duration: Option<Duration>,
+    /// Number of successful trial requests needed to close the circuit again. Defaults to 1.
+    pub half_open_requests: Option<u32>,
+}
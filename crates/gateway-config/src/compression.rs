@@ -0,0 +1,43 @@
+use serde::{de::Error, Deserializer};
+use size::Size;
+
+use crate::size_ext;
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CompressionConfig {
+    /// Compress the responses for clients accepting it with `Accept-Encoding`.
+    pub enabled: bool,
+    /// Responses smaller than this are sent uncompressed, must be under 64KiB. Streamed responses
+    /// are always compressed, each event or part being sent as soon as it's ready. Default: 1KiB.
+    #[serde(deserialize_with = "deserialize_min_size")]
+    pub min_size: Size,
+    pub gzip: bool,
+    pub brotli: bool,
+    pub zstd: bool,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_size: Size::from_kibibytes(1),
+            gzip: true,
+            brotli: true,
+            zstd: true,
+        }
+    }
+}
+
+fn deserialize_min_size<'de, D>(deserializer: D) -> Result<Size, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let size = size_ext::deserialize_positive_size(deserializer)?;
+
+    if u16::try_from(size.bytes()).is_err() {
+        return Err(Error::custom("compression min_size must be under 64KiB"));
+    }
+
+    Ok(size)
+}
@@ -1,4 +1,4 @@
-use std::{path::PathBuf, time::Duration};
+use std::{borrow::Cow, net::SocketAddr, path::PathBuf, time::Duration};
 
 const DEFAULT_ENTITY_CACHE_TTL: Duration = Duration::from_secs(60);
 
@@ -22,6 +22,14 @@ pub struct EntityCachingConfig {
     /// The ttl to store cache entries with.  Defaults to 60s
     #[serde(deserialize_with = "duration_str::deserialize_duration")]
     pub ttl: Duration,
+
+    /// Admin endpoint to purge cache entries before they expire.
+    pub purge: EntityCachePurgeConfig,
+
+    /// Purge the entities returned by a mutation from the cache, along with the cached
+    /// responses containing them. Only entities with all the fields of one of their keys in
+    /// the mutation response can be purged.
+    pub invalidate_on_mutation: bool,
 }
 
 impl Default for EntityCachingConfig {
@@ -31,6 +39,28 @@ impl Default for EntityCachingConfig {
             storage: Default::default(),
             redis: Default::default(),
             ttl: DEFAULT_ENTITY_CACHE_TTL,
+            purge: Default::default(),
+            invalidate_on_mutation: false,
+        }
+    }
+}
+
+/// Entity cache purge endpoint configuration. The endpoint is not authenticated, so it
+/// is only exposed on a separate, internal address and `listen` is required when enabled.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EntityCachePurgeConfig {
+    pub enabled: bool,
+    pub listen: Option<SocketAddr>,
+    pub path: Cow<'static, str>,
+}
+
+impl Default for EntityCachePurgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: None,
+            path: Cow::Borrowed("/admin/entity-cache/purge"),
         }
     }
 }
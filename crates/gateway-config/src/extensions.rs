@@ -2,6 +2,8 @@ use std::path::{Path, PathBuf};
 
 use semver::VersionReq;
 
+use crate::WasiLimitsConfig;
+
 #[derive(PartialEq, serde::Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum ExtensionsConfig {
@@ -19,6 +21,10 @@ pub struct StructuredExtensionsConfig {
     pub stderr: bool,
     pub environment_variables: bool,
     pub max_pool_size: Option<usize>,
+    pub limits: WasiLimitsConfig,
+    /// Position of a hooks extension in the hooks chain. Lower values are called first, and
+    /// extensions without one come last. Ties are broken by the extension name.
+    pub order: Option<u16>,
 }
 
 impl Default for StructuredExtensionsConfig {
@@ -31,6 +37,8 @@ impl Default for StructuredExtensionsConfig {
             stderr: false,
             environment_variables: false,
             max_pool_size: None,
+            limits: WasiLimitsConfig::default(),
+            order: None,
         }
     }
 }
@@ -42,6 +50,7 @@ pub struct WasiExtensionsConfig {
     pub stdout: bool,
     pub stderr: bool,
     pub environment_variables: bool,
+    pub limits: WasiLimitsConfig,
 }
 
 impl ExtensionsConfig {
@@ -87,6 +96,20 @@ impl ExtensionsConfig {
         }
     }
 
+    pub fn limits(&self) -> WasiLimitsConfig {
+        match self {
+            ExtensionsConfig::Version(_) => WasiLimitsConfig::default(),
+            ExtensionsConfig::Structured(config) => config.limits.clone(),
+        }
+    }
+
+    pub fn order(&self) -> Option<u16> {
+        match self {
+            ExtensionsConfig::Version(_) => None,
+            ExtensionsConfig::Structured(config) => config.order,
+        }
+    }
+
     pub fn path(&self) -> Option<&Path> {
         match self {
             ExtensionsConfig::Version(_) => None,
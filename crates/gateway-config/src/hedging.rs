@@ -0,0 +1,20 @@
+use std::time::Duration;
+
+/// Sends a second request to a subgraph if the first one takes longer than usual, using the
+/// first successful response. Only applied to queries.
+///
+/// Hedged requests are withdrawn from the retry budget if retries are enabled. Otherwise nothing
+/// limits how many are sent beyond the configured percentile, so enabling retries is recommended
+/// to keep the additional load on the subgraph bounded.
+#[derive(Debug, serde::Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct HedgingConfig {
+    /// Whether hedging is enabled.
+    pub enabled: bool,
+    /// Latency percentile of the recent requests, between 0 and 1, after which the hedged request
+    /// is sent. Defaults to 0.95.
+    pub percentile: Option<f32>,
+    /// Minimum delay before sending the hedged request.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub min_delay: Option<Duration>,
+}
@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use crate::WasiLimitsConfig;
+
 /// Configuration for the GraphQL WASI component hooks.
 #[derive(Clone, Default, Debug, serde::Deserialize)]
 #[serde(default, deny_unknown_fields)]
@@ -18,6 +20,8 @@ pub struct HooksWasiConfig {
     pub preopened_directories: Vec<PreopenedDirectory>,
     /// The maximum number of concurrent instances of the WASI component. Defaults to four times the number of CPUs.
     pub max_pool_size: Option<usize>,
+    /// Resource limits of each instance of the WASI component.
+    pub limits: WasiLimitsConfig,
 }
 
 /// Configuration for a directory that is preopened for the WASI component.
@@ -3,21 +3,28 @@ use operation_caching::OperationCaching;
 
 pub mod apq;
 pub mod authentication;
+mod circuit_breaker;
 mod complexity_control;
+mod compression;
 pub mod cors;
 pub mod entity_caching;
 pub mod extensions;
 pub mod header;
 pub mod health;
+mod hedging;
 pub mod hooks;
 mod log_level;
 pub mod message_signatures;
 pub mod operation_caching;
 pub mod rate_limit;
+mod response_caching;
 mod size_ext;
+mod subgraph_client;
+mod subgraph_tls;
 mod subscription_protocol;
 pub mod telemetry;
 mod trusted_documents;
+mod wasi_limits;
 mod websockets_config;
 
 use std::{collections::BTreeMap, net::SocketAddr, path::PathBuf, time::Duration};
@@ -27,19 +34,26 @@ pub use self::{
     websockets_config::WebsocketsConfig,
 };
 pub use authentication::*;
+pub use circuit_breaker::*;
 pub use complexity_control::*;
+pub use compression::*;
 pub use cors::*;
 pub use entity_caching::*;
 pub use extensions::*;
 pub use header::*;
 pub use health::*;
+pub use hedging::*;
 pub use hooks::*;
 pub use message_signatures::MessageSignaturesConfig;
 pub use rate_limit::*;
+pub use response_caching::*;
 use serde_dynamic_string::DynamicString;
 use size::Size;
+pub use subgraph_client::*;
+pub use subgraph_tls::*;
 pub use telemetry::*;
 use url::Url;
+pub use wasi_limits::*;
 
 const DEFAULT_GATEWAY_TIMEOUT: Duration = Duration::from_secs(30);
 const DEFAULT_SUBGRAPH_TIMEOUT: Duration = Duration::from_secs(30);
@@ -65,6 +79,8 @@ pub struct Config {
     pub csrf: CsrfConfig,
     /// Cross-origin resource sharing settings
     pub cors: Option<CorsConfig>,
+    /// Response compression settings
+    pub compression: CompressionConfig,
     /// Server TLS settings
     pub tls: Option<TlsConfig>,
     /// Graph operation limit settings
@@ -87,6 +103,8 @@ pub struct Config {
     pub health: HealthConfig,
     /// Global configuration for entity caching
     pub entity_caching: EntityCachingConfig,
+    /// Global configuration for whole operation response caching
+    pub response_caching: ResponseCachingConfig,
     /// Configuration for complexity control
     pub complexity_control: ComplexityControlConfig,
     /// Automatic persisted queries' configuration
@@ -107,6 +125,7 @@ impl Default for Config {
             executable_document_limit: Size::from_kibibytes(32),
             csrf: Default::default(),
             cors: Default::default(),
+            compression: Default::default(),
             tls: Default::default(),
             operation_limits: Default::default(),
             telemetry: Default::default(),
@@ -117,6 +136,7 @@ impl Default for Config {
             hooks: Default::default(),
             health: Default::default(),
             entity_caching: Default::default(),
+            response_caching: Default::default(),
             complexity_control: Default::default(),
             apq: Default::default(),
             operation_caching: Default::default(),
@@ -135,6 +155,13 @@ pub struct BatchingConfig {
     pub limit: Option<u8>,
 }
 
+#[derive(Clone, Copy, Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QueryDeduplicationConfig {
+    /// If identical subgraph queries in flight at the same time should share a single request.
+    pub enabled: bool,
+}
+
 #[derive(Clone, Debug, serde::Deserialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct GatewayConfig {
@@ -148,12 +175,21 @@ pub struct GatewayConfig {
     pub rate_limit: Option<RateLimitConfig>,
     /// Global retry configuration
     pub retry: RetryConfig,
+    /// Global circuit breaker configuration
+    pub circuit_breaker: CircuitBreakerConfig,
     /// Access logs configuration
     pub access_logs: AccessLogsConfig,
     /// Query batching configuration
     pub batching: BatchingConfig,
+    /// Subgraph query deduplication configuration
+    pub query_deduplication: QueryDeduplicationConfig,
     /// Global message signatures config
     pub message_signatures: MessageSignaturesConfig,
+    /// TLS settings for the connections to all subgraphs without their own `tls` settings.
+    pub subgraph_tls: Option<SubgraphTlsConfig>,
+    /// HTTP client settings for the connections to all subgraphs, each subgraph can override them
+    /// one by one in its own `client` settings.
+    pub subgraph_client: Option<SubgraphClientConfig>,
 }
 
 impl Default for GatewayConfig {
@@ -163,9 +199,13 @@ impl Default for GatewayConfig {
             subgraph_timeout: Default::default(),
             rate_limit: Default::default(),
             retry: Default::default(),
+            circuit_breaker: Default::default(),
             access_logs: Default::default(),
             batching: Default::default(),
+            query_deduplication: Default::default(),
             message_signatures: Default::default(),
+            subgraph_tls: Default::default(),
+            subgraph_client: Default::default(),
         }
     }
 }
@@ -231,11 +271,21 @@ pub struct SubgraphConfig {
     #[serde(deserialize_with = "duration_str::deserialize_duration")]
     pub timeout: Duration,
     pub retry: Option<RetryConfig>,
+    /// Subgraph specific circuit breaker config, overriding the global one.
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Request hedging for this subgraph
+    pub hedging: Option<HedgingConfig>,
     /// Subgraph specific entity caching config  this overrides the global config if there
     /// is any
     pub entity_caching: Option<SubgraphEntityCachingConfig>,
     /// Subgraph specific message signatures config
     pub message_signatures: Option<MessageSignaturesConfig>,
+    /// TLS settings for this subgraph, replacing the global `gateway.subgraph_tls` settings.
+    /// Also applied to subscriptions over websockets.
+    pub tls: Option<SubgraphTlsConfig>,
+    /// HTTP client settings for this subgraph. Each setting left unset falls back to the global
+    /// `gateway.subgraph_client` one.
+    pub client: Option<SubgraphClientConfig>,
     /// The path of an SDL schema file for the subgraph (dev only).
     pub schema_path: Option<PathBuf>,
     /// A URL from which to retreive the subgraph SDL (dev only).
@@ -255,8 +305,12 @@ impl Default for SubgraphConfig {
             rate_limit: Default::default(),
             timeout: DEFAULT_SUBGRAPH_TIMEOUT,
             retry: Default::default(),
+            circuit_breaker: Default::default(),
+            hedging: Default::default(),
             entity_caching: Default::default(),
             message_signatures: Default::default(),
+            tls: Default::default(),
+            client: Default::default(),
             schema_path: Default::default(),
             introspection_url: Default::default(),
             introspection_headers: Default::default(),
@@ -470,6 +524,51 @@ mod tests {
         assert!(config.csrf.enabled);
     }
 
+    #[test]
+    fn compression_defaults() {
+        let config: Config = toml::from_str("").unwrap();
+
+        assert!(!config.compression.enabled);
+        assert_eq!(Size::from_kibibytes(1), config.compression.min_size);
+        assert!(config.compression.gzip && config.compression.brotli && config.compression.zstd);
+    }
+
+    #[test]
+    fn compression() {
+        let input = indoc! {r#"
+            [compression]
+            enabled = true
+            min_size = "10KiB"
+            brotli = false
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        assert!(config.compression.enabled);
+        assert_eq!(Size::from_kibibytes(10), config.compression.min_size);
+        assert!(config.compression.gzip);
+        assert!(!config.compression.brotli);
+        assert!(config.compression.zstd);
+    }
+
+    #[test]
+    fn compression_min_size_too_large() {
+        let input = indoc! {r#"
+            [compression]
+            min_size = "64KiB"
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 2, column 12
+          |
+        2 | min_size = "64KiB"
+          |            ^^^^^^^
+        compression min_size must be under 64KiB
+        "#);
+    }
+
     #[test]
     fn cors_allow_credentials() {
         let input = indoc! {r#"
@@ -954,21 +1053,26 @@ mod tests {
                             "foo",
                         ),
                         jwks: JwksConfig {
-                            url: Url {
-                                scheme: "https",
-                                cannot_be_a_base: false,
-                                username: "",
-                                password: None,
-                                host: Some(
-                                    Domain(
-                                        "example.com",
+                            url: Some(
+                                Url {
+                                    scheme: "https",
+                                    cannot_be_a_base: false,
+                                    username: "",
+                                    password: None,
+                                    host: Some(
+                                        Domain(
+                                            "example.com",
+                                        ),
                                     ),
-                                ),
-                                port: None,
-                                path: "/.well-known/jwks.json",
-                                query: None,
-                                fragment: None,
-                            },
+                                    port: None,
+                                    path: "/.well-known/jwks.json",
+                                    query: None,
+                                    fragment: None,
+                                },
+                            ),
+                            path: None,
+                            keys: [],
+                            secret: None,
                             issuer: Some(
                                 "https://example.com/",
                             ),
@@ -981,6 +1085,11 @@ mod tests {
                             name: "Authorization",
                             value_prefix: "Bearer ",
                         },
+                        cookie: None,
+                        query_parameter: None,
+                        algorithms: [],
+                        leeway: 60s,
+                        required_claims: {},
                     },
                 ),
             ],
@@ -1014,21 +1123,26 @@ mod tests {
                             "foo",
                         ),
                         jwks: JwksConfig {
-                            url: Url {
-                                scheme: "https",
-                                cannot_be_a_base: false,
-                                username: "",
-                                password: None,
-                                host: Some(
-                                    Domain(
-                                        "example.com",
+                            url: Some(
+                                Url {
+                                    scheme: "https",
+                                    cannot_be_a_base: false,
+                                    username: "",
+                                    password: None,
+                                    host: Some(
+                                        Domain(
+                                            "example.com",
+                                        ),
                                     ),
-                                ),
-                                port: None,
-                                path: "/.well-known/jwks.json",
-                                query: None,
-                                fragment: None,
-                            },
+                                    port: None,
+                                    path: "/.well-known/jwks.json",
+                                    query: None,
+                                    fragment: None,
+                                },
+                            ),
+                            path: None,
+                            keys: [],
+                            secret: None,
                             issuer: Some(
                                 "https://example.com/",
                             ),
@@ -1042,6 +1156,255 @@ mod tests {
                             name: "Authorization",
                             value_prefix: "Bearer ",
                         },
+                        cookie: None,
+                        query_parameter: None,
+                        algorithms: [],
+                        leeway: 60s,
+                        required_claims: {},
+                    },
+                ),
+            ],
+        }
+        "#);
+    }
+
+    #[test]
+    fn authentication_jwt_validation_options() {
+        let input = indoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt]
+            cookie = "session"
+            query_parameter = "access_token"
+            algorithms = ["RS256", "ES256"]
+            leeway = "5s"
+
+            [authentication.providers.jwt.jwks]
+            url = "https://example.com/.well-known/jwks.json"
+
+            [authentication.providers.jwt.required_claims]
+            azp = "my-app"
+            tenant = 42
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        let Some(AuthenticationProvider::Jwt(provider)) = result.authentication.unwrap().providers.pop() else {
+            unreachable!()
+        };
+
+        insta::assert_debug_snapshot!(
+            (&provider.cookie, &provider.query_parameter, &provider.algorithms, &provider.leeway, &provider.required_claims),
+            @r#"
+        (
+            Some(
+                "session",
+            ),
+            Some(
+                "access_token",
+            ),
+            [
+                RS256,
+                ES256,
+            ],
+            5s,
+            {
+                "azp": String(
+                    "my-app",
+                ),
+                "tenant": Integer(
+                    42,
+                ),
+            },
+        )
+        "#
+        );
+    }
+
+    #[test]
+    fn authentication_jwt_leeway_too_large() {
+        let input = indoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt]
+            leeway = "2h"
+
+            [authentication.providers.jwt.jwks]
+            url = "https://example.com/.well-known/jwks.json"
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 4, column 10
+          |
+        4 | leeway = "2h"
+          |          ^^^^
+        JWT leeway must be at most one hour
+        "#);
+    }
+    #[test]
+    fn authentication_unknown_jwt_algorithm() {
+        let input = indoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt]
+            algorithms = ["none"]
+
+            [authentication.providers.jwt.jwks]
+            url = "https://example.com/.well-known/jwks.json"
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 4, column 15
+          |
+        4 | algorithms = ["none"]
+          |               ^^^^^^
+        unknown variant `none`, expected one of `HS256`, `HS384`, `HS512`, `ES256`, `RS256`, `RS384`, `RS512`, `PS256`, `PS384`, `PS512`, `EdDSA`
+        "#);
+    }
+
+    #[test]
+    fn authentication_static_jwks() {
+        let input = indoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt.jwks]
+            path = "./jwks.json"
+
+            [[authentication.providers]]
+
+            [authentication.providers.jwt.jwks]
+            keys = [{ kty = "oct", k = "c2VjcmV0", kid = "my-key" }]
+
+            [[authentication.providers]]
+
+            [authentication.providers.jwt.jwks]
+            secret = "secret"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        let jwks = result
+            .authentication
+            .unwrap()
+            .providers
+            .into_iter()
+            .map(|provider| match provider {
+                AuthenticationProvider::Jwt(provider) => provider.jwks,
+                _ => unreachable!(),
+            })
+            .map(|jwks| {
+                (
+                    jwks.url,
+                    jwks.path,
+                    jwks.keys.len(),
+                    jwks.secret.map(|secret| secret.to_string()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        insta::assert_debug_snapshot!(&jwks, @r#"
+        [
+            (
+                None,
+                Some(
+                    "./jwks.json",
+                ),
+                0,
+                None,
+            ),
+            (
+                None,
+                None,
+                1,
+                None,
+            ),
+            (
+                None,
+                None,
+                0,
+                Some(
+                    "secret",
+                ),
+            ),
+        ]
+        "#);
+    }
+
+    #[test]
+    fn authentication_multiple_jwks_sources() {
+        let input = indoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt.jwks]
+            url = "https://example.com/.well-known/jwks.json"
+            secret = "secret"
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("exactly one of `url`, `path`, `keys` or `secret` must be defined"));
+    }
+
+    #[test]
+    fn authentication_introspection() {
+        let input = indoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.introspection]
+            name = "partner"
+            url = "https://example.com/oauth2/introspect"
+            client_id = "gateway"
+            client_secret = "secret"
+            cache_ttl = "1m"
+            timeout = "2s"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.authentication.unwrap(), @r#"
+        AuthenticationConfig {
+            providers: [
+                Introspection(
+                    IntrospectionProvider {
+                        name: Some(
+                            "partner",
+                        ),
+                        url: Url {
+                            scheme: "https",
+                            cannot_be_a_base: false,
+                            username: "",
+                            password: None,
+                            host: Some(
+                                Domain(
+                                    "example.com",
+                                ),
+                            ),
+                            port: None,
+                            path: "/oauth2/introspect",
+                            query: None,
+                            fragment: None,
+                        },
+                        client_id: Some(
+                            "gateway",
+                        ),
+                        client_secret: Some(
+                            DynamicString(
+                                "secret",
+                            ),
+                        ),
+                        header: AuthenticationHeader {
+                            name: "Authorization",
+                            value_prefix: "Bearer ",
+                        },
+                        cache_ttl: 60s,
+                        inactive_cache_ttl: 10s,
+                        timeout: 2s,
                     },
                 ),
             ],
@@ -1579,8 +1942,12 @@ mod tests {
                 rate_limit: None,
                 timeout: 30s,
                 retry: None,
+                circuit_breaker: None,
+                hedging: None,
                 entity_caching: None,
                 message_signatures: None,
+                tls: None,
+                client: None,
                 schema_path: None,
                 introspection_url: None,
                 introspection_headers: None,
@@ -1649,6 +2016,15 @@ mod tests {
                 retry_percent: None,
                 retry_mutations: false,
             },
+            circuit_breaker: CircuitBreakerConfig {
+                enabled: false,
+                failure_rate: None,
+                min_requests: None,
+                window: None,
+                slow_request_threshold: None,
+                open_duration: None,
+                half_open_requests: None,
+            },
             access_logs: AccessLogsConfig {
                 enabled: false,
                 path: "",
@@ -1659,6 +2035,9 @@ mod tests {
                 enabled: false,
                 limit: None,
             },
+            query_deduplication: QueryDeduplicationConfig {
+                enabled: false,
+            },
             message_signatures: MessageSignaturesConfig {
                 enabled: None,
                 algorithm: None,
@@ -1671,10 +2050,201 @@ mod tests {
                 derived_components: None,
                 signature_parameters: None,
             },
+            subgraph_tls: None,
+            subgraph_client: None,
+        }
+        "#);
+    }
+
+    #[test]
+    fn subgraph_tls() {
+        let input = indoc! {r#"
+            [gateway.subgraph_tls]
+            ca = "internal-ca.pem"
+
+            [subgraphs.products.tls]
+            cert = "client.pem"
+            key = "client.key"
+            ca = "mesh-ca.pem"
+
+            [subgraphs.dev.tls]
+            insecure_skip_verify = true
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.gateway.subgraph_tls, @r#"
+        Some(
+            SubgraphTlsConfig {
+                cert: None,
+                key: None,
+                ca: Some(
+                    "internal-ca.pem",
+                ),
+                insecure_skip_verify: false,
+            },
+        )
+        "#);
+
+        insta::assert_debug_snapshot!(&config.subgraphs["products"].tls, @r#"
+        Some(
+            SubgraphTlsConfig {
+                cert: Some(
+                    "client.pem",
+                ),
+                key: Some(
+                    "client.key",
+                ),
+                ca: Some(
+                    "mesh-ca.pem",
+                ),
+                insecure_skip_verify: false,
+            },
+        )
+        "#);
+
+        insta::assert_debug_snapshot!(&config.subgraphs["dev"].tls, @r#"
+        Some(
+            SubgraphTlsConfig {
+                cert: None,
+                key: None,
+                ca: None,
+                insecure_skip_verify: true,
+            },
+        )
+        "#);
+    }
+
+    #[test]
+    fn subgraph_client() {
+        let input = indoc! {r#"
+            [gateway.subgraph_client]
+            proxy = "http://egress.internal:3128"
+            pool_idle_timeout = "30s"
+            pool_max_idle_per_host = 16
+
+            [subgraphs.internal.client]
+            http2_prior_knowledge = true
+            tcp_keepalive = "60s"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.gateway.subgraph_client, @r#"
+        Some(
+            SubgraphClientConfig {
+                proxy: Some(
+                    Url {
+                        scheme: "http",
+                        cannot_be_a_base: false,
+                        username: "",
+                        password: None,
+                        host: Some(
+                            Domain(
+                                "egress.internal",
+                            ),
+                        ),
+                        port: Some(
+                            3128,
+                        ),
+                        path: "/",
+                        query: None,
+                        fragment: None,
+                    },
+                ),
+                pool_idle_timeout: Some(
+                    30s,
+                ),
+                pool_max_idle_per_host: Some(
+                    16,
+                ),
+                http2_prior_knowledge: None,
+                tcp_keepalive: None,
+                request_compression: None,
+                response_compression: None,
+            },
+        )
+        "#);
+
+        insta::assert_debug_snapshot!(&config.subgraphs["internal"].client, @r#"
+        Some(
+            SubgraphClientConfig {
+                proxy: None,
+                pool_idle_timeout: None,
+                pool_max_idle_per_host: None,
+                http2_prior_knowledge: Some(
+                    true,
+                ),
+                tcp_keepalive: Some(
+                    60s,
+                ),
+                request_compression: None,
+                response_compression: None,
+            },
+        )
+        "#);
+
+        let global = config.gateway.subgraph_client.as_ref();
+        let merged = config.subgraphs["internal"]
+            .client
+            .as_ref()
+            .unwrap()
+            .merged_with(global);
+
+        insta::assert_debug_snapshot!(&merged, @r#"
+        SubgraphClientConfig {
+            proxy: Some(
+                Url {
+                    scheme: "http",
+                    cannot_be_a_base: false,
+                    username: "",
+                    password: None,
+                    host: Some(
+                        Domain(
+                            "egress.internal",
+                        ),
+                    ),
+                    port: Some(
+                        3128,
+                    ),
+                    path: "/",
+                    query: None,
+                    fragment: None,
+                },
+            ),
+            pool_idle_timeout: Some(
+                30s,
+            ),
+            pool_max_idle_per_host: Some(
+                16,
+            ),
+            http2_prior_knowledge: Some(
+                true,
+            ),
+            tcp_keepalive: Some(
+                60s,
+            ),
+            request_compression: None,
+            response_compression: None,
         }
         "#);
     }
 
+    #[test]
+    fn subgraph_compression() {
+        let input = indoc! {r#"
+            [subgraphs.orders.client]
+            request_compression = "zstd"
+            response_compression = true
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+        let client = config.subgraphs["orders"].client.as_ref().unwrap();
+
+        assert_eq!(client.request_compression, Some(SubgraphCompression::Zstd));
+        assert_eq!(client.response_compression, Some(true));
+    }
+
     #[test]
     fn global_rate_limiting() {
         let input = indoc! {r#"
@@ -1716,6 +2286,7 @@ mod tests {
                     key_prefix: "grafbase",
                     tls: None,
                 },
+                rules: [],
             },
         )
         "###);
@@ -1756,6 +2327,7 @@ mod tests {
                     key_prefix: "grafbase",
                     tls: None,
                 },
+                rules: [],
             },
         )
         "###);
@@ -1801,6 +2373,7 @@ mod tests {
                     key_prefix: "grafbase",
                     tls: None,
                 },
+                rules: [],
             },
         )
         "###);
@@ -1844,6 +2417,7 @@ mod tests {
                     key_prefix: "kekw",
                     tls: None,
                 },
+                rules: [],
             },
         )
         "###);
@@ -1898,6 +2472,7 @@ mod tests {
                         },
                     ),
                 },
+                rules: [],
             },
         )
         "###);
@@ -1955,6 +2530,7 @@ mod tests {
                         },
                     ),
                 },
+                rules: [],
             },
         )
         "###);
@@ -1994,6 +2570,104 @@ mod tests {
         insta::assert_debug_snapshot!(&error.to_string(), @r###""TOML parse error at line 3, column 12\n  |\n3 | duration = \"0s\"\n  |            ^^^^\nrate limit duration cannot be 0\n""###);
     }
 
+    #[test]
+    fn rate_limiting_rules() {
+        let input = indoc! {r#"
+            [[gateway.rate_limit.rules]]
+            name = "per-user"
+            key = { jwt_claim = "sub" }
+            limit = 100
+            duration = "1m"
+
+            [[gateway.rate_limit.rules]]
+            name = "per-api-key"
+            key = { header = "x-api-key" }
+            limit = 10
+            duration = "1s"
+
+            [[gateway.rate_limit.rules]]
+            name = "per-ip"
+            key = "ip"
+            limit = 1000
+            duration = "10s"
+
+            [[gateway.rate_limit.rules]]
+            name = "per-operation"
+            key = "operation_name"
+            limit = 50
+            duration = "1s"
+        "#};
+
+        let config = toml::from_str::<Config>(input).unwrap();
+        let rules = config.gateway.rate_limit.unwrap().rules;
+
+        insta::assert_debug_snapshot!(&rules, @r#"
+        [
+            RateLimitRule {
+                name: "per-user",
+                key: JwtClaim(
+                    "sub",
+                ),
+                limit: 100,
+                duration: 60s,
+                unit: Requests,
+            },
+            RateLimitRule {
+                name: "per-api-key",
+                key: Header(
+                    "x-api-key",
+                ),
+                limit: 10,
+                duration: 1s,
+                unit: Requests,
+            },
+            RateLimitRule {
+                name: "per-ip",
+                key: Ip,
+                limit: 1000,
+                duration: 10s,
+                unit: Requests,
+            },
+            RateLimitRule {
+                name: "per-operation",
+                key: OperationName,
+                limit: 50,
+                duration: 1s,
+                unit: Requests,
+            },
+        ]
+        "#);
+    }
+
+    #[test]
+    fn rate_limiting_complexity_rule() {
+        let input = indoc! {r#"
+            [[gateway.rate_limit.rules]]
+            name = "complexity-per-user"
+            key = { jwt_claim = "sub" }
+            limit = 10000
+            duration = "1m"
+            unit = "complexity"
+        "#};
+
+        let config = toml::from_str::<Config>(input).unwrap();
+        let rules = config.gateway.rate_limit.unwrap().rules;
+
+        insta::assert_debug_snapshot!(&rules, @r#"
+        [
+            RateLimitRule {
+                name: "complexity-per-user",
+                key: JwtClaim(
+                    "sub",
+                ),
+                limit: 10000,
+                duration: 60s,
+                unit: Complexity,
+            },
+        ]
+        "#);
+    }
+
     #[test]
     fn subgraph_global_retry() {
         let input = indoc! {r#"
@@ -2040,8 +2714,12 @@ mod tests {
                         retry_mutations: false,
                     },
                 ),
+                circuit_breaker: None,
+                hedging: None,
                 entity_caching: None,
                 message_signatures: None,
+                tls: None,
+                client: None,
                 schema_path: None,
                 introspection_url: None,
                 introspection_headers: None,
@@ -2051,6 +2729,89 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn circuit_breaker() {
+        let input = indoc! {r#"
+            [gateway.circuit_breaker]
+            enabled = true
+            failure_rate = 0.25
+            window = "30s"
+
+            [subgraphs.products.circuit_breaker]
+            enabled = true
+            min_requests = 5
+            slow_request_threshold = "2s"
+            open_duration = "1m"
+            half_open_requests = 3
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.gateway.circuit_breaker, @r#"
+        CircuitBreakerConfig {
+            enabled: true,
+            failure_rate: Some(
+                0.25,
+            ),
+            min_requests: None,
+            window: Some(
+                30s,
+            ),
+            slow_request_threshold: None,
+            open_duration: None,
+            half_open_requests: None,
+        }
+        "#);
+
+        insta::assert_debug_snapshot!(&config.subgraphs["products"].circuit_breaker, @r#"
+        Some(
+            CircuitBreakerConfig {
+                enabled: true,
+                failure_rate: None,
+                min_requests: Some(
+                    5,
+                ),
+                window: None,
+                slow_request_threshold: Some(
+                    2s,
+                ),
+                open_duration: Some(
+                    60s,
+                ),
+                half_open_requests: Some(
+                    3,
+                ),
+            },
+        )
+        "#);
+    }
+
+    #[test]
+    fn subgraph_hedging() {
+        let input = indoc! {r#"
+            [subgraphs.products.hedging]
+            enabled = true
+            percentile = 0.9
+            min_delay = "50ms"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.subgraphs["products"].hedging, @r#"
+        Some(
+            HedgingConfig {
+                enabled: true,
+                percentile: Some(
+                    0.9,
+                ),
+                min_delay: Some(
+                    50ms,
+                ),
+            },
+        )
+        "#);
+    }
+
     #[test]
     fn access_logs_default() {
         let input = indoc! {r#"
@@ -2170,6 +2931,22 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn query_deduplication() {
+        let input = indoc! {r#"
+            [gateway.query_deduplication]
+            enabled = true
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.gateway.query_deduplication, @r#"
+        QueryDeduplicationConfig {
+            enabled: true,
+        }
+        "#);
+    }
+
     #[test]
     fn batching_with_too_high_limit() {
         let input = indoc! {r#"
@@ -2223,6 +3000,61 @@ mod tests {
         assert_eq!(500, config.operation_caching.limit);
     }
 
+    #[test]
+    fn response_cache_defaults() {
+        let config: Config = toml::from_str("").unwrap();
+
+        assert!(!config.response_caching.enabled);
+        assert_eq!(Duration::from_secs(60), config.response_caching.ttl);
+        assert_eq!("grafbase-response-cache", config.response_caching.redis.key_prefix);
+        assert!(!config.response_caching.has_scoped_key());
+    }
+
+    #[test]
+    fn response_cache_settings() {
+        let input = indoc! {r#"
+            [response_caching]
+            enabled = true
+            storage = "redis"
+            ttl = "5m"
+            key_headers = ["x-tenant-id"]
+            key_claims = ["sub"]
+
+            [response_caching.redis]
+            url = "redis://cache:6379"
+            key_prefix = "responses"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        assert!(config.response_caching.enabled);
+        assert_eq!(EntityCachingStorage::Redis, config.response_caching.storage);
+        assert_eq!(Duration::from_secs(300), config.response_caching.ttl);
+        assert_eq!(vec!["x-tenant-id".to_string()], config.response_caching.key_headers);
+        assert_eq!(vec!["sub".to_string()], config.response_caching.key_claims);
+        assert_eq!("redis://cache:6379", config.response_caching.redis.url.as_str());
+        assert_eq!("responses", config.response_caching.redis.key_prefix);
+        assert!(config.response_caching.has_scoped_key());
+    }
+
+    #[test]
+    fn entity_cache_purge() {
+        let input = indoc! {r#"
+            [entity_caching.purge]
+            enabled = true
+            listen = "127.0.0.1:9669"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        assert!(config.entity_caching.purge.enabled);
+        assert_eq!(
+            Some("127.0.0.1:9669".parse().unwrap()),
+            config.entity_caching.purge.listen
+        );
+        assert_eq!("/admin/entity-cache/purge", config.entity_caching.purge.path);
+    }
+
     #[test]
     fn extension_only_version() {
         let input = indoc! {r#"
@@ -2297,10 +3129,73 @@ mod tests {
                         max_pool_size: Some(
                             1000,
                         ),
+                        limits: WasiLimitsConfig {
+                            max_memory: None,
+                            max_table_elements: None,
+                            execution_timeout: None,
+                        },
+                        order: None,
                     },
                 ),
             },
         )
         "#);
     }
+
+    #[test]
+    fn extension_order() {
+        let input = indoc! {r#"
+            [extensions]
+            logger = "1.0"
+
+            [extensions.tenant]
+            version = "1.0"
+            order = 1
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+        let extensions = config.extensions.unwrap();
+
+        assert_eq!(None, extensions["logger"].order());
+        assert_eq!(Some(1), extensions["tenant"].order());
+    }
+
+    #[test]
+    fn extension_limits() {
+        let input = indoc! {r#"
+            [extensions.rest]
+            version = "0.1.0"
+
+            [extensions.rest.limits]
+            max_memory = "64MiB"
+            max_table_elements = 10000
+            execution_timeout = "500ms"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+        let limits = config.extensions.unwrap()["rest"].limits();
+
+        assert_eq!(Some(Size::from_mebibytes(64)), limits.max_memory);
+        assert_eq!(Some(10000), limits.max_table_elements);
+        assert_eq!(Some(Duration::from_millis(500)), limits.execution_timeout);
+    }
+
+    #[test]
+    fn hooks_limits() {
+        let input = indoc! {r#"
+            [hooks]
+            location = "hooks.wasm"
+
+            [hooks.limits]
+            max_memory = 1048576
+            execution_timeout = "2s"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+        let limits = config.hooks.unwrap().limits;
+
+        assert_eq!(Some(Size::from_mebibytes(1)), limits.max_memory);
+        assert_eq!(None, limits.max_table_elements);
+        assert_eq!(Some(Duration::from_secs(2)), limits.execution_timeout);
+    }
 }
@@ -20,6 +20,50 @@ pub struct RateLimitConfig {
     pub storage: RateLimitStorage,
     #[serde(default)]
     pub redis: RateLimitRedisConfig,
+    /// Rate limits applied to every request sharing the same key, such as a JWT claim,
+    /// a header value or the client IP. A request only consumes from the rules once all of them
+    /// allow it.
+    #[serde(default)]
+    pub rules: Vec<RateLimitRule>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitRule {
+    /// Unique name of the rule, used to keep the counters of each rule apart.
+    pub name: String,
+    /// Where to take the value identifying the client from.
+    pub key: RateLimitRuleKey,
+    pub limit: usize,
+    #[serde(deserialize_with = "deserialize_duration_internal")]
+    pub duration: Duration,
+    /// What a request consumes from the limit.
+    #[serde(default)]
+    pub unit: RateLimitUnit,
+}
+
+impl RateLimitRule {
+    pub fn rate_limit(&self) -> GraphRateLimit {
+        GraphRateLimit {
+            limit: self.limit,
+            duration: self.duration,
+        }
+    }
+}
+
+/// Source of the rate limit key of a rule. Requests without a value for it, for example
+/// without the header, are not limited by the rule.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitRuleKey {
+    /// Value of a request header.
+    Header(String),
+    /// Value of a claim of the authenticated JWT.
+    JwtClaim(String),
+    /// IP address of the client connection.
+    Ip,
+    /// Operation name provided by the client.
+    OperationName,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
@@ -74,6 +118,18 @@ pub struct RateLimitRedisTlsConfig {
     pub ca: Option<PathBuf>,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitUnit {
+    /// Each request consumes one unit.
+    #[default]
+    Requests,
+    /// Each request consumes its complexity cost, as computed by complexity control. The limit
+    /// is a budget which can be spent at once and refills over the duration. Requests are not
+    /// limited by the rule if complexity control is disabled.
+    Complexity,
+}
+
 fn deserialize_duration_internal<'de, D>(data: D) -> Result<Duration, D::Error>
 where
     D: Deserializer<'de>,
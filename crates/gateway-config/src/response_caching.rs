@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use crate::{EntityCachingRedisConfig, EntityCachingStorage};
+
+const DEFAULT_RESPONSE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResponseCachingConfig {
+    /// If whole operation responses should be cached. Operations depending on authorization
+    /// decisions, with `@authenticated`, `@requiresScopes`, `@authorized` or authorization
+    /// extensions, are never cached.
+    pub enabled: bool,
+    pub storage: EntityCachingStorage,
+    pub redis: EntityCachingRedisConfig,
+    /// Upper bound of the ttl of cache entries. Responses are stored for the smallest max-age of their
+    /// subgraph responses, and not at all without one. Defaults to 60s
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub ttl: Duration,
+    /// Request headers whose values are part of the cache key. Private responses are only cached if
+    /// the key includes `authorization`, `cookie` or a claim.
+    pub key_headers: Vec<String>,
+    /// JWT claims whose values are part of the cache key.
+    pub key_claims: Vec<String>,
+}
+
+impl ResponseCachingConfig {
+    /// Whether the cache key is scoped to the caller with headers or claims, making it safe to cache
+    /// authenticated responses.
+    pub fn has_scoped_key(&self) -> bool {
+        !self.key_headers.is_empty() || !self.key_claims.is_empty()
+    }
+}
+
+impl Default for ResponseCachingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            storage: Default::default(),
+            redis: EntityCachingRedisConfig {
+                key_prefix: String::from("grafbase-response-cache"),
+                ..Default::default()
+            },
+            ttl: DEFAULT_RESPONSE_CACHE_TTL,
+            key_headers: Vec::new(),
+            key_claims: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use url::Url;
+
+/// HTTP client settings of the connections to subgraphs. Settings of a subgraph left unset fall
+/// back to the global ones.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubgraphClientConfig {
+    /// HTTP(S) proxy all subgraph requests are sent through, e.g. `http://egress.internal:3128`.
+    pub proxy: Option<Url>,
+    /// How long idle connections are kept open in the pool. Defaults to 90s.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub pool_idle_timeout: Option<Duration>,
+    /// Maximum number of idle connections kept open per host. Unlimited by default.
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Only speak HTTP/2, without negotiating it first. Required for HTTP/2 over cleartext (h2c).
+    /// Disabled by default.
+    pub http2_prior_knowledge: Option<bool>,
+    /// Interval of the TCP keepalive probes. Disabled by default.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub tcp_keepalive: Option<Duration>,
+    /// Compress the request bodies with this algorithm. Bodies under 1 KiB are sent uncompressed.
+    pub request_compression: Option<SubgraphCompression>,
+    /// Advertise gzip, brotli and zstd support with `Accept-Encoding` and decompress the responses.
+    /// Disabled by default.
+    pub response_compression: Option<bool>,
+}
+
+impl SubgraphClientConfig {
+    /// Settings of a subgraph, taking the global ones for those it doesn't define.
+    pub fn merged_with(&self, global: Option<&SubgraphClientConfig>) -> SubgraphClientConfig {
+        let Some(global) = global else {
+            return self.clone();
+        };
+
+        SubgraphClientConfig {
+            proxy: self.proxy.clone().or_else(|| global.proxy.clone()),
+            pool_idle_timeout: self.pool_idle_timeout.or(global.pool_idle_timeout),
+            pool_max_idle_per_host: self.pool_max_idle_per_host.or(global.pool_max_idle_per_host),
+            http2_prior_knowledge: self.http2_prior_knowledge.or(global.http2_prior_knowledge),
+            tcp_keepalive: self.tcp_keepalive.or(global.tcp_keepalive),
+            request_compression: self.request_compression.or(global.request_compression),
+            response_compression: self.response_compression.or(global.response_compression),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubgraphCompression {
+    Gzip,
+    Brotli,
+    Zstd,
+}
+
+impl SubgraphCompression {
+    pub fn content_encoding(self) -> &'static str {
+        match self {
+            SubgraphCompression::Gzip => "gzip",
+            SubgraphCompression::Brotli => "br",
+            SubgraphCompression::Zstd => "zstd",
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+/// TLS settings of the connections to subgraphs. Relative paths are resolved from the working
+/// directory of the gateway, not from the directory of the configuration file.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubgraphTlsConfig {
+    /// Client certificate presented to the subgraph, in PEM format. Requires `key`.
+    pub cert: Option<PathBuf>,
+    /// Private key of the client certificate, in PEM format.
+    pub key: Option<PathBuf>,
+    /// Root certificates to trust in addition to the system ones, as a PEM bundle.
+    pub ca: Option<PathBuf>,
+    /// Accept any server certificate, including invalid and self-signed ones. Only meant for
+    /// development.
+    pub insecure_skip_verify: bool,
+}
@@ -1,6 +1,7 @@
 pub mod logs;
 pub mod metrics;
 pub mod otlp;
+pub mod prometheus;
 pub mod response_extension;
 pub mod stdout;
 pub mod tracing;
@@ -11,6 +12,7 @@ pub use otlp::{
     Headers, OtlpExporterConfig, OtlpExporterGrpcConfig, OtlpExporterHttpConfig, OtlpExporterProtocol,
     OtlpExporterTlsConfig,
 };
+pub use prometheus::PrometheusExporterConfig;
 pub use response_extension::*;
 pub use tracing::{PropagationConfig, TracingCollectConfig, TracingConfig, DEFAULT_SAMPLING};
 
@@ -23,6 +25,8 @@ pub struct GlobalExporterConfig {
     pub stdout: Option<StdoutExporterConfig>,
     pub otlp: Option<OtlpExporterConfig>,
     pub response_extension: Option<ResponseExtensionExporterConfig>,
+    /// Metrics only, served on a scrape endpoint.
+    pub prometheus: Option<PrometheusExporterConfig>,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Deserialize)]
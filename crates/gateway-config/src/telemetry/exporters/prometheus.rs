@@ -0,0 +1,24 @@
+use std::{borrow::Cow, net::SocketAddr};
+
+/// Prometheus exporter configuration, exposing the metrics on a scrape endpoint.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PrometheusExporterConfig {
+    /// Enable or disable the exporter
+    pub enabled: bool,
+    /// Address of a separate listener for the metrics endpoint. If not set, the metrics are served
+    /// on the main listener.
+    pub listen: Option<SocketAddr>,
+    /// Path of the metrics endpoint. Defaults to `/metrics`.
+    pub path: Cow<'static, str>,
+}
+
+impl Default for PrometheusExporterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            listen: None,
+            path: Cow::Borrowed("/metrics"),
+        }
+    }
+}
@@ -12,7 +12,9 @@ pub use exporters::{
     LogsConfig, MetricsConfig, PropagationConfig, {TracingCollectConfig, TracingConfig, DEFAULT_SAMPLING},
 };
 
-pub use exporters::{BatchExportConfig, OpenTelemetryExportersConfig, StdoutExporterConfig};
+pub use exporters::{
+    BatchExportConfig, OpenTelemetryExportersConfig, PrometheusExporterConfig, StdoutExporterConfig,
+};
 
 /// Holds telemetry configuration
 #[derive(Debug, Clone, PartialEq, serde::Deserialize)]
@@ -96,6 +98,10 @@ impl TelemetryConfig {
         }
     }
 
+    pub fn metrics_prometheus_config(&self) -> Option<&PrometheusExporterConfig> {
+        self.exporters.prometheus.as_ref().filter(|c| c.enabled)
+    }
+
     pub fn logs_stdout_config(&self) -> Option<&StdoutExporterConfig> {
         match self.logs.as_ref().and_then(|c| c.exporters.stdout.as_ref()) {
             Some(config) if config.enabled => Some(config),
@@ -1117,6 +1123,56 @@ mod tests {
         assert!(expected.is_some());
     }
 
+    #[test]
+    fn metrics_prometheus_defaults() {
+        let input = indoc! {r#"
+            [exporters.prometheus]
+        "#};
+
+        let config: TelemetryConfig = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            Some(&PrometheusExporterConfig {
+                enabled: true,
+                listen: None,
+                path: "/metrics".into(),
+            }),
+            config.metrics_prometheus_config()
+        );
+    }
+
+    #[test]
+    fn metrics_prometheus_separate_listener() {
+        let input = indoc! {r#"
+            [exporters.prometheus]
+            listen = "0.0.0.0:9090"
+            path = "/prometheus"
+        "#};
+
+        let config: TelemetryConfig = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            Some(&PrometheusExporterConfig {
+                enabled: true,
+                listen: Some("0.0.0.0:9090".parse().unwrap()),
+                path: "/prometheus".into(),
+            }),
+            config.metrics_prometheus_config()
+        );
+    }
+
+    #[test]
+    fn metrics_prometheus_not_enabled() {
+        let input = indoc! {r#"
+            [exporters.prometheus]
+            enabled = false
+        "#};
+
+        let config: TelemetryConfig = toml::from_str(input).unwrap();
+
+        assert_eq!(None, config.metrics_prometheus_config());
+    }
+
     #[test]
     fn stdout_exporter_kitchen_sink() {
         // prepare
@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use size::Size;
+
+use crate::size_ext;
+
+/// Resource limits applied to every instance of a WASI component. A call tripping one of them
+/// fails, and the instance is thrown away instead of going back to the pool.
+#[derive(Clone, Default, Debug, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WasiLimitsConfig {
+    /// The maximum size of the linear memory of an instance. Unlimited by default.
+    #[serde(deserialize_with = "size_ext::deserialize_option_positive_size")]
+    pub max_memory: Option<Size>,
+    /// The maximum number of elements of any table of an instance. Unlimited by default.
+    pub max_table_elements: Option<usize>,
+    /// The maximum wall-clock time of a single call into the component. When polling the next item
+    /// of a subscription, waiting for the next event may take any time and only the time spent
+    /// running the component counts. Unlimited by default.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub execution_timeout: Option<Duration>,
+}
@@ -22,6 +22,26 @@ pub fn authentication_extension(input: TokenStream) -> TokenStream {
     expand(authentication_init(ast))
 }
 
+/// A proc macro for generating initialization code for an authorization extension.
+///
+/// Add it on top of the type which implements `Extension` and `Authorizer` traits to
+/// register it as an authorization extension.
+#[proc_macro_derive(AuthorizationExtension)]
+pub fn authorization_extension(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input as DeriveInput);
+    expand(authorization_init(ast))
+}
+
+/// A proc macro for generating initialization code for a hooks extension.
+///
+/// Add it on top of the type which implements `Extension` and `Hooks` traits to
+/// register it as a hooks extension.
+#[proc_macro_derive(HooksExtension)]
+pub fn hooks_extension(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input as DeriveInput);
+    expand(hooks_init(ast))
+}
+
 fn expand(init: proc_macro2::TokenStream) -> TokenStream {
     let token_stream = quote! {
         #[doc(hidden)]
@@ -63,3 +83,33 @@ fn authentication_init(ast: DeriveInput) -> proc_macro2::TokenStream {
         grafbase_sdk::extension::authentication::register(Box::new(init_fn));
     }
 }
+
+fn authorization_init(ast: DeriveInput) -> proc_macro2::TokenStream {
+    let name = &ast.ident;
+
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+
+    quote! {
+        let init_fn = |directives, config| {
+            let result = <#name #ty_generics as grafbase_sdk::Extension>::new(directives, config);
+            result.map(|extension| Box::new(extension) as Box<dyn grafbase_sdk::Authorizer>)
+        };
+
+        grafbase_sdk::extension::authorization::register(Box::new(init_fn));
+    }
+}
+
+fn hooks_init(ast: DeriveInput) -> proc_macro2::TokenStream {
+    let name = &ast.ident;
+
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+
+    quote! {
+        let init_fn = |directives, config| {
+            let result = <#name #ty_generics as grafbase_sdk::Extension>::new(directives, config);
+            result.map(|extension| Box::new(extension) as Box<dyn grafbase_sdk::Hooks>)
+        };
+
+        grafbase_sdk::extension::hooks::register(Box::new(init_fn));
+    }
+}
@@ -1,14 +1,21 @@
 #![allow(static_mut_refs)]
 
 pub mod authentication;
+pub mod authorization;
+pub mod hooks;
 pub mod resolver;
 
 pub use authentication::Authenticator;
-pub use resolver::Resolver;
+pub use authorization::Authorizer;
+pub use hooks::Hooks;
+pub use resolver::{Resolver, Subscription};
 
 use crate::{
-    types::{Configuration, FieldInputs},
-    wit::{Directive, Error, ExtensionType, FieldDefinition, FieldOutput, Guest, Headers, SharedContext, Token},
+    types::{Configuration, FieldInputs, ResponseItems},
+    wit::{
+        Directive, Error, ExecutedHttpRequest, ExtensionType, FieldDefinition, FieldOutput, Guest, Headers, HttpMethod,
+        QueryElement, SharedContext, Token,
+    },
     Component,
 };
 
@@ -43,6 +50,8 @@ impl Guest for Component {
         let result = match r#type {
             ExtensionType::Resolver => resolver::init(directives, config),
             ExtensionType::Authentication => authentication::init(directives, config),
+            ExtensionType::Authorization => authorization::init(directives, config),
+            ExtensionType::Hooks => hooks::init(directives, config),
         };
 
         result.map_err(|e| e.to_string())
@@ -64,6 +73,43 @@ impl Guest for Component {
         result.map(Into::into)
     }
 
+    fn resolve_subscription(
+        context: SharedContext,
+        directive: Directive,
+        definition: FieldDefinition,
+    ) -> Result<(), Error> {
+        let subscription =
+            resolver::get_extension()?.resolve_subscription(context, directive.into(), definition.into())?;
+
+        resolver::set_subscription(subscription);
+
+        Ok(())
+    }
+
+    fn resolve_next_subscription_item() -> Result<Option<FieldOutput>, Error> {
+        let item = resolver::get_subscription()?.next()?;
+
+        Ok(item.map(Into::into))
+    }
+
+    fn authorize_query(context: SharedContext, elements: Vec<QueryElement>) -> Result<Vec<Result<(), Error>>, Error> {
+        authorization::get_extension()?.authorize_query(context, elements.into_iter().map(Into::into).collect())
+    }
+
+    fn authorize_response(
+        context: SharedContext,
+        directive: Directive,
+        definition: FieldDefinition,
+        items: Vec<Vec<u8>>,
+    ) -> Result<Vec<Result<(), Error>>, Error> {
+        authorization::get_extension()?.authorize_response(
+            context,
+            directive.into(),
+            definition.into(),
+            ResponseItems::new(items),
+        )
+    }
+
     fn authenticate(headers: Headers) -> Result<Token, crate::wit::ErrorResponse> {
         let result = authentication::get_extension()
             .map_err(|_| crate::wit::ErrorResponse {
@@ -77,4 +123,34 @@ impl Guest for Component {
 
         result.map(Into::into).map_err(Into::into)
     }
+
+    fn on_gateway_request(context: SharedContext, headers: Headers) -> Result<(), crate::wit::ErrorResponse> {
+        let result = hooks::get_extension()
+            .map_err(|_| crate::wit::ErrorResponse {
+                status_code: 500,
+                errors: vec![Error {
+                    extensions: Vec::new(),
+                    message: String::from("internal server error"),
+                }],
+            })?
+            .on_gateway_request(context, headers);
+
+        result.map_err(Into::into)
+    }
+
+    fn on_subgraph_request(
+        context: SharedContext,
+        subgraph_name: String,
+        method: HttpMethod,
+        url: String,
+        headers: Headers,
+    ) -> Result<(), Error> {
+        hooks::get_extension()?.on_subgraph_request(context, subgraph_name, method, url, headers)
+    }
+
+    fn on_http_response(context: SharedContext, request: ExecutedHttpRequest) {
+        if let Ok(extension) = hooks::get_extension() {
+            extension.on_http_response(context, request);
+        }
+    }
 }
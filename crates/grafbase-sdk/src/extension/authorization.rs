@@ -0,0 +1,95 @@
+use crate::{
+    types::{Configuration, Directive, FieldDefinition, QueryElement, ResponseItems},
+    wit::{Error, SharedContext},
+};
+
+use super::Extension;
+
+type InitFn = Box<dyn Fn(Vec<Directive>, Configuration) -> Result<Box<dyn Authorizer>, Box<dyn std::error::Error>>>;
+
+pub(super) static mut EXTENSION: Option<Box<dyn Authorizer>> = None;
+pub static mut INIT_FN: Option<InitFn> = None;
+
+pub(super) fn get_extension() -> Result<&'static mut dyn Authorizer, Error> {
+    // Safety: This is hidden, only called by us. Every extension call to an instance happens
+    // in a single-threaded environment. Do not call this multiple times from different threads.
+    unsafe {
+        EXTENSION.as_deref_mut().ok_or_else(|| Error {
+            message: "Authorization extension not initialized correctly.".to_string(),
+            extensions: Vec::new(),
+        })
+    }
+}
+
+/// Initializes the authorization extension with the provided directives using the closure
+/// function created with the `register_extension!` macro.
+pub(super) fn init(directives: Vec<Directive>, configuration: Configuration) -> Result<(), Box<dyn std::error::Error>> {
+    // Safety: This function is only called from the SDK macro, so we can assume that there is only one caller at a time.
+    unsafe {
+        let init = INIT_FN
+            .as_ref()
+            .expect("Authorization extension not initialized correctly.");
+        EXTENSION = Some(init(directives, configuration)?);
+    }
+
+    Ok(())
+}
+
+/// This function gets called when the extension is registered in the user code with the `register_extension!` macro.
+///
+/// This should never be called manually by the user.
+#[doc(hidden)]
+pub fn register(f: InitFn) {
+    // Safety: This function is only called from the SDK macro, so we can assume that there is only one caller at a time.
+    unsafe {
+        INIT_FN = Some(f);
+    }
+}
+
+/// A trait that extends `Extension` and provides authorization for the fields and types carrying
+/// the extension's directives.
+///
+/// Every verdict is either `Ok(())` granting access to the element, or an error which is added to
+/// the GraphQL response in place of the element.
+pub trait Authorizer: Extension {
+    /// Authorizes the query before its execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The shared context containing runtime information
+    /// * `elements` - Every field and type of the query carrying one of the extension's directives
+    ///
+    /// # Returns
+    ///
+    /// A verdict for each element, in the same order. An error applies to the whole query.
+    fn authorize_query(
+        &mut self,
+        context: SharedContext,
+        elements: Vec<QueryElement>,
+    ) -> Result<Vec<Result<(), Error>>, Error>;
+
+    /// Authorizes the response data of a field carrying one of the extension's directives, after
+    /// its execution. Access is granted to all items by default. Directives applied on types are
+    /// not checked against the response, only by [`Authorizer::authorize_query`].
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The shared context containing runtime information
+    /// * `directive` - The authorization directive on the field
+    /// * `definition` - The field definition
+    /// * `items` - The field values, one for each parent object of the response
+    ///
+    /// # Returns
+    ///
+    /// A verdict for each item, in the same order, or a single one applying to all of them.
+    #[allow(unused_variables)]
+    fn authorize_response(
+        &mut self,
+        context: SharedContext,
+        directive: Directive,
+        definition: FieldDefinition,
+        items: ResponseItems,
+    ) -> Result<Vec<Result<(), Error>>, Error> {
+        Ok(vec![Ok(())])
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{
+    types::{Configuration, Directive, ErrorResponse},
+    wit::{Error, ExecutedHttpRequest, Headers, HttpMethod, SharedContext},
+};
+
+use super::Extension;
+
+type InitFn = Box<dyn Fn(Vec<Directive>, Configuration) -> Result<Box<dyn Hooks>, Box<dyn std::error::Error>>>;
+
+pub(super) static mut EXTENSION: Option<Box<dyn Hooks>> = None;
+pub static mut INIT_FN: Option<InitFn> = None;
+
+pub(super) fn get_extension() -> Result<&'static mut dyn Hooks, Error> {
+    // Safety: This is hidden, only called by us. Every extension call to an instance happens
+    // in a single-threaded environment. Do not call this multiple times from different threads.
+    unsafe {
+        EXTENSION.as_deref_mut().ok_or_else(|| Error {
+            message: "Hooks extension not initialized correctly.".to_string(),
+            extensions: Vec::new(),
+        })
+    }
+}
+
+/// Initializes the hooks extension with the provided directives using the closure
+/// function created with the `register_extension!` macro.
+pub(super) fn init(directives: Vec<Directive>, configuration: Configuration) -> Result<(), Box<dyn std::error::Error>> {
+    // Safety: This function is only called from the SDK macro, so we can assume that there is only one caller at a time.
+    unsafe {
+        let init = INIT_FN.as_ref().expect("Hooks extension not initialized correctly.");
+        EXTENSION = Some(init(directives, configuration)?);
+    }
+
+    Ok(())
+}
+
+/// This function gets called when the extension is registered in the user code with the `register_extension!` macro.
+///
+/// This should never be called manually by the user.
+#[doc(hidden)]
+pub fn register(f: InitFn) {
+    // Safety: This function is only called from the SDK macro, so we can assume that there is only one caller at a time.
+    unsafe {
+        INIT_FN = Some(f);
+    }
+}
+
+/// A trait that extends `Extension` and hooks into the lifecycle of the gateway requests.
+///
+/// Every hook does nothing by default. When several hooks extensions are configured, they are
+/// called one after the other, in the order defined in the gateway configuration, after the
+/// hooks component if any.
+pub trait Hooks: Extension {
+    /// Called just before authentication, with the headers of the client request.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The shared context containing runtime information
+    /// * `headers` - The request headers, which can be modified
+    ///
+    /// # Returns
+    ///
+    /// An error response stops the request processing and is returned to the client.
+    #[allow(unused_variables)]
+    fn on_gateway_request(&mut self, context: SharedContext, headers: Headers) -> Result<(), ErrorResponse> {
+        Ok(())
+    }
+
+    /// Called just before requesting a subgraph.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The shared context containing runtime information
+    /// * `subgraph_name` - The name of the requested subgraph
+    /// * `method` - The HTTP method of the subgraph request
+    /// * `url` - The URL of the subgraph
+    /// * `headers` - The subgraph request headers, which can be modified
+    ///
+    /// # Returns
+    ///
+    /// An error skips the subgraph request and is added to the GraphQL response.
+    #[allow(unused_variables)]
+    fn on_subgraph_request(
+        &mut self,
+        context: SharedContext,
+        subgraph_name: String,
+        method: HttpMethod,
+        url: String,
+        headers: Headers,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called right before the response is sent to the client.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The shared context containing runtime information
+    /// * `request` - The method, URL and response status code of the request
+    #[allow(unused_variables)]
+    fn on_http_response(&mut self, context: SharedContext, request: ExecutedHttpRequest) {}
+}
@@ -8,6 +8,7 @@ use super::Extension;
 type InitFn = Box<dyn Fn(Vec<Directive>, Configuration) -> Result<Box<dyn Resolver>, Box<dyn std::error::Error>>>;
 
 pub(super) static mut EXTENSION: Option<Box<dyn Resolver>> = None;
+pub(super) static mut SUBSCRIPTION: Option<Box<dyn Subscription>> = None;
 pub static mut INIT_FN: Option<InitFn> = None;
 
 pub(super) fn get_extension() -> Result<&'static mut dyn Resolver, Error> {
@@ -21,6 +22,24 @@ pub(super) fn get_extension() -> Result<&'static mut dyn Resolver, Error> {
     }
 }
 
+/// Stores the subscription of this instance, to be polled by the gateway.
+pub(super) fn set_subscription(subscription: Box<dyn Subscription>) {
+    // Safety: see get_extension
+    unsafe {
+        SUBSCRIPTION = Some(subscription);
+    }
+}
+
+pub(super) fn get_subscription() -> Result<&'static mut dyn Subscription, Error> {
+    // Safety: see get_extension
+    unsafe {
+        SUBSCRIPTION.as_deref_mut().ok_or_else(|| Error {
+            message: "No active subscription.".to_string(),
+            extensions: Vec::new(),
+        })
+    }
+}
+
 /// Initializes the resolver extension with the provided directives using the closure
 /// function created with the `register_extension!` macro.
 pub(super) fn init(directives: Vec<Directive>, configuration: Configuration) -> Result<(), Box<dyn std::error::Error>> {
@@ -70,4 +89,44 @@ pub trait Resolver: Extension {
         definition: FieldDefinition,
         inputs: FieldInputs,
     ) -> Result<FieldOutput, Error>;
+
+    /// Subscribes to a subscription field, returning the stream of its items. Each item
+    /// must hold a single output, which becomes the data of one subscription response.
+    ///
+    /// The gateway dedicates an instance of the extension to every subscription, so
+    /// implementations can keep connections or cursors in the returned value.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The shared context containing runtime information
+    /// * `directive` - The directive associated with this field resolution
+    /// * `definition` - The field definition containing metadata
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing either the `Subscription` to poll or an `Error`
+    fn resolve_subscription(
+        &mut self,
+        context: SharedContext,
+        directive: Directive,
+        definition: FieldDefinition,
+    ) -> Result<Box<dyn Subscription>, Error> {
+        let _ = (context, directive, definition);
+
+        Err(Error {
+            message: "This extension does not support subscriptions.".to_string(),
+            extensions: Vec::new(),
+        })
+    }
+}
+
+/// A stream of subscription items, created by [`Resolver::resolve_subscription`].
+///
+/// The gateway polls it until it returns `None`, an error, or the client goes away.
+pub trait Subscription {
+    /// Returns the next item of the subscription, or `None` once the subscription is over.
+    ///
+    /// This call may block until an item is available, for example while waiting for an HTTP
+    /// response.
+    fn next(&mut self) -> Result<Option<FieldOutput>, Error>;
 }
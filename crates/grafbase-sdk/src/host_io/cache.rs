@@ -1,4 +1,5 @@
-//! A key-value cache shared with all instances of the extension.
+//! A key-value cache shared with all instances of the extension, and access to the gateway
+//! entity cache.
 
 use std::time::Duration;
 
@@ -34,6 +35,50 @@ where
     }
 }
 
+/// Purges every entry of the gateway entity cache fetched from the given subgraph.
+///
+/// # Errors
+///
+/// Returns an error if the gateway failed to purge the entity cache.
+pub fn purge_subgraph_entities(subgraph_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    purge_entities(&crate::wit::EntityCacheSelector::Subgraph(subgraph_name.to_string()))
+}
+
+/// Purges every entity of the given type from the gateway entity cache.
+///
+/// # Errors
+///
+/// Returns an error if the gateway failed to purge the entity cache.
+pub fn purge_entity_type(type_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    purge_entities(&crate::wit::EntityCacheSelector::EntityType(type_name.to_string()))
+}
+
+/// Purges a single entity from the gateway entity cache.
+///
+/// # Arguments
+///
+/// * `type_name` - The name of the entity type
+/// * `key` - The key fields of the entity, such as `{"upc": "1"}`
+///
+/// # Errors
+///
+/// Returns an error if the key cannot be serialized or if the gateway failed to purge the entity cache.
+pub fn purge_entity<K>(type_name: &str, key: &K) -> Result<(), Box<dyn std::error::Error>>
+where
+    K: serde::Serialize,
+{
+    purge_entities(&crate::wit::EntityCacheSelector::Entity(crate::wit::EntityCacheKey {
+        type_name: type_name.to_string(),
+        key: serde_json::to_string(key)?,
+    }))
+}
+
+fn purge_entities(selector: &crate::wit::EntityCacheSelector) -> Result<(), Box<dyn std::error::Error>> {
+    crate::wit::Cache::purge_entities(selector)?;
+
+    Ok(())
+}
+
 /// A value to be stored in the cache with an optional time-to-live duration.
 pub struct CachedItem<T> {
     value: T,
@@ -8,11 +8,11 @@ pub mod host_io;
 pub mod test;
 pub mod types;
 
-pub use extension::{Authenticator, Extension, Resolver};
-pub use grafbase_sdk_derive::{AuthenticationExtension, ResolverExtension};
+pub use extension::{Authenticator, Authorizer, Extension, Hooks, Resolver, Subscription};
+pub use grafbase_sdk_derive::{AuthenticationExtension, AuthorizationExtension, HooksExtension, ResolverExtension};
 #[doc(hidden)]
 pub use wit::ExtensionType;
-pub use wit::{Error, Headers, SharedContext};
+pub use wit::{Error, ExecutedHttpRequest, HeaderError, Headers, SharedContext};
 
 struct Component;
 
@@ -129,7 +129,84 @@ impl FieldInputs {
     }
 }
 
-/// Configuration data for the extension, from the gateway toml config.
+/// The schema definition an authorization directive is applied on.
+pub enum AuthorizedDefinition {
+    /// A field definition.
+    Field(FieldDefinition),
+    /// A type definition, with its name.
+    Type(String),
+}
+
+impl From<crate::wit::AuthorizedDefinition> for AuthorizedDefinition {
+    fn from(value: crate::wit::AuthorizedDefinition) -> Self {
+        match value {
+            crate::wit::AuthorizedDefinition::Field(definition) => Self::Field(definition.into()),
+            crate::wit::AuthorizedDefinition::TypeDefinition(name) => Self::Type(name),
+        }
+    }
+}
+
+/// An element of the query carrying one of the extension's authorization directives.
+pub struct QueryElement {
+    directive: Directive,
+    definition: AuthorizedDefinition,
+}
+
+impl QueryElement {
+    /// The authorization directive. Its arguments referencing the field arguments have them
+    /// already injected.
+    pub fn directive(&self) -> &Directive {
+        &self.directive
+    }
+
+    /// The definition the directive is applied on.
+    pub fn definition(&self) -> &AuthorizedDefinition {
+        &self.definition
+    }
+}
+
+impl From<crate::wit::QueryElement> for QueryElement {
+    fn from(value: crate::wit::QueryElement) -> Self {
+        Self {
+            directive: value.directive.into(),
+            definition: value.definition.into(),
+        }
+    }
+}
+
+/// The values of an authorized field, one for each parent object of the response.
+#[derive(Debug)]
+pub struct ResponseItems(Vec<Vec<u8>>);
+
+impl ResponseItems {
+    pub(crate) fn new(items: Vec<Vec<u8>>) -> Self {
+        Self(items)
+    }
+
+    /// The number of items.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Deserializes each item to a collection of values.
+    pub fn deserialize<'de, T>(&'de self) -> Result<Vec<T>, Box<dyn std::error::Error>>
+    where
+        T: Deserialize<'de>,
+    {
+        self.0
+            .iter()
+            .map(|item| minicbor_serde::from_slice(item).map_err(|e| Box::new(e) as Box<dyn std::error::Error>))
+            .collect()
+    }
+}
+
+/// Configuration data for the extension, from the gateway toml config. The gateway validates it
+/// at startup against the `config_schema` of the extension, if any.
 pub struct Configuration(Vec<u8>);
 
 impl Configuration {
@@ -38,6 +38,11 @@ impl Query {
         tokio::time::sleep(tokio::time::Duration::from_millis(ms.into())).await;
         Some(ms)
     }
+
+    async fn list_delay(&self, ms: u32, count: u32) -> Vec<u32> {
+        tokio::time::sleep(tokio::time::Duration::from_millis(ms.into())).await;
+        (0..count).collect()
+    }
 }
 
 #[async_trait::async_trait]
@@ -110,6 +110,10 @@ impl TestGateway {
         http::Response::from_parts(parts, bytes)
     }
 
+    pub fn runtime(&self) -> &TestRuntime {
+        &self.engine.runtime
+    }
+
     pub fn subgraph<S: graphql_mocks::Subgraph>(&self) -> &MockSubgraph {
         self.subgraphs.get_mock_by_type::<S>().unwrap()
     }
@@ -50,6 +50,23 @@ impl TestRequest {
         self
     }
 
+    pub fn query_param(mut self, name: &str, value: &str) -> Self {
+        let path = self.parts.uri.path().to_string();
+        let mut query = self
+            .parts
+            .uri
+            .query()
+            .map(|query| format!("{query}&"))
+            .unwrap_or_default();
+        query.push_str(&serde_urlencoded::to_string([(name, value)]).unwrap());
+
+        self.parts.uri = http::uri::Builder::from(std::mem::take(&mut self.parts.uri))
+            .path_and_query(format!("{path}?{query}"))
+            .build()
+            .unwrap();
+        self
+    }
+
     pub fn variables(mut self, variables: impl serde::Serialize) -> Self {
         self.body.variables = Some(serde_json::to_value(variables).expect("variables to be serializable"));
         self
@@ -82,11 +99,10 @@ impl TestRequest {
             body,
         } = self;
         if parts.method == http::Method::GET {
+            let mut query = parts.uri.query().map(|query| format!("{query}&")).unwrap_or_default();
+            query.push_str(&serde_urlencoded::to_string(body.into_query_params()).unwrap());
             parts.uri = http::uri::Builder::from(std::mem::take(&mut parts.uri))
-                .path_and_query(format!(
-                    "/graphql?{}",
-                    serde_urlencoded::to_string(body.into_query_params()).unwrap()
-                ))
+                .path_and_query(format!("/graphql?{query}"))
                 .build()
                 .unwrap();
             (
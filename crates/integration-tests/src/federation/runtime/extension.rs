@@ -1,10 +1,11 @@
 use std::{collections::HashMap, future::Future, sync::Arc};
 
-use engine_schema::SubgraphId;
+use engine_schema::{Subgraph, SubgraphId};
 use extension_catalog::{Extension, ExtensionCatalog, ExtensionId, Id, Manifest};
+use futures::{stream::BoxStream, StreamExt};
 use runtime::{
     error::{ErrorResponse, PartialGraphqlError},
-    extension::{Data, ExtensionFieldDirective},
+    extension::{Data, ExtensionAuthorizationDirective, ExtensionFieldDirective},
     hooks::{Anything, DynHookContext},
 };
 use tokio::sync::Mutex;
@@ -46,6 +47,7 @@ impl TestExtensions {
             license: None,
             readme: None,
             repository_url: None,
+            config_schema: None,
         };
         let dir = self.tmpdir.path().join(manifest.id.to_string());
         std::fs::create_dir(&dir).unwrap();
@@ -104,6 +106,59 @@ pub trait TestExtension: Send + Sync + 'static {
     ) -> Result<Vec<Result<serde_json::Value, PartialGraphqlError>>, PartialGraphqlError> {
         Err(PartialGraphqlError::internal_extension_error())
     }
+
+    async fn resolve_subscription<'a>(
+        &self,
+        context: &DynHookContext,
+        directive: ExtensionFieldDirective<'a, serde_json::Value>,
+    ) -> Result<BoxStream<'static, Result<serde_json::Value, PartialGraphqlError>>, PartialGraphqlError> {
+        Err(PartialGraphqlError::internal_extension_error())
+    }
+
+    async fn authorize_query<'a>(
+        &self,
+        context: &DynHookContext,
+        elements: Vec<ExtensionAuthorizationDirective<'a, serde_json::Value>>,
+    ) -> Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError> {
+        Err(PartialGraphqlError::internal_extension_error())
+    }
+
+    async fn authorize_response<'a>(
+        &self,
+        context: &DynHookContext,
+        directive: ExtensionAuthorizationDirective<'a, serde_json::Value>,
+        items: Vec<serde_json::Value>,
+    ) -> Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError> {
+        Ok(vec![Ok(())])
+    }
+}
+
+impl TestExtensions {
+    async fn get_global_instance(&self, extension_id: ExtensionId) -> Arc<dyn TestExtension> {
+        self.global_instances
+            .lock()
+            .await
+            .entry(extension_id)
+            .or_insert_with(|| self.builders.get(&extension_id).unwrap().build(Vec::new()))
+            .clone()
+    }
+
+    async fn get_subgraph_instance(&self, extension_id: ExtensionId, subgraph: Subgraph<'_>) -> Arc<dyn TestExtension> {
+        self.subgraph_instances
+            .lock()
+            .await
+            .entry((extension_id, subgraph.id()))
+            .or_insert_with(|| {
+                self.builders.get(&extension_id).unwrap().build(
+                    subgraph
+                        .extension_schema_directives()
+                        .filter(|dir| dir.extension_id == extension_id)
+                        .map(|dir| (dir.name(), serde_json::to_value(dir.static_arguments()).unwrap()))
+                        .collect(),
+                )
+            })
+            .clone()
+    }
 }
 
 impl runtime::extension::ExtensionRuntime for TestExtensions {
@@ -130,21 +185,7 @@ impl runtime::extension::ExtensionRuntime for TestExtensions {
             .collect::<Result<Vec<_>, _>>()
             .unwrap();
         async move {
-            let instance = self
-                .subgraph_instances
-                .lock()
-                .await
-                .entry((extension_id, subgraph.id()))
-                .or_insert_with(|| {
-                    self.builders.get(&extension_id).unwrap().build(
-                        subgraph
-                            .extension_schema_directives()
-                            .filter(|dir| dir.extension_id == extension_id)
-                            .map(|dir| (dir.name(), serde_json::to_value(dir.static_arguments()).unwrap()))
-                            .collect(),
-                    )
-                })
-                .clone();
+            let instance = self.get_subgraph_instance(extension_id, subgraph).await;
 
             instance
                 .resolve(
@@ -168,22 +209,126 @@ impl runtime::extension::ExtensionRuntime for TestExtensions {
         }
     }
 
+    fn resolve_subscription<'ctx, 'f>(
+        &'ctx self,
+        context: &'ctx Self::SharedContext,
+        ExtensionFieldDirective {
+            extension_id,
+            subgraph,
+            field,
+            name,
+            arguments,
+        }: ExtensionFieldDirective<'ctx, impl Anything<'ctx>>,
+    ) -> impl Future<Output = Result<BoxStream<'f, Result<Data, PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f,
+    {
+        let arguments = serde_json::to_value(arguments).unwrap();
+        async move {
+            let instance = self.get_subgraph_instance(extension_id, subgraph).await;
+
+            let stream = instance
+                .resolve_subscription(
+                    context,
+                    ExtensionFieldDirective {
+                        extension_id,
+                        subgraph,
+                        field,
+                        name,
+                        arguments,
+                    },
+                )
+                .await?;
+
+            Ok(stream
+                .map(|item| item.map(|value| Data::JsonBytes(serde_json::to_vec(&value).unwrap())))
+                .boxed())
+        }
+    }
+
     async fn authenticate(
         &self,
         extension_id: ExtensionId,
         _authorizer_id: runtime::extension::AuthorizerId,
         _headers: http::HeaderMap,
     ) -> Result<(http::HeaderMap, HashMap<String, serde_json::Value>), ErrorResponse> {
-        let _instance = self
-            .global_instances
-            .lock()
-            .await
-            .entry(extension_id)
-            .or_insert_with(|| self.builders.get(&extension_id).unwrap().build(Vec::new()))
-            .clone();
+        let _instance = self.get_global_instance(extension_id).await;
         Err(ErrorResponse {
             status: http::StatusCode::INTERNAL_SERVER_ERROR,
             errors: Vec::new(),
         })
     }
+
+    fn authorize_query<'ctx, 'f, Args: Anything<'ctx>>(
+        &'ctx self,
+        context: &'ctx Self::SharedContext,
+        extension_id: ExtensionId,
+        elements: Vec<ExtensionAuthorizationDirective<'ctx, Args>>,
+    ) -> impl Future<Output = Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f,
+    {
+        let elements = elements
+            .into_iter()
+            .map(
+                |ExtensionAuthorizationDirective {
+                     extension_id,
+                     subgraph,
+                     definition,
+                     name,
+                     arguments,
+                 }| ExtensionAuthorizationDirective {
+                    extension_id,
+                    subgraph,
+                    definition,
+                    name,
+                    arguments: serde_json::to_value(arguments).unwrap(),
+                },
+            )
+            .collect::<Vec<_>>();
+        async move {
+            let instance = self.get_global_instance(extension_id).await;
+            instance.authorize_query(context, elements).await
+        }
+    }
+
+    fn authorize_response<'ctx, 'resp, 'f>(
+        &'ctx self,
+        context: &'ctx Self::SharedContext,
+        ExtensionAuthorizationDirective {
+            extension_id,
+            subgraph,
+            definition,
+            name,
+            arguments,
+        }: ExtensionAuthorizationDirective<'ctx, impl Anything<'ctx>>,
+        items: impl IntoIterator<Item: Anything<'resp>> + Send,
+    ) -> impl Future<Output = Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f,
+    {
+        let arguments = serde_json::to_value(arguments).unwrap();
+        let items = items
+            .into_iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        async move {
+            let instance = self.get_global_instance(extension_id).await;
+
+            instance
+                .authorize_response(
+                    context,
+                    ExtensionAuthorizationDirective {
+                        extension_id,
+                        subgraph,
+                        definition,
+                        name,
+                        arguments,
+                    },
+                    items,
+                )
+                .await
+        }
+    }
 }
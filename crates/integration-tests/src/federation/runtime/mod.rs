@@ -26,12 +26,13 @@ pub struct TestRuntime {
     pub hooks: DynamicHooks,
     pub rate_limiter: runtime::rate_limiting::RateLimiter,
     pub entity_cache: InMemoryEntityCache,
+    pub response_cache: InMemoryEntityCache,
     pub extensions: TestExtensions,
 }
 
 impl TestRuntime {
     pub fn new(config: &Config) -> Self {
-        let (_, rx) = watch::channel(Default::default());
+        let (_, rx) = watch::channel(config.clone());
 
         Self {
             fetcher: DynamicFetcher::wrap(NativeFetcher::new(config).expect("couldnt construct NativeFetcher")),
@@ -41,6 +42,7 @@ impl TestRuntime {
             hooks: Default::default(),
             rate_limiter: InMemoryRateLimiter::runtime_with_watcher(rx),
             entity_cache: InMemoryEntityCache::default(),
+            response_cache: InMemoryEntityCache::default(),
             operation_cache: InMemoryOperationCache::default(),
             extensions: Default::default(),
         }
@@ -85,6 +87,10 @@ impl engine::Runtime for TestRuntime {
         &self.entity_cache
     }
 
+    fn response_cache(&self) -> &dyn EntityCache {
+        &self.response_cache
+    }
+
     fn metrics(&self) -> &EngineMetrics {
         &self.metrics
     }
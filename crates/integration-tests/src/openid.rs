@@ -11,6 +11,7 @@ pub const JWKS_URI_2: &str = "http://127.0.0.1:4454/.well-known/jwks.json";
 pub const AUDIENCE: &str = "integration-tests";
 pub const OTHER_AUDIENCE: &str = "other-audience";
 const HYDRA_ADMIN_URL: &str = "http://127.0.0.1:4445";
+pub const INTROSPECTION_URL: &str = "http://127.0.0.1:4445/admin/oauth2/introspect";
 // Second provider
 pub const ISSUER_2: &str = "http://127.0.0.1:4454";
 const HYDRA_2_ADMIN_URL: &str = "http://127.0.0.1:4455";
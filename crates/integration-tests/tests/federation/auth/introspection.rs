@@ -0,0 +1,252 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use axum::{extract::State, routing::post, Json, Router};
+use engine::Engine;
+use graphql_mocks::SecureSchema;
+use integration_tests::{
+    federation::EngineExt,
+    openid::{CoreClientExt, OryHydraOpenIDProvider, INTROSPECTION_URL, READ_SCOPE},
+    runtime,
+};
+
+/// Introspection endpoint answering that every token is inactive after `delay`, counting the
+/// requests it receives.
+async fn start_introspection_endpoint(delay: Duration) -> (String, Arc<AtomicUsize>) {
+    async fn introspect(State((delay, count)): State<(Duration, Arc<AtomicUsize>)>) -> Json<serde_json::Value> {
+        count.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(delay).await;
+        Json(serde_json::json!({ "active": false }))
+    }
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let app = Router::new()
+        .route("/introspect", post(introspect))
+        .with_state((delay, count.clone()));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/introspect", listener.local_addr().unwrap());
+
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (url, count)
+}
+
+#[test]
+fn active_token() {
+    runtime().block_on(async move {
+        let config = indoc::formatdoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.introspection]
+            url = "{INTROSPECTION_URL}"
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(SecureSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let token = OryHydraOpenIDProvider::default()
+            .create_client()
+            .await
+            .get_access_token_with_client_credentials(&[("scope", READ_SCOPE)])
+            .await;
+
+        // The second request is served from the cache.
+        let mut responses = Vec::new();
+        for _ in 0..2 {
+            let response = engine
+                .post("query { check { mustHaveReadScope } }")
+                .header("Authorization", format!("Bearer {token}"))
+                .await;
+            responses.push(response);
+        }
+        insta::assert_json_snapshot!(responses, @r###"
+        [
+          {
+            "data": {
+              "check": {
+                "mustHaveReadScope": "You have read scope"
+              }
+            }
+          },
+          {
+            "data": {
+              "check": {
+                "mustHaveReadScope": "You have read scope"
+              }
+            }
+          }
+        ]
+        "###);
+
+        let response = engine
+            .post("query { check { mustHaveWriteScope } }")
+            .header("Authorization", format!("Bearer {token}"))
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": null,
+          "errors": [
+            {
+              "message": "Insufficient scopes",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 17
+                }
+              ],
+              "path": [
+                "check",
+                "mustHaveWriteScope"
+              ],
+              "extensions": {
+                "code": "UNAUTHORIZED"
+              }
+            }
+          ]
+        }
+        "###);
+    });
+}
+
+#[test]
+fn inactive_token() {
+    runtime().block_on(async move {
+        let config = indoc::formatdoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.introspection]
+            url = "{INTROSPECTION_URL}"
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(SecureSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let response = engine
+            .post("query { check { mustHaveReadScope } }")
+            .header("Authorization", "Bearer not-a-token")
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Unauthenticated",
+              "extensions": {
+                "code": "UNAUTHENTICATED"
+              }
+            }
+          ]
+        }
+        "###);
+    });
+}
+
+#[test]
+fn inactive_token_is_cached() {
+    runtime().block_on(async move {
+        let (url, count) = start_introspection_endpoint(Duration::ZERO).await;
+
+        let config = indoc::formatdoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.introspection]
+            url = "{url}"
+            inactive_cache_ttl = "1m"
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(SecureSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        // The second request is served from the cache.
+        let mut responses = Vec::new();
+        for _ in 0..2 {
+            let response = engine
+                .post("query { check { mustHaveReadScope } }")
+                .header("Authorization", "Bearer not-a-token")
+                .await;
+            responses.push(response);
+        }
+        insta::assert_json_snapshot!(responses, @r###"
+        [
+          {
+            "errors": [
+              {
+                "message": "Unauthenticated",
+                "extensions": {
+                  "code": "UNAUTHENTICATED"
+                }
+              }
+            ]
+          },
+          {
+            "errors": [
+              {
+                "message": "Unauthenticated",
+                "extensions": {
+                  "code": "UNAUTHENTICATED"
+                }
+              }
+            ]
+          }
+        ]
+        "###);
+
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn introspection_timeout() {
+    runtime().block_on(async move {
+        let (url, count) = start_introspection_endpoint(Duration::from_secs(30)).await;
+
+        let config = indoc::formatdoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.introspection]
+            url = "{url}"
+            timeout = "100ms"
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(SecureSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let start = Instant::now();
+        let response = engine
+            .post("query { check { mustHaveReadScope } }")
+            .header("Authorization", "Bearer some-token")
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Unauthenticated",
+              "extensions": {
+                "code": "UNAUTHENTICATED"
+              }
+            }
+          ]
+        }
+        "###);
+
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    });
+}
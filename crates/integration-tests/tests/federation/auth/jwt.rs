@@ -6,7 +6,7 @@ use integration_tests::federation::GraphqlResponse;
 use integration_tests::openid::{CoreClientExt, OryHydraOpenIDProvider};
 use integration_tests::{
     federation::EngineExt,
-    openid::{AUDIENCE, JWKS_URI, OTHER_AUDIENCE},
+    openid::{AUDIENCE, JWKS_URI, OTHER_AUDIENCE, READ_SCOPE},
     runtime,
 };
 
@@ -92,6 +92,98 @@ fn test_different_header_location() {
     });
 }
 
+#[test]
+fn test_cookie_location() {
+    runtime().block_on(async move {
+        let config = indoc::formatdoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt]
+            name = "my-jwt"
+            cookie = "session"
+
+            [authentication.providers.jwt.jwks]
+            url = "{JWKS_URI}"
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let token = OryHydraOpenIDProvider::default()
+            .create_client()
+            .await
+            .get_access_token_with_client_credentials(&[])
+            .await;
+
+        let response: GraphqlResponse = engine
+            .post("query { serverVersion }")
+            .header("Cookie", format!("theme=dark; session={token}"))
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+    });
+}
+
+#[test]
+fn test_query_parameter_location() {
+    runtime().block_on(async move {
+        let config = indoc::formatdoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt]
+            name = "my-jwt"
+            query_parameter = "access_token"
+
+            [authentication.providers.jwt.jwks]
+            url = "{JWKS_URI}"
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let token = OryHydraOpenIDProvider::default()
+            .create_client()
+            .await
+            .get_access_token_with_client_credentials(&[])
+            .await;
+
+        let response: GraphqlResponse = engine
+            .post("query { serverVersion }")
+            .query_param("access_token", &token)
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+
+        let response: GraphqlResponse = engine
+            .get("query { serverVersion }")
+            .query_param("access_token", &token)
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+    });
+}
+
 #[test]
 fn test_unauthorized() {
     runtime().block_on(async move {
@@ -361,3 +453,274 @@ fn test_audience() {
         "###);
     });
 }
+
+#[test]
+fn test_algorithms() {
+    runtime().block_on(async move {
+        let token = OryHydraOpenIDProvider::default()
+            .create_client()
+            .await
+            .get_access_token_with_client_credentials(&[])
+            .await;
+
+        // Hydra signs its tokens with RS256.
+        for (algorithms, authenticated) in [(r#"["RS256", "ES256"]"#, true), (r#"["ES256"]"#, false)] {
+            let config = indoc::formatdoc! {r#"
+                [[authentication.providers]]
+
+                [authentication.providers.jwt]
+                name = "my-jwt"
+                algorithms = {algorithms}
+
+                [authentication.providers.jwt.jwks]
+                url = "{JWKS_URI}"
+            "#};
+
+            let engine = Engine::builder()
+                .with_subgraph(FakeGithubSchema)
+                .with_toml_config(config)
+                .build()
+                .await;
+
+            let response: GraphqlResponse = engine
+                .post("query { serverVersion }")
+                .header("Authorization", format!("Bearer {token}"))
+                .await;
+
+            assert_eq!(response.errors().is_empty(), authenticated, "{algorithms}");
+        }
+    });
+}
+
+#[test]
+fn test_required_claims() {
+    runtime().block_on(async move {
+        let config = indoc::formatdoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt]
+            name = "my-jwt"
+
+            [authentication.providers.jwt.jwks]
+            url = "{JWKS_URI}"
+
+            [authentication.providers.jwt.required_claims]
+            scp = "{READ_SCOPE}"
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let token = OryHydraOpenIDProvider::default()
+            .create_client()
+            .await
+            .get_access_token_with_client_credentials(&[("scope", READ_SCOPE)])
+            .await;
+
+        let response: GraphqlResponse = engine
+            .post("query { serverVersion }")
+            .header("Authorization", format!("Bearer {token}"))
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+
+        // Missing scope
+        let token = OryHydraOpenIDProvider::default()
+            .create_client()
+            .await
+            .get_access_token_with_client_credentials(&[])
+            .await;
+
+        let response: GraphqlResponse = engine
+            .post("query { serverVersion }")
+            .header("Authorization", format!("Bearer {token}"))
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Unauthenticated",
+              "extensions": {
+                "code": "UNAUTHENTICATED"
+              }
+            }
+          ]
+        }
+        "###);
+    });
+}
+
+fn hs256_token(secret: &[u8]) -> String {
+    use jwt_compact::{
+        alg::{Hs256, Hs256Key},
+        AlgorithmExt, Claims, Header, TimeOptions,
+    };
+
+    let claims = Claims::new(serde_json::json!({ "sub": "me" }))
+        .set_duration_and_issuance(&TimeOptions::default(), chrono::Duration::minutes(5));
+
+    Hs256.token(&Header::empty(), &claims, &Hs256Key::new(secret)).unwrap()
+}
+
+#[test]
+fn test_hmac_secret() {
+    runtime().block_on(async move {
+        let config = indoc::indoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt]
+            name = "my-jwt"
+
+            [authentication.providers.jwt.jwks]
+            secret = "my-secret"
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let response: GraphqlResponse = engine
+            .post("query { serverVersion }")
+            .header("Authorization", format!("Bearer {}", hs256_token(b"my-secret")))
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+
+        let response: GraphqlResponse = engine
+            .post("query { serverVersion }")
+            .header("Authorization", format!("Bearer {}", hs256_token(b"other-secret")))
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Unauthenticated",
+              "extensions": {
+                "code": "UNAUTHENTICATED"
+              }
+            }
+          ]
+        }
+        "###);
+    });
+}
+
+#[test]
+fn test_inline_keys() {
+    runtime().block_on(async move {
+        use base64::{engine::general_purpose, Engine as _};
+
+        let k = general_purpose::URL_SAFE_NO_PAD.encode("my-secret");
+        let config = indoc::formatdoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt]
+            name = "my-jwt"
+
+            [authentication.providers.jwt.jwks]
+            keys = [{{ kty = "oct", k = "{k}" }}]
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let response: GraphqlResponse = engine
+            .post("query { serverVersion }")
+            .header("Authorization", format!("Bearer {}", hs256_token(b"my-secret")))
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+    });
+}
+
+#[test]
+fn test_invalid_inline_keys() {
+    runtime().block_on(async move {
+        let config = indoc::indoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt]
+            name = "my-jwt"
+
+            [authentication.providers.jwt.jwks]
+            keys = [{ kty = "oct" }]
+        "#};
+
+        let result = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(config)
+            .try_build()
+            .await;
+
+        insta::assert_debug_snapshot!(result.err(), @r#"
+        Some(
+            "Invalid JSON Web Key at index 0: missing field `k`",
+        )
+        "#);
+    });
+}
+
+#[test]
+fn test_jwks_file() {
+    runtime().block_on(async move {
+        use base64::{engine::general_purpose, Engine as _};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jwks.json");
+        let jwks = serde_json::json!({
+            "keys": [{ "kty": "oct", "k": general_purpose::URL_SAFE_NO_PAD.encode("my-secret") }]
+        });
+        std::fs::write(&path, serde_json::to_vec(&jwks).unwrap()).unwrap();
+
+        let config = indoc::formatdoc! {r#"
+            [[authentication.providers]]
+
+            [authentication.providers.jwt]
+            name = "my-jwt"
+
+            [authentication.providers.jwt.jwks]
+            path = "{}"
+        "#, path.display()};
+
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let response: GraphqlResponse = engine
+            .post("query { serverVersion }")
+            .header("Authorization", format!("Bearer {}", hs256_token(b"my-secret")))
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+    });
+}
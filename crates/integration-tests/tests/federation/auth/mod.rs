@@ -1,4 +1,5 @@
 mod authenticated;
+mod introspection;
 mod jwt;
 mod multiple;
 mod requires_scopes;
@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use engine::Engine;
+use graphql_mocks::{FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema};
+use headers::{CacheControl, HeaderMapExt};
+use integration_tests::{federation::EngineExt, runtime};
+
+struct CacheControlProductSubgraph(CacheControl);
+
+impl graphql_mocks::Subgraph for CacheControlProductSubgraph {
+    fn name(&self) -> String {
+        "products".into()
+    }
+
+    async fn start(self) -> graphql_mocks::MockGraphQlServer {
+        FederatedProductsSchema.start().await.with_additional_header(self.0)
+    }
+}
+
+struct CacheControlReviewSubgraph(CacheControl);
+
+impl graphql_mocks::Subgraph for CacheControlReviewSubgraph {
+    fn name(&self) -> String {
+        "reviews".into()
+    }
+
+    async fn start(self) -> graphql_mocks::MockGraphQlServer {
+        FederatedReviewsSchema.start().await.with_additional_header(self.0)
+    }
+}
+
+#[test]
+fn get_query_uses_the_smallest_max_age() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(CacheControlProductSubgraph(
+                CacheControl::new().with_max_age(Duration::from_secs(60)),
+            ))
+            .with_subgraph(CacheControlReviewSubgraph(
+                CacheControl::new().with_max_age(Duration::from_secs(30)),
+            ))
+            .with_subgraph(FederatedInventorySchema)
+            .build()
+            .await;
+
+        let response = engine.get("{ topProducts { upc reviews { id } } }").await;
+        assert_eq!(response.status, 200);
+
+        let cache_control = response.headers.typed_get::<CacheControl>().unwrap();
+        assert!(cache_control.public());
+        assert!(!cache_control.private());
+        assert_eq!(cache_control.max_age(), Some(Duration::from_secs(30)));
+    })
+}
+
+#[test]
+fn get_query_is_private_if_any_fetch_was() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(CacheControlProductSubgraph(
+                CacheControl::new().with_max_age(Duration::from_secs(60)),
+            ))
+            .with_subgraph(CacheControlReviewSubgraph(
+                CacheControl::new()
+                    .with_private()
+                    .with_max_age(Duration::from_secs(120)),
+            ))
+            .with_subgraph(FederatedInventorySchema)
+            .build()
+            .await;
+
+        let response = engine.get("{ topProducts { upc reviews { id } } }").await;
+
+        let cache_control = response.headers.typed_get::<CacheControl>().unwrap();
+        assert!(cache_control.private());
+        assert_eq!(cache_control.max_age(), Some(Duration::from_secs(60)));
+    })
+}
+
+#[test]
+fn get_query_without_max_age_must_be_revalidated() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FederatedProductsSchema).build().await;
+
+        let response = engine.get("{ topProducts { upc } }").await;
+
+        let cache_control = response.headers.typed_get::<CacheControl>().unwrap();
+        assert!(cache_control.no_cache());
+        assert_eq!(cache_control.max_age(), None);
+    })
+}
+
+#[test]
+fn get_query_with_errors_is_not_stored() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(CacheControlProductSubgraph(
+                CacheControl::new().with_max_age(Duration::from_secs(60)),
+            ))
+            .build()
+            .await;
+
+        let response = engine.get("{ topProducts { unknown } }").await;
+
+        let cache_control = response.headers.typed_get::<CacheControl>().unwrap();
+        assert!(cache_control.no_store());
+    })
+}
+
+#[test]
+fn post_responses_have_no_cache_control() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(CacheControlProductSubgraph(
+                CacheControl::new().with_max_age(Duration::from_secs(60)),
+            ))
+            .build()
+            .await;
+
+        let response = engine.post("{ topProducts { upc } }").await;
+
+        assert!(response.headers.get(http::header::CACHE_CONTROL).is_none());
+    })
+}
+
+#[test]
+fn get_query_must_be_revalidated_if_any_subgraph_has_no_cache_control() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(CacheControlProductSubgraph(
+                CacheControl::new().with_max_age(Duration::from_secs(60)),
+            ))
+            .with_subgraph(FederatedReviewsSchema)
+            .with_subgraph(FederatedInventorySchema)
+            .build()
+            .await;
+
+        let response = engine.get("{ topProducts { upc reviews { id } } }").await;
+
+        let cache_control = response.headers.typed_get::<CacheControl>().unwrap();
+        assert!(cache_control.no_cache());
+        assert_eq!(cache_control.max_age(), None);
+    })
+}
+
+#[test]
+fn get_query_served_from_the_entity_cache_uses_the_remaining_ttl() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [entity_caching]
+                enabled = true
+                ttl = "30s"
+                "#,
+            )
+            .build()
+            .await;
+
+        let miss = engine.get("{ topProducts { upc } }").await;
+        let hit = engine.get("{ topProducts { upc } }").await;
+
+        let miss = miss.headers.typed_get::<CacheControl>().unwrap();
+        assert!(miss.no_cache());
+
+        let hit = hit.headers.typed_get::<CacheControl>().unwrap();
+        assert!(hit.public());
+        assert!(hit
+            .max_age()
+            .is_some_and(|max_age| !max_age.is_zero() && max_age <= Duration::from_secs(30)));
+    })
+}
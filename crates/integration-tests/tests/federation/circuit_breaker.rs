@@ -0,0 +1,111 @@
+use engine::Engine;
+use graphql_mocks::Stateful;
+use integration_tests::{federation::EngineExt, runtime};
+
+#[test]
+fn open_circuit_rejects_requests_without_calling_the_subgraph() {
+    runtime().block_on(async move {
+        let config = indoc::indoc! {r#"
+            [subgraphs.stateful.circuit_breaker]
+            enabled = true
+            min_requests = 2
+            failure_rate = 0.5
+            open_duration = "1h"
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(Stateful::default())
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        for _ in 0..2 {
+            let response = engine.post("query { incrementAndFailIfLessThan(n: 100) }").await;
+            assert_eq!(
+                response.errors()[0]["extensions"]["code"],
+                "SUBGRAPH_REQUEST_ERROR",
+                "{response}"
+            );
+        }
+        assert_eq!(engine.drain_http_requests_sent_to::<Stateful>().len(), 2);
+
+        let response = engine.post("query { value }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": null,
+          "errors": [
+            {
+              "message": "Subgraph 'stateful' is unavailable, its circuit breaker is open",
+              "path": [
+                "value"
+              ],
+              "extensions": {
+                "code": "SUBGRAPH_UNAVAILABLE"
+              }
+            }
+          ]
+        }
+        "#);
+        assert!(engine.drain_http_requests_sent_to::<Stateful>().is_empty());
+    })
+}
+
+#[test]
+fn circuit_stays_closed_below_the_failure_rate() {
+    runtime().block_on(async move {
+        let config = indoc::indoc! {r#"
+            [gateway.circuit_breaker]
+            enabled = true
+            min_requests = 4
+            failure_rate = 0.5
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(Stateful::default())
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let response = engine.post("query { incrementAndFailIfLessThan(n: 1) }").await;
+        assert_eq!(
+            response.errors()[0]["extensions"]["code"],
+            "SUBGRAPH_REQUEST_ERROR",
+            "{response}"
+        );
+
+        for _ in 0..5 {
+            let response = engine.post("query { value }").await;
+            assert!(response.errors().is_empty(), "{response}");
+        }
+    })
+}
+
+#[test]
+fn subgraph_can_disable_the_global_circuit_breaker() {
+    runtime().block_on(async move {
+        let config = indoc::indoc! {r#"
+            [gateway.circuit_breaker]
+            enabled = true
+            min_requests = 1
+
+            [subgraphs.stateful.circuit_breaker]
+            enabled = false
+        "#};
+
+        let engine = Engine::builder()
+            .with_subgraph(Stateful::default())
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        for _ in 0..3 {
+            let response = engine.post("query { incrementAndFailIfLessThan(n: 100) }").await;
+            assert_eq!(
+                response.errors()[0]["extensions"]["code"],
+                "SUBGRAPH_REQUEST_ERROR",
+                "{response}"
+            );
+        }
+        assert_eq!(engine.drain_http_requests_sent_to::<Stateful>().len(), 3);
+    })
+}
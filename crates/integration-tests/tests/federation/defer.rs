@@ -0,0 +1,162 @@
+use engine::Engine;
+use graphql_mocks::{FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema, SlowSchema};
+use integration_tests::{federation::EngineExt, runtime};
+
+const QUERY: &str = r#"
+    query {
+        topProducts {
+            upc
+            name
+            ... @defer(label: "reviews") {
+                reviews {
+                    body
+                }
+            }
+        }
+    }
+"#;
+
+#[test]
+fn defer_is_ignored_with_complete_response_format() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .with_subgraph(FederatedInventorySchema)
+            .build()
+            .await;
+
+        let deferred = engine.post(QUERY).await.into_data();
+        let not_deferred = engine
+            .post(QUERY.replace(r#"@defer(label: "reviews")"#, ""))
+            .await
+            .into_data();
+
+        assert_eq!(deferred, not_deferred);
+    })
+}
+
+#[test]
+fn defer_delivers_fragment_in_subsequent_payloads() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .with_subgraph(FederatedInventorySchema)
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).into_multipart_stream().await.collect().await;
+        let messages = response.messages;
+        assert!(messages.len() >= 2, "{messages:#?}");
+
+        let initial = &messages[0];
+        assert_eq!(initial["hasNext"], true);
+        let products = initial["data"]["topProducts"].as_array().unwrap();
+        assert!(!products.is_empty());
+        for product in products {
+            assert!(product.get("upc").is_some());
+            assert!(product.get("reviews").is_none());
+        }
+
+        let results = messages[1..]
+            .iter()
+            .flat_map(|message| message["incremental"].as_array().cloned().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), products.len());
+        for result in results {
+            assert_eq!(result["label"], "reviews");
+            assert_eq!(result["path"][0], "topProducts");
+            assert!(result["data"]["reviews"].is_array());
+        }
+
+        assert_eq!(messages.last().unwrap()["hasNext"], false);
+    })
+}
+
+#[test]
+fn deferred_root_fields_of_the_same_subgraph_are_delivered_separately() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(SlowSchema).build().await;
+
+        let response = engine
+            .post(
+                r#"
+                query {
+                    fast: delay(ms: 0)
+                    ... @defer(label: "slow") {
+                        slow: delay(ms: 200)
+                    }
+                }
+                "#,
+            )
+            .into_multipart_stream()
+            .await
+            .collect()
+            .await;
+        let messages = response.messages;
+        assert_eq!(messages.len(), 2, "{messages:#?}");
+
+        let initial = &messages[0];
+        assert_eq!(initial["hasNext"], true);
+        assert_eq!(initial["data"], serde_json::json!({ "fast": 0 }));
+
+        let results = messages[1]["incremental"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["label"], "slow");
+        assert_eq!(results[0]["path"], serde_json::json!([]));
+        assert_eq!(results[0]["data"], serde_json::json!({ "slow": 200 }));
+        assert_eq!(messages[1]["hasNext"], false);
+    })
+}
+
+#[test]
+fn defer_with_false_condition_is_not_deferred() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .with_subgraph(FederatedInventorySchema)
+            .build()
+            .await;
+
+        let response = engine
+            .post(QUERY.replace(r#"@defer(label: "reviews")"#, "@defer(if: false)"))
+            .into_multipart_stream()
+            .await
+            .collect()
+            .await;
+
+        assert_eq!(response.messages.len(), 1);
+        assert!(response.messages[0].get("hasNext").is_none());
+        assert!(response.messages[0]["data"]["topProducts"][0]["reviews"].is_array());
+    })
+}
+
+#[test]
+fn defer_is_not_allowed_on_fields() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FederatedProductsSchema).build().await;
+
+        engine.post("query { topProducts { upc @defer } }").await
+    });
+
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "errors": [
+        {
+          "message": "Directive '@defer' can only be used on fragment spreads and inline fragments",
+          "locations": [
+            {
+              "line": 1,
+              "column": 27
+            }
+          ],
+          "extensions": {
+            "code": "OPERATION_VALIDATION_ERROR"
+          }
+        }
+      ]
+    }
+    "#);
+}
@@ -6,6 +6,8 @@ use integration_tests::{federation::EngineExt, runtime};
 use serde_json::json;
 
 mod directive_scopes;
+mod mutation_invalidation;
+mod purge;
 mod redis;
 mod subgraph_cache_control;
 
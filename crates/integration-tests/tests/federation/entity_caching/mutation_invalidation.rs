@@ -0,0 +1,159 @@
+use engine::Engine;
+use graphql_mocks::{
+    dynamic::DynamicSchema, FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema,
+};
+use integration_tests::{
+    federation::{EngineExt, TestGateway},
+    runtime,
+};
+use serde_json::json;
+
+const QUERY: &str = "{ topProducts { upc reviews { id body } } }";
+
+async fn build_engine(config: &str) -> TestGateway {
+    Engine::builder()
+        .with_subgraph(FederatedProductsSchema)
+        .with_subgraph(FederatedReviewsSchema)
+        .with_subgraph(FederatedInventorySchema)
+        .with_subgraph(
+            DynamicSchema::builder(
+                r#"
+                type Query {
+                    adminVersion: String
+                }
+
+                type Mutation {
+                    updateProduct(upc: String!): Product!
+                }
+
+                type Product @key(fields: "upc") {
+                    upc: String!
+                }
+                "#,
+            )
+            .with_resolver("Mutation", "updateProduct", json!({ "upc": "top-1" }))
+            .into_subgraph("admin"),
+        )
+        .with_toml_config(config)
+        .build()
+        .await
+}
+
+#[test]
+fn mutated_entities_are_purged() {
+    runtime().block_on(async move {
+        let engine = build_engine(
+            r#"
+            [entity_caching]
+            enabled = true
+            invalidate_on_mutation = true
+            "#,
+        )
+        .await;
+
+        let response = engine.post(QUERY).await.into_data();
+        engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>();
+
+        engine
+            .post(r#"mutation { updateProduct(upc: "top-1") { upc } }"#)
+            .await
+            .into_data();
+
+        assert_eq!(engine.post(QUERY).await.into_data(), response);
+
+        let requests = engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>();
+        assert_eq!(requests.len(), 1);
+
+        let variables = serde_json::to_value(&requests[0].variables).unwrap();
+        assert_eq!(variables["var0"], json!([{ "__typename": "Product", "upc": "top-1" }]));
+    })
+}
+
+#[test]
+fn mutated_entities_with_requires_are_purged() {
+    runtime().block_on(async move {
+        let engine = build_engine(
+            r#"
+            [entity_caching]
+            enabled = true
+            invalidate_on_mutation = true
+            "#,
+        )
+        .await;
+        let query = "{ topProducts { upc shippingEstimate } }";
+
+        let response = engine.post(query).await.into_data();
+        engine.drain_graphql_requests_sent_to::<FederatedInventorySchema>();
+
+        engine
+            .post(r#"mutation { updateProduct(upc: "top-1") { upc } }"#)
+            .await
+            .into_data();
+
+        assert_eq!(engine.post(query).await.into_data(), response);
+
+        // Only the mutated entity is fetched again, even though its representation also holds the
+        // required weight.
+        let requests = engine.drain_graphql_requests_sent_to::<FederatedInventorySchema>();
+        assert_eq!(requests.len(), 1);
+
+        let variables = serde_json::to_value(&requests[0].variables).unwrap();
+        let representations = variables["var0"].as_array().unwrap();
+        assert_eq!(representations.len(), 1);
+        assert_eq!(representations[0]["upc"], "top-1");
+    })
+}
+
+#[test]
+fn mutated_entities_are_kept_by_default() {
+    runtime().block_on(async move {
+        let engine = build_engine(
+            r#"
+            [entity_caching]
+            enabled = true
+            "#,
+        )
+        .await;
+
+        let response = engine.post(QUERY).await.into_data();
+        engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>();
+
+        engine
+            .post(r#"mutation { updateProduct(upc: "top-1") { upc } }"#)
+            .await
+            .into_data();
+
+        assert_eq!(engine.post(QUERY).await.into_data(), response);
+        assert!(engine
+            .drain_graphql_requests_sent_to::<FederatedReviewsSchema>()
+            .is_empty());
+    })
+}
+
+#[test]
+fn aliased_key_fields_are_purged() {
+    runtime().block_on(async move {
+        let engine = build_engine(
+            r#"
+            [entity_caching]
+            enabled = true
+            invalidate_on_mutation = true
+            "#,
+        )
+        .await;
+
+        engine.post(QUERY).await.into_data();
+        engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>();
+
+        engine
+            .post(r#"mutation { updateProduct(upc: "top-1") { code: upc } }"#)
+            .await
+            .into_data();
+
+        engine.post(QUERY).await.into_data();
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>().len(),
+            1
+        );
+    })
+}
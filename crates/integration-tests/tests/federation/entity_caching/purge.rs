@@ -0,0 +1,138 @@
+use engine::Engine;
+use graphql_mocks::{FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{
+    federation::{EngineExt, TestGateway},
+    runtime,
+};
+use runtime::entity_cache::{EntityCache, EntityCacheTag};
+use serde_json::json;
+
+const QUERY: &str = "{ topProducts { upc reviews { id body } } }";
+
+async fn build_engine() -> TestGateway {
+    Engine::builder()
+        .with_subgraph(FederatedProductsSchema)
+        .with_subgraph(FederatedReviewsSchema)
+        .with_subgraph(FederatedInventorySchema)
+        .with_toml_config(
+            r#"
+            [entity_caching]
+            enabled = true
+            "#,
+        )
+        .build()
+        .await
+}
+
+async fn purge(engine: &TestGateway, tag: EntityCacheTag<'_>) {
+    engine.runtime().entity_cache.purge(&[tag.to_string()]).await.unwrap();
+}
+
+#[test]
+fn purge_by_type() {
+    runtime().block_on(async move {
+        let engine = build_engine().await;
+
+        let response = engine.post(QUERY).await.into_data();
+        engine.post(QUERY).await;
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>().len(),
+            1
+        );
+
+        purge(&engine, EntityCacheTag::Type("Product")).await;
+
+        assert_eq!(engine.post(QUERY).await.into_data(), response);
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>().len(),
+            1
+        );
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+    })
+}
+
+#[test]
+fn purge_by_key() {
+    runtime().block_on(async move {
+        let engine = build_engine().await;
+
+        let response = engine.post(QUERY).await.into_data();
+        engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>();
+
+        purge(
+            &engine,
+            EntityCacheTag::Entity {
+                typename: "Product",
+                key: &json!({ "upc": "top-1" }),
+            },
+        )
+        .await;
+
+        assert_eq!(engine.post(QUERY).await.into_data(), response);
+
+        let requests = engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>();
+        assert_eq!(requests.len(), 1);
+
+        let variables = serde_json::to_value(&requests[0].variables).unwrap();
+        assert_eq!(variables["var0"], json!([{ "__typename": "Product", "upc": "top-1" }]));
+    })
+}
+
+#[test]
+fn purge_by_key_with_requires() {
+    runtime().block_on(async move {
+        let engine = build_engine().await;
+        let query = "{ topProducts { upc shippingEstimate } }";
+
+        let response = engine.post(query).await.into_data();
+        engine.drain_graphql_requests_sent_to::<FederatedInventorySchema>();
+
+        // The inventory subgraph requires the weight, which is part of the representation but
+        // not of the key.
+        purge(
+            &engine,
+            EntityCacheTag::Entity {
+                typename: "Product",
+                key: &json!({ "upc": "top-1" }),
+            },
+        )
+        .await;
+
+        assert_eq!(engine.post(query).await.into_data(), response);
+
+        let requests = engine.drain_graphql_requests_sent_to::<FederatedInventorySchema>();
+        assert_eq!(requests.len(), 1);
+
+        let variables = serde_json::to_value(&requests[0].variables).unwrap();
+        let representations = variables["var0"].as_array().unwrap();
+        assert_eq!(representations.len(), 1);
+        assert_eq!(representations[0]["upc"], "top-1");
+        assert!(representations[0].get("weight").is_some());
+    })
+}
+
+#[test]
+fn purge_by_subgraph() {
+    runtime().block_on(async move {
+        let engine = build_engine().await;
+
+        let response = engine.post(QUERY).await.into_data();
+        engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>();
+        engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>();
+
+        purge(&engine, EntityCacheTag::Subgraph("products")).await;
+
+        assert_eq!(engine.post(QUERY).await.into_data(), response);
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+        assert!(engine
+            .drain_graphql_requests_sent_to::<FederatedReviewsSchema>()
+            .is_empty());
+    })
+}
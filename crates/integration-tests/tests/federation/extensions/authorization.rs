@@ -0,0 +1,259 @@
+use std::sync::Arc;
+
+use engine::Engine;
+use extension_catalog::Id;
+use graphql_mocks::dynamic::DynamicSchema;
+use integration_tests::{
+    federation::{EngineExt, TestExtension, TestExtensionBuilder, TestExtensionConfig},
+    runtime,
+};
+use runtime::{
+    error::{PartialErrorCode, PartialGraphqlError},
+    extension::ExtensionAuthorizationDirective,
+    hooks::DynHookContext,
+};
+use serde_json::json;
+
+/// Denies every element requiring the `admin` role and every response item equal to `"secret"`.
+#[derive(Default)]
+struct RoleExt;
+
+impl TestExtensionBuilder for RoleExt {
+    fn id(&self) -> Id {
+        Id {
+            name: "role".to_string(),
+            version: "1.0.0".parse().unwrap(),
+        }
+    }
+
+    fn config(&self) -> TestExtensionConfig {
+        TestExtensionConfig {
+            kind: extension_catalog::Kind::Authorizer(extension_catalog::Authorizer {
+                authorization_directives: vec!["role".to_string()],
+            }),
+            sdl: Some(
+                r#"
+                directive @role(name: String!) on FIELD_DEFINITION | OBJECT
+                "#,
+            ),
+        }
+    }
+
+    fn build(&self, _schema_directives: Vec<(&str, serde_json::Value)>) -> Arc<dyn TestExtension> {
+        Arc::new(RoleExt)
+    }
+}
+
+fn forbidden() -> PartialGraphqlError {
+    PartialGraphqlError::new("Forbidden", PartialErrorCode::Unauthorized)
+}
+
+#[async_trait::async_trait]
+impl TestExtension for RoleExt {
+    async fn authorize_query<'a>(
+        &self,
+        _context: &DynHookContext,
+        elements: Vec<ExtensionAuthorizationDirective<'a, serde_json::Value>>,
+    ) -> Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError> {
+        Ok(elements
+            .into_iter()
+            .map(|element| {
+                if element.arguments["name"] == "admin" {
+                    Err(forbidden())
+                } else {
+                    Ok(())
+                }
+            })
+            .collect())
+    }
+
+    async fn authorize_response<'a>(
+        &self,
+        _context: &DynHookContext,
+        _directive: ExtensionAuthorizationDirective<'a, serde_json::Value>,
+        items: Vec<serde_json::Value>,
+    ) -> Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError> {
+        Ok(items
+            .into_iter()
+            .map(|item| if item == "secret" { Err(forbidden()) } else { Ok(()) })
+            .collect())
+    }
+}
+
+fn subgraph() -> graphql_mocks::dynamic::DynamicSubgraph {
+    DynamicSchema::builder(
+        r#"
+        extend schema
+            @link(url: "role-1.0.0", import: ["@role"])
+
+        type Query {
+            users: [User!]!
+            audit: Audit
+        }
+
+        type User {
+            name: String!
+            email: String @role(name: "admin")
+            nickname: String @role(name: "user")
+        }
+
+        type Audit @role(name: "admin") {
+            id: ID!
+        }
+        "#,
+    )
+    .with_resolver(
+        "Query",
+        "users",
+        json!([
+            {"name": "Alice", "email": "alice@example.com", "nickname": "ali"},
+            {"name": "Bob", "email": "bob@example.com", "nickname": "secret"}
+        ]),
+    )
+    .with_resolver("Query", "audit", json!({"id": "1"}))
+    .into_subgraph("x")
+}
+
+#[test]
+fn field_denied_before_execution() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(subgraph())
+            .with_extension(RoleExt)
+            .build()
+            .await;
+
+        let response = engine.post("query { users { name email } }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "users": [
+              {
+                "name": "Alice",
+                "email": null
+              },
+              {
+                "name": "Bob",
+                "email": null
+              }
+            ]
+          },
+          "errors": [
+            {
+              "message": "Forbidden",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 22
+                }
+              ],
+              "path": [
+                "users",
+                0,
+                "email"
+              ],
+              "extensions": {
+                "code": "UNAUTHORIZED"
+              }
+            },
+            {
+              "message": "Forbidden",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 22
+                }
+              ],
+              "path": [
+                "users",
+                1,
+                "email"
+              ],
+              "extensions": {
+                "code": "UNAUTHORIZED"
+              }
+            }
+          ]
+        }
+        "#);
+    });
+}
+
+#[test]
+fn type_denied_before_execution() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(subgraph())
+            .with_extension(RoleExt)
+            .build()
+            .await;
+
+        let response = engine.post("query { audit { id } }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "audit": null
+          },
+          "errors": [
+            {
+              "message": "Forbidden",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 9
+                }
+              ],
+              "path": [
+                "audit"
+              ],
+              "extensions": {
+                "code": "UNAUTHORIZED"
+              }
+            }
+          ]
+        }
+        "#);
+    });
+}
+
+#[test]
+fn field_denied_after_execution() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(subgraph())
+            .with_extension(RoleExt)
+            .build()
+            .await;
+
+        let response = engine.post("query { users { name nickname } }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "users": [
+              {
+                "name": "Alice",
+                "nickname": "ali"
+              },
+              {
+                "name": "Bob",
+                "nickname": null
+              }
+            ]
+          },
+          "errors": [
+            {
+              "message": "Forbidden",
+              "path": [
+                "users",
+                1,
+                "nickname"
+              ],
+              "extensions": {
+                "code": "UNAUTHORIZED"
+              }
+            }
+          ]
+        }
+        "#);
+    });
+}
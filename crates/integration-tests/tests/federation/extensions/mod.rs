@@ -1,4 +1,6 @@
+mod authorization;
 mod basic;
 mod injection;
 mod subgraph;
+mod subscription;
 mod validation;
@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use engine::Engine;
+use extension_catalog::Id;
+use futures::{stream::BoxStream, StreamExt};
+use integration_tests::{
+    federation::{EngineExt, TestExtension, TestExtensionBuilder, TestExtensionConfig},
+    runtime,
+};
+use runtime::{error::PartialGraphqlError, extension::ExtensionFieldDirective, hooks::DynHookContext};
+
+#[derive(Default, Clone)]
+pub struct TickExt;
+
+impl TestExtensionBuilder for TickExt {
+    fn id(&self) -> Id {
+        Id {
+            name: "tick".to_string(),
+            version: "1.0.0".parse().unwrap(),
+        }
+    }
+
+    fn config(&self) -> TestExtensionConfig {
+        TestExtensionConfig {
+            kind: extension_catalog::Kind::FieldResolver(extension_catalog::FieldResolver {
+                resolver_directives: vec!["tick".to_string()],
+            }),
+            sdl: Some(
+                r#"
+                directive @tick(count: Int!) on FIELD_DEFINITION
+                "#,
+            ),
+        }
+    }
+
+    fn build(&self, _schema_directives: Vec<(&str, serde_json::Value)>) -> std::sync::Arc<dyn TestExtension> {
+        Arc::new(TickExt)
+    }
+}
+
+#[async_trait::async_trait]
+impl TestExtension for TickExt {
+    async fn resolve_subscription<'a>(
+        &self,
+        _context: &DynHookContext,
+        directive: ExtensionFieldDirective<'a, serde_json::Value>,
+    ) -> Result<BoxStream<'static, Result<serde_json::Value, PartialGraphqlError>>, PartialGraphqlError> {
+        let count = directive.arguments["count"].as_u64().unwrap();
+
+        let stream = futures::stream::iter(1..=count).map(|tick| {
+            if tick % 2 == 0 {
+                Err(PartialGraphqlError::new(
+                    format!("Tick {tick} is even"),
+                    runtime::error::PartialErrorCode::InternalServerError,
+                ))
+            } else {
+                Ok(serde_json::json!({ "tick": tick }))
+            }
+        });
+
+        Ok(stream.boxed())
+    }
+}
+
+#[test]
+fn subscription_resolver() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph_sdl(
+                "a",
+                r#"
+                extend schema
+                    @link(url: "tick-1.0.0", import: ["@tick"])
+
+                type Query {
+                    hello: String
+                }
+
+                type Subscription {
+                    ticks: Tick @tick(count: 3)
+                }
+
+                type Tick {
+                    tick: Int!
+                }
+                "#,
+            )
+            .with_extension(TickExt)
+            .build()
+            .await;
+
+        engine
+            .post("subscription { ticks { tick } }")
+            .into_sse_stream()
+            .await
+            .collect()
+            .await
+    });
+
+    insta::assert_json_snapshot!(response.messages, @r#"
+    [
+      {
+        "data": {
+          "ticks": {
+            "tick": 1
+          }
+        }
+      },
+      {
+        "data": {
+          "ticks": null
+        },
+        "errors": [
+          {
+            "message": "Tick 2 is even",
+            "path": [
+              "ticks"
+            ],
+            "extensions": {
+              "code": "INTERNAL_SERVER_ERROR"
+            }
+          }
+        ]
+      },
+      {
+        "data": {
+          "ticks": {
+            "tick": 3
+          }
+        }
+      }
+    ]
+    "#);
+}
+
+#[test]
+fn subscription_not_supported() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph_sdl(
+                "a",
+                r#"
+                extend schema
+                    @link(url: "greet-1.0.0", import: ["@greet"])
+
+                type Query {
+                    hello: String
+                }
+
+                type Subscription {
+                    greetings: String @greet
+                }
+                "#,
+            )
+            .with_extension(super::basic::GreetExt::default())
+            .build()
+            .await;
+
+        engine
+            .post("subscription { greetings }")
+            .into_sse_stream()
+            .await
+            .collect()
+            .await
+    });
+
+    insta::assert_json_snapshot!(response.messages, @r#"
+    [
+      {
+        "data": null,
+        "errors": [
+          {
+            "message": "Internal extension error",
+            "extensions": {
+              "code": "INTERNAL_SERVER_ERROR"
+            }
+          }
+        ]
+      }
+    ]
+    "#);
+}
@@ -0,0 +1,57 @@
+use engine::Engine;
+use graphql_mocks::SlowSchema;
+use integration_tests::{federation::EngineExt, runtime};
+
+#[test]
+fn slow_request_is_hedged_once_the_usual_latency_is_known() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(SlowSchema)
+            .with_toml_config(
+                r#"
+                [subgraphs.slow.hedging]
+                enabled = true
+                percentile = 0.9
+                "#,
+            )
+            .build()
+            .await;
+
+        // Not enough latencies were observed yet to hedge anything.
+        let response = engine.post("query { delay(ms: 200) }").await;
+        assert!(response.errors().is_empty(), "{response}");
+        assert_eq!(engine.drain_http_requests_sent_to::<SlowSchema>().len(), 1);
+
+        for _ in 0..32 {
+            let response = engine.post("query { delay(ms: 0) }").await;
+            assert!(response.errors().is_empty(), "{response}");
+        }
+        assert_eq!(engine.drain_http_requests_sent_to::<SlowSchema>().len(), 32);
+
+        let response = engine.post("query { delay(ms: 200) }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "delay": 200
+          }
+        }
+        "#);
+        assert_eq!(engine.drain_http_requests_sent_to::<SlowSchema>().len(), 2);
+    })
+}
+
+#[test]
+fn hedging_is_disabled_by_default() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(SlowSchema).build().await;
+
+        for _ in 0..32 {
+            let response = engine.post("query { delay(ms: 0) }").await;
+            assert!(response.errors().is_empty(), "{response}");
+        }
+
+        let response = engine.post("query { delay(ms: 200) }").await;
+        assert!(response.errors().is_empty(), "{response}");
+        assert_eq!(engine.drain_http_requests_sent_to::<SlowSchema>().len(), 33);
+    })
+}
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use engine::Engine;
+use extension_catalog::ExtensionId;
+use gateway_config::{WasiExtensionsConfig, WasiLimitsConfig};
+use graphql_mocks::EchoSchema;
+use integration_tests::{
+    federation::{EngineExt, TestGateway},
+    runtime,
+};
+use runtime::hooks::DynamicHooks;
+use runtime_local::{
+    wasi::{
+        extensions::{ExtensionConfig, ExtensionPoolId, ExtensionType, WasiExtensions},
+        hooks::{self, HooksWasi},
+    },
+    InMemoryEntityCache,
+};
+
+const LOCATION: &str = "../wasi-component-loader/examples/target/wasm32-wasip2/debug/chained_hooks.wasm";
+
+fn chained_hooks(id: usize, name: &str, order: u16) -> ExtensionConfig {
+    let config = serde_json::json!({ "name": name });
+
+    ExtensionConfig {
+        id: ExtensionPoolId::Hooks(ExtensionId::from(id)),
+        name: name.to_string(),
+        version: semver::Version::new(1, 0, 0),
+        extension_type: ExtensionType::Hooks,
+        schema_directives: Vec::new(),
+        max_pool_size: None,
+        wasi_config: WasiExtensionsConfig {
+            location: LOCATION.into(),
+            networking: false,
+            stdout: false,
+            stderr: false,
+            environment_variables: false,
+            limits: WasiLimitsConfig::default(),
+        },
+        extension_config: minicbor_serde::to_vec(&config).unwrap(),
+        order: Some(order),
+    }
+}
+
+async fn gateway() -> TestGateway {
+    assert!(
+        std::path::Path::new(LOCATION).exists(),
+        "Wasm examples weren't built, please run:\ncd crates/wasi-component-loader/examples && cargo build --target wasm32-wasip2"
+    );
+
+    let meter = grafbase_telemetry::metrics::meter_from_global_provider();
+    let counter = meter.i64_up_down_counter("grafbase.gateway.access_log.pending").build();
+    let (access_log, _) = hooks::create_log_channel(false, counter);
+
+    // Declared in the reverse order of the chain, the `order` setting decides.
+    let configs = vec![chained_hooks(0, "second", 2), chained_hooks(1, "first", 1)];
+
+    let extensions = WasiExtensions::new(access_log.clone(), Arc::new(InMemoryEntityCache::default()), configs)
+        .await
+        .unwrap();
+
+    let hooks = HooksWasi::new(None, None, &meter, access_log)
+        .await
+        .with_extensions(extensions);
+
+    Engine::builder()
+        .with_subgraph(EchoSchema)
+        .with_toml_config(
+            r#"
+            [[subgraphs.echo.headers]]
+            rule = "forward"
+            name = "x-chain"
+            "#,
+        )
+        .with_mock_hooks(DynamicHooks::wrap(hooks))
+        .build()
+        .await
+}
+
+#[test]
+fn hooks_are_called_in_order() {
+    let response = runtime().block_on(async move {
+        let engine = gateway().await;
+
+        engine
+            .post(
+                r#"
+                query {
+                    chain: header(name: "x-chain")
+                    subgraphChain: header(name: "x-subgraph-chain")
+                }
+                "#,
+            )
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "data": {
+        "chain": "first,second",
+        "subgraphChain": "first,second"
+      }
+    }
+    "#);
+}
+
+#[test]
+fn rejection_short_circuits_the_chain() {
+    let (first, second) = runtime().block_on(async move {
+        let engine = gateway().await;
+        let query = r#"query { chain: header(name: "x-chain") }"#;
+
+        let first = engine.post(query).header("x-reject", "first,second").await;
+        let second = engine.post(query).header("x-reject", "second").await;
+
+        (first, second)
+    });
+
+    assert_eq!(first.status, http::StatusCode::FORBIDDEN);
+    assert_eq!(second.status, http::StatusCode::FORBIDDEN);
+
+    // The second extension is never called once the first one rejected the request.
+    insta::assert_json_snapshot!(first, @r#"
+    {
+      "errors": [
+        {
+          "message": "rejected by first",
+          "extensions": {
+            "code": "BAD_REQUEST"
+          }
+        }
+      ]
+    }
+    "#);
+
+    insta::assert_json_snapshot!(second, @r#"
+    {
+      "errors": [
+        {
+          "message": "rejected by second",
+          "extensions": {
+            "code": "BAD_REQUEST"
+          }
+        }
+      ]
+    }
+    "#);
+}
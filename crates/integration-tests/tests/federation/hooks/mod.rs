@@ -2,6 +2,7 @@ mod authorize_edge_node_post_execution;
 mod authorize_edge_pre_execution;
 mod authorize_node_pre_execution;
 mod authorize_parent_edge_post_execution;
+mod extensions;
 mod on_gateway_request;
 mod on_subgraph_request;
 
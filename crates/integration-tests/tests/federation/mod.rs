@@ -1,19 +1,30 @@
 mod apq;
 mod auth;
 mod basic;
+mod cache_control;
+mod circuit_breaker;
 mod complexity_control;
 mod config;
+mod defer;
 mod deser;
 mod entity_caching;
 mod extensions;
 mod graphql_over_http;
+mod hedging;
 mod hooks;
 mod inaccessible;
 mod introspection;
 mod issues;
 mod message_signing;
+mod query_deduplication;
+mod rate_limiting;
+mod response_caching;
 mod response_extensions;
+mod stream;
+mod subgraph_compression;
+mod subgraph_proxy;
 mod subgraph_retries;
+mod subgraph_tls;
 mod subgraphs;
 mod subscriptions;
 mod timeouts;
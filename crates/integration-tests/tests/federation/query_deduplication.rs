@@ -0,0 +1,103 @@
+use std::future::IntoFuture;
+
+use engine::Engine;
+use graphql_mocks::SlowSchema;
+use integration_tests::{federation::EngineExt, runtime};
+
+const QUERY: &str = "query { delay(ms: 200) }";
+
+#[test]
+fn identical_inflight_queries_share_a_single_subgraph_request() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(SlowSchema)
+            .with_toml_config(
+                r#"
+                [gateway.query_deduplication]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        let responses = futures::future::join_all((0..5).map(|_| engine.post(QUERY).into_future())).await;
+
+        insta::assert_json_snapshot!(responses, @r#"
+        [
+          {
+            "data": {
+              "delay": 200
+            }
+          },
+          {
+            "data": {
+              "delay": 200
+            }
+          },
+          {
+            "data": {
+              "delay": 200
+            }
+          },
+          {
+            "data": {
+              "delay": 200
+            }
+          },
+          {
+            "data": {
+              "delay": 200
+            }
+          }
+        ]
+        "#);
+
+        assert_eq!(engine.drain_http_requests_sent_to::<SlowSchema>().len(), 1);
+    })
+}
+
+#[test]
+fn queries_are_not_deduplicated_by_default() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(SlowSchema).build().await;
+
+        let responses = futures::future::join_all((0..5).map(|_| engine.post(QUERY).into_future())).await;
+
+        for response in responses {
+            assert!(response.errors().is_empty(), "{response}");
+        }
+
+        assert_eq!(engine.drain_http_requests_sent_to::<SlowSchema>().len(), 5);
+    })
+}
+
+#[test]
+fn queries_with_different_headers_are_not_deduplicated() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(SlowSchema)
+            .with_toml_config(
+                r#"
+                [gateway.query_deduplication]
+                enabled = true
+
+                [[subgraphs.slow.headers]]
+                rule = "forward"
+                name = "x-user"
+                "#,
+            )
+            .build()
+            .await;
+
+        let responses = futures::future::join_all(
+            ["a", "b", "a"].map(|user| engine.post(QUERY).header("x-user", user).into_future()),
+        )
+        .await;
+
+        for response in responses {
+            assert!(response.errors().is_empty(), "{response}");
+        }
+
+        assert_eq!(engine.drain_http_requests_sent_to::<SlowSchema>().len(), 2);
+    })
+}
@@ -0,0 +1,209 @@
+use engine::Engine;
+use graphql_mocks::FederatedProductsSchema;
+use integration_tests::{federation::EngineExt, runtime};
+
+use super::complexity_control::ComplexitySchema;
+
+const QUERY: &str = "query Products { topProducts { upc } }";
+
+#[test]
+fn header_rule_limits_each_key_separately() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [[gateway.rate_limit.rules]]
+                name = "per-api-key"
+                key = { header = "x-api-key" }
+                limit = 1
+                duration = "1s"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).header("x-api-key", "a").await;
+        assert_eq!(response.status, 200, "{response}");
+
+        let response = engine.post(QUERY).header("x-api-key", "a").await;
+        assert_eq!(response.status, 429);
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "errors": [
+            {
+              "message": "Rate limited",
+              "extensions": {
+                "rate_limit_key": "rule:per-api-key",
+                "code": "RATE_LIMITED"
+              }
+            }
+          ]
+        }
+        "#);
+        assert_eq!(response.headers["ratelimit-limit"], "1");
+        assert_eq!(response.headers["ratelimit-remaining"], "0");
+        assert_eq!(response.headers["ratelimit-reset"], "1");
+        assert_eq!(response.headers["retry-after"], "1");
+
+        let response = engine.post(QUERY).header("x-api-key", "b").await;
+        assert_eq!(response.status, 200, "{response}");
+
+        // Requests without the header aren't limited by the rule.
+        for _ in 0..3 {
+            let response = engine.post(QUERY).await;
+            assert_eq!(response.status, 200, "{response}");
+        }
+    })
+}
+
+#[test]
+fn operation_name_rule() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [[gateway.rate_limit.rules]]
+                name = "per-operation"
+                key = "operation_name"
+                limit = 1
+                duration = "1s"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await;
+        assert_eq!(response.status, 200, "{response}");
+
+        let response = engine.post(QUERY).await;
+        assert_eq!(response.status, 429);
+
+        let response = engine.post("query Other { topProducts { upc } }").await;
+        assert_eq!(response.status, 200, "{response}");
+    })
+}
+
+#[test]
+fn rejected_request_does_not_consume_from_other_rules() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [[gateway.rate_limit.rules]]
+                name = "per-api-key"
+                key = { header = "x-api-key" }
+                limit = 2
+                duration = "60s"
+
+                [[gateway.rate_limit.rules]]
+                name = "per-operation"
+                key = "operation_name"
+                limit = 1
+                duration = "60s"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).header("x-api-key", "a").await;
+        assert_eq!(response.status, 200, "{response}");
+
+        // Rejected by the second rule, the first one is left untouched.
+        let response = engine.post(QUERY).header("x-api-key", "a").await;
+        assert_eq!(response.status, 429);
+        assert_eq!(
+            response.body["errors"][0]["extensions"]["rate_limit_key"],
+            "rule:per-operation"
+        );
+
+        let response = engine
+            .post("query Other { topProducts { upc } }")
+            .header("x-api-key", "a")
+            .await;
+        assert_eq!(response.status, 200, "{response}");
+
+        let response = engine
+            .post("query Third { topProducts { upc } }")
+            .header("x-api-key", "a")
+            .await;
+        assert_eq!(response.status, 429);
+        assert_eq!(
+            response.body["errors"][0]["extensions"]["rate_limit_key"],
+            "rule:per-api-key"
+        );
+    })
+}
+
+#[test]
+fn complexity_rule_consumes_the_operation_cost() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(ComplexitySchema)
+            .with_toml_config(
+                r#"
+                [complexity_control]
+                mode = "measure"
+                limit = 1000
+
+                [[gateway.rate_limit.rules]]
+                name = "complexity-per-api-key"
+                key = { header = "x-api-key" }
+                limit = 150
+                duration = "60s"
+                unit = "complexity"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post("query { expensiveField }").header("x-api-key", "a").await;
+        assert_eq!(response.status, 200, "{response}");
+
+        let response = engine.post("query { expensiveField }").header("x-api-key", "a").await;
+        assert_eq!(response.status, 429);
+
+        let header = |name: &str| -> u64 { response.headers[name].to_str().unwrap().parse().unwrap() };
+        assert_eq!(header("ratelimit-limit"), 150);
+        assert!((50..100).contains(&header("ratelimit-remaining")));
+        assert!(header("ratelimit-reset") <= 60);
+        assert!((1..=60).contains(&header("retry-after")));
+
+        // What is left of the budget is still enough for cheaper operations.
+        let response = engine.post("query { cheapField }").header("x-api-key", "a").await;
+        assert_eq!(response.status, 200, "{response}");
+
+        let response = engine.post("query { expensiveField }").header("x-api-key", "b").await;
+        assert_eq!(response.status, 200, "{response}");
+    })
+}
+
+#[test]
+fn global_limit_reports_the_configured_limit() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [gateway.rate_limit.global]
+                limit = 20
+                duration = "10s"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = loop {
+            let response = engine.post(QUERY).await;
+            if response.status != 200 {
+                break response;
+            }
+        };
+
+        assert_eq!(response.status, 429);
+        assert_eq!(response.headers["ratelimit-limit"], "20");
+        assert_eq!(response.headers["ratelimit-remaining"], "0");
+    })
+}
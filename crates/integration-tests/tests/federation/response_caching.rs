@@ -0,0 +1,482 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use engine::Engine;
+use graphql_mocks::{
+    dynamic::DynamicSchema, FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema, SecureSchema,
+};
+use headers::{CacheControl, HeaderMapExt};
+use integration_tests::{federation::EngineExt, runtime};
+use runtime::{
+    error::PartialGraphqlError,
+    hooks::{DynHookContext, DynHooks, ExecutedOperation},
+};
+use serde_json::json;
+
+const CONFIG: &str = r#"
+    [response_caching]
+    enabled = true
+"#;
+
+/// Subgraph allowing its responses to be cached for a minute.
+struct Cacheable<S>(S);
+
+impl<S: graphql_mocks::Subgraph + Send> graphql_mocks::Subgraph for Cacheable<S> {
+    fn name(&self) -> String {
+        self.0.name()
+    }
+
+    async fn start(self) -> graphql_mocks::MockGraphQlServer {
+        self.0
+            .start()
+            .await
+            .with_additional_header(CacheControl::new().with_max_age(Duration::from_secs(60)))
+    }
+}
+
+struct PrivateProductSubgraph;
+
+impl graphql_mocks::Subgraph for PrivateProductSubgraph {
+    fn name(&self) -> String {
+        "products".into()
+    }
+
+    async fn start(self) -> graphql_mocks::MockGraphQlServer {
+        FederatedProductsSchema
+            .start()
+            .await
+            .with_additional_header(CacheControl::new().with_private().with_max_age(Duration::from_secs(60)))
+    }
+}
+
+#[test]
+fn identical_operations_are_served_from_the_cache() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(Cacheable(FederatedProductsSchema))
+            .with_subgraph(Cacheable(FederatedReviewsSchema))
+            .with_subgraph(Cacheable(FederatedInventorySchema))
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let first = engine
+            .post("{ topProducts { upc name reviews { id body } } }")
+            .await
+            .into_data();
+        // Formatting doesn't matter.
+        let second = engine
+            .post("{\n  topProducts {\n    upc\n    name\n    reviews { id body }\n  }\n}")
+            .await
+            .into_data();
+
+        assert_eq!(first, second);
+        assert_eq!(
+            engine
+                .drain_graphql_requests_sent_to::<Cacheable<FederatedProductsSchema>>()
+                .len(),
+            1
+        );
+        assert_eq!(
+            engine
+                .drain_graphql_requests_sent_to::<Cacheable<FederatedReviewsSchema>>()
+                .len(),
+            1
+        );
+    })
+}
+
+#[test]
+fn response_caching_is_disabled_by_default() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FederatedProductsSchema).build().await;
+
+        engine.post("{ topProducts { upc name } }").await.into_data();
+        engine.post("{ topProducts { upc name } }").await.into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+    })
+}
+
+#[test]
+fn variables_and_literals_are_part_of_the_key() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(Cacheable(FederatedProductsSchema))
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        const QUERY: &str = "query ($upc: String!) { product(upc: $upc) { upc name } }";
+
+        let first = engine
+            .post(QUERY)
+            .variables(json!({ "upc": "top-1" }))
+            .await
+            .into_data();
+        let second = engine
+            .post(QUERY)
+            .variables(json!({ "upc": "top-2" }))
+            .await
+            .into_data();
+        engine
+            .post(QUERY)
+            .variables(json!({ "upc": "top-1" }))
+            .await
+            .into_data();
+        let literal = engine
+            .post(r#"{ product(upc: "top-2") { upc name } }"#)
+            .await
+            .into_data();
+
+        assert_ne!(first, second);
+        assert_eq!(second, literal);
+        assert_eq!(
+            engine
+                .drain_graphql_requests_sent_to::<Cacheable<FederatedProductsSchema>>()
+                .len(),
+            3
+        );
+    })
+}
+
+#[test]
+fn key_headers_scope_the_cache() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(Cacheable(FederatedProductsSchema))
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                key_headers = ["x-tenant"]
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "{ topProducts { upc name } }";
+
+        engine.post(QUERY).header("x-tenant", "a").await.into_data();
+        engine.post(QUERY).header("x-tenant", "b").await.into_data();
+        engine.post(QUERY).header("x-tenant", "b").await.into_data();
+        engine.post(QUERY).await.into_data();
+
+        assert_eq!(
+            engine
+                .drain_graphql_requests_sent_to::<Cacheable<FederatedProductsSchema>>()
+                .len(),
+            3
+        );
+    })
+}
+
+#[test]
+fn private_responses_are_not_cached_without_a_scoped_key() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(PrivateProductSubgraph)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        engine.post("{ topProducts { upc name } }").await.into_data();
+        engine.post("{ topProducts { upc name } }").await.into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<PrivateProductSubgraph>().len(),
+            2
+        );
+    })
+}
+
+#[test]
+fn private_responses_are_not_cached_with_non_identity_key_headers() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(PrivateProductSubgraph)
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                key_headers = ["x-tenant"]
+                "#,
+            )
+            .build()
+            .await;
+
+        engine
+            .post("{ topProducts { upc name } }")
+            .header("x-tenant", "a")
+            .await
+            .into_data();
+        engine
+            .post("{ topProducts { upc name } }")
+            .header("x-tenant", "a")
+            .await
+            .into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<PrivateProductSubgraph>().len(),
+            2
+        );
+    })
+}
+
+#[test]
+fn private_responses_are_cached_with_an_identity_key_header() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(PrivateProductSubgraph)
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                key_headers = ["Authorization"]
+                "#,
+            )
+            .build()
+            .await;
+
+        engine
+            .post("{ topProducts { upc name } }")
+            .header("authorization", "Bearer a")
+            .await
+            .into_data();
+        engine
+            .post("{ topProducts { upc name } }")
+            .header("authorization", "Bearer a")
+            .await
+            .into_data();
+        engine
+            .post("{ topProducts { upc name } }")
+            .header("authorization", "Bearer b")
+            .await
+            .into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<PrivateProductSubgraph>().len(),
+            2
+        );
+    })
+}
+
+#[test]
+fn responses_without_max_age_are_not_cached() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        engine.post("{ topProducts { upc name } }").await.into_data();
+        engine.post("{ topProducts { upc name } }").await.into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+    })
+}
+
+#[test]
+fn cache_hits_keep_the_cache_hints() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(Cacheable(FederatedProductsSchema))
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                ttl = "30s"
+                "#,
+            )
+            .build()
+            .await;
+
+        let miss = engine.get("{ topProducts { upc name } }").await;
+        let hit = engine.get("{ topProducts { upc name } }").await;
+
+        assert_eq!(
+            engine
+                .drain_graphql_requests_sent_to::<Cacheable<FederatedProductsSchema>>()
+                .len(),
+            1
+        );
+
+        let miss = miss.headers.typed_get::<CacheControl>().unwrap();
+        let hit = hit.headers.typed_get::<CacheControl>().unwrap();
+        assert_eq!(miss.max_age(), Some(Duration::from_secs(60)));
+        assert!(hit.public());
+        assert!(hit.max_age().is_some_and(|max_age| max_age <= Duration::from_secs(30)));
+    })
+}
+
+#[test]
+fn cache_hits_call_the_operation_response_hook() {
+    #[derive(Clone, Default)]
+    struct TestHooks(Arc<AtomicUsize>);
+
+    #[async_trait::async_trait]
+    impl DynHooks for TestHooks {
+        async fn on_gateway_response(
+            &self,
+            _context: &DynHookContext,
+            _operation: ExecutedOperation<'_, Vec<u8>>,
+        ) -> Result<Vec<u8>, PartialGraphqlError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(Vec::new())
+        }
+    }
+
+    let hooks = TestHooks::default();
+    let calls = hooks.0.clone();
+
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_mock_hooks(hooks)
+            .with_subgraph(Cacheable(FederatedProductsSchema))
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        engine.post("{ topProducts { upc name } }").await.into_data();
+        engine.post("{ topProducts { upc name } }").await.into_data();
+
+        assert_eq!(
+            engine
+                .drain_graphql_requests_sent_to::<Cacheable<FederatedProductsSchema>>()
+                .len(),
+            1
+        );
+    });
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn mutated_entities_are_purged() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(Cacheable(FederatedProductsSchema))
+            .with_subgraph(Cacheable(FederatedReviewsSchema))
+            .with_subgraph(Cacheable(FederatedInventorySchema))
+            .with_subgraph(
+                DynamicSchema::builder(
+                    r#"
+                    type Query {
+                        adminVersion: String
+                    }
+
+                    type Mutation {
+                        updateProduct(upc: String!): Product!
+                    }
+
+                    type Product @key(fields: "upc") {
+                        upc: String!
+                    }
+                    "#,
+                )
+                .with_resolver("Mutation", "updateProduct", json!({ "upc": "top-1" }))
+                .into_subgraph("admin"),
+            )
+            .with_toml_config(
+                r#"
+                [entity_caching]
+                invalidate_on_mutation = true
+
+                [response_caching]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "{ topProducts { upc reviews { id body } } }";
+
+        let response = engine.post(QUERY).await.into_data();
+        assert_eq!(engine.post(QUERY).await.into_data(), response);
+        assert_eq!(
+            engine
+                .drain_graphql_requests_sent_to::<Cacheable<FederatedProductsSchema>>()
+                .len(),
+            1
+        );
+
+        engine
+            .post(r#"mutation { updateProduct(upc: "top-1") { upc } }"#)
+            .await
+            .into_data();
+
+        assert_eq!(engine.post(QUERY).await.into_data(), response);
+        assert_eq!(
+            engine
+                .drain_graphql_requests_sent_to::<Cacheable<FederatedProductsSchema>>()
+                .len(),
+            1
+        );
+    })
+}
+
+#[test]
+fn operations_with_authorization_are_not_cached() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(Cacheable(SecureSchema))
+            .with_toml_config(
+                r#"
+                [response_caching]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "{ check { anonymous faillibleMustBeAuthenticated } }";
+
+        let response = engine.post(QUERY).await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "check": {
+              "anonymous": "Hello anonymous!",
+              "faillibleMustBeAuthenticated": null
+            }
+          },
+          "errors": [
+            {
+              "message": "Unauthenticated",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 21
+                }
+              ],
+              "path": [
+                "check",
+                "faillibleMustBeAuthenticated"
+              ],
+              "extensions": {
+                "code": "UNAUTHENTICATED"
+              }
+            }
+          ]
+        }
+        "#);
+        assert_eq!(engine.post(QUERY).await.into_value(), response.into_value());
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<Cacheable<SecureSchema>>().len(),
+            2
+        );
+    })
+}
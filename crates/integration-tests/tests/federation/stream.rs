@@ -0,0 +1,152 @@
+use engine::Engine;
+use graphql_mocks::{FederatedProductsSchema, SlowSchema};
+use integration_tests::{federation::EngineExt, runtime};
+
+const QUERY: &str = r#"
+    query {
+        topProducts @stream(initialCount: 1, label: "products") {
+            upc
+            name
+        }
+    }
+"#;
+
+#[test]
+fn stream_is_ignored_with_complete_response_format() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FederatedProductsSchema).build().await;
+
+        let streamed = engine.post(QUERY).await.into_data();
+        let not_streamed = engine
+            .post(QUERY.replace(r#"@stream(initialCount: 1, label: "products")"#, ""))
+            .await
+            .into_data();
+
+        assert_eq!(streamed, not_streamed);
+    })
+}
+
+#[test]
+fn stream_delivers_remaining_items_in_a_batch() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FederatedProductsSchema).build().await;
+
+        let expected = engine
+            .post(QUERY.replace(r#"@stream(initialCount: 1, label: "products")"#, ""))
+            .await
+            .into_data();
+        let expected = expected["topProducts"].as_array().unwrap();
+        assert!(expected.len() > 1);
+
+        let response = engine.post(QUERY).into_multipart_stream().await.collect().await;
+        let messages = response.messages;
+        assert_eq!(messages.len(), 2, "{messages:#?}");
+
+        // Streamed plans never hold back the initial payload.
+        let initial = &messages[0];
+        assert_eq!(initial["hasNext"], true);
+        assert_eq!(initial["data"], serde_json::json!({}));
+
+        let subsequent = &messages[1];
+        assert_eq!(subsequent["hasNext"], false);
+        let results = subsequent["incremental"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["label"], "products");
+        assert_eq!(results[0]["path"], serde_json::json!([]));
+        assert_eq!(results[0]["data"]["topProducts"], serde_json::json!(&expected[..1]));
+        assert_eq!(results[1]["label"], "products");
+        assert_eq!(results[1]["path"], serde_json::json!(["topProducts", 1]));
+        assert_eq!(results[1]["items"], serde_json::json!(&expected[1..]));
+    })
+}
+
+#[test]
+fn initial_payload_is_sent_before_the_streamed_list_is_resolved() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(SlowSchema).build().await;
+
+        let response = engine
+            .post(
+                r#"
+                query {
+                    fast: delay(ms: 0)
+                    slow: listDelay(ms: 200, count: 3) @stream(initialCount: 1, label: "slow")
+                }
+                "#,
+            )
+            .into_multipart_stream()
+            .await
+            .collect()
+            .await;
+        let messages = response.messages;
+        assert_eq!(messages.len(), 2, "{messages:#?}");
+
+        let initial = &messages[0];
+        assert_eq!(initial["hasNext"], true);
+        assert_eq!(initial["data"], serde_json::json!({ "fast": 0 }));
+
+        let subsequent = &messages[1];
+        assert_eq!(subsequent["hasNext"], false);
+        assert_eq!(
+            subsequent["incremental"],
+            serde_json::json!([
+                {
+                    "data": { "slow": [0] },
+                    "path": [],
+                    "label": "slow"
+                },
+                {
+                    "items": [1, 2],
+                    "path": ["slow", 1],
+                    "label": "slow"
+                }
+            ])
+        );
+    })
+}
+
+#[test]
+fn stream_with_false_condition_is_not_streamed() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FederatedProductsSchema).build().await;
+
+        let response = engine
+            .post(QUERY.replace(r#"label: "products""#, "if: false"))
+            .into_multipart_stream()
+            .await
+            .collect()
+            .await;
+
+        assert_eq!(response.messages.len(), 1);
+        assert!(response.messages[0].get("hasNext").is_none());
+        assert!(response.messages[0]["data"]["topProducts"].as_array().unwrap().len() > 1);
+    })
+}
+
+#[test]
+fn stream_is_not_allowed_on_non_list_fields() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FederatedProductsSchema).build().await;
+
+        engine.post("query { topProducts { upc @stream } }").await
+    });
+
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "errors": [
+        {
+          "message": "Directive '@stream' can only be used on list fields",
+          "locations": [
+            {
+              "line": 1,
+              "column": 27
+            }
+          ],
+          "extensions": {
+            "code": "OPERATION_VALIDATION_ERROR"
+          }
+        }
+      ]
+    }
+    "#);
+}
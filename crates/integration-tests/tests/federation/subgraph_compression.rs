@@ -0,0 +1,155 @@
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    body::Bytes,
+    extract::{Request, State},
+    middleware::{self, Next},
+    response::Response,
+    routing::post,
+    Router,
+};
+use engine::Engine;
+use http::HeaderMap;
+use integration_tests::{
+    federation::{EngineExt, TestGateway},
+    runtime,
+};
+use tower_http::{compression::CompressionLayer, decompression::RequestDecompressionLayer};
+
+const SDL: &str = r#"
+    type Query {
+        echo(input: String!): String!
+    }
+"#;
+
+/// Subgraph decompressing the requests and compressing the responses as negotiated, keeping
+/// the headers of each request.
+async fn start_subgraph() -> (u16, Arc<Mutex<Vec<HeaderMap>>>) {
+    // Headers are kept before the decompression layer removes `Content-Encoding`.
+    async fn record(State(received): State<Arc<Mutex<Vec<HeaderMap>>>>, request: Request, next: Next) -> Response {
+        received.lock().unwrap().push(request.headers().clone());
+        next.run(request).await
+    }
+
+    async fn handler(body: Bytes) -> String {
+        // Fails if the body wasn't decompressed.
+        let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let input = request["variables"]
+            .as_object()
+            .and_then(|variables| variables.values().next())
+            .cloned()
+            .unwrap_or_default();
+
+        serde_json::json!({ "data": { "echo": input } }).to_string()
+    }
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let app = Router::new()
+        .route("/", post(handler))
+        .layer(RequestDecompressionLayer::new())
+        .layer(CompressionLayer::new())
+        .layer(middleware::from_fn_with_state(received.clone(), record));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (port, received)
+}
+
+async fn build_engine(port: u16, client: &str) -> TestGateway {
+    Engine::builder()
+        .with_subgraph_sdl("echo", SDL)
+        .with_toml_config(format!(
+            r#"
+            [subgraphs.echo]
+            url = "http://127.0.0.1:{port}/"
+
+            [subgraphs.echo.client]
+            {client}
+            "#
+        ))
+        .build()
+        .await
+}
+
+fn header(headers: &HeaderMap, name: http::HeaderName) -> Option<&str> {
+    headers.get(name).map(|value| value.to_str().unwrap())
+}
+
+#[test]
+fn compression_is_disabled_by_default() {
+    runtime().block_on(async move {
+        let (port, received) = start_subgraph().await;
+        let engine = build_engine(port, "").await;
+
+        let input = "a".repeat(2048);
+        let response = engine
+            .post("query($input: String!) { echo(input: $input) }")
+            .variables(serde_json::json!({ "input": input }))
+            .await;
+        assert!(response.errors().is_empty(), "{response}");
+
+        let headers = received.lock().unwrap().pop().unwrap();
+        assert_eq!(header(&headers, http::header::CONTENT_ENCODING), None);
+        assert_eq!(header(&headers, http::header::ACCEPT_ENCODING), None);
+    })
+}
+
+#[test]
+fn request_bodies_are_compressed() {
+    for (compression, encoding) in [("gzip", "gzip"), ("brotli", "br"), ("zstd", "zstd")] {
+        runtime().block_on(async move {
+            let (port, received) = start_subgraph().await;
+            let engine = build_engine(port, &format!(r#"request_compression = "{compression}""#)).await;
+
+            let input = "a".repeat(2048);
+            let response = engine
+                .post("query($input: String!) { echo(input: $input) }")
+                .variables(serde_json::json!({ "input": input }))
+                .await;
+            assert_eq!(response.into_data()["echo"], serde_json::json!(input));
+
+            let headers = received.lock().unwrap().pop().unwrap();
+            assert_eq!(header(&headers, http::header::CONTENT_ENCODING), Some(encoding));
+        })
+    }
+}
+
+#[test]
+fn small_request_bodies_are_not_compressed() {
+    runtime().block_on(async move {
+        let (port, received) = start_subgraph().await;
+        let engine = build_engine(port, r#"request_compression = "gzip""#).await;
+
+        let response = engine
+            .post("query($input: String!) { echo(input: $input) }")
+            .variables(serde_json::json!({ "input": "a" }))
+            .await;
+        assert!(response.errors().is_empty(), "{response}");
+
+        let headers = received.lock().unwrap().pop().unwrap();
+        assert_eq!(header(&headers, http::header::CONTENT_ENCODING), None);
+    })
+}
+
+#[test]
+fn responses_are_decompressed() {
+    runtime().block_on(async move {
+        let (port, received) = start_subgraph().await;
+        let engine = build_engine(port, "response_compression = true").await;
+
+        let response = engine
+            .post("query($input: String!) { echo(input: $input) }")
+            .variables(serde_json::json!({ "input": "hello" }))
+            .await;
+        assert_eq!(response.into_data()["echo"], "hello");
+
+        let headers = received.lock().unwrap().pop().unwrap();
+        let accept_encoding = header(&headers, http::header::ACCEPT_ENCODING).unwrap();
+        for encoding in ["gzip", "br", "zstd"] {
+            assert!(accept_encoding.contains(encoding), "{accept_encoding}");
+        }
+    })
+}
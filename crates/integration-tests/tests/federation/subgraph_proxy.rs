@@ -0,0 +1,158 @@
+use std::sync::{Arc, Mutex};
+
+use engine::Engine;
+use futures::StreamExt as _;
+use graphql_mocks::{EchoSchema, FederatedProductsSchema, MockGraphQlServer, Schema};
+use integration_tests::{federation::EngineExt, runtime};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// HTTP proxy tunneling `CONNECT` requests and forwarding the others to the host of their
+/// absolute URL, keeping the request line of each connection.
+async fn start_proxy() -> (u16, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let requests = received.clone();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let requests = requests.clone();
+
+            tokio::spawn(async move {
+                let mut head = Vec::new();
+                while !head.ends_with(b"\r\n\r\n") {
+                    let Ok(byte) = stream.read_u8().await else {
+                        return;
+                    };
+                    head.push(byte);
+                }
+
+                let head = String::from_utf8(head).unwrap();
+                let request_line = head.lines().next().unwrap().to_string();
+                requests.lock().unwrap().push(request_line.clone());
+
+                let mut parts = request_line.split(' ');
+                let method = parts.next().unwrap();
+                let target = parts.next().unwrap();
+
+                let mut upstream = if method == "CONNECT" {
+                    let upstream = TcpStream::connect(target).await.unwrap();
+                    stream
+                        .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+                        .await
+                        .unwrap();
+                    upstream
+                } else {
+                    let url = url::Url::parse(target).unwrap();
+                    let mut upstream = TcpStream::connect((url.host_str().unwrap(), url.port().unwrap()))
+                        .await
+                        .unwrap();
+                    upstream.write_all(head.as_bytes()).await.unwrap();
+                    upstream
+                };
+
+                tokio::io::copy_bidirectional(&mut stream, &mut upstream).await.ok();
+            });
+        }
+    });
+
+    (port, received)
+}
+
+#[test]
+fn subgraph_client_settings_keep_the_global_proxy() {
+    runtime().block_on(async move {
+        let subgraph = MockGraphQlServer::new(EchoSchema).await;
+        let (proxy_port, received) = start_proxy().await;
+
+        let engine = Engine::builder()
+            .with_subgraph_sdl("echo", &EchoSchema.sdl())
+            .with_toml_config(format!(
+                r#"
+                [gateway.subgraph_client]
+                proxy = "http://127.0.0.1:{proxy_port}"
+
+                [subgraphs.echo]
+                url = "{}"
+
+                [subgraphs.echo.client]
+                pool_max_idle_per_host = 1
+                "#,
+                subgraph.url()
+            ))
+            .build()
+            .await;
+
+        let response = engine.post(r#"query { string(input: "hello") }"#).await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "string": "hello"
+          }
+        }
+        "#);
+
+        let received = received.lock().unwrap().clone();
+        assert_eq!(received, vec![format!("POST {} HTTP/1.1", subgraph.url())]);
+    })
+}
+
+#[test]
+fn websocket_subscriptions_go_through_the_proxy() {
+    runtime().block_on(async move {
+        let subgraph = MockGraphQlServer::new(FederatedProductsSchema).await;
+        let (proxy_port, received) = start_proxy().await;
+
+        let engine = Engine::builder()
+            .with_subgraph_sdl("products", &FederatedProductsSchema.sdl())
+            .with_toml_config(format!(
+                r#"
+                [gateway.subgraph_client]
+                proxy = "http://127.0.0.1:{proxy_port}"
+
+                [subgraphs.products]
+                url = "{}"
+                websocket_url = "{}"
+                "#,
+                subgraph.url(),
+                subgraph.websocket_url(),
+            ))
+            .build()
+            .await;
+
+        let items = engine
+            .ws("subscription { newProducts { upc } }")
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+
+        insta::assert_json_snapshot!(items, @r#"
+        [
+          {
+            "data": {
+              "newProducts": {
+                "upc": "top-4"
+              }
+            }
+          },
+          {
+            "data": {
+              "newProducts": {
+                "upc": "top-5"
+              }
+            }
+          }
+        ]
+        "#);
+
+        let received = received.lock().unwrap().clone();
+        assert_eq!(
+            received,
+            vec![format!("CONNECT 127.0.0.1:{} HTTP/1.1", subgraph.port())]
+        );
+    })
+}
@@ -0,0 +1,207 @@
+//! The certificates in `data/tls` are signed by a test CA and valid for `localhost`. The subgraph
+//! is served behind a TLS proxy requiring a client certificate signed by the same CA.
+
+use std::{path::PathBuf, sync::Arc};
+
+use engine::Engine;
+use futures::StreamExt as _;
+use graphql_mocks::{EchoSchema, FederatedProductsSchema, MockGraphQlServer, Schema};
+use integration_tests::{federation::EngineExt, runtime};
+use rustls::{
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    server::WebPkiClientVerifier,
+    RootCertStore,
+};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::TlsAcceptor;
+
+fn tls_file(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/tls").join(name)
+}
+
+/// Terminates mutual TLS on a local port and forwards the connections to the subgraph.
+async fn start_mtls_proxy(subgraph: &MockGraphQlServer) -> u16 {
+    let mut roots = RootCertStore::empty();
+    roots
+        .add(CertificateDer::from_pem_file(tls_file("ca.pem")).unwrap())
+        .unwrap();
+
+    let client_verifier = WebPkiClientVerifier::builder(Arc::new(roots)).build().unwrap();
+    let config = rustls::ServerConfig::builder()
+        .with_client_cert_verifier(client_verifier)
+        .with_single_cert(
+            vec![CertificateDer::from_pem_file(tls_file("server.pem")).unwrap()],
+            PrivateKeyDer::from_pem_file(tls_file("server-key.pem")).unwrap(),
+        )
+        .unwrap();
+
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let subgraph_port = subgraph.port();
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+
+            tokio::spawn(async move {
+                let Ok(mut stream) = acceptor.accept(stream).await else {
+                    return;
+                };
+
+                let mut upstream = TcpStream::connect(("127.0.0.1", subgraph_port)).await.unwrap();
+                tokio::io::copy_bidirectional(&mut stream, &mut upstream).await.ok();
+            });
+        }
+    });
+
+    port
+}
+
+fn config(port: u16, tls: &str) -> String {
+    format!(
+        r#"
+        [subgraphs.echo]
+        url = "https://localhost:{port}/"
+
+        [subgraphs.echo.tls]
+        {tls}
+        "#
+    )
+}
+
+#[test]
+fn client_certificate_and_private_ca() {
+    runtime().block_on(async move {
+        let subgraph = MockGraphQlServer::new(EchoSchema).await;
+        let port = start_mtls_proxy(&subgraph).await;
+
+        let tls = format!(
+            r#"
+            ca = "{}"
+            cert = "{}"
+            key = "{}"
+            "#,
+            tls_file("ca.pem").display(),
+            tls_file("client.pem").display(),
+            tls_file("client-key.pem").display(),
+        );
+
+        let engine = Engine::builder()
+            .with_subgraph_sdl("echo", &EchoSchema.sdl())
+            .with_toml_config(config(port, &tls))
+            .build()
+            .await;
+
+        let response = engine.post(r#"query { string(input: "hello") }"#).await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "string": "hello"
+          }
+        }
+        "#);
+        assert_eq!(subgraph.drain_received_requests().count(), 1);
+    })
+}
+
+#[test]
+fn websocket_subscriptions_use_the_tls_settings() {
+    runtime().block_on(async move {
+        let subgraph = MockGraphQlServer::new(FederatedProductsSchema).await;
+        let port = start_mtls_proxy(&subgraph).await;
+
+        let engine = Engine::builder()
+            .with_subgraph_sdl("products", &FederatedProductsSchema.sdl())
+            .with_toml_config(format!(
+                r#"
+                [subgraphs.products]
+                url = "https://localhost:{port}/"
+                websocket_url = "wss://localhost:{port}/ws"
+
+                [subgraphs.products.tls]
+                ca = "{}"
+                cert = "{}"
+                key = "{}"
+                "#,
+                tls_file("ca.pem").display(),
+                tls_file("client.pem").display(),
+                tls_file("client-key.pem").display(),
+            ))
+            .build()
+            .await;
+
+        let items = engine
+            .ws("subscription { newProducts { upc } }")
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+
+        insta::assert_json_snapshot!(items, @r#"
+        [
+          {
+            "data": {
+              "newProducts": {
+                "upc": "top-4"
+              }
+            }
+          },
+          {
+            "data": {
+              "newProducts": {
+                "upc": "top-5"
+              }
+            }
+          }
+        ]
+        "#);
+    })
+}
+
+#[test]
+fn missing_client_certificate_is_rejected() {
+    runtime().block_on(async move {
+        let subgraph = MockGraphQlServer::new(EchoSchema).await;
+        let port = start_mtls_proxy(&subgraph).await;
+
+        let tls = format!(r#"ca = "{}""#, tls_file("ca.pem").display());
+
+        let engine = Engine::builder()
+            .with_subgraph_sdl("echo", &EchoSchema.sdl())
+            .with_toml_config(config(port, &tls))
+            .build()
+            .await;
+
+        let response = engine.post(r#"query { string(input: "hello") }"#).await;
+        assert!(!response.errors().is_empty(), "{response}");
+        assert_eq!(subgraph.drain_received_requests().count(), 0);
+    })
+}
+
+#[test]
+fn unknown_server_certificate_is_rejected() {
+    runtime().block_on(async move {
+        let subgraph = MockGraphQlServer::new(EchoSchema).await;
+        let port = start_mtls_proxy(&subgraph).await;
+
+        let tls = format!(
+            r#"
+            cert = "{}"
+            key = "{}"
+            "#,
+            tls_file("client.pem").display(),
+            tls_file("client-key.pem").display(),
+        );
+
+        let engine = Engine::builder()
+            .with_subgraph_sdl("echo", &EchoSchema.sdl())
+            .with_toml_config(config(port, &tls))
+            .build()
+            .await;
+
+        let response = engine.post(r#"query { string(input: "hello") }"#).await;
+        assert!(!response.errors().is_empty(), "{response}");
+        assert_eq!(subgraph.drain_received_requests().count(), 0);
+    })
+}
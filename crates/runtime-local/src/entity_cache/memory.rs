@@ -1,6 +1,10 @@
-use std::time::Instant;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use futures_util::{future::BoxFuture, FutureExt};
+use runtime::entity_cache::EntryWithTtl;
 use tracing::{field::Empty, Instrument};
 
 pub struct InMemoryEntityCache {
@@ -11,6 +15,9 @@ pub struct InMemoryEntityCache {
 struct CacheValue {
     data: Vec<u8>,
     expires_at: Instant,
+    /// Kept with the entry rather than in a separate index, so that evicted entries don't
+    /// leave anything behind.
+    tags: Arc<[String]>,
 }
 
 impl InMemoryEntityCache {
@@ -20,17 +27,18 @@ impl InMemoryEntityCache {
         }
     }
 
-    async fn get(&self, name: &str) -> anyhow::Result<Option<Vec<u8>>> {
+    async fn get(&self, name: &str) -> anyhow::Result<Option<(Vec<u8>, Duration)>> {
         let Some(value) = self.inner.get(&name.to_string()) else {
             return Ok(None);
         };
 
-        if value.expires_at < Instant::now() {
+        let now = Instant::now();
+        if value.expires_at <= now {
             self.inner.invalidate(&name.to_string());
             return Ok(None);
         }
 
-        Ok(Some(value.data))
+        Ok(Some((value.data, value.expires_at - now)))
     }
 
     async fn put(
@@ -38,16 +46,33 @@ impl InMemoryEntityCache {
         name: &str,
         bytes: std::borrow::Cow<'_, [u8]>,
         expiration_ttl: std::time::Duration,
+        tags: &[String],
     ) -> anyhow::Result<()> {
         self.inner.insert(
             name.to_string(),
             CacheValue {
                 data: bytes.into_owned(),
                 expires_at: Instant::now() + expiration_ttl,
+                tags: tags.into(),
             },
         );
         Ok(())
     }
+
+    async fn purge(&self, tags: &[String]) -> anyhow::Result<()> {
+        let names = self
+            .inner
+            .iter()
+            .filter(|entry| entry.value().tags.iter().any(|tag| tags.contains(tag)))
+            .map(|entry| entry.key().clone())
+            .collect::<Vec<_>>();
+
+        for name in names {
+            self.inner.invalidate(&name);
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for InMemoryEntityCache {
@@ -58,6 +83,13 @@ impl Default for InMemoryEntityCache {
 
 impl runtime::entity_cache::EntityCache for InMemoryEntityCache {
     fn get<'a>(&'a self, name: &'a str) -> BoxFuture<'a, anyhow::Result<Option<Vec<u8>>>> {
+        Box::pin(
+            self.get_with_ttl(name)
+                .map(|result| result.map(|entry| entry.map(|(data, _)| data))),
+        )
+    }
+
+    fn get_with_ttl<'a>(&'a self, name: &'a str) -> BoxFuture<'a, anyhow::Result<Option<EntryWithTtl>>> {
         let cache_span = tracing::info_span!(
             "entity cache get",
             "grafbase.entity_cache.status" = Empty,
@@ -66,6 +98,7 @@ impl runtime::entity_cache::EntityCache for InMemoryEntityCache {
 
         let cache_get = self
             .get(name)
+            .map(|result| result.map(|entry| entry.map(|(data, ttl)| (data, Some(ttl)))))
             .instrument(cache_span.clone())
             .inspect(move |item| match item {
                 Ok(Some(_)) => {
@@ -88,8 +121,14 @@ impl runtime::entity_cache::EntityCache for InMemoryEntityCache {
         name: &'a str,
         bytes: std::borrow::Cow<'a, [u8]>,
         expiration_ttl: std::time::Duration,
+        tags: &'a [String],
     ) -> BoxFuture<'a, anyhow::Result<()>> {
         let cache_span = tracing::info_span!("entity cache put");
-        Box::pin(self.put(name, bytes, expiration_ttl).instrument(cache_span))
+        Box::pin(self.put(name, bytes, expiration_ttl, tags).instrument(cache_span))
+    }
+
+    fn purge<'a>(&'a self, tags: &'a [String]) -> BoxFuture<'a, anyhow::Result<()>> {
+        let cache_span = tracing::info_span!("entity cache purge");
+        Box::pin(self.purge(tags).instrument(cache_span))
     }
 }
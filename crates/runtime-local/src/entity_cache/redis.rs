@@ -1,10 +1,26 @@
+use std::time::Duration;
+
 use deadpool::managed::Object;
 use futures_util::{future::BoxFuture, FutureExt};
 use redis::{AsyncCommands, SetOptions};
+use runtime::entity_cache::EntryWithTtl;
 use tracing::{field::Empty, Instrument};
 
 use crate::redis::{Manager, Pool};
 
+/// Extends the expiry of a tag set to the given number of seconds, never shortening it. A new
+/// set has no expiry and a negative TTL, so it's always set. Works without the `NX` and `GT`
+/// options of `EXPIRE`, which need Redis 7.
+const EXTEND_EXPIRY_SCRIPT: &str = r"
+local ttl = redis.call('TTL', KEYS[1])
+if ttl < tonumber(ARGV[1]) then
+    redis.call('EXPIRE', KEYS[1], ARGV[1])
+end
+";
+
+/// Number of entries of a tag set fetched and deleted at once when purging.
+const PURGE_BATCH_SIZE: usize = 1000;
+
 pub struct RedisEntityCache {
     pool: Pool,
     key_prefix: String,
@@ -18,9 +34,15 @@ impl RedisEntityCache {
         }
     }
 
-    async fn get(&self, name: &str) -> anyhow::Result<Option<Vec<u8>>> {
+    async fn get(&self, name: &str) -> anyhow::Result<Option<EntryWithTtl>> {
         let mut connection = self.connection().await?;
-        Ok(connection.get(self.key(name)).await?)
+        let key = self.key(name);
+
+        // PTTL is negative if the key has no expiry or doesn't exist anymore.
+        let (data, ttl): (Option<Vec<u8>>, i64) =
+            redis::pipe().get(&key).pttl(&key).query_async(&mut *connection).await?;
+
+        Ok(data.map(|data| (data, u64::try_from(ttl).ok().map(Duration::from_millis))))
     }
 
     async fn put(
@@ -28,16 +50,75 @@ impl RedisEntityCache {
         name: &str,
         bytes: std::borrow::Cow<'_, [u8]>,
         expiration_ttl: std::time::Duration,
+        tags: &[String],
     ) -> anyhow::Result<()> {
         let mut connection = self.connection().await?;
+        let key = self.key(name);
         let options = SetOptions::default().with_expiration(self.expiry_time(expiration_ttl));
-        Ok(connection.set_options(self.key(name), bytes.as_ref(), options).await?)
+
+        let mut pipeline = redis::pipe();
+        pipeline.set_options(&key, bytes.as_ref(), options).ignore();
+
+        // Tag sets live as long as their longest-lived entry.
+        let ttl_secs = expiration_ttl.as_secs().max(1);
+
+        for tag in tags {
+            let tag_key = self.tag_key(tag);
+            pipeline.sadd(&tag_key, &key).ignore();
+            pipeline
+                .cmd("EVAL")
+                .arg(EXTEND_EXPIRY_SCRIPT)
+                .arg(1)
+                .arg(&tag_key)
+                .arg(ttl_secs)
+                .ignore();
+        }
+
+        Ok(pipeline.query_async::<()>(&mut *connection).await?)
+    }
+
+    async fn purge(&self, tags: &[String]) -> anyhow::Result<()> {
+        let mut connection = self.connection().await?;
+
+        for tag in tags {
+            let tag_key = self.tag_key(tag);
+            let mut cursor = 0u64;
+
+            // Tag sets can be large, so they're scanned in batches instead of loaded at once.
+            loop {
+                let (next_cursor, keys): (u64, Vec<String>) = redis::cmd("SSCAN")
+                    .arg(&tag_key)
+                    .arg(cursor)
+                    .arg("COUNT")
+                    .arg(PURGE_BATCH_SIZE)
+                    .query_async(&mut *connection)
+                    .await?;
+
+                if !keys.is_empty() {
+                    connection.del::<_, ()>(keys).await?;
+                }
+
+                if next_cursor == 0 {
+                    break;
+                }
+
+                cursor = next_cursor;
+            }
+
+            connection.del::<_, ()>(&tag_key).await?;
+        }
+
+        Ok(())
     }
 
     fn key(&self, name: &str) -> String {
         format!("{}-{name}", self.key_prefix)
     }
 
+    fn tag_key(&self, tag: &str) -> String {
+        format!("{}-tag-{tag}", self.key_prefix)
+    }
+
     fn expiry_time(&self, duration: std::time::Duration) -> redis::SetExpiry {
         if duration.as_secs() > 60 {
             redis::SetExpiry::PX(duration.as_millis() as u64)
@@ -59,6 +140,13 @@ impl RedisEntityCache {
 
 impl runtime::entity_cache::EntityCache for RedisEntityCache {
     fn get<'a>(&'a self, name: &'a str) -> BoxFuture<'a, anyhow::Result<Option<Vec<u8>>>> {
+        Box::pin(
+            self.get_with_ttl(name)
+                .map(|result| result.map(|entry| entry.map(|(data, _)| data))),
+        )
+    }
+
+    fn get_with_ttl<'a>(&'a self, name: &'a str) -> BoxFuture<'a, anyhow::Result<Option<EntryWithTtl>>> {
         let cache_span = tracing::info_span!(
             "entity cache get",
             "grafbase.entity_cache.status" = Empty,
@@ -89,8 +177,14 @@ impl runtime::entity_cache::EntityCache for RedisEntityCache {
         name: &'a str,
         bytes: std::borrow::Cow<'a, [u8]>,
         expiration_ttl: std::time::Duration,
+        tags: &'a [String],
     ) -> BoxFuture<'a, anyhow::Result<()>> {
         let cache_span = tracing::info_span!("entity cache put");
-        Box::pin(self.put(name, bytes, expiration_ttl).instrument(cache_span))
+        Box::pin(self.put(name, bytes, expiration_ttl, tags).instrument(cache_span))
+    }
+
+    fn purge<'a>(&'a self, tags: &'a [String]) -> BoxFuture<'a, anyhow::Result<()>> {
+        let cache_span = tracing::info_span!("entity cache purge");
+        Box::pin(self.purge(tags).instrument(cache_span))
     }
 }
@@ -1,12 +1,15 @@
+mod compression;
 mod signing;
+mod websocket;
 
 use std::collections::HashMap;
 use std::future::Future;
 
+use anyhow::Context;
 use bytes::Bytes;
 use futures_util::Stream;
 use futures_util::{StreamExt, TryStreamExt};
-use gateway_config::Config;
+use gateway_config::{Config, SubgraphClientConfig, SubgraphCompression, SubgraphTlsConfig};
 use reqwest::RequestBuilder;
 use reqwest_eventsource::RequestBuilderExt;
 use runtime::bytes::OwnedOrSharedBytes;
@@ -16,7 +19,9 @@ use signing::SigningParameters;
 
 #[derive(Clone)]
 pub struct NativeFetcher {
-    client: reqwest::Client,
+    client: SubgraphClient,
+    /// Dedicated clients of the subgraphs with their own TLS or HTTP client settings.
+    subgraph_clients: HashMap<String, SubgraphClient>,
     default_signing_parameters: Option<SigningParameters>,
     subgraph_signing_parameters: HashMap<String, Option<SigningParameters>>,
 }
@@ -36,12 +41,130 @@ impl NativeFetcher {
             })
             .collect::<anyhow::Result<_>>()?;
 
+        let global_tls = config.gateway.subgraph_tls.as_ref();
+        let global_client = config.gateway.subgraph_client.as_ref();
+
+        let subgraph_clients = config
+            .subgraphs
+            .iter()
+            .filter(|(_, value)| value.tls.is_some() || value.client.is_some())
+            .map(|(name, value)| {
+                let client_config = value.client.as_ref().map(|client| client.merged_with(global_client));
+                let client = build_client(
+                    value.tls.as_ref().or(global_tls),
+                    client_config.as_ref().or(global_client),
+                )
+                .with_context(|| format!("subgraph {name}"))?;
+
+                Ok((name.clone(), client))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
         Ok(NativeFetcher {
-            client: reqwest::Client::default(),
+            client: build_client(global_tls, global_client)?,
+            subgraph_clients,
             default_signing_parameters: default_signing_params,
             subgraph_signing_parameters,
         })
     }
+
+    fn client(&self, subgraph_name: &str) -> &SubgraphClient {
+        self.subgraph_clients.get(subgraph_name).unwrap_or(&self.client)
+    }
+}
+
+#[derive(Clone)]
+struct SubgraphClient {
+    inner: reqwest::Client,
+    request_compression: Option<SubgraphCompression>,
+    /// Connector of the websocket subscriptions, the default webpki roots are used without any TLS settings.
+    websocket_tls: Option<tokio_rustls::TlsConnector>,
+    /// reqwest doesn't do websockets, so subscriptions go through the proxy on their own.
+    websocket_proxy: Option<reqwest::Url>,
+}
+
+fn build_client(
+    tls: Option<&SubgraphTlsConfig>,
+    client: Option<&SubgraphClientConfig>,
+) -> anyhow::Result<SubgraphClient> {
+    // Response decompression is opt-in, we don't advertise it by default.
+    let mut builder = reqwest::Client::builder().no_gzip().no_brotli().no_zstd();
+
+    if let Some(client) = client {
+        builder = with_client_config(builder, client)?;
+    }
+
+    if let Some(tls) = tls {
+        builder = with_tls(builder, tls)?;
+    }
+
+    Ok(SubgraphClient {
+        inner: builder.build()?,
+        request_compression: client.and_then(|client| client.request_compression),
+        websocket_tls: tls.map(websocket::tls_connector).transpose()?,
+        websocket_proxy: client.and_then(|client| client.proxy.clone()),
+    })
+}
+
+fn with_client_config(
+    mut builder: reqwest::ClientBuilder,
+    client: &SubgraphClientConfig,
+) -> anyhow::Result<reqwest::ClientBuilder> {
+    if let Some(proxy) = &client.proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy.as_str()).context("invalid proxy URL")?);
+    }
+
+    if let Some(timeout) = client.pool_idle_timeout {
+        builder = builder.pool_idle_timeout(timeout);
+    }
+
+    if let Some(max_idle) = client.pool_max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(max_idle);
+    }
+
+    if client.http2_prior_knowledge.unwrap_or_default() {
+        builder = builder.http2_prior_knowledge();
+    }
+
+    if let Some(interval) = client.tcp_keepalive {
+        builder = builder.tcp_keepalive(interval);
+    }
+
+    if client.response_compression.unwrap_or_default() {
+        builder = builder.gzip(true).brotli(true).zstd(true);
+    }
+
+    Ok(builder)
+}
+
+fn with_tls(mut builder: reqwest::ClientBuilder, tls: &SubgraphTlsConfig) -> anyhow::Result<reqwest::ClientBuilder> {
+    if let Some(ca) = &tls.ca {
+        let pem = std::fs::read(ca).with_context(|| format!("loading the CA bundle {}", ca.display()))?;
+
+        for certificate in reqwest::Certificate::from_pem_bundle(&pem).context("parsing the CA bundle")? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    match (&tls.cert, &tls.key) {
+        (Some(cert), Some(key)) => {
+            let mut pem =
+                std::fs::read(cert).with_context(|| format!("loading the client certificate {}", cert.display()))?;
+            pem.push(b'\n');
+            pem.extend(std::fs::read(key).with_context(|| format!("loading the client key {}", key.display()))?);
+
+            let identity = reqwest::Identity::from_pem(&pem).context("parsing the client certificate and key")?;
+            builder = builder.identity(identity);
+        }
+        (None, None) => {}
+        _ => anyhow::bail!("a client certificate requires both a cert and a key"),
+    }
+
+    if tls.insecure_skip_verify {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    Ok(builder)
 }
 
 impl Fetcher for NativeFetcher {
@@ -51,15 +174,19 @@ impl Fetcher for NativeFetcher {
     ) -> (FetchResult<http::Response<OwnedOrSharedBytes>>, Option<ResponseInfo>) {
         let mut info = ResponseInfo::builder();
         let subgraph_name = request.subgraph_name;
+        let client = self.client(subgraph_name);
 
-        let request = into_reqwest(request);
+        let request = match into_reqwest(request, client.request_compression).await {
+            Ok(request) => request,
+            Err(error) => return (Err(error), None),
+        };
 
         let request = match self.sign_request(subgraph_name, request).await {
             Ok(request) => request,
             Err(error) => return (Err(error), None),
         };
 
-        let result = self.client.execute(request).await.map_err(|e| {
+        let result = client.inner.execute(request).await.map_err(|e| {
             if e.is_timeout() {
                 FetchError::Timeout
             } else {
@@ -103,11 +230,14 @@ impl Fetcher for NativeFetcher {
         &self,
         request: FetchRequest<'_, Bytes>,
     ) -> FetchResult<impl Stream<Item = FetchResult<OwnedOrSharedBytes>> + Send + 'static> {
-        let mut request = into_reqwest(request);
+        let client = self.client(request.subgraph_name);
+        let compression = client.request_compression;
+        let client = client.inner.clone();
+        let mut request = into_reqwest(request, compression).await?;
         // We're doing a streaming request, for subscriptions, so we don't want to timeout
         *request.timeout_mut() = None;
 
-        let events = RequestBuilder::from_parts(self.client.clone(), request)
+        let events = RequestBuilder::from_parts(client, request)
             .eventsource()
             .unwrap()
             .map_err(|err| match err {
@@ -146,6 +276,10 @@ impl Fetcher for NativeFetcher {
     {
         use tungstenite::{client::IntoClientRequest, http::HeaderValue};
 
+        let client = self.client(request.subgraph_name);
+        let tls_connector = client.websocket_tls.clone();
+        let proxy = client.websocket_proxy.clone();
+
         // graphql_ws_client requires a 'static body which we can't provide.
         let body = serde_json::value::to_raw_value(&request.body).map_err(|err| FetchError::any(err.to_string()));
         let mut ws_request = request.url.as_ref().into_client_request().unwrap();
@@ -156,11 +290,9 @@ impl Fetcher for NativeFetcher {
         );
 
         async move {
-            let (connection, _) = {
-                async_tungstenite::tokio::connect_async(ws_request)
-                    .await
-                    .map_err(FetchError::any)?
-            };
+            let connection = websocket::connect(ws_request, tls_connector, proxy.as_ref())
+                .await
+                .map_err(FetchError::any)?;
 
             Ok(graphql_ws_client::Client::build(connection)
                 .payload(request.websocket_init_payload)
@@ -177,12 +309,31 @@ fn reqwest_error_to_fetch_error(e: reqwest::Error) -> FetchError {
     FetchError::any(e.without_url())
 }
 
-fn into_reqwest(request: FetchRequest<'_, Bytes>) -> reqwest::Request {
+async fn into_reqwest(
+    request: FetchRequest<'_, Bytes>,
+    compression: Option<SubgraphCompression>,
+) -> FetchResult<reqwest::Request> {
+    let mut headers = request.headers;
+    let mut body = request.body;
+
+    let compressed = match compression {
+        Some(compression) => compression::compress(compression, body.clone())
+            .await
+            .map_err(FetchError::any)?,
+        None => None,
+    };
+
+    if let Some((compressed, encoding)) = compressed {
+        headers.insert(http::header::CONTENT_ENCODING, http::HeaderValue::from_static(encoding));
+        headers.insert(http::header::CONTENT_LENGTH, http::HeaderValue::from(compressed.len()));
+        body = compressed;
+    }
+
     let mut req = reqwest::Request::new(request.method, request.url.into_owned());
-    *req.headers_mut() = request.headers;
-    *req.body_mut() = Some(request.body.into());
+    *req.headers_mut() = headers;
+    *req.body_mut() = Some(body.into());
     *req.timeout_mut() = Some(request.timeout);
-    req
+    Ok(req)
 }
 
 #[derive(serde::Serialize)]
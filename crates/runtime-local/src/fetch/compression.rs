@@ -0,0 +1,48 @@
+use std::io::Write;
+
+use bytes::Bytes;
+use gateway_config::SubgraphCompression;
+
+/// Smaller bodies are sent as is, compressing them gains little and may even grow them.
+const MIN_COMPRESSION_SIZE: usize = 1024;
+
+/// Compresses a request body on the blocking thread pool, returning it along with its
+/// `Content-Encoding`. Bodies smaller than [MIN_COMPRESSION_SIZE] aren't compressed.
+pub(super) async fn compress(
+    compression: SubgraphCompression,
+    body: Bytes,
+) -> std::io::Result<Option<(Bytes, &'static str)>> {
+    if body.len() < MIN_COMPRESSION_SIZE {
+        return Ok(None);
+    }
+
+    tokio::task::spawn_blocking(move || compress_blocking(compression, &body))
+        .await
+        .map_err(std::io::Error::other)?
+        .map(Some)
+}
+
+fn compress_blocking(compression: SubgraphCompression, body: &[u8]) -> std::io::Result<(Bytes, &'static str)> {
+    let compressed = match compression {
+        SubgraphCompression::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(body)?;
+            encoder.finish()?
+        }
+        SubgraphCompression::Brotli => {
+            let mut compressed = Vec::new();
+            {
+                let mut encoder = brotli::CompressorWriter::new(&mut compressed, 4096, BROTLI_QUALITY, BROTLI_WINDOW);
+                encoder.write_all(body)?;
+            }
+            compressed
+        }
+        SubgraphCompression::Zstd => zstd::encode_all(body, zstd::DEFAULT_COMPRESSION_LEVEL)?,
+    };
+
+    Ok((Bytes::from(compressed), compression.content_encoding()))
+}
+
+/// The default quality of 11 is far too slow to be used on every request.
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_WINDOW: u32 = 22;
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use async_tungstenite::{tokio::ConnectStream, WebSocketStream};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use gateway_config::SubgraphTlsConfig;
+use reqwest::Url;
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+    ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use tokio_rustls::TlsConnector;
+use tungstenite::handshake::client::Request;
+
+/// Largest response head accepted from a proxy to a `CONNECT` request.
+const MAX_PROXY_RESPONSE_HEAD: usize = 8 * 1024;
+
+/// Opens a websocket connection to the subgraph, through the proxy if there is one.
+pub(super) async fn connect(
+    request: Request,
+    tls: Option<TlsConnector>,
+    proxy: Option<&Url>,
+) -> anyhow::Result<WebSocketStream<ConnectStream>> {
+    let Some(proxy) = proxy else {
+        let (connection, _) = async_tungstenite::tokio::connect_async_with_tls_connector(request, tls).await?;
+        return Ok(connection);
+    };
+
+    let uri = request.uri();
+    let host = uri.host().context("missing host in the websocket URL")?;
+    let port = match uri.port_u16() {
+        Some(port) => port,
+        None if uri.scheme_str() == Some("wss") => 443,
+        None => 80,
+    };
+
+    let stream = tunnel(proxy, &format!("{host}:{port}")).await?;
+    let (connection, _) =
+        async_tungstenite::tokio::client_async_tls_with_connector_and_config(request, stream, tls, None).await?;
+
+    Ok(connection)
+}
+
+/// Opens a tunnel to the subgraph with a `CONNECT` request, the way reqwest goes through a proxy
+/// for HTTPS requests. Only plain HTTP proxies are supported.
+async fn tunnel(proxy: &Url, authority: &str) -> anyhow::Result<TcpStream> {
+    if proxy.scheme() != "http" {
+        anyhow::bail!("websocket subscriptions only support http:// proxies");
+    }
+
+    let proxy_host = proxy.host_str().context("missing host in the proxy URL")?;
+    let proxy_port = proxy.port_or_known_default().unwrap_or(80);
+
+    let mut stream = TcpStream::connect((proxy_host, proxy_port))
+        .await
+        .context("connecting to the proxy")?;
+
+    let mut head = format!("CONNECT {authority} HTTP/1.1\r\nHost: {authority}\r\n");
+
+    if !proxy.username().is_empty() {
+        let credentials = format!("{}:{}", proxy.username(), proxy.password().unwrap_or_default());
+        head.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            BASE64_STANDARD.encode(credentials)
+        ));
+    }
+
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes()).await?;
+
+    // The subgraph doesn't talk before the websocket handshake, so nothing past the response head
+    // can be read here.
+    let mut response = Vec::new();
+
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() >= MAX_PROXY_RESPONSE_HEAD {
+            anyhow::bail!("proxy response head is too large");
+        }
+
+        if stream.read_u8().await.map(|byte| response.push(byte)).is_err() {
+            anyhow::bail!("proxy closed the connection");
+        }
+    }
+
+    let status_line = response.split(|byte| *byte == b'\r').next().unwrap_or_default();
+    let status = status_line.split(|byte| *byte == b' ').nth(1).unwrap_or_default();
+
+    if !status.starts_with(b"2") {
+        anyhow::bail!("proxy refused the tunnel: {}", String::from_utf8_lossy(status_line));
+    }
+
+    Ok(stream)
+}
+
+/// TLS connector of the websocket connections, built from the same settings as the HTTP client
+/// since reqwest doesn't do websockets.
+pub(super) fn tls_connector(tls: &SubgraphTlsConfig) -> anyhow::Result<TlsConnector> {
+    let mut roots = RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+    if let Some(ca) = &tls.ca {
+        let pem = std::fs::read(ca).with_context(|| format!("loading the CA bundle {}", ca.display()))?;
+
+        for certificate in CertificateDer::pem_slice_iter(&pem) {
+            roots
+                .add(certificate.context("parsing the CA bundle")?)
+                .context("parsing the CA bundle")?;
+        }
+    }
+
+    let builder = ClientConfig::builder().with_root_certificates(roots);
+
+    let mut config = match (&tls.cert, &tls.key) {
+        (Some(cert), Some(key)) => {
+            let cert =
+                std::fs::read(cert).with_context(|| format!("loading the client certificate {}", cert.display()))?;
+            let key = std::fs::read(key).with_context(|| format!("loading the client key {}", key.display()))?;
+
+            let chain = CertificateDer::pem_slice_iter(&cert)
+                .collect::<Result<Vec<_>, _>>()
+                .context("parsing the client certificate")?;
+            let key = PrivateKeyDer::from_pem_slice(&key).context("parsing the client key")?;
+
+            builder
+                .with_client_auth_cert(chain, key)
+                .context("parsing the client certificate and key")?
+        }
+        (None, None) => builder.with_no_client_auth(),
+        _ => anyhow::bail!("a client certificate requires both a cert and a key"),
+    };
+
+    if tls.insecure_skip_verify {
+        let provider = config.crypto_provider().clone();
+        config
+            .dangerous()
+            .set_certificate_verifier(Arc::new(SkipServerVerification(provider)));
+    }
+
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// Accepts any server certificate, still checking the handshake signatures.
+#[derive(Debug)]
+struct SkipServerVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for SkipServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
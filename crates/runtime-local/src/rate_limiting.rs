@@ -1,3 +1,36 @@
 pub mod in_memory;
 #[cfg(feature = "redis")]
 pub mod redis;
+
+use gateway_config::{RateLimitRule, RateLimitRuleKey, RateLimitUnit};
+use runtime::rate_limiting::RateLimiterContext;
+
+/// The value a request is keyed on for a rule. Requests without a value for the rule's key source
+/// are not limited by it.
+pub(crate) fn rule_key_value(key: &RateLimitRuleKey, context: &dyn RateLimiterContext) -> Option<String> {
+    match key {
+        RateLimitRuleKey::Header(name) => {
+            let name = http::HeaderName::from_bytes(name.as_bytes()).ok()?;
+            context.header(name)?.to_str().ok().map(str::to_owned)
+        }
+        RateLimitRuleKey::JwtClaim(name) => match context.jwt_claim(name)? {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(value) => Some(value.clone()),
+            value => Some(value.to_string()),
+        },
+        RateLimitRuleKey::Ip => context.ip().map(|ip| ip.to_string()),
+        RateLimitRuleKey::OperationName => context.graphql_operation_name().map(str::to_owned),
+    }
+}
+
+/// How much a request consumes from the limit of a rule. Requests without a cost, because
+/// complexity control is disabled, are not limited by complexity rules.
+pub(crate) fn rule_cost(rule: &RateLimitRule, context: &dyn RateLimiterContext) -> Option<u32> {
+    match rule.unit {
+        RateLimitUnit::Requests => Some(1),
+        RateLimitUnit::Complexity => context
+            .complexity_cost()
+            .filter(|cost| *cost > 0)
+            .map(|cost| u32::try_from(cost).unwrap_or(u32::MAX)),
+    }
+}
@@ -1,16 +1,214 @@
+use std::borrow::Cow;
+use std::hash::Hash;
 use std::num::NonZeroU32;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use std::{collections::HashMap, sync::RwLock};
 
 use futures_util::future::BoxFuture;
 use futures_util::FutureExt;
-use gateway_config::{Config, GraphRateLimit};
+use gateway_config::{Config, GraphRateLimit, RateLimitRule};
+use governor::clock::{Clock, DefaultClock};
 use governor::Quota;
 
-use runtime::rate_limiting::{Error, RateLimitKey, RateLimiter, RateLimiterContext};
+use runtime::rate_limiting::{Error, ExceededCapacity, RateLimitKey, RateLimiter, RateLimiterContext};
 use tokio::sync::watch;
 
-type Limiters = HashMap<RateLimitKey<'static>, governor::DefaultKeyedRateLimiter<usize>>;
+use crate::rate_limiting::{rule_cost, rule_key_value};
+
+/// How often the state of the rule limiters is cleaned up from keys which are back to full capacity.
+const RULE_LIMITERS_CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+struct Limiters {
+    keys: HashMap<RateLimitKey<'static>, KeyedLimiter<usize>>,
+    /// One limiter per rule. They're all behind the same lock, so a request can be checked against
+    /// every rule before consuming from any of them.
+    rules: Mutex<Vec<RuleLimiter>>,
+}
+
+/// A limiter with its configured limit, to describe the exceeded limit to clients. The quota
+/// given to governor may be derived from it, with a different burst.
+struct KeyedLimiter<K: Clone + Hash + Eq> {
+    inner: governor::DefaultKeyedRateLimiter<K>,
+    quota: Quota,
+    config: GraphRateLimit,
+}
+
+impl<K: Clone + Hash + Eq> KeyedLimiter<K> {
+    fn new(quota: Quota, config: GraphRateLimit) -> Self {
+        Self {
+            inner: governor::RateLimiter::keyed(quota),
+            quota,
+            config,
+        }
+    }
+
+    fn check(&self, name: &RateLimitKey<'_>, key: &K, cost: NonZeroU32) -> Result<(), Error> {
+        let retry_after = match self.inner.check_key_n(key, cost) {
+            Ok(Ok(())) => return Ok(()),
+            Ok(Err(not_until)) => Some(not_until.wait_time_from(DefaultClock::default().now())),
+            // The cost is higher than the whole limit, the request will never fit.
+            Err(_) => None,
+        };
+
+        // The time to wait tells us how many units are missing for the request to fit.
+        let replenish_interval = self.quota.replenish_interval();
+        let remaining = retry_after
+            .map(|wait| {
+                let missing = wait.as_nanos().div_ceil(replenish_interval.as_nanos().max(1));
+                u128::from(cost.get()).saturating_sub(missing) as u32
+            })
+            .unwrap_or_default();
+
+        let limit = u32::try_from(self.config.limit).unwrap_or(u32::MAX).max(1);
+        let interval = self.config.duration / limit;
+
+        Err(Error::ExceededCapacity(ExceededCapacity {
+            key: name.to_string(),
+            limit: self.config.limit,
+            remaining: remaining as usize,
+            reset: interval * limit.saturating_sub(remaining),
+            retry_after,
+        }))
+    }
+}
+
+/// Token buckets of a rule, one per value requests are keyed on. A bucket holds the whole limit
+/// and is refilled over the duration, so the whole limit can be consumed at once, by as many
+/// requests or by a single complex one.
+struct RuleLimiter {
+    rule: RateLimitRule,
+    buckets: HashMap<String, Bucket>,
+}
+
+#[derive(Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RuleLimiter {
+    fn new(rule: RateLimitRule) -> Option<Self> {
+        if rule.limit == 0 || rule.duration.is_zero() {
+            tracing::error!("the limit and duration of a rate limit rule cannot be zero");
+            return None;
+        }
+
+        Some(Self {
+            rule,
+            buckets: HashMap::new(),
+        })
+    }
+
+    /// Tokens added back per second.
+    fn refill_rate(&self) -> f64 {
+        self.rule.limit as f64 / self.rule.duration.as_secs_f64()
+    }
+
+    /// The bucket of the key as of now, refilled since it was last used.
+    fn bucket(&self, key: &str, now: Instant) -> Bucket {
+        let limit = self.rule.limit as f64;
+
+        match self.buckets.get(key) {
+            Some(bucket) => {
+                let elapsed = now.saturating_duration_since(bucket.updated_at).as_secs_f64();
+
+                Bucket {
+                    tokens: (bucket.tokens + elapsed * self.refill_rate()).min(limit),
+                    updated_at: now,
+                }
+            }
+            None => Bucket {
+                tokens: limit,
+                updated_at: now,
+            },
+        }
+    }
+
+    fn exceeded(&self, value: &str, bucket: Bucket, cost: u32) -> Error {
+        let rate = self.refill_rate();
+        let limit = self.rule.limit as f64;
+        let cost = f64::from(cost);
+
+        let name = RateLimitKey::Rule {
+            name: Cow::Borrowed(&self.rule.name),
+            value: Cow::Borrowed(value),
+        };
+
+        Error::ExceededCapacity(ExceededCapacity {
+            key: name.to_string(),
+            limit: self.rule.limit,
+            remaining: bucket.tokens as usize,
+            reset: Duration::from_secs_f64((limit - bucket.tokens) / rate),
+            // A cost higher than the limit will never fit.
+            retry_after: (cost <= limit).then(|| Duration::from_secs_f64((cost - bucket.tokens) / rate)),
+        })
+    }
+
+    /// Drops the buckets which are back to full capacity, they're equivalent to a missing one.
+    fn cleanup(&mut self, now: Instant) {
+        let limit = self.rule.limit as f64;
+        let rate = self.refill_rate();
+
+        self.buckets.retain(|_, bucket| {
+            let elapsed = now.saturating_duration_since(bucket.updated_at).as_secs_f64();
+            bucket.tokens + elapsed * rate < limit
+        });
+        self.buckets.shrink_to_fit();
+    }
+}
+
+impl Limiters {
+    fn new(keys: HashMap<RateLimitKey<'static>, GraphRateLimit>, rules: &[RateLimitRule]) -> Self {
+        let mut limiters = Self::default();
+
+        for (key, limits) in keys {
+            let Some(limiter) = create_limiter(limits) else {
+                continue;
+            };
+
+            limiters.keys.insert(key, limiter);
+        }
+
+        limiters.rules = Mutex::new(rules.iter().cloned().filter_map(RuleLimiter::new).collect());
+
+        limiters
+    }
+
+    /// Checks the request against every rule, and only consumes from them if all of them allow it.
+    fn limit_rules(&self, context: &dyn RateLimiterContext) -> Result<(), Error> {
+        let mut rules = self.rules.lock().unwrap();
+        let now = Instant::now();
+
+        let mut consumed = Vec::new();
+
+        for (i, limiter) in rules.iter().enumerate() {
+            let Some(value) = rule_key_value(&limiter.rule.key, context) else {
+                continue;
+            };
+
+            let Some(cost) = rule_cost(&limiter.rule, context) else {
+                continue;
+            };
+
+            let mut bucket = limiter.bucket(&value, now);
+
+            if bucket.tokens < f64::from(cost) {
+                return Err(limiter.exceeded(&value, bucket, cost));
+            }
+
+            bucket.tokens -= f64::from(cost);
+            consumed.push((i, value, bucket));
+        }
+
+        for (i, value, bucket) in consumed {
+            rules[i].buckets.insert(value, bucket);
+        }
+
+        Ok(())
+    }
+}
 
 pub struct InMemoryRateLimiter {
     limiters: Arc<RwLock<Limiters>>,
@@ -35,34 +233,15 @@ pub fn as_keyed_rate_limit_config(config: &Config) -> HashMap<RateLimitKey<'stat
 
 impl InMemoryRateLimiter {
     pub fn runtime(rate_limiting_configs: HashMap<RateLimitKey<'static>, GraphRateLimit>) -> RateLimiter {
-        let mut limiters = HashMap::new();
-
-        // add subgraph rate limiting configuration
-        for (key, limits) in rate_limiting_configs {
-            let Some(limiter) = create_limiter(limits) else {
-                continue;
-            };
-
-            limiters.insert(key.clone(), limiter);
-        }
-
-        let limiters = Arc::new(RwLock::new(limiters));
+        let limiters = Arc::new(RwLock::new(Limiters::new(rate_limiting_configs, &[])));
         RateLimiter::new(Self { limiters })
     }
 
     pub fn runtime_with_watcher(mut config: watch::Receiver<Config>) -> RateLimiter {
-        let mut limiters = HashMap::new();
-        let rate_limiting_configs = as_keyed_rate_limit_config(&config.borrow());
-
-        // add subgraph rate limiting configuration
-        for (key, limits) in rate_limiting_configs {
-            let Some(limiter) = create_limiter(limits) else {
-                continue;
-            };
-
-            limiters.insert(key.clone(), limiter);
-        }
-
+        let limiters = Limiters::new(
+            as_keyed_rate_limit_config(&config.borrow()),
+            rate_limit_rules(&config.borrow()),
+        );
         let limiters = Arc::new(RwLock::new(limiters));
         let limiters_copy = Arc::downgrade(&limiters);
 
@@ -72,16 +251,31 @@ impl InMemoryRateLimiter {
                     break;
                 };
 
-                let mut limiters = limiters.write().unwrap();
-                limiters.clear();
+                let new_limiters = Limiters::new(
+                    as_keyed_rate_limit_config(&config.borrow()),
+                    rate_limit_rules(&config.borrow()),
+                );
+                *limiters.write().unwrap() = new_limiters;
+            }
+        });
+
+        let limiters_copy = Arc::downgrade(&limiters);
+
+        // Rule limiters keep a state per key, which would otherwise grow with every new client.
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(RULE_LIMITERS_CLEANUP_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                let Some(limiters) = limiters_copy.upgrade() else {
+                    break;
+                };
 
-                let rate_limiting_configs = as_keyed_rate_limit_config(&config.borrow());
-                for (key, limits) in rate_limiting_configs {
-                    let Some(limiter) = create_limiter(limits) else {
-                        continue;
-                    };
+                let now = Instant::now();
 
-                    limiters.insert(key, limiter);
+                for limiter in limiters.read().unwrap().rules.lock().unwrap().iter_mut() {
+                    limiter.cleanup(now);
                 }
             }
         });
@@ -90,7 +284,19 @@ impl InMemoryRateLimiter {
     }
 }
 
-fn create_limiter(rate_limit_config: GraphRateLimit) -> Option<governor::DefaultKeyedRateLimiter<usize>> {
+fn rate_limit_rules(config: &Config) -> &[RateLimitRule] {
+    config
+        .gateway
+        .rate_limit
+        .as_ref()
+        .map(|config| config.rules.as_slice())
+        .unwrap_or_default()
+}
+
+fn create_limiter<K>(rate_limit_config: GraphRateLimit) -> Option<KeyedLimiter<K>>
+where
+    K: Clone + Hash + Eq,
+{
     let Some(quota) = (rate_limit_config.limit as u64).checked_div(rate_limit_config.duration.as_secs()) else {
         tracing::error!("the duration for rate limit cannot be zero");
         return None;
@@ -101,19 +307,20 @@ fn create_limiter(rate_limit_config: GraphRateLimit) -> Option<governor::Default
         return None;
     };
 
-    Some(governor::RateLimiter::keyed(Quota::per_second(quota)))
+    Some(KeyedLimiter::new(Quota::per_second(quota), rate_limit_config))
 }
 
 impl runtime::rate_limiting::RateLimiterInner for InMemoryRateLimiter {
     fn limit<'a>(&'a self, context: &'a dyn RateLimiterContext) -> BoxFuture<'a, Result<(), Error>> {
         async {
-            let Some(key) = context.key() else { return Ok(()) };
             let limiters = self.limiters.read().unwrap();
 
-            if let Some(rate_limiter) = limiters.get(key) {
-                rate_limiter
-                    .check_key(&usize::MIN)
-                    .map_err(|_err| Error::ExceededCapacity)?;
+            let Some(key) = context.key() else {
+                return limiters.limit_rules(context);
+            };
+
+            if let Some(rate_limiter) = limiters.keys.get(key) {
+                rate_limiter.check(key, &usize::MIN, NonZeroU32::MIN)?;
             };
 
             Ok(())
@@ -1,4 +1,7 @@
-use std::time::{Duration, SystemTime};
+use std::{
+    borrow::Cow,
+    time::{Duration, SystemTime},
+};
 
 use futures_util::future::BoxFuture;
 use gateway_config::{Config, GraphRateLimit};
@@ -6,11 +9,14 @@ use grafbase_telemetry::otel::opentelemetry::{
     metrics::{Histogram, Meter},
     KeyValue,
 };
-use runtime::rate_limiting::{Error, RateLimitKey, RateLimiter, RateLimiterContext};
+use runtime::rate_limiting::{Error, ExceededCapacity, RateLimitKey, RateLimiter, RateLimiterContext};
 use tokio::sync::watch;
 use tracing::{field::Empty, Instrument};
 
-use crate::redis::Pool;
+use crate::{
+    rate_limiting::{rule_cost, rule_key_value},
+    redis::Pool,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct RateLimitRedisConfig<'a> {
@@ -86,6 +92,12 @@ impl RedisRateLimiter {
             RateLimitKey::Subgraph(ref graph) => {
                 format!("{}:subgraph:rate_limit:{graph}:{bucket}", self.key_prefix)
             }
+            RateLimitKey::Rule { name, value } => {
+                let name = escape_key_segment(name);
+                let value = escape_key_segment(value);
+
+                format!("{}:rate_limit:rule:{name}:{value}:{bucket}", self.key_prefix)
+            }
         }
     }
 
@@ -94,7 +106,17 @@ impl RedisRateLimiter {
         self.latencies.record(duration.as_millis() as u64, &attributes);
     }
 
-    async fn limit_inner(&self, key: &RateLimitKey<'_>, config: GraphRateLimit) -> Result<(), Error> {
+    /// Consumes `cost` from the limit of the key, one for request-based limits.
+    async fn limit_inner(&self, key: &RateLimitKey<'_>, config: GraphRateLimit, cost: u64) -> Result<(), Error> {
+        let current_bucket = self.check(key, config, cost).await?;
+        tokio::spawn(incr_counter(self.pool.clone(), current_bucket, config.duration, cost));
+
+        Ok(())
+    }
+
+    /// Checks whether `cost` fits in the limit of the key without consuming it, returning the
+    /// counter key of the current window to increment.
+    async fn check(&self, key: &RateLimitKey<'_>, config: GraphRateLimit, cost: u64) -> Result<String, Error> {
         let now = SystemTime::now();
 
         let current_ts = match now.duration_since(SystemTime::UNIX_EPOCH) {
@@ -149,12 +171,22 @@ impl RedisRateLimiter {
                 // current window.
                 let average = previous_count as f64 * (1.0 - bucket_percentage) + current_count as f64;
 
-                if average < config.limit as f64 {
-                    tokio::spawn(incr_counter(self.pool.clone(), current_bucket, config.duration));
-
-                    Ok(())
+                // The request fits if what is left of the limit covers its cost.
+                if average + ((cost - 1) as f64) < config.limit as f64 {
+                    Ok(current_bucket)
                 } else {
-                    Err(Error::ExceededCapacity)
+                    let window_end = Duration::from_nanos(duration_ns - current_ts % duration_ns);
+
+                    Err(Error::ExceededCapacity(ExceededCapacity {
+                        key: key.to_string(),
+                        limit: config.limit,
+                        remaining: (config.limit as f64 - average).max(0.0) as usize,
+                        // The current window is counted until the end of the next one.
+                        reset: window_end + config.duration,
+                        // A cost higher than the limit will never fit, otherwise the current window
+                        // is the one filled up.
+                        retry_after: (cost <= config.limit as u64).then_some(window_end),
+                    }))
                 }
             }
             Err(e) => {
@@ -166,7 +198,7 @@ impl RedisRateLimiter {
     }
 }
 
-async fn incr_counter(pool: Pool, current_bucket: String, expire: Duration) -> Result<(), Error> {
+async fn incr_counter(pool: Pool, current_bucket: String, expire: Duration, cost: u64) -> Result<(), Error> {
     let mut conn = match pool.get().await {
         Ok(conn) => conn,
         Err(error) => {
@@ -178,7 +210,7 @@ async fn incr_counter(pool: Pool, current_bucket: String, expire: Duration) -> R
     let mut pipe = redis::pipe();
     pipe.atomic();
 
-    pipe.cmd("INCR").arg(&current_bucket);
+    pipe.cmd("INCRBY").arg(&current_bucket).arg(cost);
 
     // Sets the timeout to the set. This will delete the data after the duration if we do not modify the value.
     pipe.cmd("EXPIRE")
@@ -194,10 +226,65 @@ async fn incr_counter(pool: Pool, current_bucket: String, expire: Duration) -> R
     Ok(())
 }
 
+/// Rule names and values may contain the `:` separator, which could otherwise make two different
+/// rules share the same counter.
+fn escape_key_segment(segment: &str) -> Cow<'_, str> {
+    if segment.contains([':', '%']) {
+        Cow::Owned(segment.replace('%', "%25").replace(':', "%3A"))
+    } else {
+        Cow::Borrowed(segment)
+    }
+}
+
+impl RedisRateLimiter {
+    /// Checks the request against every rule, and only consumes from them if all of them allow it.
+    async fn limit_rules(&self, context: &dyn RateLimiterContext) -> Result<(), Error> {
+        // The configuration can't be borrowed across an await point, only the matching rules are kept.
+        let limits = {
+            let config = self.config_watcher.borrow();
+
+            let Some(rate_limit) = config.gateway.rate_limit.as_ref() else {
+                return Ok(());
+            };
+
+            rate_limit
+                .rules
+                .iter()
+                .filter_map(|rule| {
+                    let value = rule_key_value(&rule.key, context)?;
+                    let cost = rule_cost(rule, context)?;
+
+                    Some((rule.name.clone(), value, rule.rate_limit(), u64::from(cost)))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut counters = Vec::with_capacity(limits.len());
+
+        for (name, value, config, cost) in limits {
+            let key = RateLimitKey::Rule {
+                name: Cow::Borrowed(&name),
+                value: Cow::Owned(value),
+            };
+
+            let span = tracing::info_span!("rate limit", "rate_limit.rule" = name.as_str());
+            let current_bucket = self.check(&key, config, cost).instrument(span).await?;
+
+            counters.push((current_bucket, config.duration, cost));
+        }
+
+        for (current_bucket, duration, cost) in counters {
+            tokio::spawn(incr_counter(self.pool.clone(), current_bucket, duration, cost));
+        }
+
+        Ok(())
+    }
+}
+
 impl runtime::rate_limiting::RateLimiterInner for RedisRateLimiter {
     fn limit<'a>(&'a self, context: &'a dyn RateLimiterContext) -> BoxFuture<'a, Result<(), Error>> {
         let Some(key) = context.key() else {
-            return Box::pin(async { Ok(()) });
+            return Box::pin(self.limit_rules(context));
         };
 
         let config = match key {
@@ -214,6 +301,14 @@ impl runtime::rate_limiting::RateLimiterInner for RedisRateLimiter {
                 .subgraphs
                 .get(name.as_ref())
                 .and_then(|sb| sb.rate_limit),
+            RateLimitKey::Rule { name, .. } => self
+                .config_watcher
+                .borrow()
+                .gateway
+                .rate_limit
+                .as_ref()
+                .and_then(|rt| rt.rules.iter().find(|rule| rule.name == name.as_ref()))
+                .map(|rule| rule.rate_limit()),
         };
 
         let Some(config) = config else {
@@ -226,6 +321,6 @@ impl runtime::rate_limiting::RateLimiterInner for RedisRateLimiter {
             span.record("subgraph.name", subgraph.as_ref());
         }
 
-        Box::pin(self.limit_inner(key, config).instrument(span))
+        Box::pin(self.limit_inner(key, config, 1).instrument(span))
     }
 }
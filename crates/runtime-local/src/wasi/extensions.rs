@@ -1,17 +1,25 @@
 mod pool;
 
 use extension_catalog::ExtensionId;
-use futures_util::StreamExt;
+use futures_util::{stream::BoxStream, StreamExt};
 use gateway_config::WasiExtensionsConfig;
 use runtime::{
+    entity_cache::EntityCache,
     error::{ErrorResponse, PartialErrorCode, PartialGraphqlError},
-    extension::{AuthorizerId, Data, ExtensionFieldDirective, ExtensionRuntime},
+    extension::{
+        AuthorizedDefinition, AuthorizerId, Data, ExtensionAuthorizationDirective, ExtensionFieldDirective,
+        ExtensionRuntime,
+    },
     hooks::Anything,
 };
 use semver::Version;
 use std::{collections::HashMap, future::Future, sync::Arc};
 use tokio::task::JoinHandle;
-use wasi_component_loader::{ChannelLogSender, ComponentLoader, FieldDefinition, InputList, SharedContext};
+use url::Url;
+use wasi_component_loader::{
+    ChannelLogSender, ComponentLoader, ExtensionsComponentInstance, FieldDefinition, InputList, QueryElement,
+    SharedContext,
+};
 pub use wasi_component_loader::{Directive, ExtensionType};
 
 use pool::Pool;
@@ -24,14 +32,28 @@ pub struct WasiExtensions(Option<Arc<WasiExtensionsInner>>);
 impl WasiExtensions {
     pub async fn new(
         access_log: ChannelLogSender,
+        entity_cache: Arc<dyn EntityCache>,
         extensions: Vec<ExtensionConfig>,
     ) -> Result<Self, wasi_component_loader::Error> {
         if extensions.is_empty() {
             return Ok(Self(None));
         }
 
-        let instance_pools = create_pools(access_log, extensions).await?;
-        let inner = WasiExtensionsInner { instance_pools };
+        let mut hooks = extensions
+            .iter()
+            .filter_map(|config| match config.id {
+                ExtensionPoolId::Hooks(id) => Some((config.order.unwrap_or(u16::MAX), config.name.clone(), id)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // Lower orders come first, ties are broken by the extension name.
+        hooks.sort();
+
+        let hooks = hooks.into_iter().map(|(_, _, id)| id).collect();
+
+        let instance_pools = create_pools(access_log, entity_cache, extensions).await?;
+        let inner = WasiExtensionsInner { instance_pools, hooks };
 
         Ok(Self(Some(Arc::new(inner))))
     }
@@ -39,6 +61,7 @@ impl WasiExtensions {
 
 async fn create_pools(
     access_log: ChannelLogSender,
+    entity_cache: Arc<dyn EntityCache>,
     extensions: Vec<ExtensionConfig>,
 ) -> Result<HashMap<ExtensionPoolId, Pool>, wasi_component_loader::Error> {
     type Handle = JoinHandle<Result<Option<(ExtensionPoolId, Pool)>, wasi_component_loader::Error>>;
@@ -47,6 +70,7 @@ async fn create_pools(
 
     for config in extensions {
         let access_log = access_log.clone();
+        let entity_cache = entity_cache.clone();
 
         creating_pools.push(tokio::task::spawn_blocking(move || {
             let manager_config = pool::ComponentManagerConfig {
@@ -59,7 +83,7 @@ async fn create_pools(
             match ComponentLoader::extensions(config.name, config.wasi_config)? {
                 Some(loader) => {
                     let pool = Pool::new(
-                        loader,
+                        loader.with_entity_cache(entity_cache),
                         manager_config,
                         config.max_pool_size,
                         config.extension_config,
@@ -158,12 +182,63 @@ impl ExtensionRuntime for WasiExtensions {
 
                         Err(error)
                     }
-                    _ => Err(PartialGraphqlError::internal_extension_error()),
+                    wasi_component_loader::Error::Internal(error) => {
+                        tracing::error!("field resolver extension error: {error}");
+                        Err(PartialGraphqlError::internal_extension_error())
+                    }
                 },
             }
         }
     }
 
+    #[allow(clippy::manual_async_fn)]
+    fn resolve_subscription<'ctx, 'f>(
+        &'ctx self,
+        context: &'ctx Self::SharedContext,
+        ExtensionFieldDirective {
+            extension_id,
+            subgraph,
+            field,
+            name,
+            arguments,
+        }: ExtensionFieldDirective<'ctx, impl Anything<'ctx>>,
+    ) -> impl Future<Output = Result<BoxStream<'f, Result<Data, PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f,
+    {
+        async move {
+            let Some(inner) = self.0.as_ref() else {
+                return Err(PartialGraphqlError::internal_extension_error());
+            };
+
+            let id = ExtensionPoolId::Resolver(extension_id);
+
+            let Some(pool) = inner.instance_pools.get(&id) else {
+                return Err(PartialGraphqlError::internal_extension_error());
+            };
+
+            // The instance belongs to the subscription until it ends, and is then dropped
+            // instead of going back to the pool.
+            let mut instance = pool.get().await.detach();
+
+            let directive = Directive::new(name.to_string(), subgraph.name().to_string(), arguments);
+
+            let definition = FieldDefinition {
+                type_name: field.parent_entity().name().to_string(),
+                name: field.name().to_string(),
+            };
+
+            instance
+                .resolve_subscription(context.clone(), directive, definition)
+                .await
+                .map_err(subscription_error_as_gql)?;
+
+            let stream = futures_util::stream::unfold(Some(instance), next_subscription_item);
+
+            Ok(stream.boxed())
+        }
+    }
+
     async fn authenticate(
         &self,
         extension_id: ExtensionId,
@@ -210,12 +285,258 @@ impl ExtensionRuntime for WasiExtensions {
             }),
         }
     }
+
+    #[allow(clippy::manual_async_fn)]
+    fn authorize_query<'ctx, 'f, Args: Anything<'ctx>>(
+        &'ctx self,
+        context: &'ctx Self::SharedContext,
+        extension_id: ExtensionId,
+        elements: Vec<ExtensionAuthorizationDirective<'ctx, Args>>,
+    ) -> impl Future<Output = Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f,
+    {
+        let elements = elements
+            .into_iter()
+            .map(|element| QueryElement {
+                directive: Directive::new(
+                    element.name.to_string(),
+                    element.subgraph.name().to_string(),
+                    element.arguments,
+                ),
+                definition: match element.definition {
+                    AuthorizedDefinition::Field(field) => {
+                        wasi_component_loader::AuthorizedDefinition::Field(FieldDefinition {
+                            type_name: field.parent_entity().name().to_string(),
+                            name: field.name().to_string(),
+                        })
+                    }
+                    AuthorizedDefinition::Type(definition) => {
+                        wasi_component_loader::AuthorizedDefinition::TypeDefinition(definition.name().to_string())
+                    }
+                },
+            })
+            .collect::<Vec<_>>();
+
+        async move {
+            let pool = self.authorization_pool(extension_id)?;
+            let mut instance = pool.get().await;
+
+            let verdicts = instance
+                .authorize_query(context.clone(), elements)
+                .await
+                .map_err(authorization_error_as_gql)?;
+
+            Ok(verdicts
+                .into_iter()
+                .map(|verdict| verdict.map_err(|error| guest_error_as_gql(error, PartialErrorCode::Unauthorized)))
+                .collect())
+        }
+    }
+
+    #[allow(clippy::manual_async_fn)]
+    fn authorize_response<'ctx, 'resp, 'f>(
+        &'ctx self,
+        context: &'ctx Self::SharedContext,
+        ExtensionAuthorizationDirective {
+            extension_id,
+            subgraph,
+            definition,
+            name,
+            arguments,
+        }: ExtensionAuthorizationDirective<'ctx, impl Anything<'ctx>>,
+        items: impl IntoIterator<Item: Anything<'resp>> + Send,
+    ) -> impl Future<Output = Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f,
+    {
+        let items = InputList::from_iter(items);
+        let directive = Directive::new(name.to_string(), subgraph.name().to_string(), arguments);
+
+        async move {
+            let AuthorizedDefinition::Field(field) = definition else {
+                return Err(PartialGraphqlError::internal_extension_error());
+            };
+
+            let definition = FieldDefinition {
+                type_name: field.parent_entity().name().to_string(),
+                name: field.name().to_string(),
+            };
+
+            let pool = self.authorization_pool(extension_id)?;
+            let mut instance = pool.get().await;
+
+            let verdicts = instance
+                .authorize_response(context.clone(), directive, definition, items)
+                .await
+                .map_err(authorization_error_as_gql)?;
+
+            Ok(verdicts
+                .into_iter()
+                .map(|verdict| verdict.map_err(|error| guest_error_as_gql(error, PartialErrorCode::Unauthorized)))
+                .collect())
+        }
+    }
+}
+
+impl WasiExtensions {
+    fn authorization_pool(&self, extension_id: ExtensionId) -> Result<&Pool, PartialGraphqlError> {
+        self.0
+            .as_ref()
+            .and_then(|inner| inner.instance_pools.get(&ExtensionPoolId::Authorization(extension_id)))
+            .ok_or_else(PartialGraphqlError::internal_extension_error)
+    }
+
+    fn hooks_pools(&self) -> impl Iterator<Item = &Pool> {
+        self.0.iter().flat_map(|inner| {
+            inner
+                .hooks
+                .iter()
+                .filter_map(|id| inner.instance_pools.get(&ExtensionPoolId::Hooks(*id)))
+        })
+    }
+
+    /// Calls the hooks extensions one after the other before authentication. The first error
+    /// stops the chain.
+    pub(crate) async fn on_gateway_request(
+        &self,
+        context: &SharedContext,
+        mut headers: http::HeaderMap,
+    ) -> Result<http::HeaderMap, ErrorResponse> {
+        for pool in self.hooks_pools() {
+            let mut instance = pool.get().await;
+
+            headers = match instance.on_gateway_request(context.clone(), headers).await {
+                Ok(headers) => headers,
+                Err(wasi_component_loader::GatewayError::Guest(error)) => {
+                    let status = http::StatusCode::from_u16(error.status_code)
+                        .unwrap_or(http::StatusCode::INTERNAL_SERVER_ERROR);
+
+                    let errors = error
+                        .errors
+                        .into_iter()
+                        .map(|error| guest_error_as_gql(error, PartialErrorCode::BadRequest))
+                        .collect();
+
+                    return Err(ErrorResponse { status, errors });
+                }
+                Err(wasi_component_loader::GatewayError::Internal(error)) => {
+                    tracing::error!("on_gateway_request extension error: {error}");
+
+                    return Err(ErrorResponse {
+                        status: http::StatusCode::INTERNAL_SERVER_ERROR,
+                        errors: vec![PartialGraphqlError::internal_extension_error()],
+                    });
+                }
+            };
+        }
+
+        Ok(headers)
+    }
+
+    /// Calls the hooks extensions one after the other before a subgraph request. The first
+    /// error stops the chain.
+    pub(crate) async fn on_subgraph_request(
+        &self,
+        context: &SharedContext,
+        subgraph_name: &str,
+        method: &http::Method,
+        url: &Url,
+        mut headers: http::HeaderMap,
+    ) -> Result<http::HeaderMap, PartialGraphqlError> {
+        for pool in self.hooks_pools() {
+            let mut instance = pool.get().await;
+
+            headers = instance
+                .on_subgraph_request(context.clone(), subgraph_name, method.clone(), url, headers)
+                .await
+                .map_err(|error| match error {
+                    wasi_component_loader::Error::Guest(error) => {
+                        guest_error_as_gql(error, PartialErrorCode::HookError)
+                    }
+                    wasi_component_loader::Error::Internal(error) => {
+                        tracing::error!("on_subgraph_request extension error: {error}");
+                        PartialGraphqlError::internal_extension_error()
+                    }
+                })?;
+        }
+
+        Ok(headers)
+    }
+
+    /// Calls the hooks extensions one after the other before sending the response. Errors are
+    /// only logged, the response being already built.
+    pub(crate) async fn on_http_response(
+        &self,
+        context: &SharedContext,
+        method: &http::Method,
+        url: &str,
+        status_code: http::StatusCode,
+    ) {
+        for pool in self.hooks_pools() {
+            let mut instance = pool.get().await;
+
+            let result = instance
+                .on_http_response(context.clone(), method.clone(), url.to_string(), status_code)
+                .await;
+
+            if let Err(error) = result {
+                tracing::error!("on_http_response extension error: {error}");
+            }
+        }
+    }
+}
+
+/// Polls the next item of a subscription. The subscription ends after the first error.
+async fn next_subscription_item(
+    instance: Option<ExtensionsComponentInstance>,
+) -> Option<(Result<Data, PartialGraphqlError>, Option<ExtensionsComponentInstance>)> {
+    let mut instance = instance?;
+
+    match instance.resolve_next_subscription_item().await {
+        Ok(Some(output)) => {
+            let item = match output.outputs.into_iter().next() {
+                Some(Ok(data)) => Ok(Data::CborBytes(data)),
+                Some(Err(error)) => Err(guest_error_as_gql(error, PartialErrorCode::InternalServerError)),
+                None => {
+                    tracing::error!("subscription extension returned an item without output");
+                    Err(PartialGraphqlError::internal_extension_error())
+                }
+            };
+
+            Some((item, Some(instance)))
+        }
+        Ok(None) => None,
+        Err(error) => Some((Err(subscription_error_as_gql(error)), None)),
+    }
+}
+
+fn subscription_error_as_gql(error: wasi_component_loader::Error) -> PartialGraphqlError {
+    match error {
+        wasi_component_loader::Error::Guest(error) => guest_error_as_gql(error, PartialErrorCode::InternalServerError),
+        wasi_component_loader::Error::Internal(error) => {
+            tracing::error!("subscription extension error: {error}");
+            PartialGraphqlError::internal_extension_error()
+        }
+    }
+}
+
+fn authorization_error_as_gql(error: wasi_component_loader::Error) -> PartialGraphqlError {
+    match error {
+        wasi_component_loader::Error::Guest(error) => guest_error_as_gql(error, PartialErrorCode::Unauthorized),
+        wasi_component_loader::Error::Internal(error) => {
+            tracing::error!("authorization extension error: {error}");
+            PartialGraphqlError::internal_extension_error()
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Hash, Eq, PartialOrd, Ord)]
 pub enum ExtensionPoolId {
     Resolver(ExtensionId),
     Authorizer(ExtensionId, AuthorizerId),
+    Authorization(ExtensionId),
+    Hooks(ExtensionId),
 }
 
 impl From<ExtensionId> for ExtensionPoolId {
@@ -232,6 +553,8 @@ impl From<(ExtensionId, AuthorizerId)> for ExtensionPoolId {
 
 struct WasiExtensionsInner {
     instance_pools: HashMap<ExtensionPoolId, Pool>,
+    /// The hooks extensions, in the order they are called.
+    hooks: Vec<ExtensionId>,
 }
 
 pub struct ExtensionConfig {
@@ -244,4 +567,6 @@ pub struct ExtensionConfig {
     pub wasi_config: WasiExtensionsConfig,
     // CBOR encoded extension configuration
     pub extension_config: Vec<u8>,
+    /// Position of a hooks extension in the hooks chain, extensions without one come last.
+    pub order: Option<u16>,
 }
@@ -26,6 +26,14 @@ impl DerefMut for ComponentGuard {
     }
 }
 
+impl ComponentGuard {
+    /// Takes the instance out of the pool for good. It is dropped when done instead of being
+    /// recycled.
+    pub(super) fn detach(self) -> ExtensionsComponentInstance {
+        managed::Object::take(self.inner)
+    }
+}
+
 impl Pool {
     pub(super) fn new(
         loader: ComponentLoader,
@@ -27,10 +27,14 @@ pub use wasi_component_loader::{
     HooksWasiConfig as Config, SharedContext,
 };
 
-use super::guest_error_as_gql;
+use super::{extensions::WasiExtensions, guest_error_as_gql};
 
 #[derive(Clone)]
-pub struct HooksWasi(Option<Arc<HooksWasiInner>>);
+pub struct HooksWasi {
+    inner: Option<Arc<HooksWasiInner>>,
+    /// The hooks extensions, called after the hooks component.
+    extensions: WasiExtensions,
+}
 
 struct HooksWasiInner {
     pool: Pool,
@@ -158,32 +162,31 @@ impl HooksWasi {
                     hook_latencies: meter.u64_histogram("grafbase.hook.duration").build(),
                 };
 
-                Self(Some(Arc::new(inner)))
+                Self {
+                    inner: Some(Arc::new(inner)),
+                    extensions: WasiExtensions::default(),
+                }
             }
-            None => Self(None),
+            None => Self {
+                inner: None,
+                extensions: WasiExtensions::default(),
+            },
         }
     }
-}
 
-impl Hooks for HooksWasi {
-    type Context = SharedContext;
-    type OnSubgraphResponseOutput = Vec<u8>;
-    type OnOperationResponseOutput = Vec<u8>;
-
-    fn new_context(&self) -> Self::Context {
-        let kv = HashMap::new();
-        let trace_id = Span::current().context().span().span_context().trace_id();
-        SharedContext::new(Arc::new(kv), trace_id)
+    /// Chains the hooks extensions after the hooks component.
+    pub fn with_extensions(self, extensions: WasiExtensions) -> Self {
+        Self { extensions, ..self }
     }
 
-    async fn on_gateway_request(
+    async fn on_gateway_request_component(
         &self,
         headers: HeaderMap,
-    ) -> Result<(Self::Context, HeaderMap), (Self::Context, ErrorResponse)> {
+    ) -> Result<(SharedContext, HeaderMap), (SharedContext, ErrorResponse)> {
         let kv = HashMap::new();
         let trace_id = Span::current().context().span().span_context().trace_id();
 
-        let Some(ref inner) = self.0 else {
+        let Some(ref inner) = self.inner else {
             return Ok((SharedContext::new(Arc::new(kv), trace_id), headers));
         };
 
@@ -231,15 +234,15 @@ impl Hooks for HooksWasi {
             })
     }
 
-    async fn on_subgraph_request(
+    async fn on_subgraph_request_component(
         &self,
-        context: &Self::Context,
+        context: &SharedContext,
         subgraph_name: &str,
         method: http::Method,
         url: &Url,
         headers: HeaderMap,
     ) -> Result<HeaderMap, PartialGraphqlError> {
-        let Some(ref inner) = self.0 else {
+        let Some(ref inner) = self.inner else {
             return Ok(headers);
         };
 
@@ -265,6 +268,47 @@ impl Hooks for HooksWasi {
                 wasi_component_loader::Error::Guest(err) => guest_error_as_gql(err, PartialErrorCode::HookError),
             })
     }
+}
+
+impl Hooks for HooksWasi {
+    type Context = SharedContext;
+    type OnSubgraphResponseOutput = Vec<u8>;
+    type OnOperationResponseOutput = Vec<u8>;
+
+    fn new_context(&self) -> Self::Context {
+        let kv = HashMap::new();
+        let trace_id = Span::current().context().span().span_context().trace_id();
+        SharedContext::new(Arc::new(kv), trace_id)
+    }
+
+    async fn on_gateway_request(
+        &self,
+        headers: HeaderMap,
+    ) -> Result<(Self::Context, HeaderMap), (Self::Context, ErrorResponse)> {
+        let (context, headers) = self.on_gateway_request_component(headers).await?;
+
+        match self.extensions.on_gateway_request(&context, headers).await {
+            Ok(headers) => Ok((context, headers)),
+            Err(response) => Err((context, response)),
+        }
+    }
+
+    async fn on_subgraph_request(
+        &self,
+        context: &Self::Context,
+        subgraph_name: &str,
+        method: http::Method,
+        url: &Url,
+        headers: HeaderMap,
+    ) -> Result<HeaderMap, PartialGraphqlError> {
+        let headers = self
+            .on_subgraph_request_component(context, subgraph_name, method.clone(), url, headers)
+            .await?;
+
+        self.extensions
+            .on_subgraph_request(context, subgraph_name, &method, url, headers)
+            .await
+    }
 
     fn authorized(&self) -> &impl AuthorizedHooks<Self::Context> {
         self
@@ -291,6 +335,18 @@ impl Hooks for HooksWasi {
         context: &Self::Context,
         request: runtime::hooks::ExecutedHttpRequest<Self::OnOperationResponseOutput>,
     ) -> impl Future<Output = Result<(), PartialGraphqlError>> + Send {
-        HooksWasi::on_http_response(self, context, request)
+        let method = request.method.clone();
+        let url = request.url.to_string();
+        let status_code = request.status_code;
+
+        async move {
+            let result = HooksWasi::on_http_response(self, context, request).await;
+
+            self.extensions
+                .on_http_response(context, &method, &url, status_code)
+                .await;
+
+            result
+        }
     }
 }
@@ -9,7 +9,7 @@ use super::{guest_error_as_gql, HooksWasi};
 
 macro_rules! prepare_authorized {
     ($span_name: expr; $impl:path; $self:ident named $func_name:literal at $definition:expr; [$(($name:literal, $input:expr),)+]) => {{
-        let Some(ref inner) = $self.0 else {
+        let Some(ref inner) = $self.inner else {
             return Err(PartialGraphqlError::new(
                 "@authorized directive cannot be used, so access was denied",
                 PartialErrorCode::Unauthorized,
@@ -13,7 +13,7 @@ impl HooksWasi {
         context: &SharedContext,
         request: runtime::hooks::ExecutedSubgraphRequest<'_>,
     ) -> Result<Vec<u8>, PartialGraphqlError> {
-        let Some(ref inner) = self.0 else {
+        let Some(ref inner) = self.inner else {
             return Ok(Vec::new());
         };
 
@@ -87,7 +87,7 @@ impl HooksWasi {
         context: &SharedContext,
         operation: runtime::hooks::ExecutedOperation<'_, Vec<u8>>,
     ) -> Result<Vec<u8>, PartialGraphqlError> {
-        let Some(ref inner) = self.0 else {
+        let Some(ref inner) = self.inner else {
             return Ok(Vec::new());
         };
 
@@ -150,7 +150,7 @@ impl HooksWasi {
         context: &SharedContext,
         request: runtime::hooks::ExecutedHttpRequest<Vec<u8>>,
     ) -> Result<(), PartialGraphqlError> {
-        let Some(ref inner) = self.0 else {
+        let Some(ref inner) = self.inner else {
             return Ok(());
         };
 
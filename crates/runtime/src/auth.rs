@@ -10,6 +10,7 @@ pub enum AccessToken {
     Anonymous,
     Jwt(JwtToken),
     Extension(ExtensionToken),
+    Introspected(IntrospectedToken),
 }
 
 /// Represents an *arbitrary* JWT token. It's only guaranteed to have been validated
@@ -25,12 +26,20 @@ pub struct ExtensionToken {
     pub claims: HashMap<String, serde_json::Value>,
 }
 
+/// An opaque token reported as active by an OAuth2 introspection endpoint. The claims are the
+/// fields of the introspection response, such as `scope`, `sub` or `client_id`.
+#[derive(Clone)]
+pub struct IntrospectedToken {
+    pub claims: HashMap<String, serde_json::Value>,
+}
+
 impl AccessToken {
     pub fn stable_id(&self) -> u8 {
         match self {
             AccessToken::Anonymous => 0,
             AccessToken::Jwt(_) => 1,
             AccessToken::Extension(_) => 2,
+            AccessToken::Introspected(_) => 3,
         }
     }
 
@@ -43,6 +52,7 @@ impl AccessToken {
             AccessToken::Anonymous => &NULL,
             AccessToken::Jwt(token) => token.claims.get(key).unwrap_or(&NULL),
             AccessToken::Extension(token) => token.claims.get(key).unwrap_or(&NULL),
+            AccessToken::Introspected(token) => token.claims.get(key).unwrap_or(&NULL),
         }
     }
 
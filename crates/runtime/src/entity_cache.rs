@@ -1,18 +1,33 @@
-use std::{borrow::Cow, time::Duration};
+use std::{borrow::Cow, fmt, time::Duration};
 
 use futures_util::{future::BoxFuture, FutureExt};
 
+/// The data of an entry along with its remaining time to live, if known.
+pub type EntryWithTtl = (Vec<u8>, Option<Duration>);
+
 /// A simplified cache trait with just enough features to handle entity caching
 pub trait EntityCache: Send + Sync {
     fn get<'a>(&'a self, name: &'a str) -> BoxFuture<'a, anyhow::Result<Option<Vec<u8>>>>;
 
-    /// Put an entry into the store, with an optional expiry TTL.
+    /// Like `get`, along with the remaining time to live of the entry if the store knows it.
+    fn get_with_ttl<'a>(&'a self, name: &'a str) -> BoxFuture<'a, anyhow::Result<Option<EntryWithTtl>>> {
+        self.get(name)
+            .map(|result| result.map(|data| data.map(|data| (data, None))))
+            .boxed()
+    }
+
+    /// Put an entry into the store, with an optional expiry TTL. The tags can later be used
+    /// to purge the entry before it expires.
     fn put<'a>(
         &'a self,
         name: &'a str,
         bytes: Cow<'a, [u8]>,
         expiration_ttl: Duration,
+        tags: &'a [String],
     ) -> BoxFuture<'a, anyhow::Result<()>>;
+
+    /// Removes all entries stored with any of the given tags.
+    fn purge<'a>(&'a self, tags: &'a [String]) -> BoxFuture<'a, anyhow::Result<()>>;
 }
 
 impl EntityCache for () {
@@ -25,7 +40,81 @@ impl EntityCache for () {
         _name: &'a str,
         _bytes: Cow<'a, [u8]>,
         _expiration_ttl: Duration,
+        _tags: &'a [String],
     ) -> BoxFuture<'a, anyhow::Result<()>> {
         futures_util::future::ready(Ok(())).boxed()
     }
+
+    fn purge<'a>(&'a self, _tags: &'a [String]) -> BoxFuture<'a, anyhow::Result<()>> {
+        futures_util::future::ready(Ok(())).boxed()
+    }
+}
+
+/// Tags attached to entity cache entries, used to purge them.
+#[derive(Debug, Clone, Copy)]
+pub enum EntityCacheTag<'a> {
+    /// Every entry fetched from a subgraph.
+    Subgraph(&'a str),
+    /// Every entry of an entity type.
+    Type(&'a str),
+    /// A single entity, identified by its key fields. The `__typename` is ignored if present.
+    Entity {
+        typename: &'a str,
+        key: &'a serde_json::Value,
+    },
+}
+
+impl fmt::Display for EntityCacheTag<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntityCacheTag::Subgraph(name) => write!(f, "subgraph:{name}"),
+            EntityCacheTag::Type(typename) => write!(f, "type:{typename}"),
+            EntityCacheTag::Entity { typename, key } => {
+                write!(f, "entity:{typename}:")?;
+                write_canonical_key(f, key, true)
+            }
+        }
+    }
+}
+
+/// Object fields are written in sorted order, so the same key always produces the same tag
+/// whatever the order of the fields in the subgraph request or purge request.
+fn write_canonical_key(f: &mut fmt::Formatter<'_>, value: &serde_json::Value, root: bool) -> fmt::Result {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut fields = map
+                .iter()
+                .filter(|(name, _)| !root || name.as_str() != "__typename")
+                .collect::<Vec<_>>();
+
+            fields.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+            f.write_str("{")?;
+
+            for (i, (name, value)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+
+                write!(f, "{}:", serde_json::Value::String(name.clone()))?;
+                write_canonical_key(f, value, false)?;
+            }
+
+            f.write_str("}")
+        }
+        serde_json::Value::Array(items) => {
+            f.write_str("[")?;
+
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+
+                write_canonical_key(f, item, false)?;
+            }
+
+            f.write_str("]")
+        }
+        value => write!(f, "{value}"),
+    }
 }
@@ -1,7 +1,8 @@
 use std::{collections::HashMap, future::Future};
 
-use engine_schema::{FieldDefinition, Subgraph};
+use engine_schema::{Definition, FieldDefinition, Subgraph};
 use extension_catalog::ExtensionId;
+use futures_util::stream::BoxStream;
 
 #[derive(Clone, Copy, PartialEq, Hash, Eq, PartialOrd, Ord, id_derives::Id)]
 pub struct AuthorizerId(u16);
@@ -24,6 +25,21 @@ pub struct ExtensionFieldDirective<'a, Args> {
     pub arguments: Args,
 }
 
+/// The schema definition an authorization extension directive is applied on.
+#[derive(Clone, Copy)]
+pub enum AuthorizedDefinition<'a> {
+    Field(FieldDefinition<'a>),
+    Type(Definition<'a>),
+}
+
+pub struct ExtensionAuthorizationDirective<'a, Args> {
+    pub extension_id: ExtensionId,
+    pub subgraph: Subgraph<'a>,
+    pub definition: AuthorizedDefinition<'a>,
+    pub name: &'a str,
+    pub arguments: Args,
+}
+
 #[allow(async_fn_in_trait)]
 pub trait ExtensionRuntime: Send + Sync + 'static {
     type SharedContext: Send + Sync + 'static;
@@ -40,12 +56,44 @@ pub trait ExtensionRuntime: Send + Sync + 'static {
     where
         'ctx: 'f;
 
+    /// Subscribe to a subscription field through an extension. Each item of the stream is the
+    /// field data of one subscription response. The subscription ends with the stream.
+    fn resolve_subscription<'ctx, 'f>(
+        &'ctx self,
+        context: &'ctx Self::SharedContext,
+        directive: ExtensionFieldDirective<'ctx, impl Anything<'ctx>>,
+    ) -> impl Future<Output = Result<BoxStream<'f, Result<Data, PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f;
+
     fn authenticate(
         &self,
         _extension_id: ExtensionId,
         _authorizer_id: AuthorizerId,
         _headers: http::HeaderMap,
     ) -> impl Future<Output = Result<(http::HeaderMap, HashMap<String, serde_json::Value>), ErrorResponse>> + Send;
+
+    /// Authorizes, before the execution, all the elements of a query carrying the directives of
+    /// the authorization extension. Returns a verdict for each element, in the same order.
+    fn authorize_query<'ctx, 'f, Args: Anything<'ctx>>(
+        &'ctx self,
+        context: &'ctx Self::SharedContext,
+        extension_id: ExtensionId,
+        elements: Vec<ExtensionAuthorizationDirective<'ctx, Args>>,
+    ) -> impl Future<Output = Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f;
+
+    /// Authorizes the values of a field after its execution, one item for each parent object.
+    /// Returns a verdict for each item, in the same order, or a single one for all of them.
+    fn authorize_response<'ctx, 'resp, 'f>(
+        &'ctx self,
+        context: &'ctx Self::SharedContext,
+        directive: ExtensionAuthorizationDirective<'ctx, impl Anything<'ctx>>,
+        items: impl IntoIterator<Item: Anything<'resp>> + Send,
+    ) -> impl Future<Output = Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f;
 }
 
 impl ExtensionRuntime for () {
@@ -64,6 +112,18 @@ impl ExtensionRuntime for () {
         async { Err(PartialGraphqlError::internal_extension_error()) }
     }
 
+    #[allow(clippy::manual_async_fn)]
+    fn resolve_subscription<'ctx, 'f>(
+        &'ctx self,
+        _context: &'ctx Self::SharedContext,
+        _directive: ExtensionFieldDirective<'ctx, impl Anything<'ctx>>,
+    ) -> impl Future<Output = Result<BoxStream<'f, Result<Data, PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f,
+    {
+        async { Err(PartialGraphqlError::internal_extension_error()) }
+    }
+
     async fn authenticate(
         &self,
         _extension_id: ExtensionId,
@@ -75,4 +135,30 @@ impl ExtensionRuntime for () {
             errors: vec![PartialGraphqlError::internal_extension_error()],
         })
     }
+
+    #[allow(clippy::manual_async_fn)]
+    fn authorize_query<'ctx, 'f, Args: Anything<'ctx>>(
+        &'ctx self,
+        _context: &'ctx Self::SharedContext,
+        _extension_id: ExtensionId,
+        _elements: Vec<ExtensionAuthorizationDirective<'ctx, Args>>,
+    ) -> impl Future<Output = Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f,
+    {
+        async { Err(PartialGraphqlError::internal_extension_error()) }
+    }
+
+    #[allow(clippy::manual_async_fn)]
+    fn authorize_response<'ctx, 'resp, 'f>(
+        &'ctx self,
+        _context: &'ctx Self::SharedContext,
+        _directive: ExtensionAuthorizationDirective<'ctx, impl Anything<'ctx>>,
+        _items: impl IntoIterator<Item: Anything<'resp>> + Send,
+    ) -> impl Future<Output = Result<Vec<Result<(), PartialGraphqlError>>, PartialGraphqlError>> + Send + 'f
+    where
+        'ctx: 'f,
+    {
+        async { Err(PartialGraphqlError::internal_extension_error()) }
+    }
 }
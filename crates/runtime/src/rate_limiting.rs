@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures_util::future::BoxFuture;
 use futures_util::FutureExt;
@@ -8,17 +9,37 @@ use futures_util::FutureExt;
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Too many requests")]
-    ExceededCapacity,
+    ExceededCapacity(ExceededCapacity),
     #[error("internal error: {0}")]
     Internal(String),
 }
 
+/// Details of an exceeded limit, sent back to the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExceededCapacity {
+    /// The limit which was exceeded, see the `Display` implementation of [RateLimitKey].
+    pub key: String,
+    /// Configured limit, in requests or complexity units.
+    pub limit: usize,
+    /// What is left of the limit, not enough for the request.
+    pub remaining: usize,
+    /// How long until the limit is entirely available again.
+    pub reset: Duration,
+    /// How long to wait before the limit allows the request, if it ever can.
+    pub retry_after: Option<Duration>,
+}
+
 pub trait RateLimiterContext: Send + Sync {
     fn header(&self, name: http::HeaderName) -> Option<&http::HeaderValue>;
     fn graphql_operation_name(&self) -> Option<&str>;
     fn ip(&self) -> Option<IpAddr>;
     fn jwt_claim(&self, key: &str) -> Option<&serde_json::Value>;
 
+    /// Complexity cost of the operation, if complexity control is enabled.
+    fn complexity_cost(&self) -> Option<usize> {
+        None
+    }
+
     fn key(&self) -> Option<&RateLimitKey<'_>> {
         None
     }
@@ -51,6 +72,22 @@ impl RateLimiter {
 pub enum RateLimitKey<'a> {
     Global,
     Subgraph(Cow<'a, str>),
+    /// A configured rate limit rule, with the value the request was keyed on.
+    Rule {
+        name: Cow<'a, str>,
+        value: Cow<'a, str>,
+    },
+}
+
+impl std::fmt::Display for RateLimitKey<'_> {
+    /// Identifies the limit without the value a rule was keyed on, which may be sensitive.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RateLimitKey::Global => f.write_str("global"),
+            RateLimitKey::Subgraph(name) => write!(f, "subgraph:{name}"),
+            RateLimitKey::Rule { name, .. } => write!(f, "rule:{name}"),
+        }
+    }
 }
 
 impl<'a> From<&'a str> for RateLimitKey<'a> {
@@ -1,4 +1,6 @@
 mod engine;
+#[cfg(feature = "prometheus")]
+mod prometheus_registry;
 mod request;
 
 pub use engine::*;
@@ -6,6 +8,8 @@ use opentelemetry::{
     metrics::{Meter, MeterProvider},
     InstrumentationScope,
 };
+#[cfg(feature = "prometheus")]
+pub use prometheus_registry::*;
 pub use request::*;
 
 pub fn meter_from_global_provider() -> Meter {
@@ -0,0 +1,29 @@
+use std::sync::OnceLock;
+
+use prometheus::{Encoder, TextEncoder};
+
+/// Content type of the Prometheus text exposition format.
+pub const PROMETHEUS_CONTENT_TYPE: &str = prometheus::TEXT_FORMAT;
+
+static GLOBAL_REGISTRY: OnceLock<prometheus::Registry> = OnceLock::new();
+
+/// Sets the registry scraped by the Prometheus endpoint. Like the global meter provider, it's
+/// only set once at startup.
+pub fn set_global_prometheus_registry(registry: prometheus::Registry) {
+    if GLOBAL_REGISTRY.set(registry).is_err() {
+        tracing::warn!("Prometheus registry was already set");
+    }
+}
+
+/// Encodes all the metrics in the Prometheus text format. Returns `None` if the Prometheus exporter
+/// isn't enabled.
+pub fn encode_global_prometheus_registry() -> Option<Result<String, prometheus::Error>> {
+    let registry = GLOBAL_REGISTRY.get()?;
+    let mut buffer = Vec::new();
+
+    Some(
+        TextEncoder::new()
+            .encode(&registry.gather(), &mut buffer)
+            .map(|_| String::from_utf8_lossy(&buffer).into_owned()),
+    )
+}
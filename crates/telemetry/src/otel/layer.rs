@@ -24,6 +24,9 @@ pub type FilteredLayer<S> = Filtered<BoxedLayer<S>, BoxedFilter<S>, S>;
 pub struct OtelTelemetry<Subscriber> {
     pub tracer: Option<Tracer<Subscriber>>,
     pub meter_provider: Option<opentelemetry_sdk::metrics::SdkMeterProvider>,
+    /// Registry of the Prometheus exporter, if enabled, to be served on the scrape endpoint.
+    #[cfg(feature = "prometheus")]
+    pub prometheus_registry: Option<prometheus::Registry>,
     pub logger: Option<Logger>,
 }
 
@@ -47,6 +50,8 @@ where
     OtelTelemetry {
         tracer: None,
         meter_provider: None,
+        #[cfg(feature = "prometheus")]
+        prometheus_registry: None,
         logger: None,
     }
 }
@@ -68,11 +73,11 @@ where
     resource_attributes.push(KeyValue::new("service.name", config.service_name.clone()));
     let resource = Resource::new(resource_attributes);
 
-    let meter_provider = Some(super::metrics::build_meter_provider(
-        runtime.clone(),
-        config,
-        resource.clone(),
-    )?);
+    let super::metrics::MeterProviders {
+        meter_provider,
+        #[cfg(feature = "prometheus")]
+        prometheus_registry,
+    } = super::metrics::build_meter_provider(runtime.clone(), config, resource.clone())?;
 
     let logger = match super::logs::build_logs_provider(runtime.clone(), config, resource.clone())? {
         Some(provider) if config.logs_exporters_enabled() => Some(Logger {
@@ -99,7 +104,9 @@ where
 
     Ok(OtelTelemetry {
         tracer,
-        meter_provider,
+        meter_provider: Some(meter_provider),
+        #[cfg(feature = "prometheus")]
+        prometheus_registry,
         logger,
     })
 }
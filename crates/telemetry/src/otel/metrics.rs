@@ -10,7 +10,11 @@ use crate::error::TracingError;
 
 pub struct DeltaTemporality;
 
-pub struct AggForLatencyHistogram;
+pub struct AggForLatencyHistogram {
+    /// Prometheus doesn't support exponential histograms, so explicit buckets are used instead when
+    /// its exporter is enabled.
+    pub explicit_buckets: bool,
+}
 
 impl View for AggForLatencyHistogram {
     fn match_inst(&self, inst: &Instrument) -> Option<Stream> {
@@ -26,6 +30,7 @@ impl View for AggForLatencyHistogram {
                 | InstrumentKind::ObservableCounter
                 | InstrumentKind::ObservableUpDownCounter => stream.aggregation(Aggregation::Sum),
                 InstrumentKind::Gauge | InstrumentKind::ObservableGauge => stream.aggregation(Aggregation::LastValue),
+                InstrumentKind::Histogram if self.explicit_buckets => stream.aggregation(Aggregation::Default),
                 InstrumentKind::Histogram => stream.aggregation(Aggregation::Base2ExponentialHistogram {
                     max_size: 160,
                     max_scale: 20,
@@ -36,17 +41,47 @@ impl View for AggForLatencyHistogram {
     }
 }
 
+pub(super) struct MeterProviders {
+    pub meter_provider: SdkMeterProvider,
+    #[cfg(feature = "prometheus")]
+    pub prometheus_registry: Option<prometheus::Registry>,
+}
+
 pub(super) fn build_meter_provider<R>(
     runtime: R,
     config: &TelemetryConfig,
     resource: Resource,
-) -> Result<SdkMeterProvider, TracingError>
+) -> Result<MeterProviders, TracingError>
 where
     R: Runtime,
 {
     let mut provider = SdkMeterProvider::builder()
         .with_resource(resource)
-        .with_view(AggForLatencyHistogram);
+        .with_view(AggForLatencyHistogram {
+            explicit_buckets: config.metrics_prometheus_config().is_some(),
+        });
+
+    #[cfg(feature = "prometheus")]
+    let mut prometheus_registry = None;
+
+    #[cfg(feature = "prometheus")]
+    if config.metrics_prometheus_config().is_some() {
+        let registry = prometheus::Registry::new();
+        let exporter = opentelemetry_prometheus::exporter()
+            .with_registry(registry.clone())
+            .build()
+            .map_err(|e| TracingError::MetricsExporterSetup(e.to_string()))?;
+
+        provider = provider.with_reader(exporter);
+        prometheus_registry = Some(registry);
+    }
+
+    #[cfg(not(feature = "prometheus"))]
+    if config.metrics_prometheus_config().is_some() {
+        return Err(TracingError::MetricsExporterSetup(
+            "the Prometheus exporter is not available in this build".to_string(),
+        ));
+    }
 
     if let Some(config) = config.metrics_stdout_config() {
         let reader = PeriodicReader::builder(
@@ -79,7 +114,11 @@ where
         provider = attach_reader(config, &runtime, provider)?;
     }
 
-    Ok(provider.build())
+    Ok(MeterProviders {
+        meter_provider: provider.build(),
+        #[cfg(feature = "prometheus")]
+        prometheus_registry,
+    })
 }
 
 #[cfg(feature = "otlp")]
@@ -39,6 +39,9 @@ impl SubgraphRequestSpanBuilder<'_> {
             "subgraph.name" = self.subgraph_name,
             "graphql.operation.type" = self.operation_type,
             "graphql.operation.document" = self.sanitized_query,
+            // Hedging
+            "subgraph.request.hedged" = Empty,
+            "subgraph.request.hedge_won" = Empty,
             // "Describes a class of error the operation ended with."
             "error.type" = Empty,
             // Response
@@ -110,6 +113,12 @@ impl SubgraphHttpRequestSpan {
 }
 
 impl SubgraphGraphqlRequestSpan {
+    /// A second request was sent because the first one was too slow.
+    pub fn record_hedged_request(&self, hedge_won: bool) {
+        self.record("subgraph.request.hedged", true);
+        self.record("subgraph.request.hedge_won", hedge_won);
+    }
+
     pub fn record_graphql_response_status(&self, status: SubgraphResponseStatus) {
         match status {
             SubgraphResponseStatus::WellFormedGraphqlResponse(status) => {
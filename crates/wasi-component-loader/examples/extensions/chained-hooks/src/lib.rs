@@ -0,0 +1,75 @@
+//! A hooks extension named by its configuration, to test several of them called in a chain.
+//! Each one appends its name to the `x-chain` header of the gateway request and to the
+//! `x-subgraph-chain` header of the subgraph requests. A gateway request is rejected by the
+//! extensions whose name is listed in the `x-reject` header.
+
+use grafbase_sdk::{
+    host_io::http::HttpMethod,
+    types::{Configuration, Directive, ErrorResponse, StatusCode},
+    Error, Extension, Headers, Hooks, HooksExtension, SharedContext,
+};
+
+#[derive(HooksExtension)]
+struct ChainedHooks {
+    config: Config,
+}
+
+#[derive(serde::Deserialize)]
+struct Config {
+    name: String,
+}
+
+impl Extension for ChainedHooks {
+    fn new(_: Vec<Directive>, config: Configuration) -> Result<Self, Box<dyn std::error::Error>> {
+        let config: Config = config.deserialize()?;
+
+        Ok(Self { config })
+    }
+}
+
+impl ChainedHooks {
+    fn append_name(&self, headers: &Headers, name: &str) {
+        let value = match headers.get(name) {
+            Some(chain) => format!("{chain},{}", self.config.name),
+            None => self.config.name.clone(),
+        };
+
+        headers.set(name, &value).unwrap();
+    }
+}
+
+impl Hooks for ChainedHooks {
+    fn on_gateway_request(&mut self, _: SharedContext, headers: Headers) -> Result<(), ErrorResponse> {
+        let rejected = headers
+            .get("x-reject")
+            .is_some_and(|names| names.split(',').any(|name| name == self.config.name));
+
+        if rejected {
+            let mut response = ErrorResponse::new(StatusCode::FORBIDDEN);
+
+            response.push_error(Error {
+                message: format!("rejected by {}", self.config.name),
+                extensions: Vec::new(),
+            });
+
+            return Err(response);
+        }
+
+        self.append_name(&headers, "x-chain");
+
+        Ok(())
+    }
+
+    fn on_subgraph_request(
+        &mut self,
+        _: SharedContext,
+        _: String,
+        _: HttpMethod,
+        _: String,
+        headers: Headers,
+    ) -> Result<(), Error> {
+        self.append_name(&headers, "x-subgraph-chain");
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
+use std::time::{Duration, Instant};
+
 use grafbase_sdk::{
     types::{Configuration, Directive, FieldDefinition, FieldInputs, FieldOutput},
-    Error, Extension, Resolver, ResolverExtension, SharedContext,
+    Error, Extension, Resolver, ResolverExtension, SharedContext, Subscription,
 };
 
 #[derive(ResolverExtension)]
@@ -16,6 +18,12 @@ struct SchemaArgs {
 #[derive(serde::Deserialize)]
 struct FieldArgs<'a> {
     name: &'a str,
+    /// Milliseconds to wait before each subscription item.
+    #[serde(default)]
+    delay_ms: u64,
+    /// Busy loop instead of sleeping while waiting.
+    #[serde(default)]
+    spin: bool,
 }
 
 #[derive(serde::Serialize)]
@@ -56,4 +64,57 @@ impl Resolver for SimpleResolver {
 
         Ok(output)
     }
+    fn resolve_subscription(
+        &mut self,
+        _: SharedContext,
+        directive: Directive,
+        _: FieldDefinition,
+    ) -> Result<Box<dyn Subscription>, Error> {
+        let args: FieldArgs = directive.arguments().unwrap();
+
+        Ok(Box::new(Counter {
+            id: self.schema_args.id,
+            name: args.name.to_string(),
+            remaining: 2,
+            delay: Duration::from_millis(args.delay_ms),
+            spin: args.spin,
+        }))
+    }
+}
+
+struct Counter {
+    id: usize,
+    name: String,
+    remaining: usize,
+    delay: Duration,
+    spin: bool,
+}
+
+impl Subscription for Counter {
+    fn next(&mut self) -> Result<Option<FieldOutput>, Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        if self.spin {
+            let start = Instant::now();
+            while start.elapsed() < self.delay {
+                std::hint::spin_loop();
+            }
+        } else {
+            std::thread::sleep(self.delay);
+        }
+
+        self.remaining -= 1;
+        self.id += 1;
+
+        let mut output = FieldOutput::new();
+
+        output.push_value(ResponseOutput {
+            id: self.id,
+            name: &self.name,
+        });
+
+        Ok(Some(output))
+    }
 }
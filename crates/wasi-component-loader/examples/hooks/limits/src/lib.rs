@@ -0,0 +1,30 @@
+use grafbase_hooks::{grafbase_hooks, Context, ErrorResponse, Headers, Hooks};
+
+struct Component;
+
+#[grafbase_hooks]
+impl Hooks for Component {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self
+    }
+
+    fn on_gateway_request(&mut self, _: Context, headers: Headers) -> Result<(), ErrorResponse> {
+        match headers.get("action").as_deref() {
+            Some("loop") => loop {
+                std::hint::black_box(());
+            },
+            Some("allocate") => {
+                let data = vec![1u8; 64 * 1024 * 1024];
+                std::hint::black_box(data);
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
+
+grafbase_hooks::register_hooks!(Component);
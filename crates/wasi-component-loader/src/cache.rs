@@ -5,15 +5,16 @@ use std::{
 
 use dashmap::DashMap;
 use futures::TryFutureExt;
+use runtime::entity_cache::EntityCacheTag;
 use tokio::sync::{mpsc, oneshot};
 use ulid::Ulid;
 use wasmtime::{
-    component::{LinkerInstance, ResourceType},
+    component::{ComponentType, Lift, LinkerInstance, ResourceType},
     StoreContextMut,
 };
 
 use crate::{
-    names::{CACHE_GET_FUNCTION, CACHE_RESOURCE, CACHE_SET_FUNCTION},
+    names::{CACHE_GET_FUNCTION, CACHE_PURGE_ENTITIES_FUNCTION, CACHE_RESOURCE, CACHE_SET_FUNCTION},
     state::WasiState,
 };
 
@@ -24,12 +25,36 @@ pub(crate) fn inject_mapping(types: &mut LinkerInstance<'_, WasiState>) -> crate
     types.resource(CACHE_RESOURCE, ResourceType::host::<()>(), |_, _| Ok(()))?;
     types.func_wrap_async(CACHE_GET_FUNCTION, cache_get)?;
     types.func_wrap_async(CACHE_SET_FUNCTION, cache_set)?;
+    types.func_wrap_async(CACHE_PURGE_ENTITIES_FUNCTION, cache_purge_entities)?;
 
     Ok(())
 }
 
+/// Selects entries of the gateway entity cache.
+#[derive(Debug, ComponentType, Lift)]
+#[component(variant)]
+enum EntityCacheSelector {
+    #[component(name = "subgraph")]
+    Subgraph(String),
+    #[component(name = "entity-type")]
+    EntityType(String),
+    #[component(name = "entity")]
+    Entity(EntityCacheKey),
+}
+
+/// Identifies a single entity in the gateway entity cache.
+#[derive(Debug, ComponentType, Lift)]
+#[component(record)]
+struct EntityCacheKey {
+    #[component(name = "type-name")]
+    type_name: String,
+    /// The key fields, serialized in JSON.
+    key: String,
+}
+
 type CacheGetResult<'a> = Box<dyn Future<Output = anyhow::Result<(Option<Vec<u8>>,)>> + Send + 'a>;
 type CacheSetResult<'a> = Box<dyn Future<Output = anyhow::Result<()>> + Send + 'a>;
+type CachePurgeEntitiesResult<'a> = Box<dyn Future<Output = anyhow::Result<(Result<(), String>,)>> + Send + 'a>;
 
 fn cache_get(store: StoreContextMut<'_, WasiState>, (key,): (String,)) -> CacheGetResult<'_> {
     Box::new(async move {
@@ -49,6 +74,39 @@ fn cache_set(
     })
 }
 
+fn cache_purge_entities(
+    store: StoreContextMut<'_, WasiState>,
+    (selector,): (EntityCacheSelector,),
+) -> CachePurgeEntitiesResult<'_> {
+    Box::new(async move {
+        let tag = match selector {
+            EntityCacheSelector::Subgraph(name) => EntityCacheTag::Subgraph(&name).to_string(),
+            EntityCacheSelector::EntityType(name) => EntityCacheTag::Type(&name).to_string(),
+            EntityCacheSelector::Entity(EntityCacheKey { type_name, key }) => {
+                let key = match serde_json::from_str::<serde_json::Value>(&key) {
+                    Ok(key) => key,
+                    Err(err) => return Ok((Err(format!("invalid entity key: {err}")),)),
+                };
+
+                EntityCacheTag::Entity {
+                    typename: &type_name,
+                    key: &key,
+                }
+                .to_string()
+            }
+        };
+
+        let result = store
+            .data()
+            .entity_cache()
+            .purge(&[tag])
+            .await
+            .map_err(|err| err.to_string());
+
+        Ok((result,))
+    })
+}
+
 pub(crate) struct Cache {
     cache: DashMap<String, CachedValue>,
     wait_list: DashMap<String, (Ulid, WaitListSender, WaitListReceiver)>,
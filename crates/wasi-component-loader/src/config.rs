@@ -30,6 +30,7 @@ pub(crate) fn build_extensions_context(config: &WasiExtensionsConfig) -> WasiCtx
 pub(crate) fn build_hooks_context(
     HooksWasiConfig {
         max_pool_size: _,
+        limits: _,
         location: _,
         networking,
         environment_variables,
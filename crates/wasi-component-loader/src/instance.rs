@@ -3,11 +3,12 @@ use std::any::Any;
 use gateway_config::{HooksWasiConfig, WasiExtensionsConfig};
 use wasmtime::{
     component::{ComponentNamedList, Instance, Lift, Lower, TypedFunc},
-    Store,
+    Store, UpdateDeadline,
 };
 
 use crate::{
     config::{build_extensions_context, build_hooks_context},
+    limits::{epoch_deadline, store_limits},
     state::WasiState,
     ChannelLogSender, ComponentLoader,
 };
@@ -20,8 +21,16 @@ fn initialize_hooks_store(
     loader: &ComponentLoader,
     access_log: ChannelLogSender,
 ) -> crate::Result<Store<WasiState>> {
-    let state = WasiState::new(build_hooks_context(config), access_log, loader.cache().clone());
-    let store = Store::new(loader.engine(), state);
+    let state = WasiState::new(
+        build_hooks_context(config),
+        access_log,
+        loader.cache().clone(),
+        loader.entity_cache().clone(),
+        store_limits(&config.limits),
+    );
+
+    let mut store = Store::new(loader.engine(), state);
+    store.limiter(|state| state.limits_mut());
 
     Ok(store)
 }
@@ -31,8 +40,16 @@ fn initialize_extensions_store(
     loader: &ComponentLoader,
     access_log: ChannelLogSender,
 ) -> crate::Result<Store<WasiState>> {
-    let state = WasiState::new(build_extensions_context(config), access_log, loader.cache().clone());
-    let store = Store::new(loader.engine(), state);
+    let state = WasiState::new(
+        build_extensions_context(config),
+        access_log,
+        loader.cache().clone(),
+        loader.entity_cache().clone(),
+        store_limits(&config.limits),
+    );
+
+    let mut store = Store::new(loader.engine(), state);
+    store.limiter(|state| state.limits_mut());
 
     Ok(store)
 }
@@ -45,15 +62,26 @@ pub struct ComponentInstance {
     instance: Instance,
     function_cache: FunctionCache,
     poisoned: bool,
+    epoch_deadline: Option<u64>,
 }
 
 impl ComponentInstance {
     pub async fn new(loader: &ComponentLoader, access_log: ChannelLogSender) -> crate::Result<Self> {
-        let mut store = match loader.config() {
-            either::Either::Left(config) => initialize_hooks_store(config, loader, access_log)?,
-            either::Either::Right((_, config)) => initialize_extensions_store(config, loader, access_log)?,
+        let (mut store, epoch_deadline) = match loader.config() {
+            either::Either::Left(config) => (
+                initialize_hooks_store(config, loader, access_log)?,
+                epoch_deadline(&config.limits),
+            ),
+            either::Either::Right((_, config)) => (
+                initialize_extensions_store(config, loader, access_log)?,
+                epoch_deadline(&config.limits),
+            ),
         };
 
+        if let Some(ticks) = epoch_deadline {
+            store.set_epoch_deadline(ticks);
+        }
+
         let instance = loader
             .linker()
             .instantiate_async(&mut store, loader.component())
@@ -64,6 +92,7 @@ impl ComponentInstance {
             instance,
             function_cache: Default::default(),
             poisoned: false,
+            epoch_deadline,
         })
     }
 
@@ -71,6 +100,42 @@ impl ComponentInstance {
         &mut self.store
     }
 
+    /// Returns the store for a call into the guest, restarting the execution deadline if any.
+    /// A call running past it traps, which poisons the instance.
+    pub fn call_store(&mut self) -> &mut Store<WasiState> {
+        if let Some(ticks) = self.epoch_deadline {
+            self.store.epoch_deadline_trap();
+            self.store.set_epoch_deadline(ticks);
+        }
+
+        &mut self.store
+    }
+
+    /// Returns the store for a call into the guest that may legitimately wait for long, such as
+    /// polling a subscription. Only the ticks spent running guest code count towards the execution
+    /// deadline: the guest yields back to the executor on every tick, and traps once it has run
+    /// for as many ticks as the deadline allows. Waiting on the host mostly goes unnoticed, it
+    /// costs a single tick once the guest resumes.
+    pub fn waiting_call_store(&mut self) -> &mut Store<WasiState> {
+        if let Some(ticks) = self.epoch_deadline {
+            let mut remaining = ticks;
+
+            self.store.epoch_deadline_callback(move |_| {
+                remaining = remaining.saturating_sub(1);
+
+                if remaining == 0 {
+                    anyhow::bail!("the guest ran for longer than the execution timeout");
+                }
+
+                Ok(UpdateDeadline::Yield(1))
+            });
+
+            self.store.set_epoch_deadline(1);
+        }
+
+        &mut self.store
+    }
+
     pub fn get_typed_func<Params, Results>(&mut self, function_name: &'static str) -> Option<TypedFunc<Params, Results>>
     where
         Params: ComponentNamedList + Lower + Send + Sync + 'static,
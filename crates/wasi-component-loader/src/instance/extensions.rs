@@ -3,16 +3,20 @@ mod types;
 use anyhow::anyhow;
 use http::HeaderMap;
 use serde::de::DeserializeOwned;
-use types::Token;
-pub use types::{Directive, ExtensionType, FieldDefinition, FieldOutput};
+pub use types::{AuthorizedDefinition, Directive, ExtensionType, FieldDefinition, FieldOutput, QueryElement};
+use types::{ExecutedHttpRequest, Token};
+use url::Url;
 use wasmtime::component::{ComponentNamedList, Lift, Lower, Resource, TypedFunc};
 
 use super::ComponentInstance;
 use crate::{
     error::guest::ErrorResponse,
+    http_client::HttpMethod,
     names::{
-        AUTEHNTICATE_EXTENSION_FUNCTION, INIT_GATEWAY_EXTENSION_FUNCTION, REGISTER_EXTENSION_FUNCTION,
-        RESOLVE_FIELD_EXTENSION_FUNCTION,
+        AUTEHNTICATE_EXTENSION_FUNCTION, AUTHORIZE_QUERY_EXTENSION_FUNCTION, AUTHORIZE_RESPONSE_EXTENSION_FUNCTION,
+        INIT_GATEWAY_EXTENSION_FUNCTION, ON_GATEWAY_REQUEST_EXTENSION_FUNCTION, ON_HTTP_RESPONSE_EXTENSION_FUNCTION,
+        ON_SUBGRAPH_REQUEST_EXTENSION_FUNCTION, REGISTER_EXTENSION_FUNCTION, RESOLVE_FIELD_EXTENSION_FUNCTION,
+        RESOLVE_NEXT_SUBSCRIPTION_ITEM_EXTENSION_FUNCTION, RESOLVE_SUBSCRIPTION_EXTENSION_FUNCTION,
     },
     ChannelLogSender, ComponentLoader, GuestError, SharedContext,
 };
@@ -53,7 +57,7 @@ impl ExtensionsComponentInstance {
             .get_typed_func::<(), ()>(REGISTER_EXTENSION_FUNCTION)
             .ok_or_else(|| anyhow!("register-extension function not found"))?;
 
-        register.call_async(component.store_mut(), ()).await?;
+        register.call_async(component.call_store(), ()).await?;
         register.post_return_async(component.store_mut()).await?;
 
         let mut this = Self { component };
@@ -93,6 +97,104 @@ impl ExtensionsComponentInstance {
         Ok(result?)
     }
 
+    /// Subscribes to a subscription field. The instance then belongs to the subscription, and
+    /// its items are polled with [`Self::resolve_next_subscription_item`].
+    pub async fn resolve_subscription(
+        &mut self,
+        context: SharedContext,
+        directive: Directive,
+        definition: FieldDefinition,
+    ) -> crate::Result<()> {
+        type Params = (Resource<SharedContext>, Directive, FieldDefinition);
+        type Response = Result<(), GuestError>;
+
+        // The guest may keep the context for the whole subscription, so we leave it in the
+        // store. It goes away with the instance.
+        let context = self.component.store_mut().data_mut().push_resource(context)?;
+
+        let result = self
+            .call_typed_func::<Params, Response>(
+                RESOLVE_SUBSCRIPTION_EXTENSION_FUNCTION,
+                (context, directive, definition),
+            )
+            .await?;
+
+        Ok(result?)
+    }
+
+    /// Polls the next item of the subscription started with [`Self::resolve_subscription`].
+    /// Returns `None` once the subscription is over. The guest may wait on the host for an item
+    /// as long as it needs, only the time it spends running counts towards the execution timeout.
+    pub async fn resolve_next_subscription_item(&mut self) -> crate::Result<Option<FieldOutput>> {
+        type Response = Result<Option<FieldOutput>, GuestError>;
+
+        let func = self.get_typed_func::<(), (Response,)>(RESOLVE_NEXT_SUBSCRIPTION_ITEM_EXTENSION_FUNCTION)?;
+        let result = func.call_async(self.component.waiting_call_store(), ()).await;
+
+        if result.is_err() {
+            self.component.poisoned = true;
+        } else {
+            func.post_return_async(self.component.store_mut()).await?;
+        }
+
+        Ok(result?.0?)
+    }
+
+    /// Authorizes the elements of a query before its execution, returning a verdict for each of
+    /// them.
+    pub async fn authorize_query(
+        &mut self,
+        context: SharedContext,
+        elements: Vec<QueryElement>,
+    ) -> crate::Result<Vec<Result<(), GuestError>>> {
+        type Params = (Resource<SharedContext>, Vec<QueryElement>);
+        type Response = Result<Vec<Result<(), GuestError>>, GuestError>;
+
+        let context = self.component.store_mut().data_mut().push_resource(context)?;
+        let context_rep = context.rep();
+
+        let result = self
+            .call_typed_func::<Params, Response>(AUTHORIZE_QUERY_EXTENSION_FUNCTION, (context, elements))
+            .await?;
+
+        self.component
+            .store_mut()
+            .data_mut()
+            .take_resource::<SharedContext>(context_rep)?;
+
+        Ok(result?)
+    }
+
+    /// Authorizes the values of a field after its execution, returning a verdict for each of
+    /// them or a single one for all.
+    pub async fn authorize_response(
+        &mut self,
+        context: SharedContext,
+        directive: Directive,
+        definition: FieldDefinition,
+        items: InputList,
+    ) -> crate::Result<Vec<Result<(), GuestError>>> {
+        type Params = (Resource<SharedContext>, Directive, FieldDefinition, Vec<Vec<u8>>);
+        type Response = Result<Vec<Result<(), GuestError>>, GuestError>;
+
+        let context = self.component.store_mut().data_mut().push_resource(context)?;
+        let context_rep = context.rep();
+
+        let result = self
+            .call_typed_func::<Params, Response>(
+                AUTHORIZE_RESPONSE_EXTENSION_FUNCTION,
+                (context, directive, definition, items.0),
+            )
+            .await?;
+
+        self.component
+            .store_mut()
+            .data_mut()
+            .take_resource::<SharedContext>(context_rep)?;
+
+        Ok(result?)
+    }
+
     /// Performs authentication based on the provided request headers.
     pub async fn authenticate<S>(&mut self, headers: HeaderMap) -> crate::GatewayResult<(HeaderMap, S)>
     where
@@ -119,6 +221,126 @@ impl ExtensionsComponentInstance {
         Ok((headers, result))
     }
 
+    /// Calls the hooks extension before authentication, returning the possibly modified headers.
+    pub async fn on_gateway_request(
+        &mut self,
+        context: SharedContext,
+        headers: HeaderMap,
+    ) -> crate::GatewayResult<HeaderMap> {
+        type Params = (Resource<SharedContext>, Resource<HeaderMap>);
+        type Response = Result<(), ErrorResponse>;
+
+        let context = self.component.store_mut().data_mut().push_resource(context)?;
+        let headers = self.component.store_mut().data_mut().push_resource(headers)?;
+
+        let context_rep = context.rep();
+        let headers_rep = headers.rep();
+
+        let result = self
+            .call_typed_func::<Params, Response>(ON_GATEWAY_REQUEST_EXTENSION_FUNCTION, (context, headers))
+            .await?;
+
+        self.component
+            .store_mut()
+            .data_mut()
+            .take_resource::<SharedContext>(context_rep)?;
+
+        let headers = self
+            .component
+            .store_mut()
+            .data_mut()
+            .take_resource::<HeaderMap>(headers_rep)?;
+
+        result?;
+
+        Ok(headers)
+    }
+
+    /// Calls the hooks extension before a subgraph request, returning the possibly modified
+    /// headers.
+    pub async fn on_subgraph_request(
+        &mut self,
+        context: SharedContext,
+        subgraph_name: &str,
+        method: http::Method,
+        url: &Url,
+        headers: HeaderMap,
+    ) -> crate::Result<HeaderMap> {
+        type Params = (Resource<SharedContext>, String, HttpMethod, String, Resource<HeaderMap>);
+        type Response = Result<(), GuestError>;
+
+        let context = self.component.store_mut().data_mut().push_resource(context)?;
+        let headers = self.component.store_mut().data_mut().push_resource(headers)?;
+
+        let context_rep = context.rep();
+        let headers_rep = headers.rep();
+
+        let params = (
+            context,
+            subgraph_name.to_string(),
+            HttpMethod::from(method),
+            url.to_string(),
+            headers,
+        );
+
+        let result = self
+            .call_typed_func::<Params, Response>(ON_SUBGRAPH_REQUEST_EXTENSION_FUNCTION, params)
+            .await?;
+
+        self.component
+            .store_mut()
+            .data_mut()
+            .take_resource::<SharedContext>(context_rep)?;
+
+        let headers = self
+            .component
+            .store_mut()
+            .data_mut()
+            .take_resource::<HeaderMap>(headers_rep)?;
+
+        result?;
+
+        Ok(headers)
+    }
+
+    /// Calls the hooks extension right before the response is sent to the client.
+    pub async fn on_http_response(
+        &mut self,
+        context: SharedContext,
+        method: http::Method,
+        url: String,
+        status_code: http::StatusCode,
+    ) -> crate::Result<()> {
+        type Params = (Resource<SharedContext>, ExecutedHttpRequest);
+
+        let context = self.component.store_mut().data_mut().push_resource(context)?;
+        let context_rep = context.rep();
+
+        let request = ExecutedHttpRequest {
+            method: HttpMethod::from(method),
+            url,
+            status_code: status_code.as_u16(),
+        };
+
+        let func = self.get_typed_func::<Params, ()>(ON_HTTP_RESPONSE_EXTENSION_FUNCTION)?;
+        let result = func.call_async(self.component.call_store(), (context, request)).await;
+
+        if result.is_err() {
+            self.component.poisoned = true;
+        } else {
+            func.post_return_async(self.component.store_mut()).await?;
+        }
+
+        result?;
+
+        self.component
+            .store_mut()
+            .data_mut()
+            .take_resource::<SharedContext>(context_rep)?;
+
+        Ok(())
+    }
+
     async fn init_gateway_extension(
         &mut self,
         r#type: ExtensionType,
@@ -147,7 +369,7 @@ impl ExtensionsComponentInstance {
     {
         let func = self.get_typed_func::<Params, (Response,)>(function_name).unwrap();
 
-        let result = func.call_async(self.component.store_mut(), params).await;
+        let result = func.call_async(self.component.call_store(), params).await;
 
         if result.is_err() {
             self.component.poisoned = true;
@@ -1,7 +1,7 @@
 use serde::{de::DeserializeOwned, Deserialize};
 use wasmtime::component::{ComponentType, Lift, Lower};
 
-use crate::{Error, GuestError};
+use crate::{http_client::HttpMethod, Error, GuestError};
 
 /// Defines the type of the extension.
 #[derive(Debug, Clone, Copy, Lower, ComponentType)]
@@ -14,6 +14,13 @@ pub enum ExtensionType {
     /// A resolver extension can call the `authenticate` function.
     #[component(name = "authentication")]
     Authentication,
+    /// An authorization extension can call the `authorize-query` and `authorize-response` functions.
+    #[component(name = "authorization")]
+    Authorization,
+    /// A hooks extension can call the `on-gateway-request`, `on-subgraph-request` and
+    /// `on-http-response` functions.
+    #[component(name = "hooks")]
+    Hooks,
 }
 
 /// A directive related to the extension.
@@ -51,6 +58,30 @@ pub struct FieldDefinition {
     pub name: String,
 }
 
+/// The schema definition an authorization directive is applied on.
+#[derive(Clone, Lower, ComponentType)]
+#[component(variant)]
+pub enum AuthorizedDefinition {
+    /// A field definition.
+    #[component(name = "field")]
+    Field(FieldDefinition),
+    /// A type definition, by name.
+    #[component(name = "type-definition")]
+    TypeDefinition(String),
+}
+
+/// An element of the query carrying an authorization directive of the extension.
+#[derive(Clone, Lower, ComponentType)]
+#[component(record)]
+pub struct QueryElement {
+    /// The directive, with the field arguments it references already injected.
+    #[component(name = "directive")]
+    pub directive: Directive,
+    /// The definition the directive is applied on.
+    #[component(name = "definition")]
+    pub definition: AuthorizedDefinition,
+}
+
 /// The output of a field resolver extension.
 #[derive(Clone, Lift, ComponentType)]
 #[component(record)]
@@ -76,6 +107,18 @@ impl FieldOutput {
     }
 }
 
+/// Info about an executed HTTP request, sent to the hooks extensions.
+#[derive(Clone, Lower, ComponentType)]
+#[component(record)]
+pub(crate) struct ExecutedHttpRequest {
+    #[component(name = "method")]
+    pub method: HttpMethod,
+    #[component(name = "url")]
+    pub url: String,
+    #[component(name = "status-code")]
+    pub status_code: u16,
+}
+
 #[derive(Clone, Lift, ComponentType)]
 #[component(record)]
 pub struct Token {
@@ -127,7 +127,7 @@ impl HooksComponentInstance {
             .get_typed_func::<(), (i64,)>(INIT_HOOKS_FUNCTION)
             .ok_or_else(|| anyhow!("init-hooks function not found"))?;
 
-        let (bits,) = init.call_async(component.store_mut(), ()).await?;
+        let (bits,) = init.call_async(component.call_store(), ()).await?;
         init.post_return_async(component.store_mut()).await?;
 
         Ok(Self {
@@ -170,7 +170,7 @@ impl HooksComponentInstance {
         let headers_rep = headers.rep();
         let context_rep = context.rep();
 
-        let result = hook.call_async(self.component.store_mut(), (context, headers)).await;
+        let result = hook.call_async(self.component.call_store(), (context, headers)).await;
 
         if result.is_err() {
             self.component.poison();
@@ -228,7 +228,7 @@ impl HooksComponentInstance {
 
         let result = hook
             .call_async(
-                self.component.store_mut(),
+                self.component.call_store(),
                 (context, subgraph_name, method, url, headers),
             )
             .await;
@@ -530,7 +530,7 @@ impl HooksComponentInstance {
         let context = self.component.store_mut().data_mut().push_resource(context)?;
         let context_rep = context.rep();
 
-        let result = hook.call_async(self.component.store_mut(), (context, arg)).await;
+        let result = hook.call_async(self.component.call_store(), (context, arg)).await;
 
         // We check if the hook call trapped, and if so we mark the instance poisoned.
         //
@@ -585,7 +585,7 @@ impl HooksComponentInstance {
         let context = self.component.store_mut().data_mut().push_resource(context)?;
         let context_rep = context.rep();
 
-        let result = hook.call_async(self.component.store_mut(), (context, arg)).await;
+        let result = hook.call_async(self.component.call_store(), (context, arg)).await;
 
         // We check if the hook call trapped, and if so we mark the instance poisoned.
         //
@@ -642,7 +642,7 @@ impl HooksComponentInstance {
         let context_rep = context.rep();
 
         let result = hook
-            .call_async(self.component.store_mut(), (context, args.0, args.1))
+            .call_async(self.component.call_store(), (context, args.0, args.1))
             .await;
 
         // We check if the hook call trapped, and if so we mark the instance poisoned.
@@ -701,7 +701,7 @@ impl HooksComponentInstance {
         let context_rep = context.rep();
 
         let result = hook
-            .call_async(self.component.store_mut(), (context, args.0, args.1, args.2))
+            .call_async(self.component.call_store(), (context, args.0, args.1, args.2))
             .await;
 
         // We check if the hook call trapped, and if so we mark the instance poisoned.
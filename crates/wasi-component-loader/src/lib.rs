@@ -16,6 +16,7 @@ mod error;
 mod headers;
 mod http_client;
 mod instance;
+mod limits;
 mod names;
 mod state;
 
@@ -34,7 +35,8 @@ use either::Either;
 pub use error::{guest::GuestError, Error, GatewayError};
 use gateway_config::WasiExtensionsConfig;
 pub use instance::extensions::{
-    Directive, ExtensionType, ExtensionsComponentInstance, FieldDefinition, FieldOutput, InputList,
+    AuthorizedDefinition, Directive, ExtensionType, ExtensionsComponentInstance, FieldDefinition, FieldOutput,
+    InputList, QueryElement,
 };
 pub use instance::hooks::{
     authorization::{EdgeDefinition, NodeDefinition},
@@ -44,6 +46,7 @@ pub use instance::hooks::{
     },
     HookImplementation, HooksComponentInstance,
 };
+use runtime::entity_cache::EntityCache;
 
 /// The crate result type
 pub type Result<T> = std::result::Result<T, Error>;
@@ -75,6 +78,8 @@ pub struct ComponentLoader {
     config: Either<HooksWasiConfig, (String, WasiExtensionsConfig)>,
     /// Shared cache between component instances.
     cache: Arc<Cache>,
+    /// The gateway entity cache, which extensions can purge.
+    entity_cache: Arc<dyn EntityCache>,
 }
 
 impl ComponentLoader {
@@ -110,21 +115,35 @@ impl ComponentLoader {
         Self::new(Either::Right((extension_name, config.into())), instantiate)
     }
 
+    /// Gives the component access to the gateway entity cache, so it can purge entries.
+    /// Without it, purging does nothing.
+    pub fn with_entity_cache(mut self, entity_cache: Arc<dyn EntityCache>) -> Self {
+        self.entity_cache = entity_cache;
+        self
+    }
+
     fn new<F>(config: Either<HooksWasiConfig, (String, WasiExtensionsConfig)>, instantiate: F) -> Result<Option<Self>>
     where
         F: FnOnce(LinkerInstance<'_, WasiState>) -> Result<()>,
     {
         let mut wasm_config = wasmtime::Config::new();
 
+        let (networking, location, limits) = match config {
+            Either::Left(ref hooks) => (hooks.networking, hooks.location.clone(), &hooks.limits),
+            Either::Right((_, ref config)) => (config.networking, config.location.clone(), &config.limits),
+        };
+
+        let epoch_interruption = limits.execution_timeout.is_some();
+
         wasm_config.wasm_component_model(true);
         wasm_config.async_support(true);
+        wasm_config.epoch_interruption(epoch_interruption);
 
         let engine = Engine::new(&wasm_config)?;
 
-        let (networking, location) = match config {
-            Either::Left(ref hooks) => (hooks.networking, hooks.location.clone()),
-            Either::Right((_, ref config)) => (config.networking, config.location.clone()),
-        };
+        if epoch_interruption {
+            limits::start_epoch_ticker(&engine).map_err(anyhow::Error::from)?;
+        }
 
         let this = match Component::from_file(&engine, &location) {
             Ok(component) => {
@@ -151,6 +170,7 @@ impl ComponentLoader {
                     component,
                     config,
                     cache: Arc::new(Cache::new()),
+                    entity_cache: Arc::new(()),
                 })
             }
             Err(e) => {
@@ -211,4 +231,9 @@ impl ComponentLoader {
     fn cache(&self) -> &Arc<Cache> {
         &self.cache
     }
+
+    /// The gateway entity cache.
+    fn entity_cache(&self) -> &Arc<dyn EntityCache> {
+        &self.entity_cache
+    }
 }
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use gateway_config::WasiLimitsConfig;
+use wasmtime::{Engine, StoreLimits, StoreLimitsBuilder};
+
+/// How often the epoch of an engine with an execution timeout is incremented.
+const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// Builds the memory and table limits of a store. Growing past them traps instead of failing
+/// silently, so the call errors out and the instance gets poisoned.
+pub(crate) fn store_limits(config: &WasiLimitsConfig) -> StoreLimits {
+    let mut builder = StoreLimitsBuilder::new().trap_on_grow_failure(true);
+
+    if let Some(max_memory) = config.max_memory {
+        builder = builder.memory_size(usize::try_from(max_memory.bytes()).unwrap_or(usize::MAX));
+    }
+
+    if let Some(max_table_elements) = config.max_table_elements {
+        builder = builder.table_elements(max_table_elements);
+    }
+
+    builder.build()
+}
+
+/// The number of epoch ticks a call may run before being interrupted, if it has a timeout.
+/// We add a tick to never interrupt a call before its timeout, as the first tick may come at
+/// any time.
+pub(crate) fn epoch_deadline(config: &WasiLimitsConfig) -> Option<u64> {
+    let timeout = config.execution_timeout?;
+    let ticks = timeout.as_millis().div_ceil(EPOCH_TICK.as_millis());
+
+    Some(u64::try_from(ticks).unwrap_or(u64::MAX).saturating_add(1))
+}
+
+/// Increments the epoch of the engine in a background thread, until the engine is dropped.
+pub(crate) fn start_epoch_ticker(engine: &Engine) -> std::io::Result<()> {
+    let engine = engine.weak();
+
+    std::thread::Builder::new()
+        .name("wasi-epoch-ticker".into())
+        .spawn(move || loop {
+            std::thread::sleep(EPOCH_TICK);
+
+            match engine.upgrade() {
+                Some(engine) => engine.increment_epoch(),
+                None => break,
+            }
+        })?;
+
+    Ok(())
+}
@@ -42,8 +42,16 @@ pub(crate) const HEADERS_ENTRIES_METHOD: &str = "[method]headers.entries";
 pub(crate) const REGISTER_EXTENSION_FUNCTION: &str = "register-extension";
 pub(crate) const INIT_GATEWAY_EXTENSION_FUNCTION: &str = "init-gateway-extension";
 pub(crate) const RESOLVE_FIELD_EXTENSION_FUNCTION: &str = "resolve-field";
+pub(crate) const RESOLVE_SUBSCRIPTION_EXTENSION_FUNCTION: &str = "resolve-subscription";
+pub(crate) const RESOLVE_NEXT_SUBSCRIPTION_ITEM_EXTENSION_FUNCTION: &str = "resolve-next-subscription-item";
 pub(crate) const AUTEHNTICATE_EXTENSION_FUNCTION: &str = "authenticate";
+pub(crate) const AUTHORIZE_QUERY_EXTENSION_FUNCTION: &str = "authorize-query";
+pub(crate) const AUTHORIZE_RESPONSE_EXTENSION_FUNCTION: &str = "authorize-response";
+pub(crate) const ON_GATEWAY_REQUEST_EXTENSION_FUNCTION: &str = "on-gateway-request";
+pub(crate) const ON_SUBGRAPH_REQUEST_EXTENSION_FUNCTION: &str = "on-subgraph-request";
+pub(crate) const ON_HTTP_RESPONSE_EXTENSION_FUNCTION: &str = "on-http-response";
 
 pub(crate) const CACHE_RESOURCE: &str = "cache";
 pub(crate) const CACHE_GET_FUNCTION: &str = "[static]cache.get";
 pub(crate) const CACHE_SET_FUNCTION: &str = "[static]cache.set";
+pub(crate) const CACHE_PURGE_ENTITIES_FUNCTION: &str = "[static]cache.purge-entities";
@@ -2,7 +2,8 @@ use std::sync::Arc;
 
 use super::cache::Cache;
 use grafbase_telemetry::{metrics::meter_from_global_provider, otel::opentelemetry::metrics::Histogram};
-use wasmtime::component::Resource;
+use runtime::entity_cache::EntityCache;
+use wasmtime::{component::Resource, StoreLimits};
 use wasmtime_wasi::{IoView, ResourceTable, WasiCtx, WasiView};
 use wasmtime_wasi_http::{WasiHttpCtx, WasiHttpView};
 
@@ -34,6 +35,12 @@ pub(crate) struct WasiState {
 
     /// A cache to be used for storing data between calls to different instances of the same extension.
     cache: Arc<Cache>,
+
+    /// The gateway entity cache, which extensions can purge.
+    entity_cache: Arc<dyn EntityCache>,
+
+    /// The memory and table limits of the instance.
+    limits: StoreLimits,
 }
 
 impl WasiState {
@@ -42,12 +49,22 @@ impl WasiState {
     /// # Arguments
     ///
     /// * `ctx` - A `WasiCtx` instance that represents the WASI environment context.
+    /// * `access_log` - A sender for the access log channel.
+    /// * `cache` - The cache shared between instances of the same component.
+    /// * `entity_cache` - The gateway entity cache.
+    /// * `limits` - The memory and table limits of the instance.
     ///
     /// # Returns
     ///
     /// A new `WasiState` instance initialized with the provided context and default
     /// HTTP and resource table contexts.
-    pub fn new(ctx: WasiCtx, access_log: ChannelLogSender, cache: Arc<Cache>) -> Self {
+    pub fn new(
+        ctx: WasiCtx,
+        access_log: ChannelLogSender,
+        cache: Arc<Cache>,
+        entity_cache: Arc<dyn EntityCache>,
+        limits: StoreLimits,
+    ) -> Self {
         let meter = meter_from_global_provider();
         let request_durations = meter.u64_histogram("grafbase.hook.http_request.duration").build();
         let http_client = reqwest::Client::new();
@@ -60,6 +77,8 @@ impl WasiState {
             http_client,
             access_log,
             cache,
+            entity_cache,
+            limits,
         }
     }
 
@@ -111,6 +130,16 @@ impl WasiState {
     pub fn cache(&self) -> &Cache {
         &self.cache
     }
+
+    /// Returns a reference to the gateway entity cache.
+    pub fn entity_cache(&self) -> &dyn EntityCache {
+        self.entity_cache.as_ref()
+    }
+
+    /// Returns the memory and table limits of the instance.
+    pub fn limits_mut(&mut self) -> &mut StoreLimits {
+        &mut self.limits
+    }
 }
 
 impl IoView for WasiState {
@@ -1,11 +1,11 @@
-use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
 
 use crate::{
     tests::create_log_channel, ComponentLoader, Directive, ExtensionType, ExtensionsComponentInstance, FieldDefinition,
     SharedContext,
 };
 use futures::{stream::FuturesOrdered, StreamExt};
-use gateway_config::WasiExtensionsConfig;
+use gateway_config::{WasiExtensionsConfig, WasiLimitsConfig};
 use grafbase_telemetry::otel::opentelemetry::trace::TraceId;
 use http::{HeaderMap, HeaderValue};
 use serde_json::json;
@@ -28,6 +28,7 @@ async fn simple_resolver() {
         stdout: false,
         stderr: false,
         environment_variables: false,
+        limits: Default::default(),
     };
 
     assert!(config.location.exists());
@@ -70,6 +71,220 @@ async fn simple_resolver() {
     "#);
 }
 
+#[tokio::test]
+async fn simple_subscription() {
+    #[derive(serde::Serialize)]
+    struct SchemaArgs {
+        id: usize,
+    }
+
+    #[derive(serde::Serialize)]
+    struct FieldArgs<'a> {
+        name: &'a str,
+    }
+
+    let config = WasiExtensionsConfig {
+        location: PathBuf::from("examples/target/wasm32-wasip2/debug/simple_resolver.wasm"),
+        networking: false,
+        stdout: false,
+        stderr: false,
+        environment_variables: false,
+        limits: Default::default(),
+    };
+
+    assert!(config.location.exists());
+
+    let (access_log, _) = create_log_channel();
+    let loader = ComponentLoader::extensions(String::new(), config).unwrap().unwrap();
+    let schema_directive = Directive::new("schemaArgs".into(), "mySubgraph".into(), &SchemaArgs { id: 10 });
+
+    let mut extension = ExtensionsComponentInstance::new(
+        &loader,
+        ExtensionType::Resolver,
+        vec![schema_directive],
+        Vec::new(),
+        access_log,
+    )
+    .await
+    .unwrap();
+
+    let context = SharedContext::new(Arc::new(HashMap::new()), TraceId::INVALID);
+
+    let field_directive = Directive::new("myDirective".into(), "mySubgraph".into(), &FieldArgs { name: "cat" });
+
+    let definition = FieldDefinition {
+        type_name: "Subscription".into(),
+        name: "cats".into(),
+    };
+
+    extension
+        .resolve_subscription(context, field_directive, definition)
+        .await
+        .unwrap();
+
+    let mut items = Vec::new();
+
+    while let Some(output) = extension.resolve_next_subscription_item().await.unwrap() {
+        let item: serde_json::Value = output.serialize_outputs().pop().unwrap().unwrap();
+        items.push(item);
+    }
+
+    insta::assert_json_snapshot!(&items, @r#"
+    [
+      {
+        "id": 11,
+        "name": "cat"
+      },
+      {
+        "id": 12,
+        "name": "cat"
+      }
+    ]
+    "#);
+}
+
+#[tokio::test]
+async fn subscription_items_may_wait_past_the_execution_timeout() {
+    #[derive(serde::Serialize)]
+    struct SchemaArgs {
+        id: usize,
+    }
+
+    #[derive(serde::Serialize)]
+    struct FieldArgs<'a> {
+        name: &'a str,
+        delay_ms: u64,
+    }
+
+    let config = WasiExtensionsConfig {
+        location: PathBuf::from("examples/target/wasm32-wasip2/debug/simple_resolver.wasm"),
+        networking: false,
+        stdout: false,
+        stderr: false,
+        environment_variables: false,
+        limits: WasiLimitsConfig {
+            execution_timeout: Some(Duration::from_millis(50)),
+            ..Default::default()
+        },
+    };
+
+    assert!(config.location.exists());
+
+    let (access_log, _) = create_log_channel();
+    let loader = ComponentLoader::extensions(String::new(), config).unwrap().unwrap();
+    let schema_directive = Directive::new("schemaArgs".into(), "mySubgraph".into(), &SchemaArgs { id: 10 });
+
+    let mut extension = ExtensionsComponentInstance::new(
+        &loader,
+        ExtensionType::Resolver,
+        vec![schema_directive],
+        Vec::new(),
+        access_log,
+    )
+    .await
+    .unwrap();
+
+    let context = SharedContext::new(Arc::new(HashMap::new()), TraceId::INVALID);
+
+    // Each item takes longer than the execution timeout to come.
+    let field_directive = Directive::new(
+        "myDirective".into(),
+        "mySubgraph".into(),
+        &FieldArgs {
+            name: "cat",
+            delay_ms: 200,
+        },
+    );
+
+    let definition = FieldDefinition {
+        type_name: "Subscription".into(),
+        name: "cats".into(),
+    };
+
+    extension
+        .resolve_subscription(context, field_directive, definition)
+        .await
+        .unwrap();
+
+    let mut items = 0;
+
+    while extension.resolve_next_subscription_item().await.unwrap().is_some() {
+        items += 1;
+    }
+
+    assert_eq!(items, 2);
+    assert!(extension.recycle().is_ok());
+}
+
+#[tokio::test]
+async fn spinning_subscription_poll_is_interrupted() {
+    #[derive(serde::Serialize)]
+    struct SchemaArgs {
+        id: usize,
+    }
+
+    #[derive(serde::Serialize)]
+    struct FieldArgs<'a> {
+        name: &'a str,
+        delay_ms: u64,
+        spin: bool,
+    }
+
+    let config = WasiExtensionsConfig {
+        location: PathBuf::from("examples/target/wasm32-wasip2/debug/simple_resolver.wasm"),
+        networking: false,
+        stdout: false,
+        stderr: false,
+        environment_variables: false,
+        limits: WasiLimitsConfig {
+            execution_timeout: Some(Duration::from_millis(50)),
+            ..Default::default()
+        },
+    };
+
+    assert!(config.location.exists());
+
+    let (access_log, _) = create_log_channel();
+    let loader = ComponentLoader::extensions(String::new(), config).unwrap().unwrap();
+    let schema_directive = Directive::new("schemaArgs".into(), "mySubgraph".into(), &SchemaArgs { id: 10 });
+
+    let mut extension = ExtensionsComponentInstance::new(
+        &loader,
+        ExtensionType::Resolver,
+        vec![schema_directive],
+        Vec::new(),
+        access_log,
+    )
+    .await
+    .unwrap();
+
+    let context = SharedContext::new(Arc::new(HashMap::new()), TraceId::INVALID);
+
+    // The guest keeps running for longer than the execution timeout, instead of waiting.
+    let field_directive = Directive::new(
+        "myDirective".into(),
+        "mySubgraph".into(),
+        &FieldArgs {
+            name: "cat",
+            delay_ms: 200,
+            spin: true,
+        },
+    );
+
+    let definition = FieldDefinition {
+        type_name: "Subscription".into(),
+        name: "cats".into(),
+    };
+
+    extension
+        .resolve_subscription(context, field_directive, definition)
+        .await
+        .unwrap();
+
+    assert!(extension.resolve_next_subscription_item().await.is_err());
+    assert!(extension.recycle().is_err());
+}
+
 #[tokio::test]
 async fn single_call_caching_auth() {
     let config = WasiExtensionsConfig {
@@ -78,6 +293,7 @@ async fn single_call_caching_auth() {
         stdout: false,
         stderr: false,
         environment_variables: false,
+        limits: Default::default(),
     };
 
     assert!(config.location.exists());
@@ -119,6 +335,7 @@ async fn single_call_caching_auth_invalid() {
         stdout: false,
         stderr: false,
         environment_variables: false,
+        limits: Default::default(),
     };
 
     assert!(config.location.exists());
@@ -160,6 +377,7 @@ async fn multiple_cache_calls() {
         stdout: false,
         stderr: false,
         environment_variables: false,
+        limits: Default::default(),
     };
 
     assert!(config.location.exists());
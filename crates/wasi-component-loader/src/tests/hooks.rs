@@ -216,6 +216,66 @@ async fn guest_error() {
     assert_eq!(Some(expected), error.into_guest_error());
 }
 
+#[tokio::test]
+async fn execution_timeout() {
+    // the guest code in examples/limits/src/lib.rs
+
+    let config = indoc! {r#"
+        location = "examples/target/wasm32-wasip2/debug/limits.wasm"
+
+        [limits]
+        execution_timeout = "100ms"
+    "#};
+
+    let config: Config = toml::from_str(config).unwrap();
+    assert!(config.location.exists());
+
+    let loader = ComponentLoader::hooks(config).unwrap().unwrap();
+    let (access_log, _) = create_log_channel();
+    let mut hook = HooksComponentInstance::new(&loader, access_log).await.unwrap();
+
+    hook.on_gateway_request(HashMap::new(), HeaderMap::new()).await.unwrap();
+    assert!(hook.recycle().is_ok());
+
+    let mut headers = HeaderMap::new();
+    headers.insert("action", HeaderValue::from_static("loop"));
+
+    let error = hook.on_gateway_request(HashMap::new(), headers).await.unwrap_err();
+
+    assert!(error.into_guest_error().is_none());
+    assert!(hook.recycle().is_err());
+}
+
+#[tokio::test]
+async fn max_memory() {
+    // the guest code in examples/limits/src/lib.rs
+
+    let config = indoc! {r#"
+        location = "examples/target/wasm32-wasip2/debug/limits.wasm"
+
+        [limits]
+        max_memory = "16MiB"
+    "#};
+
+    let config: Config = toml::from_str(config).unwrap();
+    assert!(config.location.exists());
+
+    let loader = ComponentLoader::hooks(config).unwrap().unwrap();
+    let (access_log, _) = create_log_channel();
+    let mut hook = HooksComponentInstance::new(&loader, access_log).await.unwrap();
+
+    hook.on_gateway_request(HashMap::new(), HeaderMap::new()).await.unwrap();
+    assert!(hook.recycle().is_ok());
+
+    let mut headers = HeaderMap::new();
+    headers.insert("action", HeaderValue::from_static("allocate"));
+
+    let error = hook.on_gateway_request(HashMap::new(), headers).await.unwrap_err();
+
+    assert!(error.into_guest_error().is_none());
+    assert!(hook.recycle().is_err());
+}
+
 #[tokio::test]
 async fn authorize_edge_pre_execution_error() {
     // the guest code in examples/authorization/src/lib.rs
@@ -62,6 +62,7 @@ pub(crate) fn init(args: &impl Args, config: &TelemetryConfig) -> anyhow::Result
     let OtelTelemetry {
         tracer,
         meter_provider,
+        prometheus_registry,
         logger,
     } = grafbase_telemetry::otel::layer::build(config, id_generator, Tokio)?;
 
@@ -69,6 +70,10 @@ pub(crate) fn init(args: &impl Args, config: &TelemetryConfig) -> anyhow::Result
         grafbase_telemetry::otel::opentelemetry::global::set_meter_provider(meter_provider.clone());
     }
 
+    if let Some(registry) = prometheus_registry {
+        grafbase_telemetry::metrics::set_global_prometheus_registry(registry);
+    }
+
     if let Some(ref tracer) = tracer {
         grafbase_telemetry::otel::opentelemetry::global::set_tracer_provider(tracer.provider.clone());
     }
@@ -1,7 +1,7 @@
 use std::{future::Future, sync::Arc};
 
 use graphql_mocks::Schema;
-use indoc::formatdoc;
+use indoc::{formatdoc, indoc};
 use rand::Rng;
 
 use crate::{runtime, Client};
@@ -53,6 +53,75 @@ fn entity_caching_via_redis() {
     );
 }
 
+#[test]
+fn entity_cache_purge_endpoint() {
+    let config = indoc! {r#"
+        [entity_caching]
+        enabled = true
+
+        [entity_caching.purge]
+        enabled = true
+        listen = "127.0.0.1:9670"
+    "#};
+
+    let subgraph_schema = graphql_mocks::EchoSchema;
+    let subgraph_sdl = subgraph_schema.sdl();
+    let subgraph_server = runtime().block_on(async { graphql_mocks::MockGraphQlServer::new(subgraph_schema).await });
+
+    with_mock_subgraph(
+        config,
+        &subgraph_sdl,
+        subgraph_server.url().as_str(),
+        |client| async move {
+            const QUERY: &str = r#"query { id(input: "hello") }"#;
+
+            client.gql::<serde_json::Value>(QUERY).send().await;
+            client.gql::<serde_json::Value>(QUERY).send().await;
+            assert_eq!(subgraph_server.drain_received_requests().count(), 1);
+
+            // The endpoint is not exposed next to the GraphQL endpoint.
+            let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+            url.set_path("/admin/entity-cache/purge");
+
+            let response = client
+                .client()
+                .post(url)
+                .json(&serde_json::json!({ "subgraph": "the-subgraph" }))
+                .send()
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), 404);
+
+            let url: reqwest::Url = "http://127.0.0.1:9670/admin/entity-cache/purge".parse().unwrap();
+
+            let response = client
+                .client()
+                .post(url.clone())
+                .json(&serde_json::json!({ "key": { "id": "1" } }))
+                .send()
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), 400);
+            assert_eq!(response.text().await.unwrap(), "a key requires a type");
+
+            let response = client
+                .client()
+                .post(url)
+                .json(&serde_json::json!({ "subgraph": "the-subgraph" }))
+                .send()
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), 204);
+
+            client.gql::<serde_json::Value>(QUERY).send().await;
+            assert_eq!(subgraph_server.drain_received_requests().count(), 1);
+        },
+    );
+}
+
 fn with_mock_subgraph<T, F>(config: &str, subgraph_schema: &str, subgraph_url: &str, test: T)
 where
     T: FnOnce(Arc<Client>) -> F,
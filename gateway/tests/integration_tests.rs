@@ -852,6 +852,204 @@ fn csrf_with_header() {
     })
 }
 
+#[test]
+fn response_compression() {
+    let config = indoc! {r#"
+        [graph]
+        introspection = true
+
+        [compression]
+        enabled = true
+        min_size = "1B"
+    "#};
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        // Decompression is disabled to keep the content encoding visible.
+        let http = reqwest::Client::builder()
+            .no_gzip()
+            .no_brotli()
+            .no_zstd()
+            .build()
+            .unwrap();
+
+        let send = |encoding: &'static str| {
+            http.post(client.endpoint())
+                .header(http::header::ACCEPT, "application/json")
+                .header(http::header::ACCEPT_ENCODING, encoding)
+                .json(&serde_json::json!({ "query": "{ __schema { types { name } } }" }))
+                .send()
+        };
+
+        let response = send("gzip").await.unwrap();
+        assert_eq!(response.headers()[http::header::CONTENT_ENCODING], "gzip");
+
+        let response = send("br").await.unwrap();
+        assert_eq!(response.headers()[http::header::CONTENT_ENCODING], "br");
+
+        let response = send("identity").await.unwrap();
+        assert!(!response.headers().contains_key(http::header::CONTENT_ENCODING));
+    })
+}
+
+#[test]
+fn response_compression_flushes_streamed_events() {
+    use axum::{body::Body, extract::State, response::Response, routing::post, Router};
+    use tokio::sync::Notify;
+
+    // Sends a first event, then waits for the test to have received it before completing.
+    async fn subscription(State(resume): State<Arc<Notify>>) -> Response {
+        let events = futures_util::stream::unfold(0, move |tick| {
+            let resume = resume.clone();
+
+            async move {
+                let event = match tick {
+                    0 => "event: next\ndata: {\"data\":{\"ticks\":0}}\n\n",
+                    1 => {
+                        resume.notified().await;
+                        "event: next\ndata: {\"data\":{\"ticks\":1}}\n\n"
+                    }
+                    2 => "event: complete\ndata:\n\n",
+                    _ => return None,
+                };
+
+                Some((Ok::<_, std::io::Error>(event), tick + 1))
+            }
+        });
+
+        Response::builder()
+            .header(http::header::CONTENT_TYPE, "text/event-stream")
+            .body(Body::from_stream(events))
+            .unwrap()
+    }
+
+    let sdl = indoc! {r#"
+        type Query {
+            hello: String
+        }
+
+        type Subscription {
+            ticks: Int!
+        }
+    "#};
+
+    let resume = Arc::new(Notify::new());
+
+    let subgraph_port = runtime().block_on(async {
+        let app = Router::new().route("/", post(subscription)).with_state(resume.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        port
+    });
+
+    let federated_schema = {
+        let mut subgraphs = graphql_composition::Subgraphs::default();
+        subgraphs
+            .ingest_str(sdl, "events", Some(&format!("http://127.0.0.1:{subgraph_port}/")))
+            .unwrap();
+        let graph = graphql_composition::compose(&subgraphs).into_result().unwrap();
+        graphql_composition::render_federated_sdl(&graph).unwrap()
+    };
+
+    let config = indoc! {r#"
+        [compression]
+        enabled = true
+        min_size = "1B"
+
+        [subgraphs.events]
+        subscription_protocol = "server_sent_events"
+    "#};
+
+    with_static_server(config, &federated_schema, None, None, |client| async move {
+        // Decompression is disabled to keep the content encoding visible.
+        let http = reqwest::Client::builder()
+            .no_gzip()
+            .no_brotli()
+            .no_zstd()
+            .build()
+            .unwrap();
+
+        for (accept, encoding) in ["text/event-stream", "multipart/mixed"]
+            .into_iter()
+            .flat_map(|accept| ["gzip", "br", "zstd"].map(|encoding| (accept, encoding)))
+        {
+            let mut response = http
+                .post(client.endpoint())
+                .header(http::header::ACCEPT, accept)
+                .header(http::header::ACCEPT_ENCODING, encoding)
+                .json(&serde_json::json!({ "query": "subscription { ticks }" }))
+                .send()
+                .await
+                .unwrap();
+
+            assert_eq!(response.headers()[http::header::CONTENT_ENCODING], encoding);
+
+            let decoded = Arc::new(Mutex::new(Vec::new()));
+            let mut decoder: Box<dyn std::io::Write + Send> = match encoding {
+                "gzip" => Box::new(flate2::write::GzDecoder::new(SharedBuffer(decoded.clone()))),
+                "br" => Box::new(brotli::DecompressorWriter::new(SharedBuffer(decoded.clone()), 4096)),
+                _ => Box::new(zstd::stream::write::Decoder::new(SharedBuffer(decoded.clone())).unwrap()),
+            };
+            let received = || String::from_utf8_lossy(&decoded.lock().unwrap()).into_owned();
+
+            // The subgraph is still waiting, so the first event can only be decoded if the encoder
+            // was flushed after it.
+            let first_event = tokio::time::timeout(Duration::from_secs(10), async {
+                while !received().contains(r#""ticks":0"#) {
+                    let chunk = response
+                        .chunk()
+                        .await
+                        .unwrap()
+                        .expect("stream ended before the first event");
+                    decoder.write_all(&chunk).unwrap();
+                    decoder.flush().unwrap();
+                }
+            })
+            .await;
+            assert!(
+                first_event.is_ok(),
+                "first {accept} event wasn't flushed by {encoding}: {:?}",
+                received()
+            );
+
+            resume.notify_one();
+
+            let completed = tokio::time::timeout(Duration::from_secs(10), async {
+                while let Some(chunk) = response.chunk().await.unwrap() {
+                    decoder.write_all(&chunk).unwrap();
+                }
+                decoder.flush().unwrap();
+            })
+            .await;
+            assert!(
+                completed.is_ok(),
+                "{accept} stream didn't complete with {encoding}: {:?}",
+                received()
+            );
+
+            assert!(received().contains(r#""ticks":1"#), "{}", received());
+        }
+    })
+}
+
+/// Decoded output shared with the test while the decoder holds it.
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 #[test]
 fn hybrid_graph() {
     let schema = load_schema("big");
@@ -1002,6 +1200,60 @@ fn health_custom_listener() {
     });
 }
 
+#[test]
+fn prometheus_metrics_endpoint() {
+    let config = r#"
+        [telemetry.exporters.prometheus]
+        enabled = true
+    "#;
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let result: serde_json::Value = client.gql("query { __typename }").send().await;
+        assert_eq!(result["data"]["__typename"], "Query");
+
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/metrics");
+
+        let response = client.client().get(url).send().await.unwrap();
+
+        assert_eq!(response.status(), 200);
+        assert!(response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain"));
+
+        let body = response.text().await.unwrap();
+        assert!(body.contains("http_server_request_duration"), "{body}");
+    });
+}
+
+#[test]
+fn prometheus_metrics_custom_listener() {
+    let config = r#"
+        [telemetry.exporters.prometheus]
+        enabled = true
+        path = "/prometheus"
+        listen = "0.0.0.0:9669"
+    "#;
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/prometheus");
+
+        let response = client.client().get(url).send().await.unwrap();
+        assert_eq!(response.status(), 404);
+
+        let url: reqwest::Url = "http://127.0.0.1:9669/prometheus".parse().unwrap();
+        let response = client.client().get(url).send().await.unwrap();
+
+        assert_eq!(response.status(), 200);
+    });
+}
+
 #[test]
 fn schema_file_hot_reload() {
     let config = indoc! {r#"